@@ -1,29 +1,29 @@
-use std::collections::BTreeMap;
-
-use ciborium::value::Value;
-
-use crate::aethos_core::protocol::{decode_cbor_value_exact, encode_cbor_value_deterministic};
-
-const WAYFARER_CHAT_V1: &str = "wayfarer.chat.v1";
-const WAYFARER_MEDIA_MANIFEST_V1: &str = "wayfarer.media_manifest.v1";
-const RESERVED_TYPES: [&str; 5] = [
-    "wayfarer.profile.v1",
-    "wayfarer.reaction.v1",
-    "wayfarer.message_update.v1",
-    "wayfarer.status_event.v1",
-    "wayfarer.notice.v1",
-];
-const MEDIA_KINDS: [&str; 4] = ["image", "video", "audio", "file"];
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ChatDisplayPayload {
-    pub text: String,
-    pub created_at_unix_ms: u64,
-}
+use crate::aethos_core::app_body::{
+    decode_app_body, encode_app_body, AppBody, AppBodyError, ChatBody, DeviceAnnounceBody,
+    MediaManifestBody, MessageUpdateBody, NoticeBody, ProfileBody, ReactionBody, RecoveryShareBody,
+    RevocationBody, StatusEventBody, SuccessionBody, WAYFARER_CHAT_V1, WAYFARER_DEVICE_ANNOUNCE_V1,
+    WAYFARER_MEDIA_MANIFEST_V1, WAYFARER_MESSAGE_UPDATE_V1, WAYFARER_NOTICE_V1,
+    WAYFARER_PROFILE_V1, WAYFARER_REACTION_V1, WAYFARER_RECOVERY_SHARE_BODY_V1,
+    WAYFARER_REVOCATION_V1, WAYFARER_STATUS_EVENT_V1, WAYFARER_SUCCESSION_V1,
+};
+pub use crate::aethos_core::app_body::{
+    MediaAsset as OutboundMediaAsset, MediaManifestBody as OutboundMediaManifestInput,
+};
+
+pub type ChatDisplayPayload = ChatBody;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum StoreNoDisplayKind {
     WayfarerMediaManifestV1,
+    Reaction(ReactionBody),
+    MessageUpdate(MessageUpdateBody),
+    Profile(ProfileBody),
+    StatusEvent(StatusEventBody),
+    Notice(NoticeBody),
+    Succession(SuccessionBody),
+    Revocation(RevocationBody),
+    RecoveryShare(RecoveryShareBody),
+    DeviceAnnounce(DeviceAnnounceBody),
     ReservedType(String),
 }
 
@@ -55,6 +55,15 @@ impl ClassificationResult {
         }
     }
 
+    fn routed(outcome: ClassificationOutcome, payload_type: &str) -> Self {
+        Self {
+            outcome,
+            payload_type: Some(payload_type.to_string()),
+            routed_to: Some(payload_type.to_string()),
+            decoder_ran: true,
+        }
+    }
+
     pub fn outcome_label(&self) -> &'static str {
         match self.outcome {
             ClassificationOutcome::AcceptDisplay { .. } => "accept/display",
@@ -65,333 +74,161 @@ impl ClassificationResult {
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct OutboundMediaAsset {
-    pub asset_ref: String,
-    pub mime_type: String,
-    pub byte_length: u64,
-    pub name: Option<String>,
+pub fn build_wayfarer_chat_body(text: &str, created_at_unix_ms: u64) -> Result<Vec<u8>, String> {
+    encode_app_body(&AppBody::Chat(ChatBody {
+        text: text.to_string(),
+        created_at_unix_ms,
+    }))
 }
 
-#[derive(Debug, Clone)]
-pub struct OutboundMediaManifestInput {
-    pub transfer_ref: String,
-    pub media_kind: String,
-    pub assets: Vec<OutboundMediaAsset>,
-    pub caption: Option<String>,
-    pub created_at_unix_ms: u64,
+pub fn build_wayfarer_media_manifest_body(input: &MediaManifestBody) -> Result<Vec<u8>, String> {
+    encode_app_body(&AppBody::MediaManifest(input.clone()))
 }
 
-pub fn build_wayfarer_chat_body(text: &str, created_at_unix_ms: u64) -> Result<Vec<u8>, String> {
-    let mut map = BTreeMap::new();
-    map.insert(
-        "created_at_unix_ms".to_string(),
-        Value::Integer(created_at_unix_ms.into()),
-    );
-    map.insert("text".to_string(), Value::Text(text.to_string()));
-    map.insert(
-        "type".to_string(),
-        Value::Text(WAYFARER_CHAT_V1.to_string()),
-    );
-    let cbor = encode_cbor_value_deterministic(&Value::Map(
-        map.into_iter()
-            .map(|(key, value)| (Value::Text(key), value))
-            .collect(),
-    ))?;
-    let classified = classify_wayfarer_app_body(&cbor);
-    if !matches!(
-        classified.outcome,
-        ClassificationOutcome::AcceptDisplay { .. }
-    ) {
-        return Err("constructed wayfarer.chat.v1 body failed strict classification".to_string());
-    }
-    Ok(cbor)
+pub fn build_wayfarer_reaction_body(reaction: &ReactionBody) -> Result<Vec<u8>, String> {
+    encode_app_body(&AppBody::Reaction(reaction.clone()))
 }
 
-pub fn build_wayfarer_media_manifest_body(
-    input: &OutboundMediaManifestInput,
-) -> Result<Vec<u8>, String> {
-    let mut top = BTreeMap::new();
-    top.insert(
-        "type".to_string(),
-        Value::Text(WAYFARER_MEDIA_MANIFEST_V1.to_string()),
-    );
-    top.insert(
-        "transfer_ref".to_string(),
-        Value::Text(input.transfer_ref.clone()),
-    );
-    top.insert(
-        "media_kind".to_string(),
-        Value::Text(input.media_kind.clone()),
-    );
-
-    let assets = input
-        .assets
-        .iter()
-        .map(|asset| {
-            let mut map = BTreeMap::new();
-            map.insert(
-                "asset_ref".to_string(),
-                Value::Text(asset.asset_ref.clone()),
-            );
-            map.insert(
-                "mime_type".to_string(),
-                Value::Text(asset.mime_type.clone()),
-            );
-            map.insert(
-                "byte_length".to_string(),
-                Value::Integer(asset.byte_length.into()),
-            );
-            if let Some(name) = asset.name.as_ref() {
-                map.insert("name".to_string(), Value::Text(name.clone()));
-            }
-            Value::Map(
-                map.into_iter()
-                    .map(|(key, value)| (Value::Text(key), value))
-                    .collect(),
-            )
-        })
-        .collect::<Vec<_>>();
-
-    top.insert("assets".to_string(), Value::Array(assets));
-    if let Some(caption) = input.caption.as_ref() {
-        top.insert("caption".to_string(), Value::Text(caption.clone()));
-    }
-    top.insert(
-        "created_at_unix_ms".to_string(),
-        Value::Integer(input.created_at_unix_ms.into()),
-    );
-
-    let cbor = encode_cbor_value_deterministic(&Value::Map(
-        top.into_iter()
-            .map(|(key, value)| (Value::Text(key), value))
-            .collect(),
-    ))?;
-    let classified = classify_wayfarer_app_body(&cbor);
-    if !matches!(
-        classified.outcome,
-        ClassificationOutcome::AcceptStoreNoDisplay {
-            kind: StoreNoDisplayKind::WayfarerMediaManifestV1
-        }
-    ) {
-        return Err(
-            "constructed wayfarer.media_manifest.v1 body failed strict classification".to_string(),
-        );
-    }
-    Ok(cbor)
+pub fn build_wayfarer_message_update_body(update: &MessageUpdateBody) -> Result<Vec<u8>, String> {
+    encode_app_body(&AppBody::MessageUpdate(update.clone()))
 }
 
-pub fn classify_wayfarer_app_body(body: &[u8]) -> ClassificationResult {
-    let decoded = match decode_cbor_value_exact(body, "wayfarer app body") {
-        Ok(value) => value,
-        Err(err) => return ClassificationResult::reject(format!("invalid_cbor: {err}")),
-    };
-
-    let canonical = match encode_cbor_value_deterministic(&decoded) {
-        Ok(value) => value,
-        Err(err) => {
-            return ClassificationResult::reject(format!("deterministic_reencode_failed: {err}"));
-        }
-    };
-    if canonical.as_slice() != body {
-        return ClassificationResult::reject("non_deterministic_cbor_encoding");
-    }
+pub fn build_wayfarer_profile_body(profile: &ProfileBody) -> Result<Vec<u8>, String> {
+    encode_app_body(&AppBody::Profile(profile.clone()))
+}
+
+pub fn build_wayfarer_status_event_body(event: &StatusEventBody) -> Result<Vec<u8>, String> {
+    encode_app_body(&AppBody::StatusEvent(event.clone()))
+}
+
+pub fn build_wayfarer_notice_body(notice: &NoticeBody) -> Result<Vec<u8>, String> {
+    encode_app_body(&AppBody::Notice(notice.clone()))
+}
 
-    let top_level_map = match value_as_text_keyed_map(decoded, "top_level") {
-        Ok(value) => value,
-        Err(err) => return ClassificationResult::reject(err),
-    };
-
-    let payload_type = match top_level_map.get("type") {
-        Some(Value::Text(value)) => value.clone(),
-        Some(_) => return ClassificationResult::reject("type_must_be_text"),
-        None => return ClassificationResult::reject("missing_type"),
-    };
-
-    match payload_type.as_str() {
-        WAYFARER_CHAT_V1 => match decode_wayfarer_chat_v1(&top_level_map) {
-            Ok(chat) => ClassificationResult {
-                outcome: ClassificationOutcome::AcceptDisplay { chat },
-                payload_type: Some(payload_type.clone()),
-                routed_to: Some(payload_type),
-                decoder_ran: true,
+pub fn build_wayfarer_succession_body(record: &[u8]) -> Result<Vec<u8>, String> {
+    encode_app_body(&AppBody::Succession(SuccessionBody {
+        record: record.to_vec(),
+    }))
+}
+
+pub fn build_wayfarer_revocation_body(certificate: &[u8]) -> Result<Vec<u8>, String> {
+    encode_app_body(&AppBody::Revocation(RevocationBody {
+        certificate: certificate.to_vec(),
+    }))
+}
+
+pub fn build_wayfarer_recovery_share_body(share: &[u8]) -> Result<Vec<u8>, String> {
+    encode_app_body(&AppBody::RecoveryShare(RecoveryShareBody {
+        share: share.to_vec(),
+    }))
+}
+
+pub fn build_wayfarer_device_announce_body(certificate: &[u8]) -> Result<Vec<u8>, String> {
+    encode_app_body(&AppBody::DeviceAnnounce(DeviceAnnounceBody {
+        certificate: certificate.to_vec(),
+    }))
+}
+
+pub fn classify_wayfarer_app_body(body: &[u8]) -> ClassificationResult {
+    match decode_app_body(body) {
+        Ok(AppBody::Chat(chat)) => ClassificationResult::routed(
+            ClassificationOutcome::AcceptDisplay { chat },
+            WAYFARER_CHAT_V1,
+        ),
+        Ok(AppBody::MediaManifest(_)) => ClassificationResult::routed(
+            ClassificationOutcome::AcceptStoreNoDisplay {
+                kind: StoreNoDisplayKind::WayfarerMediaManifestV1,
             },
-            Err(err) => ClassificationResult {
-                outcome: ClassificationOutcome::Reject {
-                    reason: format!("malformed_wayfarer_chat_v1: {err}"),
-                },
-                payload_type: Some(WAYFARER_CHAT_V1.to_string()),
-                routed_to: Some(WAYFARER_CHAT_V1.to_string()),
-                decoder_ran: true,
+            WAYFARER_MEDIA_MANIFEST_V1,
+        ),
+        Ok(AppBody::Reaction(reaction)) => ClassificationResult::routed(
+            ClassificationOutcome::AcceptStoreNoDisplay {
+                kind: StoreNoDisplayKind::Reaction(reaction),
             },
-        },
-        WAYFARER_MEDIA_MANIFEST_V1 => match decode_wayfarer_media_manifest_v1(&top_level_map) {
-            Ok(()) => ClassificationResult {
-                outcome: ClassificationOutcome::AcceptStoreNoDisplay {
-                    kind: StoreNoDisplayKind::WayfarerMediaManifestV1,
-                },
-                payload_type: Some(payload_type.clone()),
-                routed_to: Some(payload_type),
-                decoder_ran: true,
+            WAYFARER_REACTION_V1,
+        ),
+        Ok(AppBody::MessageUpdate(update)) => ClassificationResult::routed(
+            ClassificationOutcome::AcceptStoreNoDisplay {
+                kind: StoreNoDisplayKind::MessageUpdate(update),
             },
-            Err(err) => ClassificationResult {
-                outcome: ClassificationOutcome::Reject {
-                    reason: format!("malformed_wayfarer_media_manifest_v1: {err}"),
-                },
-                payload_type: Some(WAYFARER_MEDIA_MANIFEST_V1.to_string()),
-                routed_to: Some(WAYFARER_MEDIA_MANIFEST_V1.to_string()),
-                decoder_ran: true,
+            WAYFARER_MESSAGE_UPDATE_V1,
+        ),
+        Ok(AppBody::Profile(profile)) => ClassificationResult::routed(
+            ClassificationOutcome::AcceptStoreNoDisplay {
+                kind: StoreNoDisplayKind::Profile(profile),
             },
-        },
-        other if RESERVED_TYPES.contains(&other) => ClassificationResult {
+            WAYFARER_PROFILE_V1,
+        ),
+        Ok(AppBody::StatusEvent(event)) => ClassificationResult::routed(
+            ClassificationOutcome::AcceptStoreNoDisplay {
+                kind: StoreNoDisplayKind::StatusEvent(event),
+            },
+            WAYFARER_STATUS_EVENT_V1,
+        ),
+        Ok(AppBody::Notice(notice)) => ClassificationResult::routed(
+            ClassificationOutcome::AcceptStoreNoDisplay {
+                kind: StoreNoDisplayKind::Notice(notice),
+            },
+            WAYFARER_NOTICE_V1,
+        ),
+        Ok(AppBody::Succession(succession)) => ClassificationResult::routed(
+            ClassificationOutcome::AcceptStoreNoDisplay {
+                kind: StoreNoDisplayKind::Succession(succession),
+            },
+            WAYFARER_SUCCESSION_V1,
+        ),
+        Ok(AppBody::Revocation(revocation)) => ClassificationResult::routed(
+            ClassificationOutcome::AcceptStoreNoDisplay {
+                kind: StoreNoDisplayKind::Revocation(revocation),
+            },
+            WAYFARER_REVOCATION_V1,
+        ),
+        Ok(AppBody::RecoveryShare(recovery_share)) => ClassificationResult::routed(
+            ClassificationOutcome::AcceptStoreNoDisplay {
+                kind: StoreNoDisplayKind::RecoveryShare(recovery_share),
+            },
+            WAYFARER_RECOVERY_SHARE_BODY_V1,
+        ),
+        Ok(AppBody::DeviceAnnounce(announce)) => ClassificationResult::routed(
+            ClassificationOutcome::AcceptStoreNoDisplay {
+                kind: StoreNoDisplayKind::DeviceAnnounce(announce),
+            },
+            WAYFARER_DEVICE_ANNOUNCE_V1,
+        ),
+        Ok(AppBody::Reserved { payload_type, .. }) => ClassificationResult {
             outcome: ClassificationOutcome::AcceptStoreNoDisplay {
-                kind: StoreNoDisplayKind::ReservedType(other.to_string()),
+                kind: StoreNoDisplayKind::ReservedType(payload_type.clone()),
             },
-            payload_type: Some(other.to_string()),
+            payload_type: Some(payload_type),
             routed_to: None,
             decoder_ran: false,
         },
-        other => ClassificationResult {
+        Ok(AppBody::Unknown { payload_type }) => ClassificationResult {
             outcome: ClassificationOutcome::UnsupportedSafeSkip {
-                payload_type: other.to_string(),
+                payload_type: payload_type.clone(),
             },
-            payload_type: Some(other.to_string()),
+            payload_type: Some(payload_type),
             routed_to: None,
             decoder_ran: false,
         },
+        Err(err) => match &err {
+            AppBodyError::Malformed { payload_type, .. } => ClassificationResult::routed(
+                ClassificationOutcome::Reject {
+                    reason: err.reason(),
+                },
+                payload_type,
+            ),
+            _ => ClassificationResult::reject(err.reason()),
+        },
     }
 }
 
-fn decode_wayfarer_chat_v1(map: &BTreeMap<String, Value>) -> Result<ChatDisplayPayload, String> {
-    let msg_type = map
-        .get("type")
-        .and_then(|value| match value {
-            Value::Text(text) => Some(text.as_str()),
-            _ => None,
-        })
-        .ok_or_else(|| "type_must_be_text".to_string())?;
-    if msg_type != WAYFARER_CHAT_V1 {
-        return Err("type_mismatch".to_string());
-    }
-
-    let text = required_non_empty_text(map, "text")?;
-    let created_at_unix_ms = required_u64_integer(map, "created_at_unix_ms")?;
-
-    Ok(ChatDisplayPayload {
-        text,
-        created_at_unix_ms,
-    })
-}
-
-fn decode_wayfarer_media_manifest_v1(map: &BTreeMap<String, Value>) -> Result<(), String> {
-    let msg_type = map
-        .get("type")
-        .and_then(|value| match value {
-            Value::Text(text) => Some(text.as_str()),
-            _ => None,
-        })
-        .ok_or_else(|| "type_must_be_text".to_string())?;
-    if msg_type != WAYFARER_MEDIA_MANIFEST_V1 {
-        return Err("type_mismatch".to_string());
-    }
-
-    let transfer_ref = map
-        .get("transfer_ref")
-        .ok_or_else(|| "transfer_ref_required".to_string())?;
-    let Value::Text(transfer_ref) = transfer_ref else {
-        return Err("transfer_ref_must_be_text".to_string());
-    };
-    if transfer_ref.is_empty() {
-        return Err("transfer_ref_required".to_string());
-    }
-
-    let media_kind = required_non_empty_text(map, "media_kind")?;
-    if !MEDIA_KINDS.contains(&media_kind.as_str()) {
-        return Err("media_kind_invalid".to_string());
-    }
-
-    let assets = map
-        .get("assets")
-        .ok_or_else(|| "assets_required".to_string())?;
-    let Value::Array(assets) = assets else {
-        return Err("assets_must_be_array".to_string());
-    };
-    if assets.is_empty() {
-        return Err("assets_must_be_non_empty".to_string());
-    }
-
-    for (index, asset) in assets.iter().enumerate() {
-        let asset_map = value_as_text_keyed_map(asset.clone(), &format!("assets[{index}]"))?;
-        let _asset_ref = required_non_empty_text(&asset_map, "asset_ref")?;
-        let _mime_type = required_non_empty_text(&asset_map, "mime_type")?;
-        let _byte_length = required_u64_integer(&asset_map, "byte_length")?;
-        if let Some(name) = asset_map.get("name") {
-            if !matches!(name, Value::Text(_)) {
-                return Err(format!("assets[{index}].name_must_be_text"));
-            }
-        }
-    }
-
-    if let Some(caption) = map.get("caption") {
-        if !matches!(caption, Value::Text(_)) {
-            return Err("caption_must_be_text".to_string());
-        }
-    }
-
-    let _created_at_unix_ms = required_u64_integer(map, "created_at_unix_ms")?;
-    Ok(())
-}
-
-fn value_as_text_keyed_map(value: Value, context: &str) -> Result<BTreeMap<String, Value>, String> {
-    let Value::Map(entries) = value else {
-        if context == "top_level" {
-            return Err("top_level_must_be_map".to_string());
-        }
-        return Err(format!("{context}_must_be_map"));
-    };
-
-    let mut out = BTreeMap::new();
-    for (key, value) in entries {
-        let Value::Text(key) = key else {
-            if context == "top_level" {
-                return Err("top_level_keys_must_be_text".to_string());
-            }
-            return Err(format!("{context}_keys_must_be_text"));
-        };
-        if out.insert(key, value).is_some() {
-            return Err(format!("{context}_duplicate_keys"));
-        }
-    }
-
-    Ok(out)
-}
-
-fn required_non_empty_text(map: &BTreeMap<String, Value>, key: &str) -> Result<String, String> {
-    let value = map.get(key).ok_or_else(|| format!("{key}_required"))?;
-    let Value::Text(text) = value else {
-        return Err(format!("{key}_must_be_text"));
-    };
-    if text.is_empty() {
-        return Err(format!("{key}_must_be_non_empty"));
-    }
-    Ok(text.clone())
-}
-
-fn required_u64_integer(map: &BTreeMap<String, Value>, key: &str) -> Result<u64, String> {
-    let value = map.get(key).ok_or_else(|| format!("{key}_required"))?;
-    let Value::Integer(integer) = value else {
-        return Err(format!("{key}_must_be_integer"));
-    };
-    let signed = i128::try_from(integer.clone()).map_err(|_| format!("{key}_invalid_integer"))?;
-    if signed < 0 {
-        return Err(format!("{key}_must_be_non_negative"));
-    }
-    u64::try_from(signed).map_err(|_| format!("{key}_invalid_integer"))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::aethos_core::app_body::value_as_text_keyed_map;
+    use crate::aethos_core::protocol::{decode_cbor_value_exact, encode_cbor_value_deterministic};
+    use ciborium::value::Value;
     use std::path::{Path, PathBuf};
 
     use serde::Deserialize;
@@ -594,30 +431,45 @@ mod tests {
     }
 
     #[test]
-    fn reserved_types_accept_store_no_display() {
-        let fixture_names = [
-            "reserved_wayfarer_profile_v1.json",
-            "reserved_wayfarer_reaction_v1.json",
-            "reserved_wayfarer_message_update_v1.json",
-            "reserved_wayfarer_status_event_v1.json",
-            "reserved_wayfarer_notice_v1.json",
-        ];
-
-        for fixture_name in fixture_names {
-            let fixture_raw = std::fs::read_to_string(fixtures_dir().join(fixture_name))
-                .unwrap_or_else(|err| panic!("read {fixture_name}: {err}"));
-            let fixture: Fixture = serde_json::from_str(&fixture_raw)
-                .unwrap_or_else(|err| panic!("parse {fixture_name}: {err}"));
-            let body = decode_hex(&fixture.body_cbor_hex)
-                .unwrap_or_else(|err| panic!("decode {fixture_name}: {err}"));
+    fn notice_body_routes_to_store_no_display() {
+        let notice = NoticeBody {
+            severity: crate::aethos_core::app_body::NoticeSeverity::Warning,
+            title: "Relay maintenance".to_string(),
+            body: "The relay restarts tonight.".to_string(),
+            expires_at_unix_ms: 1_735_776_000_000,
+            created_at_unix_ms: 1_735_689_600_000,
+        };
+        let body = build_wayfarer_notice_body(&notice).expect("build notice body");
 
-            let result = classify_wayfarer_app_body(&body);
-            assert!(matches!(
-                result.outcome,
-                ClassificationOutcome::AcceptStoreNoDisplay { .. }
-            ));
-            assert!(!result.decoder_ran);
-        }
+        let result = classify_wayfarer_app_body(&body);
+        assert_eq!(
+            result.outcome,
+            ClassificationOutcome::AcceptStoreNoDisplay {
+                kind: StoreNoDisplayKind::Notice(notice)
+            }
+        );
+        assert!(result.decoder_ran);
+        assert_eq!(result.routed_to.as_deref(), Some(WAYFARER_NOTICE_V1));
+    }
+
+    #[test]
+    fn succession_body_routes_to_store_no_display() {
+        let record = crate::aethos_core::succession::issue_succession_record(
+            &[1u8; 32],
+            &[2u8; 32],
+            1_735_689_600_000,
+        )
+        .expect("issue succession record");
+        let body = build_wayfarer_succession_body(&record).expect("build succession body");
+
+        let result = classify_wayfarer_app_body(&body);
+        assert_eq!(
+            result.outcome,
+            ClassificationOutcome::AcceptStoreNoDisplay {
+                kind: StoreNoDisplayKind::Succession(SuccessionBody { record })
+            }
+        );
+        assert_eq!(result.routed_to.as_deref(), Some(WAYFARER_SUCCESSION_V1));
     }
 
     #[test]
@@ -643,4 +495,28 @@ mod tests {
             ClassificationOutcome::AcceptDisplay { .. }
         ));
     }
+
+    #[test]
+    fn outbound_reaction_body_routes_to_reaction_kind() {
+        let reaction = ReactionBody {
+            target_manifest_id_hex: "cd".repeat(32),
+            emoji: "\u{1f44d}".to_string(),
+            action: crate::aethos_core::app_body::ReactionAction::Add,
+            created_at_unix_ms: 1_735_689_600_000,
+        };
+        let body = build_wayfarer_reaction_body(&reaction).expect("build reaction body");
+
+        let classification = classify_wayfarer_app_body(&body);
+        assert_eq!(
+            classification.outcome,
+            ClassificationOutcome::AcceptStoreNoDisplay {
+                kind: StoreNoDisplayKind::Reaction(reaction)
+            }
+        );
+        assert!(classification.decoder_ran);
+        assert_eq!(
+            classification.routed_to.as_deref(),
+            Some(WAYFARER_REACTION_V1)
+        );
+    }
 }
@@ -7,6 +7,12 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use serde::{Deserialize, Serialize};
 
+use crate::aethos_core::app_body::{
+    MessageUpdateAction, MessageUpdateBody, NoticeBody, ReactionAction, ReactionBody,
+    StatusEventBody, StatusEventKind,
+};
+use crate::aethos_core::identity_store::profile_state_dir;
+use crate::aethos_core::lan_session::LanDowngradePolicy;
 use crate::relay::client::normalize_http_endpoint;
 
 const CHAT_HISTORY_FILE_NAME: &str = "chat-history.json";
@@ -29,13 +35,17 @@ pub enum ChatDirection {
     Outgoing,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum OutboundState {
     #[serde(alias = "Sending")]
     Sending,
     #[serde(alias = "Sent")]
     Sent,
+    /// The recipient reported the message as stored on one of their devices.
+    Delivered,
+    /// The recipient reported the message as read.
+    Read,
     #[serde(alias = "Failed")]
     Failed { error: String },
 }
@@ -99,6 +109,8 @@ pub struct ChatMessage {
     #[serde(alias = "delivered_at")]
     #[serde(default)]
     pub delivered_at: Option<String>,
+    #[serde(default)]
+    pub read_at: Option<String>,
     #[serde(alias = "outbound_state")]
     #[serde(default)]
     pub outbound_state: Option<OutboundState>,
@@ -115,6 +127,42 @@ pub struct ChatMessage {
     pub attachment: Option<ChatAttachment>,
     #[serde(default)]
     pub media: Option<ChatMediaTransfer>,
+    #[serde(default)]
+    pub author_wayfarer_id: Option<String>,
+    /// Superseded texts, oldest first; the current text is always `text`.
+    #[serde(default)]
+    pub edit_history: Vec<ChatMessageEdit>,
+    #[serde(default)]
+    pub deleted_at_unix_ms: Option<u64>,
+    /// Outbound message that went out as a cleartext v1 envelope because no contact key was known.
+    #[serde(default)]
+    pub unencrypted: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatMessageEdit {
+    pub previous_text: String,
+    pub edited_at_unix_ms: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageUpdateOutcome {
+    Applied,
+    Stale,
+    UnknownTarget,
+    AuthorMismatch,
+}
+
+impl MessageUpdateOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Applied => "applied",
+            Self::Stale => "stale",
+            Self::UnknownTarget => "unknown_target",
+            Self::AuthorMismatch => "author_mismatch",
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -126,6 +174,12 @@ pub struct PersistedChatState {
     #[serde(default)]
     pub new_contacts: Vec<String>,
     pub threads: BTreeMap<String, Vec<ChatMessage>>,
+    /// Latest reaction per reactor and emoji, keyed by the target message's manifest ID.
+    #[serde(default)]
+    pub reactions: BTreeMap<String, Vec<MessageReaction>>,
+    /// Unexpired notices from allow-listed authors, shown as banners outside any thread.
+    #[serde(default)]
+    pub notices: Vec<ReceivedNotice>,
 }
 
 impl Default for PersistedChatState {
@@ -134,10 +188,273 @@ impl Default for PersistedChatState {
             selected_contact: None,
             new_contacts: Vec::new(),
             threads: BTreeMap::new(),
+            reactions: BTreeMap::new(),
+            notices: Vec::new(),
+        }
+    }
+}
+
+impl PersistedChatState {
+    /// Applies `reaction` from `reactor_wayfarer_id`, keeping only the newest add/remove per
+    /// emoji. Returns whether the stored state changed.
+    pub fn apply_reaction(&mut self, reactor_wayfarer_id: &str, reaction: &ReactionBody) -> bool {
+        let removed = reaction.action == ReactionAction::Remove;
+        let entries = self
+            .reactions
+            .entry(reaction.target_manifest_id_hex.clone())
+            .or_default();
+        if let Some(existing) = entries.iter_mut().find(|existing| {
+            existing.reactor_wayfarer_id == reactor_wayfarer_id && existing.emoji == reaction.emoji
+        }) {
+            // On equal timestamps a removal wins so replays converge on every device.
+            if (reaction.created_at_unix_ms, removed)
+                <= (existing.updated_at_unix_ms, existing.removed)
+            {
+                return false;
+            }
+            existing.updated_at_unix_ms = reaction.created_at_unix_ms;
+            existing.removed = removed;
+            return true;
+        }
+
+        entries.push(MessageReaction {
+            emoji: reaction.emoji.clone(),
+            reactor_wayfarer_id: reactor_wayfarer_id.to_string(),
+            updated_at_unix_ms: reaction.created_at_unix_ms,
+            removed,
+        });
+        true
+    }
+
+    /// Applies an edit or delete from `author_wayfarer_id` to the message it targets. Messages
+    /// stored before authors were recorded fall back to the thread contact for incoming
+    /// messages and `local_wayfarer_id` for outgoing ones.
+    pub fn apply_message_update(
+        &mut self,
+        author_wayfarer_id: &str,
+        local_wayfarer_id: &str,
+        update: &MessageUpdateBody,
+    ) -> MessageUpdateOutcome {
+        for (contact, thread) in self.threads.iter_mut() {
+            let Some(message) = thread.iter_mut().find(|message| {
+                message.manifest_id_hex.as_deref() == Some(update.target_manifest_id_hex.as_str())
+            }) else {
+                continue;
+            };
+
+            let original_author =
+                message
+                    .author_wayfarer_id
+                    .as_deref()
+                    .unwrap_or(match message.direction {
+                        ChatDirection::Incoming => contact.as_str(),
+                        ChatDirection::Outgoing => local_wayfarer_id,
+                    });
+            if original_author != author_wayfarer_id {
+                return MessageUpdateOutcome::AuthorMismatch;
+            }
+            if message.deleted_at_unix_ms.is_some() {
+                return MessageUpdateOutcome::Stale;
+            }
+
+            match &update.action {
+                MessageUpdateAction::Edit { text } => {
+                    let is_stale = match message.edit_history.last() {
+                        Some(last_edit) => update.created_at_unix_ms <= last_edit.edited_at_unix_ms,
+                        None => update.created_at_unix_ms < message.created_at_unix_ms,
+                    };
+                    if is_stale {
+                        return MessageUpdateOutcome::Stale;
+                    }
+                    let previous_text = std::mem::replace(&mut message.text, text.clone());
+                    message.edit_history.push(ChatMessageEdit {
+                        previous_text,
+                        edited_at_unix_ms: update.created_at_unix_ms,
+                    });
+                }
+                MessageUpdateAction::Delete => {
+                    // Deletes win regardless of ordering so no copy keeps the retracted content.
+                    message.deleted_at_unix_ms = Some(update.created_at_unix_ms);
+                    message.text.clear();
+                    message.edit_history.clear();
+                    message.attachment = None;
+                }
+            }
+            return MessageUpdateOutcome::Applied;
+        }
+        MessageUpdateOutcome::UnknownTarget
+    }
+
+    /// Advances outgoing messages in `author_wayfarer_id`'s thread from a delivered/read
+    /// receipt. States only move forward, so a late `delivered` never downgrades `read`.
+    /// Returns whether any message changed.
+    pub fn apply_status_event(
+        &mut self,
+        author_wayfarer_id: &str,
+        event: &StatusEventBody,
+    ) -> bool {
+        let Some(thread) = self.threads.get_mut(author_wayfarer_id) else {
+            return false;
+        };
+        let reported_at = (event.created_at_unix_ms / 1000).to_string();
+        let mut changed = false;
+        for message in thread.iter_mut() {
+            if !matches!(message.direction, ChatDirection::Outgoing) {
+                continue;
+            }
+            let Some(manifest_id_hex) = message.manifest_id_hex.as_deref() else {
+                continue;
+            };
+            if !event
+                .target_manifest_id_hexes
+                .iter()
+                .any(|target| target == manifest_id_hex)
+            {
+                continue;
+            }
+
+            if message.delivered_at.is_none() {
+                message.delivered_at = Some(reported_at.clone());
+                changed = true;
+            }
+            if event.status == StatusEventKind::Read && message.read_at.is_none() {
+                message.read_at = Some(reported_at.clone());
+                changed = true;
+            }
+            let next_state = if message.read_at.is_some() {
+                OutboundState::Read
+            } else {
+                OutboundState::Delivered
+            };
+            if message.outbound_state.as_ref() != Some(&next_state) {
+                message.outbound_state = Some(next_state);
+                message.last_sync_error = None;
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Stores `notice` from `author_wayfarer_id` unless it already expired or was stored before.
+    /// Returns whether the stored state changed.
+    pub fn apply_notice(
+        &mut self,
+        author_wayfarer_id: &str,
+        manifest_id_hex: &str,
+        notice: &NoticeBody,
+        now_unix_ms: u64,
+    ) -> bool {
+        if notice.expires_at_unix_ms <= now_unix_ms
+            || self
+                .notices
+                .iter()
+                .any(|existing| existing.manifest_id_hex == manifest_id_hex)
+        {
+            return false;
+        }
+        self.notices.push(ReceivedNotice {
+            manifest_id_hex: manifest_id_hex.to_string(),
+            author_wayfarer_id: author_wayfarer_id.to_string(),
+            severity: notice.severity.as_str().to_string(),
+            title: notice.title.clone(),
+            body: notice.body.clone(),
+            created_at_unix_ms: notice.created_at_unix_ms,
+            expires_at_unix_ms: notice.expires_at_unix_ms,
+            dismissed: false,
+        });
+        true
+    }
+
+    pub fn dismiss_notice(&mut self, manifest_id_hex: &str) -> bool {
+        match self
+            .notices
+            .iter_mut()
+            .find(|notice| notice.manifest_id_hex == manifest_id_hex && !notice.dismissed)
+        {
+            Some(notice) => {
+                notice.dismissed = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Drops expired notices; returns whether any were removed.
+    pub fn prune_expired_notices(&mut self, now_unix_ms: u64) -> bool {
+        let before = self.notices.len();
+        self.notices
+            .retain(|notice| notice.expires_at_unix_ms > now_unix_ms);
+        self.notices.len() != before
+    }
+
+    /// Moves the thread for `old_contact` onto `new_contact` after a verified identity
+    /// succession, merging with any thread already under the new ID. Returns whether the
+    /// stored state changed.
+    pub fn migrate_contact_thread(&mut self, old_contact: &str, new_contact: &str) -> bool {
+        if old_contact == new_contact {
+            return false;
+        }
+        let mut changed = false;
+        if let Some(moved) = self.threads.remove(old_contact) {
+            let thread = self.threads.entry(new_contact.to_string()).or_default();
+            thread.extend(moved);
+            thread.sort_by_key(|message| message.created_at_unix_ms);
+            thread.dedup_by(|left, right| left.msg_id == right.msg_id);
+            changed = true;
+        }
+        if self.selected_contact.as_deref() == Some(old_contact) {
+            self.selected_contact = Some(new_contact.to_string());
+            changed = true;
+        }
+        if let Some(position) = self.new_contacts.iter().position(|id| id == old_contact) {
+            self.new_contacts[position] = new_contact.to_string();
+            self.new_contacts.sort();
+            self.new_contacts.dedup();
+            changed = true;
+        }
+        changed
+    }
+
+    /// Active reactors per emoji for the message with `manifest_id_hex`.
+    pub fn reaction_summary(&self, manifest_id_hex: &str) -> BTreeMap<String, Vec<String>> {
+        let mut summary: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for reaction in self.reactions.get(manifest_id_hex).into_iter().flatten() {
+            if !reaction.removed {
+                summary
+                    .entry(reaction.emoji.clone())
+                    .or_default()
+                    .push(reaction.reactor_wayfarer_id.clone());
+            }
         }
+        summary
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageReaction {
+    pub emoji: String,
+    pub reactor_wayfarer_id: String,
+    pub updated_at_unix_ms: u64,
+    /// Tombstone for a removed reaction so an older add cannot resurrect it.
+    #[serde(default)]
+    pub removed: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceivedNotice {
+    pub manifest_id_hex: String,
+    pub author_wayfarer_id: String,
+    pub severity: String,
+    pub title: String,
+    pub body: String,
+    pub created_at_unix_ms: u64,
+    pub expires_at_unix_ms: u64,
+    #[serde(default)]
+    pub dismissed: bool,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AppSettings {
@@ -156,6 +473,33 @@ pub struct AppSettings {
     #[serde(alias = "enter_to_send")]
     #[serde(default = "default_enter_to_send")]
     pub enter_to_send: bool,
+    /// Contacts that should not receive `read` receipts; `delivered` receipts are always sent.
+    #[serde(default)]
+    pub read_receipts_suppressed_for: Vec<String>,
+    /// Authors whose `wayfarer.notice.v1` bodies are shown; notices from anyone else are dropped.
+    #[serde(default)]
+    pub notice_author_allowlist: Vec<String>,
+    /// Whether LAN TCP sessions may fall back to plaintext for peers without encryption support.
+    #[serde(default)]
+    pub lan_downgrade_policy: LanDowngradePolicy,
+    /// Refuse to send cleartext v1 envelopes to contacts whose key is not yet known.
+    #[serde(default)]
+    pub refuse_unencrypted_messages: bool,
+}
+
+impl AppSettings {
+    pub fn read_receipts_enabled_for(&self, contact: &str) -> bool {
+        !self
+            .read_receipts_suppressed_for
+            .iter()
+            .any(|suppressed| suppressed == contact)
+    }
+
+    pub fn accepts_notice_from(&self, author_wayfarer_id: &str) -> bool {
+        self.notice_author_allowlist
+            .iter()
+            .any(|allowed| allowed == author_wayfarer_id)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -240,6 +584,10 @@ impl Default for AppSettings {
             relay_endpoints: vec![DEFAULT_RELAY_ENDPOINT.to_string()],
             message_ttl_seconds: DEFAULT_MESSAGE_TTL_SECONDS,
             enter_to_send: DEFAULT_ENTER_TO_SEND,
+            read_receipts_suppressed_for: Vec::new(),
+            notice_author_allowlist: Vec::new(),
+            lan_downgrade_policy: LanDowngradePolicy::default(),
+            refuse_unencrypted_messages: false,
         }
     }
 }
@@ -414,11 +762,36 @@ fn normalize_settings(settings: &mut AppSettings) {
     settings.message_ttl_seconds = settings
         .message_ttl_seconds
         .clamp(MIN_MESSAGE_TTL_SECONDS, MAX_MESSAGE_TTL_SECONDS);
+    settings
+        .read_receipts_suppressed_for
+        .iter_mut()
+        .for_each(|contact| *contact = contact.trim().to_string());
+    settings
+        .read_receipts_suppressed_for
+        .retain(|contact| !contact.is_empty());
+    settings.read_receipts_suppressed_for.sort();
+    settings.read_receipts_suppressed_for.dedup();
+    settings
+        .notice_author_allowlist
+        .iter_mut()
+        .for_each(|author| *author = author.trim().to_ascii_lowercase());
+    settings
+        .notice_author_allowlist
+        .retain(|author| !author.is_empty());
+    settings.notice_author_allowlist.sort();
+    settings.notice_author_allowlist.dedup();
 }
 
 pub fn normalize_chat_state(chat: &mut PersistedChatState) {
     chat.new_contacts.sort();
     chat.new_contacts.dedup();
+    chat.reactions.retain(|_, reactions| !reactions.is_empty());
+    for reactions in chat.reactions.values_mut() {
+        reactions.sort_by(|left, right| {
+            (&left.emoji, &left.reactor_wayfarer_id)
+                .cmp(&(&right.emoji, &right.reactor_wayfarer_id))
+        });
+    }
 }
 
 fn normalize_encounter_activity_state(state: &mut EncounterActivityState) {
@@ -444,6 +817,10 @@ fn ensure_parent_dir(path: &Path) -> Result<(), String> {
 }
 
 fn app_state_base_dir() -> PathBuf {
+    profile_state_dir(app_state_root_dir())
+}
+
+fn app_state_root_dir() -> PathBuf {
     if let Ok(override_dir) = std::env::var("AETHOS_STATE_DIR") {
         if !override_dir.trim().is_empty() {
             return PathBuf::from(override_dir);
@@ -586,6 +963,222 @@ mod tests {
         assert!(!persisted.trim().is_empty());
     }
 
+    #[test]
+    fn reactions_aggregate_per_message_and_latest_action_wins() {
+        let target = "ab".repeat(32);
+        let reaction = |emoji: &str, action, created_at_unix_ms| ReactionBody {
+            target_manifest_id_hex: target.clone(),
+            emoji: emoji.to_string(),
+            action,
+            created_at_unix_ms,
+        };
+        let alice = "a".repeat(64);
+        let bob = "b".repeat(64);
+
+        let mut chat = PersistedChatState::default();
+        assert!(chat.apply_reaction(&alice, &reaction("\u{1f44d}", ReactionAction::Add, 10)));
+        assert!(chat.apply_reaction(&bob, &reaction("\u{1f44d}", ReactionAction::Add, 11)));
+        assert!(chat.apply_reaction(&bob, &reaction("\u{1f525}", ReactionAction::Add, 12)));
+        assert!(!chat.apply_reaction(&alice, &reaction("\u{1f44d}", ReactionAction::Add, 10)));
+
+        assert!(chat.apply_reaction(&bob, &reaction("\u{1f525}", ReactionAction::Remove, 13)));
+        assert!(!chat.apply_reaction(&bob, &reaction("\u{1f525}", ReactionAction::Add, 12)));
+
+        let summary = chat.reaction_summary(&target);
+        assert_eq!(summary.len(), 1);
+        assert_eq!(summary["\u{1f44d}"], vec![alice, bob]);
+    }
+
+    fn test_message(manifest_id_hex: &str, direction: ChatDirection, text: &str) -> ChatMessage {
+        ChatMessage {
+            msg_id: format!("item-{manifest_id_hex}"),
+            text: text.to_string(),
+            timestamp: "0".to_string(),
+            created_at_unix: 1,
+            created_at_unix_ms: 1_000,
+            direction,
+            seen: true,
+            manifest_id_hex: Some(manifest_id_hex.to_string()),
+            delivered_at: None,
+            read_at: None,
+            outbound_state: None,
+            expires_at_unix_ms: None,
+            last_sync_attempt_unix_ms: None,
+            last_sync_error: None,
+            attachment: None,
+            media: None,
+            author_wayfarer_id: None,
+            edit_history: Vec::new(),
+            deleted_at_unix_ms: None,
+            unencrypted: false,
+        }
+    }
+
+    #[test]
+    fn message_updates_require_original_author_and_keep_edit_history() {
+        let contact = "c".repeat(64);
+        let local = "d".repeat(64);
+        let incoming = "01".repeat(32);
+        let outgoing = "02".repeat(32);
+        let mut chat = PersistedChatState::default();
+        chat.threads.insert(
+            contact.clone(),
+            vec![
+                test_message(&incoming, ChatDirection::Incoming, "helo"),
+                test_message(&outgoing, ChatDirection::Outgoing, "mine"),
+            ],
+        );
+        let edit = |target: &str, text: &str, created_at_unix_ms| MessageUpdateBody {
+            target_manifest_id_hex: target.to_string(),
+            action: MessageUpdateAction::Edit {
+                text: text.to_string(),
+            },
+            created_at_unix_ms,
+        };
+
+        assert_eq!(
+            chat.apply_message_update(&contact, &local, &edit(&outgoing, "theirs", 2_000)),
+            MessageUpdateOutcome::AuthorMismatch
+        );
+        assert_eq!(
+            chat.apply_message_update(&contact, &local, &edit(&incoming, "hello", 2_000)),
+            MessageUpdateOutcome::Applied
+        );
+        assert_eq!(
+            chat.apply_message_update(&contact, &local, &edit(&incoming, "hel", 1_500)),
+            MessageUpdateOutcome::Stale
+        );
+        assert_eq!(
+            chat.apply_message_update(&contact, &local, &edit(&"03".repeat(32), "x", 2_000)),
+            MessageUpdateOutcome::UnknownTarget
+        );
+
+        let message = &chat.threads[&contact][0];
+        assert_eq!(message.text, "hello");
+        assert_eq!(
+            message.edit_history,
+            vec![ChatMessageEdit {
+                previous_text: "helo".to_string(),
+                edited_at_unix_ms: 2_000,
+            }]
+        );
+
+        let delete = MessageUpdateBody {
+            target_manifest_id_hex: outgoing.clone(),
+            action: MessageUpdateAction::Delete,
+            created_at_unix_ms: 1_200,
+        };
+        assert_eq!(
+            chat.apply_message_update(&local, &local, &delete),
+            MessageUpdateOutcome::Applied
+        );
+        let deleted = &chat.threads[&contact][1];
+        assert!(deleted.text.is_empty());
+        assert_eq!(deleted.deleted_at_unix_ms, Some(1_200));
+        assert_eq!(
+            chat.apply_message_update(&local, &local, &edit(&outgoing, "back", 3_000)),
+            MessageUpdateOutcome::Stale
+        );
+    }
+
+    #[test]
+    fn status_events_advance_outgoing_messages_monotonically() {
+        let contact = "c".repeat(64);
+        let incoming = "01".repeat(32);
+        let outgoing = "02".repeat(32);
+        let mut chat = PersistedChatState::default();
+        chat.threads.insert(
+            contact.clone(),
+            vec![
+                test_message(&incoming, ChatDirection::Incoming, "hi"),
+                test_message(&outgoing, ChatDirection::Outgoing, "hello"),
+            ],
+        );
+        let event = |status, created_at_unix_ms| StatusEventBody {
+            status,
+            target_manifest_id_hexes: vec![incoming.clone(), outgoing.clone()],
+            created_at_unix_ms,
+        };
+
+        assert!(!chat.apply_status_event(&"e".repeat(64), &event(StatusEventKind::Read, 5_000)));
+        assert!(chat.apply_status_event(&contact, &event(StatusEventKind::Read, 5_000)));
+        assert!(!chat.apply_status_event(&contact, &event(StatusEventKind::Delivered, 6_000)));
+
+        let thread = &chat.threads[&contact];
+        assert_eq!(thread[0].outbound_state, None);
+        assert_eq!(thread[1].outbound_state, Some(OutboundState::Read));
+        assert_eq!(thread[1].delivered_at.as_deref(), Some("5"));
+        assert_eq!(thread[1].read_at.as_deref(), Some("5"));
+    }
+
+    #[test]
+    fn read_receipt_suppression_is_per_contact() {
+        let mut settings = AppSettings {
+            read_receipts_suppressed_for: vec![" b ".to_string(), "a".to_string(), "b".to_string()],
+            ..AppSettings::default()
+        };
+        normalize_settings(&mut settings);
+
+        assert_eq!(settings.read_receipts_suppressed_for, vec!["a", "b"]);
+        assert!(!settings.read_receipts_enabled_for("a"));
+        assert!(settings.read_receipts_enabled_for("c"));
+        assert!(!settings.accepts_notice_from("a"));
+    }
+
+    #[test]
+    fn notices_are_deduplicated_dismissable_and_expire() {
+        let author = "a".repeat(64);
+        let notice = NoticeBody {
+            severity: crate::aethos_core::app_body::NoticeSeverity::Info,
+            title: "Relay maintenance".to_string(),
+            body: "Tonight at 22:00.".to_string(),
+            expires_at_unix_ms: 5_000,
+            created_at_unix_ms: 1_000,
+        };
+        let manifest = "ab".repeat(32);
+
+        let mut chat = PersistedChatState::default();
+        assert!(!chat.apply_notice(&author, &manifest, &notice, 5_000));
+        assert!(chat.apply_notice(&author, &manifest, &notice, 2_000));
+        assert!(!chat.apply_notice(&author, &manifest, &notice, 2_000));
+        assert_eq!(chat.notices[0].severity, "info");
+
+        assert!(chat.dismiss_notice(&manifest));
+        assert!(!chat.dismiss_notice(&manifest));
+        assert!(!chat.prune_expired_notices(4_999));
+        assert!(chat.prune_expired_notices(5_000));
+        assert!(chat.notices.is_empty());
+    }
+
+    #[test]
+    fn contact_thread_migrates_to_successor_identity() {
+        let old_contact = "a".repeat(64);
+        let new_contact = "b".repeat(64);
+        let mut chat = PersistedChatState {
+            selected_contact: Some(old_contact.clone()),
+            new_contacts: vec![old_contact.clone()],
+            ..PersistedChatState::default()
+        };
+        let mut first = test_message(&"01".repeat(32), ChatDirection::Incoming, "before");
+        first.created_at_unix_ms = 1;
+        let mut second = test_message(&"02".repeat(32), ChatDirection::Incoming, "after");
+        second.msg_id = "second".to_string();
+        second.created_at_unix_ms = 2;
+        chat.threads.insert(old_contact.clone(), vec![first]);
+        chat.threads.insert(new_contact.clone(), vec![second]);
+
+        assert!(chat.migrate_contact_thread(&old_contact, &new_contact));
+        assert!(!chat.migrate_contact_thread(&old_contact, &new_contact));
+        assert!(!chat.threads.contains_key(&old_contact));
+        let texts: Vec<_> = chat.threads[&new_contact]
+            .iter()
+            .map(|message| message.text.as_str())
+            .collect();
+        assert_eq!(texts, vec!["before", "after"]);
+        assert_eq!(chat.selected_contact.as_deref(), Some(new_contact.as_str()));
+        assert_eq!(chat.new_contacts, vec![new_contact]);
+    }
+
     #[test]
     fn encounter_activity_ring_retains_recent_ordered_events() {
         let mut state = EncounterActivityState::default();
@@ -17,7 +17,6 @@ mod relay;
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
 use std::hash::{Hash, Hasher};
-use std::io::{Read, Write};
 use std::net::{SocketAddr, TcpListener, TcpStream, UdpSocket};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
@@ -27,15 +26,19 @@ use std::thread;
 use std::time::{Duration, Instant};
 
 use app_body::{
-    build_wayfarer_chat_body, build_wayfarer_media_manifest_body, classify_wayfarer_app_body,
-    ClassificationOutcome, OutboundMediaAsset, OutboundMediaManifestInput,
+    build_wayfarer_chat_body, build_wayfarer_device_announce_body,
+    build_wayfarer_media_manifest_body, build_wayfarer_message_update_body,
+    build_wayfarer_notice_body, build_wayfarer_profile_body, build_wayfarer_reaction_body,
+    build_wayfarer_recovery_share_body, build_wayfarer_revocation_body,
+    build_wayfarer_status_event_body, build_wayfarer_succession_body, classify_wayfarer_app_body,
+    ClassificationOutcome, OutboundMediaAsset, OutboundMediaManifestInput, StoreNoDisplayKind,
 };
 use app_state::{
     load_app_settings, load_chat_state, load_encounter_activity_state, normalize_chat_state,
     now_unix_ms, now_unix_secs, save_app_settings, save_chat_state, save_encounter_activity_state,
     AppSettings, ChatAttachment, ChatDirection, ChatMediaTransfer, ChatMessage,
     EncounterActivityCode, EncounterActivityEvent, EncounterActivityState, MediaTransferStatus,
-    OutboundState, PersistedChatState,
+    MessageUpdateOutcome, OutboundState, PersistedChatState,
 };
 use base64::Engine;
 use image::{imageops::FilterType, ImageBuffer, Luma, Rgba, RgbaImage};
@@ -46,34 +49,76 @@ use socket2::{Domain, Protocol, Socket, Type};
 use tauri::Emitter;
 use url::Url;
 
+use crate::aethos_core::app_body::{
+    MessageUpdateAction, MessageUpdateBody, NoticeBody, NoticeSeverity, ProfileBody,
+    ReactionAction, ReactionBody, StatusEventBody, StatusEventKind, MAX_PROFILE_DISPLAY_NAME_BYTES,
+    MAX_STATUS_EVENT_TARGETS,
+};
 use crate::aethos_core::ble_discovery::{
     discovery_adapter_from_env, BleDiscoveryGate, BleDiscoverySource, DiscoverySignal,
 };
+use crate::aethos_core::contact_card::{
+    contact_card_from_qr_text, contact_card_qr_text, issue_contact_card, verify_contact_card,
+};
+use crate::aethos_core::device_link::device_certificate_code;
 use crate::aethos_core::encounter_orchestration::{
     BearerAdapter, EncounterManager, TransitionReason,
 };
 use crate::aethos_core::gossip_sync::record_local_payload as gossip_record_local_payload;
 use crate::aethos_core::gossip_sync::{
-    build_hello_frame as build_gossip_hello_frame,
+    advertise_capability, build_hello_frame as build_gossip_hello_frame,
     build_relay_ingest_frame as build_gossip_relay_ingest_frame,
     build_request_frame as build_gossip_request_frame,
-    build_summary_frame as build_gossip_summary_frame, import_transfer_items,
-    missing_item_ids as gossip_missing_item_ids, parse_frame as parse_gossip_frame,
+    build_summary_frame as build_gossip_summary_frame,
+    build_summary_frame_for_peer as build_gossip_summary_frame_for_peer,
+    build_summary_page_frame as build_gossip_summary_page_frame,
+    build_summary_page_request_frame as build_gossip_summary_page_request_frame,
+    import_transfer_items, missing_item_ids as gossip_missing_item_ids, negotiate_session_profile,
+    parse_frame as parse_gossip_frame, peer_supports,
+    select_request_item_ids_from_summary_page as gossip_select_request_item_ids_from_summary_page,
     select_request_item_ids_from_summary_with_candidates as gossip_select_request_item_ids_from_summary,
-    serialize_frame as serialize_gossip_frame, transfer_items_for_request as gossip_transfer_items,
-    GossipSyncFrame, ReceiptFrame, GOSSIP_LAN_PORT, MAX_FRAME_BYTES, MAX_TRANSFER_BYTES,
-    MAX_TRANSFER_ITEMS,
+    serialize_frame as serialize_gossip_frame, summary_has_unpreviewed_items,
+    transfer_items_for_request_with_shadow_context as gossip_transfer_items_for_peer,
+    GossipCapability, GossipSessionProfile, GossipSyncFrame, HelloFrame, ReceiptFrame,
+    GOSSIP_LAN_PORT, MAX_FRAME_BYTES,
 };
 use crate::aethos_core::identity_store::{
-    delete_wayfarer_id, ensure_local_identity, load_contact_aliases, load_local_signing_key_seed,
-    regenerate_local_identity, save_contact_aliases,
+    active_profile, add_social_recovery_share, apply_contact_profile, apply_contact_succession,
+    approve_device_pairing as approve_stored_device_pairing,
+    begin_device_pairing as begin_stored_device_pairing,
+    begin_social_recovery as begin_stored_social_recovery,
+    complete_device_pairing as complete_stored_device_pairing, create_profile, delete_wayfarer_id,
+    ensure_local_identity, export_identity_backup as export_stored_identity_backup,
+    identity_passphrase_enabled, identity_recovery_phrase as stored_identity_recovery_phrase,
+    list_profiles, load_contact_aliases, load_contact_keys, load_contact_profiles,
+    load_contact_successions, load_held_recovery_shares, load_linked_devices,
+    load_local_device_certificate, load_local_signing_key_seed, load_revoked_identities,
+    local_identity_locked, local_revocation_certificate, pinned_profile, record_contact_device,
+    record_contact_device_certificate, record_contact_pubkey, record_identity_revocation,
+    regenerate_local_identity, remove_identity_passphrase as remove_stored_identity_passphrase,
+    resolve_contact_succession, restore_identity_backup as restore_stored_identity_backup,
+    restore_identity_from_recovery_phrase, rotate_local_identity, save_contact_aliases,
+    save_contact_keys, save_contact_profiles, set_contact_verified,
+    set_identity_passphrase as set_stored_identity_passphrase,
+    social_recovery_status as stored_social_recovery_status, split_identity_recovery_shares,
+    store_held_recovery_share, switch_profile, unlink_device as unlink_stored_device,
+    unlock_local_identity, ContactKey, ContactProfile, LinkedDevice, SocialRecoveryProgress,
 };
+use crate::aethos_core::lan_session::{LanDowngradePolicy, LanSession};
 use crate::aethos_core::logging::{
     app_log_file_path, log_info, log_verbose, set_verbose_logging_enabled, verbose_logging_enabled,
 };
 use crate::aethos_core::protocol::{
-    build_envelope_payload_b64, bytes_to_hex_lower, is_valid_wayfarer_id,
+    build_blinded_envelope_v2_payload_b64, build_device_signed_envelope_v2_payload_b64,
+    build_envelope_payload_b64, build_multi_recipient_envelope_v2_payload_b64, bytes_to_hex_lower,
+    is_valid_wayfarer_id, EnvelopeLifetime,
+};
+use crate::aethos_core::revocation::{
+    revocation_certificate_code, revocation_certificate_from_code, REVOCATION_RETENTION_MS,
 };
+use crate::aethos_core::safety_number::compute_safety_number;
+use crate::aethos_core::social_recovery::verify_recovery_share;
+use crate::aethos_core::succession::verify_succession_record;
 use crate::relay::client::{
     close_relay_persistent_session, connect_to_relay_gossipv1_with_auth,
     maybe_send_relay_heartbeat, normalize_http_endpoint, open_relay_persistent_session,
@@ -94,10 +139,10 @@ use media_v1::{
 };
 
 const SHARE_QR_FILE_NAME: &str = "share-wayfarer-qr.png";
+const IDENTITY_BACKUP_FILE_NAME: &str = "aethos-identity-backup.json";
 const CHAT_SNAPSHOT_EVENT: &str = "chat_snapshot";
 const SOUND_EVENT: &str = "sound_event";
 const MAX_INLINE_ATTACHMENT_BYTES: u64 = 2 * 1024 * 1024;
-const LAN_TCP_CAPABILITY: &str = "lan_tcp_transfer_v1";
 const RELAY_PERIODIC_SYNC_INTERVAL_SECS: u64 = 120;
 const RELAY_INBOUND_POLL_WINDOW_MS: u64 = 120;
 const LAN_TCP_FAILURE_COOLDOWN_SECS: u64 = 45;
@@ -465,10 +510,19 @@ impl GossipEncounterState {
 }
 
 #[derive(Debug, Clone)]
+/// Last HELLO heard from a LAN peer over UDP, keyed by source IP. UDP source addresses are
+/// trivially spoofed and frames carry no sender identity, so nothing learned over UDP counts as
+/// authenticating the peer; only a Noise-authenticated TCP session does.
+struct LanPeerHello {
+    hello: HelloFrame,
+    profile: GossipSessionProfile,
+}
+
 struct UdpPeerInteraction {
     encounter: GossipEncounterState,
     latest_summary: Option<crate::aethos_core::gossip_sync::SummaryFrame>,
     relay_ingest_candidates: Vec<String>,
+    summary_paging_started: bool,
     last_seen: Instant,
 }
 
@@ -485,6 +539,7 @@ fn new_udp_peer_interaction(peer_identity: String) -> UdpPeerInteraction {
         encounter,
         latest_summary: None,
         relay_ingest_candidates: Vec::new(),
+        summary_paging_started: false,
         last_seen: Instant::now(),
     }
 }
@@ -718,14 +773,30 @@ struct IdentityView {
     device_id: String,
     verifying_key_b64: String,
     device_name: String,
+    passphrase_protected: bool,
+    linked_device: bool,
+}
+
+/// A device certified by the local root identity; `certificate_code` is what the new device
+/// enters to finish pairing.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LinkedDeviceView {
+    device_id: String,
+    device_name: String,
+    linked_at_unix_ms: u64,
+    certificate_code: String,
 }
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct BootstrapState {
     identity: IdentityView,
+    identity_locked: bool,
     settings: AppSettings,
     contacts: BTreeMap<String, String>,
+    contact_keys: BTreeMap<String, ContactKey>,
+    revoked_contacts: Vec<String>,
     chat: PersistedChatState,
 }
 
@@ -733,9 +804,32 @@ struct BootstrapState {
 #[serde(rename_all = "camelCase")]
 struct ChatSnapshot {
     contacts: BTreeMap<String, String>,
+    profiles: BTreeMap<String, ContactProfile>,
+    contact_keys: BTreeMap<String, ContactKey>,
+    /// Wayfarer IDs whose keys were revoked; new messages from them are dropped.
+    revoked_contacts: Vec<String>,
     chat: PersistedChatState,
 }
 
+fn current_chat_snapshot() -> Result<ChatSnapshot, String> {
+    Ok(ChatSnapshot {
+        contacts: load_contact_aliases()?,
+        profiles: load_contact_profiles()?,
+        contact_keys: load_contact_keys()?,
+        revoked_contacts: load_revoked_identities()?.into_keys().collect(),
+        chat: load_chat_state()?,
+    })
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SafetyNumberView {
+    wayfarer_id: String,
+    digits: String,
+    words: String,
+    verified: bool,
+}
+
 #[derive(Clone, Debug, Serialize)]
 struct SoundEventPayload {
     kind: String,
@@ -790,6 +884,47 @@ struct SendMessageRequest {
     attachment: Option<SendAttachmentRequest>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SendReactionRequest {
+    wayfarer_id: String,
+    target_manifest_id_hex: String,
+    emoji: String,
+    #[serde(default)]
+    remove: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SendMessageUpdateRequest {
+    wayfarer_id: String,
+    target_manifest_id_hex: String,
+    /// New text for an edit; `None` deletes the message for everyone.
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PublishProfileRequest {
+    display_name: String,
+    #[serde(default)]
+    avatar_thumbnail_b64: Option<String>,
+    #[serde(default)]
+    status_line: Option<String>,
+    #[serde(default)]
+    preferred_relays: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PublishNoticeRequest {
+    severity: String,
+    title: String,
+    body: String,
+    expires_in_seconds: u64,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct SendAttachmentRequest {
@@ -851,6 +986,77 @@ struct ShareQrResponse {
     png_base64: String,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DistributeRecoverySharesRequest {
+    threshold: u8,
+    contacts: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReturnRecoveryShareRequest {
+    owner_wayfarer_id: String,
+    to_wayfarer_id: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct IdentityProfileView {
+    name: String,
+    active: bool,
+    wayfarer_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SocialRecoveryView {
+    expected_wayfarer_id: String,
+    collected: usize,
+    threshold: Option<u8>,
+    restored: Option<IdentityView>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportIdentityBackupRequest {
+    passphrase: String,
+    include_contacts: bool,
+    include_settings: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct IdentityBackupExport {
+    wayfarer_id: String,
+    file_path: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RestoreIdentityBackupRequest {
+    contents: String,
+    passphrase: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RestoreRecoveryPhraseRequest {
+    phrase: String,
+    expected_wayfarer_id: Option<String>,
+}
+
+/// Contact recovered from a scanned QR; `signed` is false for legacy bare-ID codes, which
+/// carry no key or profile hints.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct ScannedContact {
+    wayfarer_id: String,
+    display_name: Option<String>,
+    preferred_relays: Vec<String>,
+    signed: bool,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct RelayDiagnosticsRequest {
@@ -993,14 +1199,12 @@ fn bootstrap_state() -> Result<BootstrapState, String> {
             simulated_ble.len()
         ));
     }
-    start_gossip_worker_if_needed(settings.gossip_sync_enabled);
-    set_gossip_enabled(settings.gossip_sync_enabled);
-    start_relay_worker_if_needed();
-    if settings.relay_sync_enabled {
-        request_relay_sync("bootstrap_state");
-    }
-    run_media_housekeeping_best_effort("bootstrap_state", settings.message_ttl_seconds);
     let identity = ensure_local_identity()?;
+    // Workers sign outbound frames, so a locked identity holds them back until unlock.
+    let identity_locked = local_identity_locked()?;
+    if !identity_locked {
+        start_background_workers(&settings, "bootstrap_state");
+    }
     let contacts = load_contact_aliases()?;
     let chat = load_chat_state()?;
 
@@ -1010,28 +1214,533 @@ fn bootstrap_state() -> Result<BootstrapState, String> {
             device_id: identity.device_id,
             verifying_key_b64: identity.verifying_key_b64,
             device_name: identity.device_name,
+            passphrase_protected: identity_passphrase_enabled()?,
+            linked_device: identity.linked_device,
         },
+        identity_locked,
         settings,
         contacts,
+        contact_keys: load_contact_keys()?,
+        revoked_contacts: load_revoked_identities()?.into_keys().collect(),
         chat,
     })
 }
 
+fn start_background_workers(settings: &AppSettings, reason: &str) {
+    start_gossip_worker_if_needed(settings.gossip_sync_enabled);
+    set_gossip_enabled(settings.gossip_sync_enabled);
+    start_relay_worker_if_needed();
+    if settings.relay_sync_enabled {
+        request_relay_sync(reason);
+    }
+    run_media_housekeeping_best_effort(reason, settings.message_ttl_seconds);
+}
+
+/// Replaces the local key with a fresh one and sends every contact a succession record signed
+/// by both keys, so their threads follow the new ID. The old key keeps opening messages for
+/// the grace window.
 #[tauri::command]
 fn rotate_wayfarer_id() -> Result<IdentityView, String> {
-    let identity = regenerate_local_identity()?;
+    let rotation = rotate_local_identity(now_unix_ms())?;
+    let settings = load_app_settings()?;
+    let body_bytes = build_wayfarer_succession_body(&rotation.succession_record)?;
+    let expiry_ms = now_unix_ms().saturating_add(settings.message_ttl_seconds.saturating_mul(1000));
+    let mut notified = 0usize;
+    for contact in load_contact_aliases()?
+        .keys()
+        .filter(|contact| is_valid_wayfarer_id(contact))
+    {
+        if settings.refuse_unencrypted_messages && outbound_envelope_is_unencrypted(contact)? {
+            log_info(&format!(
+                "rotate_wayfarer_id_skipped: to={} reason=no_contact_key",
+                contact
+            ));
+            continue;
+        }
+        let payload = build_local_envelope_payload_b64(
+            contact,
+            &body_bytes,
+            &rotation.previous_signing_seed,
+            expiry_ms,
+        )?;
+        gossip_record_local_payload(&payload, expiry_ms)?;
+        notified += 1;
+    }
+    if notified > 0 {
+        if let Some(runtime) = GOSSIP_RUNTIME.get() {
+            runtime.force_announce.store(true, Ordering::SeqCst);
+            set_gossip_event("announce queued");
+        }
+        if settings.relay_sync_enabled && !settings.relay_endpoints.is_empty() {
+            request_relay_sync("rotate_wayfarer_id");
+        }
+    }
+    log_info(&format!(
+        "wayfarer_id_rotated: previous={} current={} notified={}",
+        rotation.previous_wayfarer_id, rotation.identity.wayfarer_id, notified
+    ));
+    let identity = rotation.identity;
+    Ok(IdentityView {
+        wayfarer_id: identity.wayfarer_id,
+        device_id: identity.device_id,
+        verifying_key_b64: identity.verifying_key_b64,
+        device_name: identity.device_name,
+        passphrase_protected: false,
+        linked_device: identity.linked_device,
+    })
+}
+
+#[tauri::command]
+async fn export_identity_backup(
+    request: ExportIdentityBackupRequest,
+) -> Result<IdentityBackupExport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let settings = if request.include_settings {
+            Some(
+                serde_json::to_value(load_app_settings()?)
+                    .map_err(|err| format!("failed to serialize settings for backup: {err}"))?,
+            )
+        } else {
+            None
+        };
+        let backup = export_stored_identity_backup(
+            &request.passphrase,
+            request.include_contacts,
+            settings,
+            now_unix_ms(),
+        )?;
+        let path = share_qr_file_path().with_file_name(IDENTITY_BACKUP_FILE_NAME);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|err| format!("failed creating backup dir {}: {err}", parent.display()))?;
+        }
+        fs::write(&path, backup)
+            .map_err(|err| format!("failed writing identity backup {}: {err}", path.display()))?;
+        let identity = ensure_local_identity()?;
+        log_info(&format!(
+            "identity_backup_exported: wayfarer_id={} contacts={} settings={}",
+            identity.wayfarer_id, request.include_contacts, request.include_settings
+        ));
+        Ok(IdentityBackupExport {
+            wayfarer_id: identity.wayfarer_id,
+            file_path: path.display().to_string(),
+        })
+    })
+    .await
+    .map_err(|err| format!("export_identity_backup task join failed: {err}"))?
+}
+
+#[tauri::command]
+fn begin_device_pairing(device_name: Option<String>) -> Result<String, String> {
+    let device_name = match device_name.map(|name| name.trim().to_string()) {
+        Some(name) if !name.is_empty() => name,
+        _ => ensure_local_identity()?.device_name,
+    };
+    let code = begin_stored_device_pairing(&device_name)?;
+    log_info(&format!(
+        "device_pairing_started: device_name={device_name}"
+    ));
+    Ok(code)
+}
+
+#[tauri::command]
+fn approve_device_pairing(pairing_code: String) -> Result<LinkedDeviceView, String> {
+    let device = approve_stored_device_pairing(&pairing_code, now_unix_ms())?;
+    let announced = announce_linked_device(&device)?;
+    log_info(&format!(
+        "device_pairing_approved: device_id={} device_name={} announced_to={}",
+        device.device_id, device.device_name, announced
+    ));
+    linked_device_view(device)
+}
+
+/// Sends the new device's certificate to every contact, so they can seal to it before it has
+/// authored anything for them.
+fn announce_linked_device(device: &LinkedDevice) -> Result<usize, String> {
+    let settings = load_app_settings()?;
+    let author_signing_seed = load_local_signing_key_seed()?;
+    let body_bytes = build_wayfarer_device_announce_body(&device.certificate()?)?;
+    let expiry_ms = now_unix_ms().saturating_add(settings.message_ttl_seconds.saturating_mul(1000));
+    let mut announced = 0usize;
+    for contact in load_contact_aliases()?
+        .keys()
+        .filter(|contact| is_valid_wayfarer_id(contact))
+    {
+        if settings.refuse_unencrypted_messages && outbound_envelope_is_unencrypted(contact)? {
+            log_info(&format!(
+                "device_announce_skipped: to={} reason=no_contact_key",
+                contact
+            ));
+            continue;
+        }
+        let payload = build_local_envelope_payload_b64(
+            contact,
+            &body_bytes,
+            &author_signing_seed,
+            expiry_ms,
+        )?;
+        gossip_record_local_payload(&payload, expiry_ms)?;
+        announced += 1;
+    }
+    if announced > 0 {
+        if let Some(runtime) = GOSSIP_RUNTIME.get() {
+            runtime.force_announce.store(true, Ordering::SeqCst);
+            set_gossip_event("announce queued");
+        }
+        if settings.relay_sync_enabled && !settings.relay_endpoints.is_empty() {
+            request_relay_sync("approve_device_pairing");
+        }
+    }
+    Ok(announced)
+}
+
+#[tauri::command]
+fn complete_device_pairing(certificate_code: String) -> Result<IdentityView, String> {
+    let identity = complete_stored_device_pairing(&certificate_code)?;
+    log_info(&format!(
+        "device_pairing_completed: wayfarer_id={} device_id={}",
+        identity.wayfarer_id, identity.device_id
+    ));
+    emit_chat_snapshot_event_best_effort("complete_device_pairing");
+    Ok(IdentityView {
+        wayfarer_id: identity.wayfarer_id,
+        device_id: identity.device_id,
+        verifying_key_b64: identity.verifying_key_b64,
+        device_name: identity.device_name,
+        passphrase_protected: false,
+        linked_device: identity.linked_device,
+    })
+}
+
+#[tauri::command]
+fn linked_devices() -> Result<Vec<LinkedDeviceView>, String> {
+    load_linked_devices()?
+        .into_iter()
+        .map(linked_device_view)
+        .collect()
+}
+
+/// Splits the identity key and sends one sealed share to each chosen contact.
+#[tauri::command]
+fn distribute_recovery_shares(request: DistributeRecoverySharesRequest) -> Result<usize, String> {
+    let count = u8::try_from(request.contacts.len())
+        .map_err(|_| "too many recovery contacts".to_string())?;
+    let shares = split_identity_recovery_shares(request.threshold, count)?;
+    let author_signing_seed = load_local_signing_key_seed()?;
+    let expiry_ms = now_unix_ms().saturating_add(
+        load_app_settings()?
+            .message_ttl_seconds
+            .saturating_mul(1000),
+    );
+    // Build every envelope before queueing any, so a contact without a known key leaves no
+    // partial split behind.
+    let payloads = request
+        .contacts
+        .iter()
+        .zip(&shares)
+        .map(|(contact, share)| {
+            build_sealed_local_envelope_payload_b64(
+                contact.trim(),
+                &build_wayfarer_recovery_share_body(share)?,
+                &author_signing_seed,
+                expiry_ms,
+            )
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    for payload in &payloads {
+        gossip_record_local_payload(payload, expiry_ms)?;
+    }
+    if let Some(runtime) = GOSSIP_RUNTIME.get() {
+        runtime.force_announce.store(true, Ordering::SeqCst);
+        set_gossip_event("announce queued");
+    }
+    log_info(&format!(
+        "recovery_shares_distributed: threshold={} shares={}",
+        request.threshold,
+        payloads.len()
+    ));
+    Ok(payloads.len())
+}
+
+/// Owner wayfarer IDs this install holds a recovery share for.
+#[tauri::command]
+fn held_recovery_shares() -> Result<Vec<String>, String> {
+    Ok(load_held_recovery_shares()?.into_keys().collect())
+}
+
+/// Sends a held share back to the owner's recovering device, which must already be a contact.
+#[tauri::command]
+fn return_recovery_share(request: ReturnRecoveryShareRequest) -> Result<(), String> {
+    let owner_wayfarer_id = request.owner_wayfarer_id.trim();
+    let share_b64 = load_held_recovery_shares()?
+        .remove(owner_wayfarer_id)
+        .ok_or_else(|| "no recovery share held for that identity".to_string())?;
+    let share = base64::engine::general_purpose::STANDARD
+        .decode(share_b64)
+        .map_err(|err| format!("failed to decode held recovery share: {err}"))?;
+    let expiry_ms = now_unix_ms().saturating_add(
+        load_app_settings()?
+            .message_ttl_seconds
+            .saturating_mul(1000),
+    );
+    let payload = build_sealed_local_envelope_payload_b64(
+        request.to_wayfarer_id.trim(),
+        &build_wayfarer_recovery_share_body(&share)?,
+        &load_local_signing_key_seed()?,
+        expiry_ms,
+    )?;
+    gossip_record_local_payload(&payload, expiry_ms)?;
+    if let Some(runtime) = GOSSIP_RUNTIME.get() {
+        runtime.force_announce.store(true, Ordering::SeqCst);
+        set_gossip_event("announce queued");
+    }
+    log_info(&format!(
+        "recovery_share_returned: owner={} to={}",
+        owner_wayfarer_id,
+        request.to_wayfarer_id.trim()
+    ));
+    Ok(())
+}
+
+#[tauri::command]
+fn identity_profiles() -> Result<Vec<IdentityProfileView>, String> {
+    Ok(list_profiles()?
+        .into_iter()
+        .map(|profile| IdentityProfileView {
+            name: profile.name,
+            active: profile.active,
+            wayfarer_id: profile.wayfarer_id,
+        })
+        .collect())
+}
+
+#[tauri::command]
+fn create_identity_profile(name: String) -> Result<Vec<IdentityProfileView>, String> {
+    create_profile(&name)?;
+    log_info(&format!("identity_profile_created: name={}", name.trim()));
+    identity_profiles()
+}
+
+/// Switches profile and restarts the app, so the gossip and relay workers, which hold the
+/// previous profile's identity and stores, only ever run for the active profile.
+#[tauri::command]
+fn switch_identity_profile(app: tauri::AppHandle, name: String) -> Result<(), String> {
+    if pinned_profile()?.is_some() {
+        return Err("profile is pinned by --aethos-profile for this process".to_string());
+    }
+    let previous = active_profile();
+    switch_profile(&name)?;
+    log_info(&format!(
+        "identity_profile_switched: previous={previous} current={}",
+        active_profile()
+    ));
+    app.restart()
+}
+
+#[tauri::command]
+fn begin_social_recovery(wayfarer_id: String) -> Result<SocialRecoveryView, String> {
+    let progress = begin_stored_social_recovery(&wayfarer_id)?;
+    log_info(&format!(
+        "social_recovery_started: expected={}",
+        progress.expected_wayfarer_id
+    ));
+    social_recovery_view(progress)
+}
+
+#[tauri::command]
+fn social_recovery_status() -> Result<Option<SocialRecoveryView>, String> {
+    stored_social_recovery_status()?
+        .map(social_recovery_view)
+        .transpose()
+}
+
+fn social_recovery_view(progress: SocialRecoveryProgress) -> Result<SocialRecoveryView, String> {
+    Ok(SocialRecoveryView {
+        expected_wayfarer_id: progress.expected_wayfarer_id,
+        collected: progress.collected,
+        threshold: progress.threshold,
+        restored: progress
+            .restored
+            .map(|identity| -> Result<IdentityView, String> {
+                Ok(IdentityView {
+                    wayfarer_id: identity.wayfarer_id,
+                    device_id: identity.device_id,
+                    verifying_key_b64: identity.verifying_key_b64,
+                    device_name: identity.device_name,
+                    passphrase_protected: identity_passphrase_enabled()?,
+                    linked_device: identity.linked_device,
+                })
+            })
+            .transpose()?,
+    })
+}
+
+#[tauri::command]
+fn unlink_device(device_id: String) -> Result<bool, String> {
+    let removed = unlink_stored_device(device_id.trim())?;
+    log_info(&format!(
+        "device_unlinked: device_id={} removed={removed}",
+        device_id.trim()
+    ));
+    Ok(removed)
+}
+
+fn linked_device_view(device: LinkedDevice) -> Result<LinkedDeviceView, String> {
+    Ok(LinkedDeviceView {
+        certificate_code: device_certificate_code(&device.certificate()?),
+        device_id: device.device_id,
+        device_name: device.device_name,
+        linked_at_unix_ms: device.linked_at_unix_ms,
+    })
+}
+
+#[tauri::command]
+fn identity_recovery_phrase() -> Result<String, String> {
+    stored_identity_recovery_phrase()
+}
+
+#[tauri::command]
+fn export_revocation_certificate() -> Result<String, String> {
+    Ok(revocation_certificate_code(&local_revocation_certificate(
+        now_unix_ms(),
+    )?))
+}
+
+/// Records a revocation code locally and gossips it as a cleartext item addressed to the
+/// revoked ID. Any identity may publish it; carriers trust the certificate's own signature.
+#[tauri::command]
+fn publish_revocation_certificate(code: String) -> Result<ChatSnapshot, String> {
+    let certificate = revocation_certificate_from_code(&code)?;
+    let now_ms = now_unix_ms();
+    let (revoked, newly_revoked) = record_identity_revocation(&certificate, now_ms)?;
+    let body_bytes = build_wayfarer_revocation_body(&certificate)?;
+    let payload = build_envelope_payload_b64(
+        &revoked.wayfarer_id(),
+        &body_bytes,
+        &load_local_signing_key_seed()?,
+    )?;
+    gossip_record_local_payload(&payload, now_ms.saturating_add(REVOCATION_RETENTION_MS))?;
+
+    let settings = load_app_settings()?;
+    if let Some(runtime) = GOSSIP_RUNTIME.get() {
+        runtime.force_announce.store(true, Ordering::SeqCst);
+        set_gossip_event("announce queued");
+    }
+    if settings.relay_sync_enabled && !settings.relay_endpoints.is_empty() {
+        request_relay_sync("publish_revocation_certificate");
+    }
+    log_info(&format!(
+        "identity_revocation_published: revoked={} newly_revoked={}",
+        revoked.wayfarer_id(),
+        newly_revoked
+    ));
+    emit_chat_snapshot_event_best_effort("publish_revocation_certificate");
+
+    current_chat_snapshot()
+}
+
+#[tauri::command]
+async fn restore_identity_backup(
+    request: RestoreIdentityBackupRequest,
+) -> Result<IdentityView, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let restored = restore_stored_identity_backup(&request.contents, &request.passphrase)?;
+        if let Some(settings) = restored.settings {
+            let settings: AppSettings = serde_json::from_value(settings)
+                .map_err(|err| format!("backed-up settings are invalid: {err}"))?;
+            save_app_settings(&settings)?;
+        }
+        log_info(&format!(
+            "identity_backup_restored: wayfarer_id={} contacts_restored={}",
+            restored.identity.wayfarer_id, restored.contacts_restored
+        ));
+        emit_chat_snapshot_event_best_effort("restore_identity_backup");
+        Ok(IdentityView {
+            wayfarer_id: restored.identity.wayfarer_id,
+            device_id: restored.identity.device_id,
+            verifying_key_b64: restored.identity.verifying_key_b64,
+            device_name: restored.identity.device_name,
+            passphrase_protected: false,
+            linked_device: restored.identity.linked_device,
+        })
+    })
+    .await
+    .map_err(|err| format!("restore_identity_backup task join failed: {err}"))?
+}
+
+#[tauri::command]
+fn restore_identity_from_phrase(
+    request: RestoreRecoveryPhraseRequest,
+) -> Result<IdentityView, String> {
+    let expected = request
+        .expected_wayfarer_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    let identity = restore_identity_from_recovery_phrase(&request.phrase, expected)?;
+    log_info(&format!(
+        "identity_restored_from_phrase: wayfarer_id={}",
+        identity.wayfarer_id
+    ));
     Ok(IdentityView {
         wayfarer_id: identity.wayfarer_id,
         device_id: identity.device_id,
         verifying_key_b64: identity.verifying_key_b64,
         device_name: identity.device_name,
+        passphrase_protected: false,
+        linked_device: identity.linked_device,
     })
 }
 
+#[tauri::command]
+async fn unlock_identity(passphrase: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let identity = unlock_local_identity(&passphrase)?;
+        log_info(&format!(
+            "identity_unlocked: wayfarer_id={}",
+            identity.wayfarer_id
+        ));
+        start_background_workers(&load_app_settings()?, "unlock_identity");
+        Ok(())
+    })
+    .await
+    .map_err(|err| format!("unlock_identity task join failed: {err}"))?
+}
+
+#[tauri::command]
+async fn set_identity_passphrase(passphrase: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        set_stored_identity_passphrase(&passphrase)?;
+        log_info("identity_passphrase_enabled");
+        Ok(())
+    })
+    .await
+    .map_err(|err| format!("set_identity_passphrase task join failed: {err}"))?
+}
+
+#[tauri::command]
+async fn remove_identity_passphrase(passphrase: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        remove_stored_identity_passphrase(&passphrase)?;
+        log_info("identity_passphrase_removed");
+        Ok(())
+    })
+    .await
+    .map_err(|err| format!("remove_identity_passphrase task join failed: {err}"))?
+}
+
 #[tauri::command]
 fn reset_wayfarer_id() -> Result<IdentityView, String> {
     delete_wayfarer_id()?;
-    rotate_wayfarer_id()
+    let identity = regenerate_local_identity()?;
+    Ok(IdentityView {
+        wayfarer_id: identity.wayfarer_id,
+        device_id: identity.device_id,
+        verifying_key_b64: identity.verifying_key_b64,
+        device_name: identity.device_name,
+        passphrase_protected: false,
+        linked_device: identity.linked_device,
+    })
 }
 
 #[tauri::command]
@@ -1044,6 +1753,25 @@ fn update_settings(settings: AppSettings) -> Result<AppSettings, String> {
     Ok(saved)
 }
 
+#[tauri::command]
+fn set_read_receipts(wayfarer_id: String, enabled: bool) -> Result<AppSettings, String> {
+    let wayfarer_id = wayfarer_id.trim();
+    if !is_valid_wayfarer_id(wayfarer_id) {
+        return Err("invalid wayfarer_id; expected 64 lowercase hex chars".to_string());
+    }
+
+    let mut settings = load_app_settings()?;
+    settings
+        .read_receipts_suppressed_for
+        .retain(|contact| contact != wayfarer_id);
+    if !enabled {
+        settings
+            .read_receipts_suppressed_for
+            .push(wayfarer_id.to_string());
+    }
+    save_app_settings(&settings)
+}
+
 #[tauri::command]
 fn gossip_status() -> GossipStatus {
     let ttl_seconds = load_app_settings()
@@ -1284,6 +2012,42 @@ fn relay_health_status_blocking() -> Result<RelayHealthStatus, String> {
     })
 }
 
+#[tauri::command]
+fn contact_safety_number(wayfarer_id: String) -> Result<SafetyNumberView, String> {
+    let wayfarer_id = wayfarer_id.trim();
+    let contact_key = load_contact_keys()?.remove(wayfarer_id).ok_or_else(|| {
+        "no signed message from this contact yet; safety number unavailable".to_string()
+    })?;
+    let identity = ensure_local_identity()?;
+    let local_pubkey: [u8; 32] = base64::engine::general_purpose::STANDARD
+        .decode(&identity.verifying_key_b64)
+        .map_err(|err| format!("failed to decode local verifying key: {err}"))?
+        .try_into()
+        .map_err(|_| "local verifying key had invalid length".to_string())?;
+
+    let safety_number = compute_safety_number(&local_pubkey, &contact_key.pubkey()?);
+    Ok(SafetyNumberView {
+        wayfarer_id: wayfarer_id.to_string(),
+        digits: safety_number.digits(),
+        words: safety_number.phrase(),
+        verified: contact_key.verified,
+    })
+}
+
+#[tauri::command]
+fn mark_contact_verified(wayfarer_id: String, verified: bool) -> Result<ChatSnapshot, String> {
+    let mut keys = load_contact_keys()?;
+    set_contact_verified(&mut keys, wayfarer_id.trim(), verified, now_unix_ms())?;
+    save_contact_keys(&keys)?;
+    log_info(&format!(
+        "contact_verification_updated: wayfarer_id={} verified={}",
+        wayfarer_id.trim(),
+        verified
+    ));
+    emit_chat_snapshot_event_best_effort("mark_contact_verified");
+    current_chat_snapshot()
+}
+
 #[tauri::command]
 fn upsert_contact(request: UpsertContactRequest) -> Result<BTreeMap<String, String>, String> {
     if !is_valid_wayfarer_id(&request.wayfarer_id) {
@@ -1335,9 +2099,13 @@ fn save_chat(chat: PersistedChatState) -> Result<PersistedChatState, String> {
         .unwrap_or(3600);
     run_media_housekeeping_best_effort("save_chat", ttl_seconds);
 
+    let previous = load_chat_state().unwrap_or_default();
     let mut normalized = chat;
     normalize_chat_state(&mut normalized);
     save_chat_state(&normalized)?;
+    for (contact, manifest_ids) in newly_seen_incoming_manifest_ids(&previous, &normalized) {
+        queue_read_receipts_best_effort(&contact, manifest_ids);
+    }
     emit_chat_snapshot_event_best_effort("save_chat");
     Ok(normalized)
 }
@@ -1349,10 +2117,7 @@ fn chat_snapshot() -> Result<ChatSnapshot, String> {
         .unwrap_or(3600);
     run_media_housekeeping_best_effort("chat_snapshot", ttl_seconds);
 
-    Ok(ChatSnapshot {
-        contacts: load_contact_aliases()?,
-        chat: load_chat_state()?,
-    })
+    current_chat_snapshot()
 }
 
 #[tauri::command]
@@ -1468,6 +2233,7 @@ fn send_message_blocking(request: SendMessageRequest) -> Result<SendMessageRespo
     let identity = ensure_local_identity()?;
     let author_signing_seed = load_local_signing_key_seed()?;
     let contacts = load_contact_aliases()?;
+    let unencrypted = outbound_envelope_is_unencrypted(wayfarer_id)?;
 
     if let Some(media_attachment) = attachment
         .as_ref()
@@ -1489,7 +2255,8 @@ fn send_message_blocking(request: SendMessageRequest) -> Result<SendMessageRespo
         let local_id = format!("local-{now_ms}-{:08x}", rand::random::<u32>());
         let mut chat = load_chat_state()?;
         chat.selected_contact = Some(wayfarer_id.to_string());
-        mark_contact_seen(&mut chat, wayfarer_id);
+        let newly_seen = mark_contact_seen(&mut chat, wayfarer_id);
+        queue_read_receipts_best_effort(wayfarer_id, newly_seen);
 
         let thread = chat.threads.entry(wayfarer_id.to_string()).or_default();
         thread.push(ChatMessage {
@@ -1506,6 +2273,7 @@ fn send_message_blocking(request: SendMessageRequest) -> Result<SendMessageRespo
             seen: true,
             manifest_id_hex: Some(media_send.object_sha256_hex.clone()),
             delivered_at: None,
+            read_at: None,
             outbound_state: Some(OutboundState::Sending),
             expires_at_unix_ms: Some(media_send.expires_at_unix_ms),
             last_sync_attempt_unix_ms: Some(now_ms),
@@ -1526,6 +2294,10 @@ fn send_message_blocking(request: SendMessageRequest) -> Result<SendMessageRespo
                 error: None,
                 expires_at_unix_ms: Some(media_send.expires_at_unix_ms),
             }),
+            author_wayfarer_id: Some(identity.wayfarer_id.clone()),
+            edit_history: Vec::new(),
+            deleted_at_unix_ms: None,
+            unencrypted,
         });
         mark_outgoing_message(&mut chat, wayfarer_id, &local_id, &media_send.item_id, None);
 
@@ -1600,8 +2372,12 @@ fn send_message_blocking(request: SendMessageRequest) -> Result<SendMessageRespo
     ));
 
     let manifest_id_hex = bytes_to_hex_lower(&sha2::Sha256::digest(&outbound_body_bytes));
-    let payload =
-        build_envelope_payload_b64(wayfarer_id, &outbound_body_bytes, &author_signing_seed)?;
+    let payload = build_local_envelope_payload_b64(
+        wayfarer_id,
+        &outbound_body_bytes,
+        &author_signing_seed,
+        expiry_ms,
+    )?;
     let item_id = gossip_record_local_payload(&payload, expiry_ms)?;
     if let Some(runtime) = GOSSIP_RUNTIME.get() {
         runtime.force_announce.store(true, Ordering::SeqCst);
@@ -1618,7 +2394,8 @@ fn send_message_blocking(request: SendMessageRequest) -> Result<SendMessageRespo
 
     let mut chat = load_chat_state()?;
     chat.selected_contact = Some(wayfarer_id.to_string());
-    mark_contact_seen(&mut chat, wayfarer_id);
+    let newly_seen = mark_contact_seen(&mut chat, wayfarer_id);
+    queue_read_receipts_best_effort(wayfarer_id, newly_seen);
 
     let thread = chat.threads.entry(wayfarer_id.to_string()).or_default();
     thread.push(ChatMessage {
@@ -1631,12 +2408,17 @@ fn send_message_blocking(request: SendMessageRequest) -> Result<SendMessageRespo
         seen: true,
         manifest_id_hex: Some(manifest_id_hex),
         delivered_at: None,
+        read_at: None,
         outbound_state: Some(OutboundState::Sending),
         expires_at_unix_ms: Some(expiry_ms),
         last_sync_attempt_unix_ms: Some(now_ms),
         last_sync_error: None,
         attachment: outbound_attachment,
         media: None,
+        author_wayfarer_id: Some(identity.wayfarer_id.clone()),
+        edit_history: Vec::new(),
+        deleted_at_unix_ms: None,
+        unencrypted,
     });
 
     let relay_http = settings
@@ -1720,6 +2502,91 @@ fn send_message_blocking(request: SendMessageRequest) -> Result<SendMessageRespo
     })
 }
 
+/// Whether an envelope for `to_wayfarer_id` would go out as cleartext v1: a root identity
+/// with no key recorded for the contact. A linked device has to carry its certificate, so it
+/// always seals.
+fn outbound_envelope_is_unencrypted(to_wayfarer_id: &str) -> Result<bool, String> {
+    Ok(load_local_device_certificate()?.is_none()
+        && !load_contact_keys()?.contains_key(to_wayfarer_id))
+}
+
+/// Builds the outbound envelope for `to_wayfarer_id`, sealed to the contact's root key and
+/// every device key seen for them and signed to expire at `expires_at_unix_ms`. When no key
+/// is known for the contact (see `outbound_envelope_is_unencrypted`) it falls back to a
+/// cleartext v1 envelope, which carries no signed lifetime, and logs the downgrade, unless
+/// `refuse_unencrypted_messages` is set, in which case the send fails.
+fn build_local_envelope_payload_b64(
+    to_wayfarer_id: &str,
+    body: &[u8],
+    author_signing_seed: &[u8; 32],
+    expires_at_unix_ms: u64,
+) -> Result<String, String> {
+    if outbound_envelope_is_unencrypted(to_wayfarer_id)? {
+        if load_app_settings()?.refuse_unencrypted_messages {
+            log_info(&format!(
+                "outbound_envelope_refused: to={} reason=no_contact_key",
+                to_wayfarer_id
+            ));
+            return Err(format!(
+                "no key known for {to_wayfarer_id}; unencrypted messages are turned off in settings"
+            ));
+        }
+        log_info(&format!(
+            "outbound_envelope_downgraded: to={} version=1 reason=no_contact_key",
+            to_wayfarer_id
+        ));
+        return build_envelope_payload_b64(to_wayfarer_id, body, author_signing_seed);
+    }
+    build_sealed_local_envelope_payload_b64(
+        to_wayfarer_id,
+        body,
+        author_signing_seed,
+        expires_at_unix_ms,
+    )
+}
+
+/// Seals `body` to the contact's root and linked-device keys, signing as a linked device when
+/// this install is one. A contact with a single key gets a blinded recipient tag instead of a
+/// cleartext wayfarer ID. Needs a key already recorded for `to_wayfarer_id`.
+fn build_sealed_local_envelope_payload_b64(
+    to_wayfarer_id: &str,
+    body: &[u8],
+    author_signing_seed: &[u8; 32],
+    expires_at_unix_ms: u64,
+) -> Result<String, String> {
+    let contact_keys = load_contact_keys()?;
+    let contact_key = contact_keys.get(to_wayfarer_id).ok_or_else(|| {
+        format!("no signing key known for {to_wayfarer_id}; sealed messages need one")
+    })?;
+    let mut recipient_pubkeys = vec![contact_key.pubkey()?];
+    recipient_pubkeys.extend(contact_key.device_pubkeys()?);
+    let lifetime = EnvelopeLifetime {
+        created_at_unix_ms: now_unix_ms(),
+        expires_at_unix_ms,
+    };
+    match load_local_device_certificate()? {
+        Some(device_certificate) => build_device_signed_envelope_v2_payload_b64(
+            &recipient_pubkeys,
+            body,
+            author_signing_seed,
+            &device_certificate,
+            lifetime,
+        ),
+        None if recipient_pubkeys.len() == 1 => build_blinded_envelope_v2_payload_b64(
+            &recipient_pubkeys[0],
+            body,
+            author_signing_seed,
+            lifetime,
+        ),
+        None => build_multi_recipient_envelope_v2_payload_b64(
+            &recipient_pubkeys,
+            body,
+            author_signing_seed,
+            lifetime,
+        ),
+    }
+}
+
 fn run_media_housekeeping_best_effort(context: &str, ttl_seconds_max: u64) {
     let min_interval_ms = media_housekeeping_min_interval_ms(context);
     if min_interval_ms > 0 {
@@ -1760,19 +2627,24 @@ fn run_media_housekeeping_best_effort(context: &str, ttl_seconds_max: u64) {
 fn try_send_media_control_fastlane_tcp(
     peer_wayfarer_id: &str,
     peer_addr: SocketAddr,
+    peer_session_capable: bool,
     envelopes: &[PendingMediaControlUnicast],
 ) -> usize {
     if envelopes.is_empty() {
         return 0;
     }
     let addr = format!("{}:{}", peer_addr.ip(), peer_addr.port());
-    let connect_stream = || -> Result<TcpStream, String> {
+    let connect_stream = || -> Result<LanSession<TcpStream>, String> {
         let stream = TcpStream::connect(&addr)
             .map_err(|err| format!("tcp connect failed ({addr}): {err}"))?;
         let _ = stream.set_nodelay(true);
-        let _ = stream.set_read_timeout(Some(Duration::from_millis(8)));
+        let _ = stream.set_read_timeout(Some(Duration::from_millis(3000)));
         let _ = stream.set_write_timeout(Some(Duration::from_millis(3000)));
-        Ok(stream)
+        let session = open_lan_tcp_session(stream, peer_session_capable)?;
+        let _ = session
+            .get_ref()
+            .set_read_timeout(Some(Duration::from_millis(8)));
+        Ok(session)
     };
 
     let mut stream = match connect_stream() {
@@ -1807,7 +2679,7 @@ fn try_send_media_control_fastlane_tcp(
             .collect::<Vec<_>>();
         let transfer =
             GossipSyncFrame::Transfer(crate::aethos_core::gossip_sync::TransferFrame { objects });
-        match send_gossip_frame_tcp(&mut stream, &transfer) {
+        match stream.send_frame(&transfer) {
             Ok(()) => {
                 sent = sent.saturating_add(batch.len());
                 reconnect_fail_streak = 0;
@@ -1826,7 +2698,7 @@ fn try_send_media_control_fastlane_tcp(
                         .unwrap_or("none")
                 ));
                 for _ in 0..3 {
-                    match read_gossip_frame_tcp(&mut stream) {
+                    match stream.read_frame() {
                         Ok(_) => {}
                         Err(err)
                             if err.contains("timeout")
@@ -1898,6 +2770,7 @@ fn try_send_media_control_fastlane_tcp(
 fn flush_media_control_fastlane(
     socket: &UdpSocket,
     peer_addr_by_node: &HashMap<String, SocketAddr>,
+    peer_lan_session_capable_by_ip: &HashMap<String, bool>,
 ) {
     let pending = media_drain_pending_media_control_unicast(lan_media_control_fastlane_max_items());
     let mut grouped: HashMap<String, Vec<_>> = HashMap::new();
@@ -1920,6 +2793,10 @@ fn flush_media_control_fastlane(
         };
 
         let tcp_enabled = media_control_fastlane_tcp_enabled() && !lan_tcp_disabled();
+        let peer_session_capable = peer_lan_session_capable_by_ip
+            .get(&peer_addr.ip().to_string())
+            .copied()
+            .unwrap_or(false);
         let tcp_min_payload_b64 = media_control_fastlane_tcp_min_payload_b64_bytes();
         let mut tcp_chunk_envelopes = Vec::new();
         let mut tcp_missing_mirror_envelopes = Vec::new();
@@ -1949,6 +2826,7 @@ fn flush_media_control_fastlane(
             let _ = try_send_media_control_fastlane_tcp(
                 &peer_wayfarer_id,
                 *peer_addr,
+                peer_session_capable,
                 &tcp_missing_mirror_envelopes,
             );
         }
@@ -1956,6 +2834,7 @@ fn flush_media_control_fastlane(
             let tcp_sent = try_send_media_control_fastlane_tcp(
                 &peer_wayfarer_id,
                 *peer_addr,
+                peer_session_capable,
                 &tcp_chunk_envelopes,
             );
             udp_envelopes.extend(tcp_chunk_envelopes.into_iter().skip(tcp_sent));
@@ -1997,6 +2876,308 @@ fn flush_media_control_fastlane(
     }
 }
 
+#[tauri::command]
+async fn send_reaction(request: SendReactionRequest) -> Result<ChatSnapshot, String> {
+    tauri::async_runtime::spawn_blocking(move || send_reaction_blocking(request))
+        .await
+        .map_err(|err| format!("send_reaction task join failed: {err}"))?
+}
+
+fn send_reaction_blocking(request: SendReactionRequest) -> Result<ChatSnapshot, String> {
+    let wayfarer_id = request.wayfarer_id.trim();
+    if !is_valid_wayfarer_id(wayfarer_id) {
+        return Err("invalid wayfarer_id; expected 64 lowercase hex chars".to_string());
+    }
+
+    let now_ms = now_unix_ms();
+    let settings = load_app_settings()?;
+    let identity = ensure_local_identity()?;
+    let author_signing_seed = load_local_signing_key_seed()?;
+    let reaction = ReactionBody {
+        target_manifest_id_hex: request.target_manifest_id_hex.trim().to_string(),
+        emoji: request.emoji.trim().to_string(),
+        action: if request.remove {
+            ReactionAction::Remove
+        } else {
+            ReactionAction::Add
+        },
+        created_at_unix_ms: now_ms,
+    };
+    let body_bytes = build_wayfarer_reaction_body(&reaction)?;
+
+    let expiry_ms = now_ms.saturating_add(settings.message_ttl_seconds.saturating_mul(1000));
+    let payload = build_local_envelope_payload_b64(
+        wayfarer_id,
+        &body_bytes,
+        &author_signing_seed,
+        expiry_ms,
+    )?;
+    let item_id = gossip_record_local_payload(&payload, expiry_ms)?;
+    if let Some(runtime) = GOSSIP_RUNTIME.get() {
+        runtime.force_announce.store(true, Ordering::SeqCst);
+        set_gossip_event("announce queued");
+    }
+    log_info(&format!(
+        "send_reaction_queued: item_id={} to={} target={} action={}",
+        item_id,
+        wayfarer_id,
+        reaction.target_manifest_id_hex,
+        reaction.action.as_str()
+    ));
+
+    let mut chat = load_chat_state()?;
+    chat.apply_reaction(&identity.wayfarer_id, &reaction);
+    save_chat_state(&chat)?;
+    if settings.relay_sync_enabled && !settings.relay_endpoints.is_empty() {
+        request_relay_sync("send_reaction");
+    }
+    emit_chat_snapshot_event_best_effort("send_reaction");
+
+    current_chat_snapshot()
+}
+
+#[tauri::command]
+async fn send_message_update(request: SendMessageUpdateRequest) -> Result<ChatSnapshot, String> {
+    tauri::async_runtime::spawn_blocking(move || send_message_update_blocking(request))
+        .await
+        .map_err(|err| format!("send_message_update task join failed: {err}"))?
+}
+
+fn send_message_update_blocking(request: SendMessageUpdateRequest) -> Result<ChatSnapshot, String> {
+    let wayfarer_id = request.wayfarer_id.trim();
+    if !is_valid_wayfarer_id(wayfarer_id) {
+        return Err("invalid wayfarer_id; expected 64 lowercase hex chars".to_string());
+    }
+
+    let now_ms = now_unix_ms();
+    let settings = load_app_settings()?;
+    let identity = ensure_local_identity()?;
+    let author_signing_seed = load_local_signing_key_seed()?;
+    let update = MessageUpdateBody {
+        target_manifest_id_hex: request.target_manifest_id_hex.trim().to_string(),
+        action: match request.text.as_deref().map(str::trim) {
+            Some(text) => MessageUpdateAction::Edit {
+                text: text.to_string(),
+            },
+            None => MessageUpdateAction::Delete,
+        },
+        created_at_unix_ms: now_ms,
+    };
+
+    // Apply locally first so only our own, still-present messages are ever sent.
+    let mut chat = load_chat_state()?;
+    let outcome = chat.apply_message_update(&identity.wayfarer_id, &identity.wayfarer_id, &update);
+    if outcome != MessageUpdateOutcome::Applied {
+        return Err(format!(
+            "message update rejected locally: {}",
+            outcome.as_str()
+        ));
+    }
+    let body_bytes = build_wayfarer_message_update_body(&update)?;
+
+    let expiry_ms = now_ms.saturating_add(settings.message_ttl_seconds.saturating_mul(1000));
+    let payload = build_local_envelope_payload_b64(
+        wayfarer_id,
+        &body_bytes,
+        &author_signing_seed,
+        expiry_ms,
+    )?;
+    let item_id = gossip_record_local_payload(&payload, expiry_ms)?;
+    if let Some(runtime) = GOSSIP_RUNTIME.get() {
+        runtime.force_announce.store(true, Ordering::SeqCst);
+        set_gossip_event("announce queued");
+    }
+    log_info(&format!(
+        "send_message_update_queued: item_id={} to={} target={} delete={}",
+        item_id,
+        wayfarer_id,
+        update.target_manifest_id_hex,
+        matches!(update.action, MessageUpdateAction::Delete)
+    ));
+
+    save_chat_state(&chat)?;
+    if settings.relay_sync_enabled && !settings.relay_endpoints.is_empty() {
+        request_relay_sync("send_message_update");
+    }
+    emit_chat_snapshot_event_best_effort("send_message_update");
+
+    current_chat_snapshot()
+}
+
+#[tauri::command]
+async fn publish_profile(request: PublishProfileRequest) -> Result<ChatSnapshot, String> {
+    tauri::async_runtime::spawn_blocking(move || publish_profile_blocking(request))
+        .await
+        .map_err(|err| format!("publish_profile task join failed: {err}"))?
+}
+
+/// Sends the local profile to every known contact, bumping the version past the last one
+/// published so recipients replace what they hold.
+fn publish_profile_blocking(request: PublishProfileRequest) -> Result<ChatSnapshot, String> {
+    let now_ms = now_unix_ms();
+    let settings = load_app_settings()?;
+    let identity = ensure_local_identity()?;
+    let author_signing_seed = load_local_signing_key_seed()?;
+    let contacts = load_contact_aliases()?;
+    let mut profiles = load_contact_profiles()?;
+
+    let avatar_thumbnail = request
+        .avatar_thumbnail_b64
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(|value| {
+            base64::engine::general_purpose::STANDARD
+                .decode(value)
+                .map_err(|_| "avatar thumbnail base64 content is invalid".to_string())
+        })
+        .transpose()?;
+    let profile = ProfileBody {
+        version: profiles
+            .get(&identity.wayfarer_id)
+            .map(|existing| existing.version)
+            .unwrap_or(0)
+            .saturating_add(1),
+        display_name: request.display_name.trim().to_string(),
+        avatar_thumbnail,
+        status_line: request
+            .status_line
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty()),
+        preferred_relays: request
+            .preferred_relays
+            .iter()
+            .map(|relay| relay.trim().to_string())
+            .filter(|relay| !relay.is_empty())
+            .collect(),
+        created_at_unix_ms: now_ms,
+    };
+    let body_bytes = build_wayfarer_profile_body(&profile)?;
+
+    let expiry_ms = now_ms.saturating_add(settings.message_ttl_seconds.saturating_mul(1000));
+    let mut published = 0usize;
+    for contact in contacts
+        .keys()
+        .filter(|contact| is_valid_wayfarer_id(contact))
+    {
+        if settings.refuse_unencrypted_messages && outbound_envelope_is_unencrypted(contact)? {
+            log_info(&format!(
+                "publish_profile_skipped: to={} reason=no_contact_key",
+                contact
+            ));
+            continue;
+        }
+        let payload = build_local_envelope_payload_b64(
+            contact,
+            &body_bytes,
+            &author_signing_seed,
+            expiry_ms,
+        )?;
+        gossip_record_local_payload(&payload, expiry_ms)?;
+        published += 1;
+    }
+    apply_contact_profile(&mut profiles, &identity.wayfarer_id, &profile);
+    save_contact_profiles(&profiles)?;
+
+    if published > 0 {
+        if let Some(runtime) = GOSSIP_RUNTIME.get() {
+            runtime.force_announce.store(true, Ordering::SeqCst);
+            set_gossip_event("announce queued");
+        }
+        if settings.relay_sync_enabled && !settings.relay_endpoints.is_empty() {
+            request_relay_sync("publish_profile");
+        }
+    }
+    log_info(&format!(
+        "publish_profile_queued: version={} recipients={}",
+        profile.version, published
+    ));
+    emit_chat_snapshot_event_best_effort("publish_profile");
+
+    current_chat_snapshot()
+}
+
+#[tauri::command]
+async fn publish_notice(request: PublishNoticeRequest) -> Result<ChatSnapshot, String> {
+    tauri::async_runtime::spawn_blocking(move || publish_notice_blocking(request))
+        .await
+        .map_err(|err| format!("publish_notice task join failed: {err}"))?
+}
+
+/// Sends an operator notice to every known contact. Recipients only show it when this
+/// identity is on their notice allow-list.
+fn publish_notice_blocking(request: PublishNoticeRequest) -> Result<ChatSnapshot, String> {
+    let severity = NoticeSeverity::parse(request.severity.trim())
+        .ok_or_else(|| "notice severity must be info, warning, or critical".to_string())?;
+    if request.expires_in_seconds == 0 {
+        return Err("notice expiry must be in the future".to_string());
+    }
+
+    let now_ms = now_unix_ms();
+    let settings = load_app_settings()?;
+    let author_signing_seed = load_local_signing_key_seed()?;
+    let contacts = load_contact_aliases()?;
+    let notice = NoticeBody {
+        severity,
+        title: request.title.trim().to_string(),
+        body: request.body.trim().to_string(),
+        expires_at_unix_ms: now_ms.saturating_add(request.expires_in_seconds.saturating_mul(1000)),
+        created_at_unix_ms: now_ms,
+    };
+    let body_bytes = build_wayfarer_notice_body(&notice)?;
+
+    let mut published = 0usize;
+    for contact in contacts
+        .keys()
+        .filter(|contact| is_valid_wayfarer_id(contact))
+    {
+        if settings.refuse_unencrypted_messages && outbound_envelope_is_unencrypted(contact)? {
+            log_info(&format!(
+                "publish_notice_skipped: to={} reason=no_contact_key",
+                contact
+            ));
+            continue;
+        }
+        let payload = build_local_envelope_payload_b64(
+            contact,
+            &body_bytes,
+            &author_signing_seed,
+            notice.expires_at_unix_ms,
+        )?;
+        gossip_record_local_payload(&payload, notice.expires_at_unix_ms)?;
+        published += 1;
+    }
+
+    if published > 0 {
+        if let Some(runtime) = GOSSIP_RUNTIME.get() {
+            runtime.force_announce.store(true, Ordering::SeqCst);
+            set_gossip_event("announce queued");
+        }
+        if settings.relay_sync_enabled && !settings.relay_endpoints.is_empty() {
+            request_relay_sync("publish_notice");
+        }
+    }
+    log_info(&format!(
+        "publish_notice_queued: severity={} expires_at_unix_ms={} recipients={}",
+        notice.severity.as_str(),
+        notice.expires_at_unix_ms,
+        published
+    ));
+
+    current_chat_snapshot()
+}
+
+#[tauri::command]
+fn dismiss_notice(manifest_id_hex: String) -> Result<ChatSnapshot, String> {
+    let mut chat = load_chat_state()?;
+    let pruned = chat.prune_expired_notices(now_unix_ms());
+    if chat.dismiss_notice(manifest_id_hex.trim()) || pruned {
+        save_chat_state(&chat)?;
+        emit_chat_snapshot_event_best_effort("dismiss_notice");
+    }
+    current_chat_snapshot()
+}
+
 #[tauri::command]
 async fn sync_inbox() -> Result<SyncInboxResponse, String> {
     tauri::async_runtime::spawn_blocking(sync_inbox_blocking)
@@ -2038,17 +3219,35 @@ fn sync_inbox_blocking() -> Result<SyncInboxResponse, String> {
 
 #[tauri::command]
 fn generate_share_qr(wayfarer_id: Option<String>) -> Result<ShareQrResponse, String> {
-    let resolved_wayfarer = if let Some(value) = wayfarer_id {
+    let identity = ensure_local_identity()?;
+    if let Some(value) = wayfarer_id {
         let trimmed = value.trim().to_ascii_lowercase();
         if !is_valid_wayfarer_id(&trimmed) {
             return Err("invalid wayfarer_id; expected 64 lowercase hex chars".to_string());
         }
-        trimmed
-    } else {
-        ensure_local_identity()?.wayfarer_id
-    };
+        if trimmed != identity.wayfarer_id {
+            return Err("share QR can only be issued for the local identity".to_string());
+        }
+    }
+
+    let signing_seed = load_local_signing_key_seed()?;
+    let local_profile = load_contact_profiles()?.remove(&identity.wayfarer_id);
+    let display_name = local_profile
+        .as_ref()
+        .map(|profile| profile.display_name.as_str())
+        .unwrap_or(identity.device_name.as_str());
+    let preferred_relays = local_profile
+        .as_ref()
+        .map(|profile| profile.preferred_relays.clone())
+        .unwrap_or_default();
+    let card = issue_contact_card(
+        &signing_seed,
+        Some(display_name).filter(|name| name.len() <= MAX_PROFILE_DISPLAY_NAME_BYTES),
+        &preferred_relays,
+        now_unix_ms(),
+    )?;
 
-    let path = generate_share_qr_png(&resolved_wayfarer)?;
+    let path = generate_share_qr_png(&contact_card_qr_text(&card))?;
     let bytes = fs::read(&path).map_err(|err| {
         format!(
             "failed reading generated qr image {}: {err}",
@@ -2058,14 +3257,14 @@ fn generate_share_qr(wayfarer_id: Option<String>) -> Result<ShareQrResponse, Str
     let png_base64 = base64::engine::general_purpose::STANDARD.encode(bytes);
 
     Ok(ShareQrResponse {
-        wayfarer_id: resolved_wayfarer,
+        wayfarer_id: identity.wayfarer_id,
         file_path: path.display().to_string(),
         png_base64,
     })
 }
 
 #[tauri::command]
-fn decode_wayfarer_id_from_qr_bytes(bytes: Vec<u8>) -> Result<String, String> {
+fn decode_wayfarer_id_from_qr_bytes(bytes: Vec<u8>) -> Result<ScannedContact, String> {
     if bytes.is_empty() {
         return Err("empty image payload".to_string());
     }
@@ -2085,8 +3284,33 @@ fn decode_wayfarer_id_from_qr_bytes(bytes: Vec<u8>) -> Result<String, String> {
             continue;
         };
 
+        if let Some(card_bytes) = contact_card_from_qr_text(&content) {
+            let card = verify_contact_card(&card_bytes?)?;
+            let wayfarer_id = card.wayfarer_id();
+            let mut keys = load_contact_keys()?;
+            if record_contact_pubkey(&mut keys, &wayfarer_id, &card.pubkey)? {
+                save_contact_keys(&keys)?;
+            }
+            log_info(&format!(
+                "contact_card_imported: wayfarer_id={} relays={}",
+                wayfarer_id,
+                card.preferred_relays.len()
+            ));
+            return Ok(ScannedContact {
+                wayfarer_id,
+                display_name: card.display_name,
+                preferred_relays: card.preferred_relays,
+                signed: true,
+            });
+        }
+
         if let Some(wayfarer_id) = extract_wayfarer_id_from_text(&content) {
-            return Ok(wayfarer_id);
+            return Ok(ScannedContact {
+                wayfarer_id,
+                display_name: None,
+                preferred_relays: Vec::new(),
+                signed: false,
+            });
         }
     }
 
@@ -2710,6 +3934,8 @@ fn start_gossip_worker_if_needed(initial_enabled: bool) {
             std::collections::HashMap::new();
         let mut peer_addr_by_node: HashMap<String, SocketAddr> = HashMap::new();
         let mut peer_tcp_capable_by_ip: HashMap<String, bool> = HashMap::new();
+        let mut peer_lan_session_capable_by_ip: HashMap<String, bool> = HashMap::new();
+        let mut peer_hello_by_ip: HashMap<String, LanPeerHello> = HashMap::new();
         let mut tcp_backoff_until_by_ip: HashMap<String, Instant> = HashMap::new();
         let mut udp_peer_interactions: HashMap<String, UdpPeerInteraction> = HashMap::new();
         let mut recent_served_request_by_peer: HashMap<String, (u64, Instant)> = HashMap::new();
@@ -2748,16 +3974,30 @@ fn start_gossip_worker_if_needed(initial_enabled: bool) {
             if let Some(listener) = tcp_listener.as_ref() {
                 loop {
                     match listener.accept() {
-                        Ok((mut stream, peer_addr)) => {
+                        Ok((stream, peer_addr)) => {
                             let peer_ip_key = peer_addr.ip().to_string();
                             let peer_node_id = peer_node_by_addr.get(&peer_ip_key).cloned();
-                            if let Err(err) = run_gossip_tcp_encounter_on_stream(
-                                &mut stream,
-                                peer_node_id,
-                                runtime,
-                                "inbound",
-                                false,
-                            ) {
+                            let known_peer_hello = known_lan_peer_hello(
+                                &peer_hello_by_ip,
+                                &peer_ip_key,
+                                peer_node_id.as_deref(),
+                            );
+                            let peer_session_capable = peer_lan_session_capable_by_ip
+                                .get(&peer_ip_key)
+                                .copied()
+                                .unwrap_or(false);
+                            if let Err(err) = accept_lan_tcp_session(stream, peer_session_capable)
+                                .and_then(|mut session| {
+                                    run_gossip_tcp_encounter_on_stream(
+                                        &mut session,
+                                        peer_node_id,
+                                        known_peer_hello,
+                                        runtime,
+                                        "inbound",
+                                        false,
+                                    )
+                                })
+                            {
                                 log_verbose(&format!(
                                     "gossip_tcp_inbound_encounter_failed: peer={} error={}",
                                     peer_addr, err
@@ -2773,7 +4013,11 @@ fn start_gossip_worker_if_needed(initial_enabled: bool) {
                 }
             }
 
-            flush_media_control_fastlane(&socket, &peer_addr_by_node);
+            flush_media_control_fastlane(
+                &socket,
+                &peer_addr_by_node,
+                &peer_lan_session_capable_by_ip,
+            );
 
             process_ble_discovery_signals(
                 &mut ble_discovery_adapter,
@@ -2823,6 +4067,8 @@ fn start_gossip_worker_if_needed(initial_enabled: bool) {
                         &mut peer_node_by_addr,
                         &mut peer_addr_by_node,
                         &mut peer_tcp_capable_by_ip,
+                        &mut peer_lan_session_capable_by_ip,
+                        &mut peer_hello_by_ip,
                         &mut tcp_backoff_until_by_ip,
                         &mut udp_peer_interactions,
                         &mut recent_served_request_by_peer,
@@ -3062,6 +4308,8 @@ fn handle_gossip_frame(
     peer_node_by_addr: &mut std::collections::HashMap<String, String>,
     peer_addr_by_node: &mut HashMap<String, SocketAddr>,
     peer_tcp_capable_by_ip: &mut HashMap<String, bool>,
+    peer_lan_session_capable_by_ip: &mut HashMap<String, bool>,
+    peer_hello_by_ip: &mut HashMap<String, LanPeerHello>,
     tcp_backoff_until_by_ip: &mut HashMap<String, Instant>,
     udp_peer_interactions: &mut HashMap<String, UdpPeerInteraction>,
     recent_served_request_by_peer: &mut HashMap<String, (u64, Instant)>,
@@ -3084,6 +4332,16 @@ fn handle_gossip_frame(
 
     match frame {
         GossipSyncFrame::Hello(hello) if hello.node_id != local_wayfarer => {
+            let profile = match negotiate_lan_session_profile(&local_lan_hello_frame()?, &hello) {
+                Ok(profile) => profile,
+                Err(err) => {
+                    log_verbose(&format!(
+                        "gossip_peer_hello_incompatible: source={} node_id={} error={}",
+                        source_key, hello.node_id, err
+                    ));
+                    return Ok(());
+                }
+            };
             let node_id = hello.node_id.clone();
             peer_node_by_addr.insert(source_key.clone(), node_id.clone());
             peer_node_by_addr.insert(source_ip_key.clone(), node_id.clone());
@@ -3095,11 +4353,20 @@ fn handle_gossip_frame(
             );
             interaction.latest_summary = None;
             interaction.relay_ingest_candidates.clear();
-            let tcp_capable = hello
-                .capabilities
-                .iter()
-                .any(|capability| capability == LAN_TCP_CAPABILITY);
+            let tcp_capable = profile.supports(GossipCapability::LanTcpTransfer);
             peer_tcp_capable_by_ip.insert(source_ip_key.clone(), tcp_capable);
+            // Sticky, so a spoofed HELLO without the capability cannot downgrade the peer.
+            let session_capable = peer_supports(&hello, GossipCapability::LanTcpSession);
+            *peer_lan_session_capable_by_ip
+                .entry(source_ip_key.clone())
+                .or_default() |= session_capable;
+            peer_hello_by_ip.insert(
+                source_ip_key.clone(),
+                LanPeerHello {
+                    hello: hello.clone(),
+                    profile: profile.clone(),
+                },
+            );
             log_verbose(&format!(
                 "gossip_peer_hello_mapped: source={} source_ip={} peers={} tcp_capable={}",
                 source_key,
@@ -3107,7 +4374,7 @@ fn handle_gossip_frame(
                 peer_node_by_addr.len(),
                 tcp_capable
             ));
-            if let Ok(summary) = build_gossip_summary_frame(now_unix_ms()) {
+            if let Ok(summary) = build_gossip_summary_frame_for_peer(now_unix_ms(), &profile) {
                 let _ =
                     send_gossip_frame(socket, &source.ip().to_string(), source.port(), &summary);
             }
@@ -3146,6 +4413,86 @@ fn handle_gossip_frame(
                 runtime,
                 "udp_summary",
             );
+            let paging_profile = peer_hello_by_ip
+                .get(&source_ip_key)
+                .map(|peer| &peer.profile)
+                .filter(|profile| profile.supports(GossipCapability::SummaryPaging));
+            if let Some(profile) = paging_profile {
+                if !interaction.summary_paging_started && summary_has_unpreviewed_items(&summary) {
+                    interaction.summary_paging_started = true;
+                    send_gossip_frame(
+                        socket,
+                        &source.ip().to_string(),
+                        source.port(),
+                        &build_gossip_summary_page_request_frame(None, profile)?,
+                    )?;
+                }
+            }
+        }
+        GossipSyncFrame::SummaryPage(page) => {
+            let Some(profile) = peer_hello_by_ip
+                .get(&source_ip_key)
+                .map(|peer| peer.profile.clone())
+            else {
+                return Ok(());
+            };
+            let interaction = ensure_udp_peer_interaction(
+                udp_peer_interactions,
+                &source_ip_key,
+                peer_node_by_addr
+                    .get(&source_key)
+                    .or_else(|| peer_node_by_addr.get(&source_ip_key))
+                    .cloned()
+                    .unwrap_or_else(|| source_ip_key.clone()),
+            );
+            log_verbose(&format!(
+                "gossip_recv_summary_page: from={} item_ids={} has_more={}",
+                source,
+                page.item_ids.len(),
+                page.next_cursor.is_some()
+            ));
+            if let Some(stop_reason) = interaction.encounter.should_stop(runtime, false) {
+                interaction
+                    .encounter
+                    .finish(stop_reason, "udp_summary_page");
+                return Ok(());
+            }
+            let want = gossip_select_request_item_ids_from_summary_page(
+                &page,
+                profile.max_want,
+                &interaction.encounter.requested_item_ids,
+            )?;
+            if !want.is_empty() {
+                let request = build_gossip_request_frame(want, profile.max_want)?;
+                if let GossipSyncFrame::Request(request_frame) = &request {
+                    for item_id in &request_frame.want {
+                        interaction
+                            .encounter
+                            .requested_item_ids
+                            .insert(item_id.clone());
+                    }
+                }
+                send_gossip_frame(socket, &source.ip().to_string(), source.port(), &request)?;
+            }
+            if let Some(next_cursor) = page.next_cursor.as_deref() {
+                send_gossip_frame(
+                    socket,
+                    &source.ip().to_string(),
+                    source.port(),
+                    &build_gossip_summary_page_request_frame(Some(next_cursor), &profile)?,
+                )?;
+            }
+        }
+        GossipSyncFrame::SummaryPageRequest(request) => {
+            let Some(peer) = peer_hello_by_ip.get(&source_ip_key) else {
+                return Ok(());
+            };
+            let page = build_gossip_summary_page_frame(
+                now_unix_ms(),
+                request.after.as_deref(),
+                &peer.profile,
+            )?;
+            send_gossip_frame(socket, &source.ip().to_string(), source.port(), &page)?;
         }
         GossipSyncFrame::RelayIngest(ingest) => {
             let interaction = ensure_udp_peer_interaction(
@@ -3283,6 +4630,10 @@ fn handle_gossip_frame(
                 .get(&peer_key)
                 .copied()
                 .unwrap_or(false);
+            let peer_session_capable = peer_lan_session_capable_by_ip
+                .get(&peer_key)
+                .copied()
+                .unwrap_or(false);
             let tcp_backoff_active = tcp_backoff_until_by_ip
                 .get(&peer_key)
                 .map(|until| *until > Instant::now())
@@ -3291,7 +4642,9 @@ fn handle_gossip_frame(
             if tcp_capable && !tcp_backoff_active && lan_tcp_request_encounter_enabled() {
                 match run_gossip_tcp_encounter_with_peer(
                     source.ip(),
-                    peer_node_id,
+                    peer_node_id.clone(),
+                    known_lan_peer_hello(peer_hello_by_ip, &source_ip_key, peer_node_id.as_deref()),
+                    peer_session_capable,
                     runtime,
                     "udp_request",
                 ) {
@@ -3309,13 +4662,19 @@ fn handle_gossip_frame(
                         ));
                     }
                 }
-                serve_udp_transfer_for_request(socket, source, &_req.want)?;
+                serve_udp_transfer_for_request(
+                    socket,
+                    source,
+                    &_req.want,
+                    peer_node_id.as_deref(),
+                )?;
                 recent_served_request_by_peer.insert(peer_key, (fingerprint, Instant::now()));
             } else {
                 if !tcp_capable {
                     log_verbose(&format!(
                         "gossip_tcp_skipped_not_capable: peer={} capability={}",
-                        source, LAN_TCP_CAPABILITY
+                        source,
+                        GossipCapability::LanTcpTransfer.as_str()
                     ));
                 } else if !lan_tcp_request_encounter_enabled() {
                     log_verbose(&format!(
@@ -3323,7 +4682,12 @@ fn handle_gossip_frame(
                         source
                     ));
                 }
-                serve_udp_transfer_for_request(socket, source, &_req.want)?;
+                serve_udp_transfer_for_request(
+                    socket,
+                    source,
+                    &_req.want,
+                    peer_node_id.as_deref(),
+                )?;
                 recent_served_request_by_peer.insert(peer_key, (fingerprint, Instant::now()));
             }
         }
@@ -3412,6 +4776,8 @@ fn handle_gossip_frame(
                     .into_iter()
                     .map(|item| crate::relay::client::EncounterMessagePreview {
                         author_wayfarer_id: item.author_wayfarer_id,
+                        author_pubkey: item.author_pubkey,
+                        author_device_pubkey: item.author_device_pubkey,
                         session_peer: item.session_peer,
                         transport_peer: item.transport_peer,
                         item_id: item.item_id,
@@ -3474,6 +4840,7 @@ fn handle_gossip_frame(
                             0u8;
                             crate::aethos_core::gossip_sync::BLOOM_FILTER_BYTES
                         ],
+                        bloom_hash_count: None,
                         item_count: interaction.relay_ingest_candidates.len() as u64,
                         preview_item_ids: Some(interaction.relay_ingest_candidates.clone()),
                         preview_cursor: interaction.relay_ingest_candidates.last().cloned(),
@@ -3581,14 +4948,65 @@ fn bind_gossip_tcp_listener() -> Result<TcpListener, String> {
     Ok(listener)
 }
 
+fn lan_downgrade_policy() -> LanDowngradePolicy {
+    load_app_settings()
+        .map(|settings| settings.lan_downgrade_policy)
+        .unwrap_or_default()
+}
+
+/// Peers that advertised `lan_tcp_session_v1` always get the encrypted handshake; everyone
+/// else gets plaintext framing when the downgrade policy allows it.
+fn open_lan_tcp_session(
+    stream: TcpStream,
+    peer_session_capable: bool,
+) -> Result<LanSession<TcpStream>, String> {
+    if peer_session_capable {
+        return LanSession::initiate(stream, &load_local_signing_key_seed()?);
+    }
+    if !lan_downgrade_policy().allows_plaintext() {
+        return Err("peer does not support encrypted lan sessions".to_string());
+    }
+    Ok(LanSession::plaintext(stream))
+}
+
+fn accept_lan_tcp_session(
+    stream: TcpStream,
+    peer_session_capable: bool,
+) -> Result<LanSession<TcpStream>, String> {
+    stream
+        .set_nonblocking(false)
+        .map_err(|err| format!("tcp set_nonblocking(false) failed: {err}"))?;
+    stream
+        .set_read_timeout(Some(Duration::from_millis(3000)))
+        .map_err(|err| format!("tcp set_read_timeout failed: {err}"))?;
+    let policy = if peer_session_capable {
+        LanDowngradePolicy::RequireEncrypted
+    } else {
+        lan_downgrade_policy()
+    };
+    LanSession::accept(stream, &load_local_signing_key_seed()?, policy)
+}
+
+/// HELLO last heard over UDP from this address, if it still belongs to the expected node.
+fn known_lan_peer_hello(
+    peer_hello_by_ip: &HashMap<String, LanPeerHello>,
+    peer_ip_key: &str,
+    peer_node_id: Option<&str>,
+) -> Option<HelloFrame> {
+    let peer = peer_hello_by_ip.get(peer_ip_key)?;
+    (peer_node_id == Some(peer.hello.node_id.as_str())).then(|| peer.hello.clone())
+}
+
 fn run_gossip_tcp_encounter_with_peer(
     peer_ip: std::net::IpAddr,
     peer_node_id: Option<String>,
+    known_peer_hello: Option<HelloFrame>,
+    peer_session_capable: bool,
     runtime: &GossipRuntime,
     trigger: &str,
 ) -> Result<(), String> {
     let addr = format!("{}:{}", peer_ip, gossip_lan_port());
-    let mut stream =
+    let stream =
         TcpStream::connect(&addr).map_err(|err| format!("tcp connect failed ({addr}): {err}"))?;
     stream
         .set_nodelay(true)
@@ -3599,16 +5017,26 @@ fn run_gossip_tcp_encounter_with_peer(
     stream
         .set_write_timeout(Some(Duration::from_millis(3000)))
         .map_err(|err| format!("tcp set_write_timeout failed ({addr}): {err}"))?;
-    run_gossip_tcp_encounter_on_stream(&mut stream, peer_node_id, runtime, trigger, true)
+    let mut session = open_lan_tcp_session(stream, peer_session_capable)?;
+    run_gossip_tcp_encounter_on_stream(
+        &mut session,
+        peer_node_id,
+        known_peer_hello,
+        runtime,
+        trigger,
+        true,
+    )
 }
 
 fn run_gossip_tcp_encounter_on_stream(
-    stream: &mut TcpStream,
+    session: &mut LanSession<TcpStream>,
     peer_node_id: Option<String>,
+    known_peer_hello: Option<HelloFrame>,
     runtime: &GossipRuntime,
     trigger: &str,
     initiate: bool,
 ) -> Result<(), String> {
+    let stream = session.get_ref();
     stream
         .set_nonblocking(false)
         .map_err(|err| format!("tcp set_nonblocking(false) failed: {err}"))?;
@@ -3629,42 +5057,66 @@ fn run_gossip_tcp_encounter_on_stream(
         .decode(&identity.verifying_key_b64)
         .map_err(|err| format!("gossip pubkey decode failed: {err}"))?;
     let node_pubkey = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(node_pubkey_raw);
-
-    let peer_label = peer_node_id
-        .as_deref()
-        .map(|value| value.to_string())
-        .unwrap_or_else(|| {
-            stream
-                .peer_addr()
-                .map(|addr| addr.to_string())
-                .unwrap_or_else(|_| "unknown-peer".to_string())
-        });
+
+    let authenticated_peer = session.remote_peer().map(|peer| peer.node_id.clone());
+    if let (Some(authenticated), Some(announced)) = (&authenticated_peer, &peer_node_id) {
+        if authenticated != announced {
+            return Err(format!(
+                "lan session identity {authenticated} does not match HELLO node_id {announced}"
+            ));
+        }
+    }
+    let transport_peer = session
+        .get_ref()
+        .peer_addr()
+        .map(|addr| addr.to_string())
+        .unwrap_or_else(|_| "tcp-peer".to_string());
+    let peer_label = authenticated_peer
+        .clone()
+        .or(peer_node_id)
+        .unwrap_or_else(|| transport_peer.clone());
     let mut encounter = GossipEncounterState::new(peer_label);
 
     log_verbose(&format!(
-        "gossip_encounter_start: transport=tcp trigger={} peer={} initiate={} round_budget={} time_budget_ms={} byte_budget={}",
+        "gossip_encounter_start: transport=tcp trigger={} peer={} initiate={} encrypted={} round_budget={} time_budget_ms={} byte_budget={}",
         trigger,
         encounter.peer_identity,
         initiate,
+        session.is_encrypted(),
         LAN_ENCOUNTER_MAX_ROUNDS,
         LAN_ENCOUNTER_MAX_TIME_MS,
         LAN_ENCOUNTER_MAX_BYTES
     ));
 
+    let local_hello = build_lan_hello_frame(&local_wayfarer, &node_pubkey)?;
+    let known_peer_hello = known_peer_hello.filter(|hello| {
+        authenticated_peer
+            .as_ref()
+            .is_none_or(|node_id| *node_id == hello.node_id)
+    });
+    let mut peer_profile = match known_peer_hello {
+        Some(hello) => Some(negotiate_lan_session_profile(&local_hello, &hello)?),
+        None => None,
+    };
+    if let Some(profile) = &peer_profile {
+        session.apply_session_profile(profile);
+    }
+    let baseline_profile = GossipSessionProfile::baseline();
+
     if initiate {
-        if let Ok(hello) = build_lan_hello_frame(&local_wayfarer, &node_pubkey) {
-            send_gossip_frame_tcp(stream, &hello)?;
-        }
-        if let Ok(summary) = build_gossip_summary_frame(now_unix_ms()) {
-            send_gossip_frame_tcp(stream, &summary)?;
+        session.send_frame(&local_hello)?;
+        let profile = peer_profile.as_ref().unwrap_or(&baseline_profile);
+        if let Ok(summary) = build_gossip_summary_frame_for_peer(now_unix_ms(), profile) {
+            session.send_frame(&summary)?;
         }
         if let Ok(ingest) = build_gossip_relay_ingest_frame(now_unix_ms()) {
-            send_gossip_frame_tcp(stream, &ingest)?;
+            session.send_frame(&ingest)?;
         }
     }
 
     let mut push_only_transfer_mode = false;
     let mut received_first_frame = false;
+    let mut summary_paging_started = false;
 
     loop {
         if let Some(stop_reason) = encounter.should_stop(runtime, false) {
@@ -3675,7 +5127,7 @@ fn run_gossip_tcp_encounter_on_stream(
             encounter.rounds = encounter.rounds.saturating_add(1);
         }
 
-        let frame = match read_gossip_frame_tcp(stream) {
+        let frame = match session.read_frame() {
             Ok(frame) => frame,
             Err(err)
                 if err.contains("timeout")
@@ -3701,35 +5153,60 @@ fn run_gossip_tcp_encounter_on_stream(
             }
         }
 
+        let profile = peer_profile.as_ref().unwrap_or(&baseline_profile);
         match frame {
-            GossipSyncFrame::Hello(_) => {
-                if let Ok(summary) = build_gossip_summary_frame(now_unix_ms()) {
-                    send_gossip_frame_tcp(stream, &summary)?;
+            GossipSyncFrame::Hello(hello) => {
+                if let Some(authenticated) = authenticated_peer.as_deref() {
+                    if authenticated != hello.node_id {
+                        log_info(&format!(
+                            "gossip_tcp_hello_identity_mismatch: trigger={} authenticated={} hello_node_id={}",
+                            trigger, authenticated, hello.node_id
+                        ));
+                        return Err(format!(
+                            "lan session identity {authenticated} does not match HELLO node_id {}",
+                            hello.node_id
+                        ));
+                    }
+                }
+                let profile =
+                    &*peer_profile.insert(negotiate_lan_session_profile(&local_hello, &hello)?);
+                session.apply_session_profile(profile);
+                if let Ok(summary) = build_gossip_summary_frame_for_peer(now_unix_ms(), profile) {
+                    session.send_frame(&summary)?;
                 }
                 if let Ok(ingest) = build_gossip_relay_ingest_frame(now_unix_ms()) {
-                    send_gossip_frame_tcp(stream, &ingest)?;
+                    session.send_frame(&ingest)?;
                 }
             }
             GossipSyncFrame::Summary(summary) => {
                 let request = build_request_from_summary_with_seen(
                     &summary,
-                    256,
+                    profile.max_want,
                     &encounter.requested_item_ids,
                     summary.preview_item_ids.as_deref().unwrap_or(&[]),
                 )?;
                 let GossipSyncFrame::Request(request_frame) = &request else {
                     continue;
                 };
-                if request_frame.want.is_empty() {
+                let start_paging = !summary_paging_started
+                    && profile.supports(GossipCapability::SummaryPaging)
+                    && summary_has_unpreviewed_items(&summary);
+                if request_frame.want.is_empty() && !start_paging {
                     encounter.finish(EncounterStopReason::NoMoreWanted, trigger);
                     break;
                 }
                 for item_id in &request_frame.want {
                     encounter.requested_item_ids.insert(item_id.clone());
                 }
-                send_gossip_frame_tcp(stream, &request)?;
+                if !request_frame.want.is_empty() {
+                    session.send_frame(&request)?;
+                }
+                if start_paging {
+                    summary_paging_started = true;
+                    session.send_frame(&build_gossip_summary_page_request_frame(None, profile)?)?;
+                }
                 if let Ok(ingest) = build_gossip_relay_ingest_frame(now_unix_ms()) {
-                    let _ = send_gossip_frame_tcp(stream, &ingest);
+                    let _ = session.send_frame(&ingest);
                 }
                 did_progress = true;
                 log_verbose(&format!(
@@ -3743,6 +5220,55 @@ fn run_gossip_tcp_encounter_on_stream(
                     encounter.bytes_imported
                 ));
             }
+            GossipSyncFrame::SummaryPage(page) => {
+                let want = gossip_select_request_item_ids_from_summary_page(
+                    &page,
+                    profile.max_want,
+                    &encounter.requested_item_ids,
+                )?;
+                if want.is_empty() && page.next_cursor.is_none() {
+                    encounter.finish(EncounterStopReason::NoMoreWanted, trigger);
+                    break;
+                }
+                if !want.is_empty() {
+                    let request = build_gossip_request_frame(want, profile.max_want)?;
+                    if let GossipSyncFrame::Request(request_frame) = &request {
+                        for item_id in &request_frame.want {
+                            encounter.requested_item_ids.insert(item_id.clone());
+                        }
+                    }
+                    session.send_frame(&request)?;
+                }
+                if let Some(next_cursor) = page.next_cursor.as_deref() {
+                    session.send_frame(&build_gossip_summary_page_request_frame(
+                        Some(next_cursor),
+                        profile,
+                    )?)?;
+                }
+                did_progress = true;
+                log_verbose(&format!(
+                    "gossip_encounter_round: transport=tcp trigger={} peer={} round={} frame=SUMMARY_PAGE page_items={} has_more={} requested={} accepted={} bytes_imported={}",
+                    trigger,
+                    encounter.peer_identity,
+                    encounter.rounds,
+                    page.item_ids.len(),
+                    page.next_cursor.is_some(),
+                    encounter.requested_item_ids.len(),
+                    encounter.accepted_item_ids.len(),
+                    encounter.bytes_imported
+                ));
+            }
+            GossipSyncFrame::SummaryPageRequest(request) => {
+                let page = build_gossip_summary_page_frame(
+                    now_unix_ms(),
+                    request.after.as_deref(),
+                    profile,
+                )?;
+                if let GossipSyncFrame::SummaryPage(page_frame) = &page {
+                    did_progress = !page_frame.item_ids.is_empty();
+                }
+                session.send_frame(&page)?;
+            }
             GossipSyncFrame::RelayIngest(ingest) => {
                 let missing_from_store = match gossip_missing_item_ids(&ingest.item_ids) {
                     Ok(values) => values,
@@ -3783,7 +5309,7 @@ fn run_gossip_tcp_encounter_on_stream(
                         encounter.bytes_imported
                     ));
                 }
-                send_gossip_frame_tcp(stream, &request)?;
+                session.send_frame(&request)?;
                 did_progress = true;
             }
             GossipSyncFrame::Request(req) => {
@@ -3791,11 +5317,13 @@ fn run_gossip_tcp_encounter_on_stream(
                     encounter.finish(EncounterStopReason::GossipDisabled, trigger);
                     break;
                 }
-                let objects = gossip_transfer_items(
+                let objects = gossip_transfer_items_for_peer(
                     &req.want,
-                    MAX_TRANSFER_ITEMS as u32,
-                    MAX_TRANSFER_BYTES,
+                    profile.max_transfer as u32,
+                    profile.max_transfer_bytes,
                     now_unix_ms(),
+                    authenticated_peer.as_deref(),
+                    authenticated_peer.is_some(),
                 )
                 .unwrap_or_default();
                 let transfer =
@@ -3803,7 +5331,7 @@ fn run_gossip_tcp_encounter_on_stream(
                         objects,
                     });
                 let transfer_bytes = frame_transfer_bytes(&transfer);
-                send_gossip_frame_tcp(stream, &transfer)?;
+                session.send_frame(&transfer)?;
                 log_verbose(&format!(
                     "gossip_encounter_round: transport=tcp trigger={} peer={} round={} frame=REQUEST requested_by_peer={} transfer_objects={} transfer_bytes={}",
                     trigger,
@@ -3818,10 +5346,6 @@ fn run_gossip_tcp_encounter_on_stream(
                 ));
             }
             GossipSyncFrame::Transfer(transfer) => {
-                let transport_peer = stream
-                    .peer_addr()
-                    .map(|addr| addr.to_string())
-                    .unwrap_or_else(|_| "tcp-peer".to_string());
                 let transfer_frame = GossipSyncFrame::Transfer(transfer.clone());
                 let transfer_bytes = frame_transfer_bytes(&transfer_frame);
                 let result = import_transfer_items(
@@ -3851,6 +5375,8 @@ fn run_gossip_tcp_encounter_on_stream(
                         .into_iter()
                         .map(|item| crate::relay::client::EncounterMessagePreview {
                             author_wayfarer_id: item.author_wayfarer_id,
+                            author_pubkey: item.author_pubkey,
+                            author_device_pubkey: item.author_device_pubkey,
                             session_peer: item.session_peer,
                             transport_peer: item.transport_peer,
                             item_id: item.item_id,
@@ -3886,17 +5412,18 @@ fn run_gossip_tcp_encounter_on_stream(
                     let receipt = GossipSyncFrame::Receipt(ReceiptFrame {
                         received: result.accepted_item_ids,
                     });
-                    if let Err(err) = send_gossip_frame_tcp(stream, &receipt) {
+                    if let Err(err) = session.send_frame(&receipt) {
                         log_verbose(&format!(
                             "gossip_tcp_receipt_send_failed: peer={} error={}",
                             transport_peer, err
                         ));
                     }
-                    if let Ok(summary) = build_gossip_summary_frame(now_unix_ms()) {
-                        let _ = send_gossip_frame_tcp(stream, &summary);
+                    if let Ok(summary) = build_gossip_summary_frame_for_peer(now_unix_ms(), profile)
+                    {
+                        let _ = session.send_frame(&summary);
                     }
                     if let Ok(ingest) = build_gossip_relay_ingest_frame(now_unix_ms()) {
-                        let _ = send_gossip_frame_tcp(stream, &ingest);
+                        let _ = session.send_frame(&ingest);
                     }
                 }
                 log_verbose(&format!(
@@ -3934,6 +5461,7 @@ fn run_gossip_tcp_encounter_on_stream(
                     encounter.bytes_imported
                 ));
             }
+            GossipSyncFrame::HelloChallenge(_) | GossipSyncFrame::HelloProof(_) => {}
         }
 
         if did_progress {
@@ -3955,10 +5483,12 @@ fn run_gossip_tcp_encounter_on_stream(
     Ok(())
 }
 
+/// Only a peer that answered our HELLO_CHALLENGE gets its own messages prioritised.
 fn serve_udp_transfer_for_request(
     socket: &UdpSocket,
     source: std::net::SocketAddr,
     want: &[String],
+    peer_node_id: Option<&str>,
 ) -> Result<(), String> {
     if want.is_empty() {
         return Ok(());
@@ -3981,9 +5511,16 @@ fn serve_udp_transfer_for_request(
         let max_items = lan_fallback_transfer_max_items();
         let max_bytes = lan_fallback_transfer_max_bytes();
         let effective_max_items = if force_single_item { 1 } else { max_items };
-        let objects =
-            gossip_transfer_items(&pending, effective_max_items, max_bytes, now_unix_ms())
-                .unwrap_or_default();
+        let objects = gossip_transfer_items_for_peer(
+            &pending,
+            effective_max_items,
+            max_bytes,
+            now_unix_ms(),
+            peer_node_id,
+            // A UDP sender is never proven, so it is not served as the destination of its items.
+            false,
+        )
+        .unwrap_or_default();
         if objects.is_empty() {
             log_verbose(&format!(
                 "gossip_udp_transfer_empty_selection: peer={} pending_items={} max_items={} max_bytes={}",
@@ -4059,63 +5596,36 @@ fn request_fingerprint(item_ids: &[String]) -> u64 {
 fn build_lan_hello_frame(wayfarer_id: &str, node_pubkey: &str) -> Result<GossipSyncFrame, String> {
     let mut frame = build_gossip_hello_frame(wayfarer_id, node_pubkey)?;
     if let GossipSyncFrame::Hello(hello) = &mut frame {
-        if !lan_tcp_disabled()
-            && !hello
-                .capabilities
-                .iter()
-                .any(|capability| capability == LAN_TCP_CAPABILITY)
-        {
-            hello.capabilities.push(LAN_TCP_CAPABILITY.to_string());
+        // LAN peers are authenticated by the Noise handshake on TCP sessions, not HELLO_PROOF.
+        hello
+            .capabilities
+            .retain(|capability| capability != GossipCapability::HelloAuth.as_str());
+        if !lan_tcp_disabled() {
+            advertise_capability(hello, GossipCapability::LanTcpTransfer);
+            advertise_capability(hello, GossipCapability::LanTcpSession);
         }
     }
     Ok(frame)
 }
 
-fn send_gossip_frame_tcp(stream: &mut TcpStream, frame: &GossipSyncFrame) -> Result<(), String> {
-    let payload = serialize_gossip_frame(frame)?;
-    if payload.len() > MAX_FRAME_BYTES {
-        return Err(format!("tcp frame exceeds max bytes: {}", payload.len()));
-    }
-    let len = u32::try_from(payload.len()).map_err(|_| "tcp frame too large".to_string())?;
-    stream
-        .write_all(&len.to_be_bytes())
-        .map_err(|err| format!("tcp write length failed: {err}"))?;
-    stream
-        .write_all(&payload)
-        .map_err(|err| format!("tcp write payload failed: {err}"))?;
-    Ok(())
+fn local_lan_hello_frame() -> Result<GossipSyncFrame, String> {
+    let identity = ensure_local_identity()?;
+    let node_pubkey_raw = base64::engine::general_purpose::STANDARD
+        .decode(&identity.verifying_key_b64)
+        .map_err(|err| format!("gossip pubkey decode failed: {err}"))?;
+    let node_pubkey = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(node_pubkey_raw);
+    build_lan_hello_frame(&identity.wayfarer_id, &node_pubkey)
 }
 
-fn read_gossip_frame_tcp(stream: &mut TcpStream) -> Result<GossipSyncFrame, String> {
-    let mut len_buf = [0u8; 4];
-    stream
-        .read_exact(&mut len_buf)
-        .map_err(|err| match err.kind() {
-            std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock => {
-                format!("tcp read length timeout: {err}")
-            }
-            std::io::ErrorKind::UnexpectedEof => {
-                format!("tcp read length eof: {err}")
-            }
-            _ => format!("tcp read length failed: {err}"),
-        })?;
-    let len = u32::from_be_bytes(len_buf) as usize;
-    if len > MAX_FRAME_BYTES {
-        return Err(format!("tcp frame length exceeds max: {len}"));
-    }
-    let mut payload = vec![0u8; len];
-    stream
-        .read_exact(&mut payload)
-        .map_err(|err| match err.kind() {
-            std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock => {
-                format!("tcp read payload timeout: {err}")
-            }
-            std::io::ErrorKind::UnexpectedEof => {
-                format!("tcp read payload eof: {err}")
-            }
-            _ => format!("tcp read payload failed: {err}"),
-        })?;
-    parse_gossip_frame(&payload)
+/// Negotiates against the LAN HELLO this node sends, transport capabilities included.
+fn negotiate_lan_session_profile(
+    local_hello: &GossipSyncFrame,
+    peer: &HelloFrame,
+) -> Result<GossipSessionProfile, String> {
+    let GossipSyncFrame::Hello(local) = local_hello else {
+        return Err("local HELLO frame has unexpected type".to_string());
+    };
+    negotiate_session_profile(local, peer)
 }
 
 fn frame_transfer_bytes(frame: &GossipSyncFrame) -> u64 {
@@ -4245,6 +5755,10 @@ fn gossip_frame_type(frame: &GossipSyncFrame) -> &'static str {
         GossipSyncFrame::Transfer(_) => "TRANSFER",
         GossipSyncFrame::Receipt(_) => "RECEIPT",
         GossipSyncFrame::RelayIngest(_) => "RELAY_INGEST",
+        GossipSyncFrame::HelloChallenge(_) => "HELLO_CHALLENGE",
+        GossipSyncFrame::HelloProof(_) => "HELLO_PROOF",
+        GossipSyncFrame::SummaryPageRequest(_) => "SUMMARY_PAGE_REQUEST",
+        GossipSyncFrame::SummaryPage(_) => "SUMMARY_PAGE",
     }
 }
 
@@ -4258,34 +5772,66 @@ fn mark_outgoing_message(
     if let Some(thread) = chat.threads.get_mut(contact) {
         if let Some(message) = thread.iter_mut().find(|item| item.msg_id == local_id) {
             message.last_sync_attempt_unix_ms = Some(now_unix_ms());
+            message.msg_id = item_id.to_string();
+            message.last_sync_error = None;
+            // A recipient receipt may already have arrived; a relay ack must not undo it.
+            if matches!(
+                message.outbound_state,
+                Some(OutboundState::Delivered | OutboundState::Read)
+            ) {
+                return;
+            }
             if let Some(err) = error {
                 log_verbose(&format!(
                     "outgoing_message_queued_without_ack: contact={} local_id={} item_id={} reason={}",
                     contact, local_id, item_id, err
                 ));
-                message.msg_id = item_id.to_string();
                 message.outbound_state = Some(OutboundState::Sending);
-                message.delivered_at = None;
-                message.last_sync_error = None;
             } else {
-                message.msg_id = item_id.to_string();
                 message.outbound_state = Some(OutboundState::Sent);
-                message.delivered_at = Some(format_timestamp_from_unix_ms(now_unix_ms()));
-                message.last_sync_error = None;
             }
         }
     }
 }
 
-fn mark_contact_seen(chat: &mut PersistedChatState, contact: &str) {
+/// Marks every incoming message from `contact` as seen and returns the manifest IDs of the
+/// ones that were not seen before, for read receipts.
+fn mark_contact_seen(chat: &mut PersistedChatState, contact: &str) -> Vec<String> {
+    let mut newly_seen = Vec::new();
     if let Some(thread) = chat.threads.get_mut(contact) {
         for message in thread.iter_mut() {
-            if matches!(message.direction, ChatDirection::Incoming) {
+            if matches!(message.direction, ChatDirection::Incoming) && !message.seen {
                 message.seen = true;
+                newly_seen.extend(message.manifest_id_hex.clone());
             }
         }
     }
     chat.new_contacts.retain(|value| value != contact);
+    newly_seen
+}
+
+fn newly_seen_incoming_manifest_ids(
+    previous: &PersistedChatState,
+    next: &PersistedChatState,
+) -> BTreeMap<String, Vec<String>> {
+    let mut newly_seen = BTreeMap::new();
+    for (contact, thread) in &next.threads {
+        let previous_thread = previous.threads.get(contact);
+        let manifest_ids: Vec<String> = thread
+            .iter()
+            .filter(|message| matches!(message.direction, ChatDirection::Incoming) && message.seen)
+            .filter_map(|message| message.manifest_id_hex.clone())
+            .filter(|manifest_id_hex| {
+                !previous_thread.into_iter().flatten().any(|existing| {
+                    existing.seen && existing.manifest_id_hex.as_ref() == Some(manifest_id_hex)
+                })
+            })
+            .collect();
+        if !manifest_ids.is_empty() {
+            newly_seen.insert(contact.clone(), manifest_ids);
+        }
+    }
+    newly_seen
 }
 
 #[derive(Debug, Default, Clone, Copy)]
@@ -4308,8 +5854,34 @@ fn merge_pulled_messages(
     let author_signing_seed = load_local_signing_key_seed().ok();
 
     let mut auto_pong_targets = BTreeSet::new();
-    let mut outcome = MergePulledOutcome::default();
+    let mut delivered_receipts: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut read_receipts: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut outcome = MergePulledOutcome {
+        chat_changed: chat.prune_expired_notices(now_unix_ms()),
+        ..MergePulledOutcome::default()
+    };
+    let mut contact_keys = load_contact_keys().unwrap_or_default();
+    let mut contact_keys_changed = false;
+    let mut contact_successions = load_contact_successions().unwrap_or_default();
     for pulled in pulled_messages {
+        if let (Some(author), Some(pubkey)) =
+            (pulled.author_wayfarer_id.as_deref(), pulled.author_pubkey)
+        {
+            let recorded = match pulled.author_device_pubkey {
+                Some(device_pubkey) => {
+                    record_contact_device(&mut contact_keys, author, &pubkey, &device_pubkey)
+                }
+                None => record_contact_pubkey(&mut contact_keys, author, &pubkey),
+            };
+            match recorded {
+                Ok(changed) => contact_keys_changed |= changed,
+                Err(err) => log_verbose(&format!(
+                    "contact_pubkey_rejected: item_id={} author={} error={}",
+                    pulled.item_id, author, err
+                )),
+            }
+        }
+
         if let (Some(local_wayfarer_id), Some(author_signing_seed)) =
             (local_wayfarer_id.as_deref(), author_signing_seed.as_ref())
         {
@@ -4349,7 +5921,11 @@ fn merge_pulled_messages(
             .unwrap_or("none")
             .to_string();
 
-        let sender_label = resolve_contact_id_for_preview(&pulled);
+        // Messages from a contact's retired ID land in the thread of the ID it moved to.
+        let sender_label = resolve_contact_succession(
+            &contact_successions,
+            &resolve_contact_id_for_preview(&pulled),
+        );
         let sender_alias = resolve_contact_alias_for_preview(&pulled);
         match classification.outcome {
             ClassificationOutcome::AcceptDisplay { chat: chat_payload } => {
@@ -4380,6 +5956,10 @@ fn merge_pulled_messages(
                     continue;
                 }
 
+                let receipt_manifest_id_hex = pulled
+                    .manifest_id_hex
+                    .clone()
+                    .filter(|_| is_valid_wayfarer_id(&sender_label));
                 let message_unix_ms = chat_payload.created_at_unix_ms;
                 let message_unix = (message_unix_ms / 1000) as i64;
                 thread.push(ChatMessage {
@@ -4392,16 +5972,35 @@ fn merge_pulled_messages(
                     seen: seen_on_insert,
                     manifest_id_hex: pulled.manifest_id_hex,
                     delivered_at: None,
+                    read_at: None,
                     outbound_state: None,
                     expires_at_unix_ms: None,
                     last_sync_attempt_unix_ms: None,
                     last_sync_error: None,
                     attachment: None,
                     media: None,
+                    author_wayfarer_id: is_valid_wayfarer_id(&sender_label)
+                        .then(|| sender_label.clone()),
+                    edit_history: Vec::new(),
+                    deleted_at_unix_ms: None,
+                    unencrypted: false,
                 });
                 sort_thread_messages(thread);
                 outcome.chat_changed = true;
 
+                if let Some(manifest_id_hex) = receipt_manifest_id_hex {
+                    if seen_on_insert {
+                        read_receipts
+                            .entry(sender_label.clone())
+                            .or_default()
+                            .push(manifest_id_hex.clone());
+                    }
+                    delivered_receipts
+                        .entry(sender_label.clone())
+                        .or_default()
+                        .push(manifest_id_hex);
+                }
+
                 if is_valid_wayfarer_id(&sender_label)
                     && is_ping_only_chat_text(
                         thread.last().map(|msg| msg.text.as_str()).unwrap_or(""),
@@ -4413,10 +6012,218 @@ fn merge_pulled_messages(
 
                 if chat.selected_contact.is_none() {
                     chat.selected_contact = Some(sender_label.clone());
-                    mark_contact_seen(chat, &sender_label);
+                    let newly_seen = mark_contact_seen(chat, &sender_label);
+                    read_receipts
+                        .entry(sender_label.clone())
+                        .or_default()
+                        .extend(newly_seen);
                     outcome.chat_changed = true;
                 }
             }
+            ClassificationOutcome::AcceptStoreNoDisplay {
+                kind: StoreNoDisplayKind::Reaction(reaction),
+            } => {
+                log_info(&format!(
+                    "inbound_app_body_classification: item_id={} outcome={} type={} routed_to={} decoder_ran=true action=reaction_{}",
+                    pulled.item_id,
+                    outcome_label,
+                    payload_type,
+                    routed_to,
+                    reaction.action.as_str()
+                ));
+                if is_valid_wayfarer_id(&sender_label) {
+                    outcome.chat_changed |= chat.apply_reaction(&sender_label, &reaction);
+                }
+            }
+            ClassificationOutcome::AcceptStoreNoDisplay {
+                kind: StoreNoDisplayKind::MessageUpdate(update),
+            } => {
+                let update_outcome = match local_wayfarer_id.as_deref() {
+                    Some(local_wayfarer_id) if is_valid_wayfarer_id(&sender_label) => {
+                        chat.apply_message_update(&sender_label, local_wayfarer_id, &update)
+                    }
+                    _ => MessageUpdateOutcome::AuthorMismatch,
+                };
+                log_info(&format!(
+                    "inbound_app_body_classification: item_id={} outcome={} type={} routed_to={} decoder_ran=true action=message_update_{}",
+                    pulled.item_id,
+                    outcome_label,
+                    payload_type,
+                    routed_to,
+                    update_outcome.as_str()
+                ));
+                outcome.chat_changed |= update_outcome == MessageUpdateOutcome::Applied;
+            }
+            ClassificationOutcome::AcceptStoreNoDisplay {
+                kind: StoreNoDisplayKind::Profile(profile),
+            } => {
+                log_info(&format!(
+                    "inbound_app_body_classification: item_id={} outcome={} type={} routed_to={} decoder_ran=true action=profile_v{}",
+                    pulled.item_id, outcome_label, payload_type, routed_to, profile.version
+                ));
+                if !is_valid_wayfarer_id(&sender_label) {
+                    continue;
+                }
+                let mut profiles = match load_contact_profiles() {
+                    Ok(profiles) => profiles,
+                    Err(err) => {
+                        log_info(&format!("contact_profiles_load_failed: error={err}"));
+                        continue;
+                    }
+                };
+                if apply_contact_profile(&mut profiles, &sender_label, &profile) {
+                    match save_contact_profiles(&profiles) {
+                        // Profiles render alongside contact aliases, so refresh contacts.
+                        Ok(()) => outcome.contacts_changed = true,
+                        Err(err) => log_info(&format!("contact_profiles_save_failed: error={err}")),
+                    }
+                }
+            }
+            ClassificationOutcome::AcceptStoreNoDisplay {
+                kind: StoreNoDisplayKind::StatusEvent(event),
+            } => {
+                log_info(&format!(
+                    "inbound_app_body_classification: item_id={} outcome={} type={} routed_to={} decoder_ran=true action=status_{} targets={}",
+                    pulled.item_id,
+                    outcome_label,
+                    payload_type,
+                    routed_to,
+                    event.status.as_str(),
+                    event.target_manifest_id_hexes.len()
+                ));
+                if is_valid_wayfarer_id(&sender_label) {
+                    outcome.chat_changed |= chat.apply_status_event(&sender_label, &event);
+                }
+            }
+            ClassificationOutcome::AcceptStoreNoDisplay {
+                kind: StoreNoDisplayKind::Notice(notice),
+            } => {
+                let allowed = is_valid_wayfarer_id(&sender_label)
+                    && load_app_settings()
+                        .map(|settings| settings.accepts_notice_from(&sender_label))
+                        .unwrap_or(false);
+                log_info(&format!(
+                    "inbound_app_body_classification: item_id={} outcome={} type={} routed_to={} decoder_ran=true action=notice_{}",
+                    pulled.item_id,
+                    outcome_label,
+                    payload_type,
+                    routed_to,
+                    if allowed { "stored" } else { "author_not_allowed" }
+                ));
+                if allowed {
+                    let manifest_id_hex = pulled
+                        .manifest_id_hex
+                        .clone()
+                        .unwrap_or_else(|| pulled.item_id.clone());
+                    outcome.chat_changed |=
+                        chat.apply_notice(&sender_label, &manifest_id_hex, &notice, now_unix_ms());
+                }
+            }
+            ClassificationOutcome::AcceptStoreNoDisplay {
+                kind: StoreNoDisplayKind::Succession(succession),
+            } => {
+                // Only the retiring key may announce its successor, so the record must come
+                // from the old ID in an envelope that key signed.
+                let verified = verify_succession_record(&succession.record).and_then(|record| {
+                    if pulled.author_pubkey == Some(record.old_pubkey)
+                        && pulled.author_wayfarer_id.as_deref()
+                            == Some(record.old_wayfarer_id().as_str())
+                    {
+                        Ok(record)
+                    } else {
+                        Err("succession record was not sent by its old key".to_string())
+                    }
+                });
+                let action = match verified {
+                    Ok(record) => {
+                        if contact_keys_changed {
+                            match save_contact_keys(&contact_keys) {
+                                Ok(()) => contact_keys_changed = false,
+                                Err(err) => {
+                                    log_info(&format!("contact_keys_save_failed: error={err}"))
+                                }
+                            }
+                        }
+                        match apply_contact_succession(&record) {
+                            Ok(applied) => {
+                                let old_wayfarer_id = record.old_wayfarer_id();
+                                let new_wayfarer_id = record.new_wayfarer_id();
+                                if let Some(alias) = contacts.remove(&old_wayfarer_id) {
+                                    contacts.entry(new_wayfarer_id.clone()).or_insert(alias);
+                                }
+                                contact_keys = load_contact_keys().unwrap_or_default();
+                                contact_successions =
+                                    load_contact_successions().unwrap_or_default();
+                                outcome.chat_changed |=
+                                    chat.migrate_contact_thread(&old_wayfarer_id, &new_wayfarer_id);
+                                outcome.contacts_changed |= applied;
+                                if applied {
+                                    "succession_applied".to_string()
+                                } else {
+                                    "succession_already_applied".to_string()
+                                }
+                            }
+                            Err(err) => format!("succession_rejected reason={err}"),
+                        }
+                    }
+                    Err(err) => format!("succession_rejected reason={err}"),
+                };
+                log_info(&format!(
+                    "inbound_app_body_classification: item_id={} outcome={} type={} routed_to={} decoder_ran=true action={}",
+                    pulled.item_id, outcome_label, payload_type, routed_to, action
+                ));
+            }
+            ClassificationOutcome::AcceptStoreNoDisplay {
+                kind: StoreNoDisplayKind::RecoveryShare(recovery_share),
+            } => {
+                // An owner hands out its own shares; anyone else is returning one to a
+                // recovering device.
+                let author = pulled.author_wayfarer_id.as_deref().unwrap_or_default();
+                let action = match verify_recovery_share(&recovery_share.share) {
+                    Ok(share) if share.owner_wayfarer_id() == author => {
+                        match store_held_recovery_share(author, &recovery_share.share) {
+                            Ok(_) => "recovery_share_held".to_string(),
+                            Err(err) => format!("recovery_share_rejected reason={err}"),
+                        }
+                    }
+                    Ok(_) => match add_social_recovery_share(&recovery_share.share) {
+                        Ok(progress) if progress.restored.is_some() => {
+                            "social_recovery_completed".to_string()
+                        }
+                        Ok(progress) => format!(
+                            "social_recovery_share_collected collected={}",
+                            progress.collected
+                        ),
+                        Err(err) => format!("recovery_share_rejected reason={err}"),
+                    },
+                    Err(err) => format!("recovery_share_rejected reason={err}"),
+                };
+                log_info(&format!(
+                    "inbound_app_body_classification: item_id={} outcome={} type={} routed_to={} decoder_ran=true action={}",
+                    pulled.item_id, outcome_label, payload_type, routed_to, action
+                ));
+            }
+            ClassificationOutcome::AcceptStoreNoDisplay {
+                kind: StoreNoDisplayKind::DeviceAnnounce(announce),
+            } => {
+                // The certificate is root-signed, so it only has to name the envelope author.
+                let author = pulled.author_wayfarer_id.as_deref().unwrap_or_default();
+                let action = match record_contact_device_certificate(
+                    &mut contact_keys,
+                    author,
+                    &announce.certificate,
+                ) {
+                    Ok(changed) => {
+                        contact_keys_changed |= changed;
+                        "device_recorded".to_string()
+                    }
+                    Err(err) => format!("device_announce_rejected reason={err}"),
+                };
+                log_info(&format!(
+                    "inbound_app_body_classification: item_id={} outcome={} type={} routed_to={} decoder_ran=true action={}",
+                    pulled.item_id, outcome_label, payload_type, routed_to, action
+                ));
+            }
             ClassificationOutcome::AcceptStoreNoDisplay { .. } => {
                 log_info(&format!(
                     "inbound_app_body_classification: item_id={} outcome={} type={} routed_to={} decoder_ran={} action=stored_without_display",
@@ -4449,6 +6256,18 @@ fn merge_pulled_messages(
         }
     }
 
+    for (contact, manifest_ids) in delivered_receipts {
+        if let Err(err) = queue_status_event(&contact, StatusEventKind::Delivered, manifest_ids) {
+            log_info(&format!(
+                "status_event_queue_failed: to={} status=delivered error={}",
+                contact, err
+            ));
+        }
+    }
+    for (contact, manifest_ids) in read_receipts {
+        queue_read_receipts_best_effort(&contact, manifest_ids);
+    }
+
     for target in auto_pong_targets {
         if let Err(err) = queue_auto_pong_message(chat, &target) {
             log_info(&format!(
@@ -4460,6 +6279,14 @@ fn merge_pulled_messages(
         }
     }
 
+    if contact_keys_changed {
+        match save_contact_keys(&contact_keys) {
+            // Verification state renders with contacts, so refresh them.
+            Ok(()) => outcome.contacts_changed = true,
+            Err(err) => log_info(&format!("contact_keys_save_failed: error={err}")),
+        }
+    }
+
     if outcome.chat_changed {
         normalize_chat_state(chat);
     }
@@ -4479,6 +6306,74 @@ fn thread_has_recent_outgoing_pong(thread: &[ChatMessage]) -> bool {
     })
 }
 
+/// Sends a `wayfarer.status_event.v1` receipt for `manifest_ids` to `wayfarer_id`, split into
+/// as many envelopes as the per-event target limit requires.
+fn queue_status_event(
+    wayfarer_id: &str,
+    status: StatusEventKind,
+    mut manifest_ids: Vec<String>,
+) -> Result<(), String> {
+    manifest_ids.sort();
+    manifest_ids.dedup();
+    if !is_valid_wayfarer_id(wayfarer_id) || manifest_ids.is_empty() {
+        return Ok(());
+    }
+
+    let now_ms = now_unix_ms();
+    let settings = load_app_settings()?;
+    let author_signing_seed = load_local_signing_key_seed()?;
+    let expiry_ms = now_ms.saturating_add(settings.message_ttl_seconds.saturating_mul(1000));
+    for targets in manifest_ids.chunks(MAX_STATUS_EVENT_TARGETS) {
+        let body_bytes = build_wayfarer_status_event_body(&StatusEventBody {
+            status,
+            target_manifest_id_hexes: targets.to_vec(),
+            created_at_unix_ms: now_ms,
+        })?;
+        let payload = build_local_envelope_payload_b64(
+            wayfarer_id,
+            &body_bytes,
+            &author_signing_seed,
+            expiry_ms,
+        )?;
+        let item_id = gossip_record_local_payload(&payload, expiry_ms)?;
+        log_verbose(&format!(
+            "status_event_queued: item_id={} to={} status={} targets={}",
+            item_id,
+            wayfarer_id,
+            status.as_str(),
+            targets.len()
+        ));
+    }
+
+    if let Some(runtime) = GOSSIP_RUNTIME.get() {
+        runtime.force_announce.store(true, Ordering::SeqCst);
+        set_gossip_event("announce queued");
+    }
+    if settings.relay_sync_enabled && !settings.relay_endpoints.is_empty() {
+        request_relay_sync("status_event");
+    }
+    Ok(())
+}
+
+/// Sends `read` receipts unless the user suppressed them for `wayfarer_id`.
+fn queue_read_receipts_best_effort(wayfarer_id: &str, manifest_ids: Vec<String>) {
+    if manifest_ids.is_empty() {
+        return;
+    }
+    let enabled = load_app_settings()
+        .map(|settings| settings.read_receipts_enabled_for(wayfarer_id))
+        .unwrap_or(false);
+    if !enabled {
+        return;
+    }
+    if let Err(err) = queue_status_event(wayfarer_id, StatusEventKind::Read, manifest_ids) {
+        log_info(&format!(
+            "status_event_queue_failed: to={} status=read error={}",
+            wayfarer_id, err
+        ));
+    }
+}
+
 fn queue_auto_pong_message(chat: &mut PersistedChatState, wayfarer_id: &str) -> Result<(), String> {
     if !is_valid_wayfarer_id(wayfarer_id) {
         return Ok(());
@@ -4487,12 +6382,17 @@ fn queue_auto_pong_message(chat: &mut PersistedChatState, wayfarer_id: &str) ->
     let now_ms = now_unix_ms();
     let now_secs = now_unix_secs();
     let settings = load_app_settings()?;
-    let _ = ensure_local_identity()?;
+    let identity = ensure_local_identity()?;
     let author_signing_seed = load_local_signing_key_seed()?;
     let body_bytes = build_wayfarer_chat_body("/pong", now_ms)?;
     let manifest_id_hex = bytes_to_hex_lower(&sha2::Sha256::digest(&body_bytes));
-    let payload = build_envelope_payload_b64(wayfarer_id, &body_bytes, &author_signing_seed)?;
     let expiry_ms = now_ms.saturating_add(settings.message_ttl_seconds.saturating_mul(1000));
+    let payload = build_local_envelope_payload_b64(
+        wayfarer_id,
+        &body_bytes,
+        &author_signing_seed,
+        expiry_ms,
+    )?;
     let item_id = gossip_record_local_payload(&payload, expiry_ms)?;
 
     if let Some(runtime) = GOSSIP_RUNTIME.get() {
@@ -4521,12 +6421,17 @@ fn queue_auto_pong_message(chat: &mut PersistedChatState, wayfarer_id: &str) ->
         seen: true,
         manifest_id_hex: Some(manifest_id_hex),
         delivered_at: None,
+        read_at: None,
         outbound_state: Some(OutboundState::Sending),
         expires_at_unix_ms: Some(expiry_ms),
         last_sync_attempt_unix_ms: Some(now_ms),
         last_sync_error: None,
         attachment: None,
         media: None,
+        author_wayfarer_id: Some(identity.wayfarer_id),
+        edit_history: Vec::new(),
+        deleted_at_unix_ms: None,
+        unencrypted: outbound_envelope_is_unencrypted(wayfarer_id)?,
     });
     sort_thread_messages(thread);
 
@@ -4696,10 +6601,7 @@ fn emit_chat_snapshot_event() -> Result<(), String> {
         return Ok(());
     };
 
-    let snapshot = ChatSnapshot {
-        contacts: load_contact_aliases()?,
-        chat: load_chat_state()?,
-    };
+    let snapshot = current_chat_snapshot()?;
 
     handle
         .emit(CHAT_SNAPSHOT_EVENT, snapshot)
@@ -4742,10 +6644,6 @@ fn format_timestamp_from_unix(unix_secs: i64) -> String {
     unix_secs.to_string()
 }
 
-fn format_timestamp_from_unix_ms(unix_ms: u64) -> String {
-    (unix_ms / 1000).to_string()
-}
-
 fn extract_wayfarer_id_from_text(raw: &str) -> Option<String> {
     let trimmed = raw.trim();
     if is_valid_wayfarer_id(trimmed) {
@@ -4776,8 +6674,8 @@ fn extract_wayfarer_id_from_text(raw: &str) -> Option<String> {
     None
 }
 
-fn generate_share_qr_png(wayfarer_id: &str) -> Result<PathBuf, String> {
-    let code = QrCode::new(wayfarer_id.as_bytes())
+fn generate_share_qr_png(payload: &str) -> Result<PathBuf, String> {
+    let code = QrCode::new(payload.as_bytes())
         .map_err(|err| format!("failed generating QR payload: {err}"))?;
     let scale: u32 = 8;
     let border: u32 = 4;
@@ -5163,12 +7061,42 @@ pub fn run() {
             open_completed_media_in_system_viewer,
             generate_e2e_large_image,
             rotate_wayfarer_id,
+            unlock_identity,
+            export_identity_backup,
+            identity_recovery_phrase,
+            export_revocation_certificate,
+            distribute_recovery_shares,
+            held_recovery_shares,
+            return_recovery_share,
+            begin_social_recovery,
+            social_recovery_status,
+            identity_profiles,
+            create_identity_profile,
+            switch_identity_profile,
+            publish_revocation_certificate,
+            restore_identity_backup,
+            restore_identity_from_phrase,
+            begin_device_pairing,
+            approve_device_pairing,
+            complete_device_pairing,
+            linked_devices,
+            unlink_device,
+            set_identity_passphrase,
+            remove_identity_passphrase,
             reset_wayfarer_id,
             update_settings,
+            set_read_receipts,
             upsert_contact,
             remove_contact,
             save_chat,
             send_message,
+            send_reaction,
+            send_message_update,
+            publish_profile,
+            publish_notice,
+            contact_safety_number,
+            mark_contact_verified,
+            dismiss_notice,
             sync_inbox,
             gossip_status,
             gossip_announce_now,
@@ -5185,6 +7113,10 @@ pub fn run() {
 
 fn main() {
     apply_cli_state_overrides();
+    if let Err(err) = pinned_profile() {
+        eprintln!("aethos: {err}");
+        std::process::exit(2);
+    }
     run();
 }
 
@@ -5197,6 +7129,10 @@ fn apply_cli_state_overrides() {
                 std::env::set_var("XDG_DATA_HOME", path);
                 std::env::set_var("XDG_STATE_HOME", path);
             }
+        } else if let Some(name) = arg.strip_prefix("--aethos-profile=") {
+            if !name.trim().is_empty() {
+                std::env::set_var("AETHOS_PROFILE", name.trim());
+            }
         } else if let Some(value) = arg.strip_prefix("--aethos-e2e=") {
             if !value.trim().is_empty() {
                 std::env::set_var("AETHOS_E2E", value.trim());
@@ -5356,6 +7292,7 @@ mod tests {
     use super::*;
     use crate::app_state::shared_test_env_lock;
     use std::collections::BTreeMap;
+    use std::io::Write;
     use std::net::{SocketAddr, TcpListener};
 
     struct EnvVarGuard {
@@ -5447,13 +7384,44 @@ mod tests {
         let path = generate_share_qr_png(id).expect("generate qr image");
         let bytes = fs::read(path).expect("read qr bytes");
         let decoded = decode_wayfarer_id_from_qr_bytes(bytes).expect("decode qr payload");
-        assert_eq!(decoded, id);
+        assert_eq!(decoded.wayfarer_id, id);
+        assert!(!decoded.signed);
+    }
+
+    #[test]
+    fn share_qr_carries_verified_contact_card() {
+        let _lock = shared_test_env_lock()
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner());
+        let temp_dir = std::env::temp_dir().join(format!(
+            "aethos-tauri-test-contact-card-{}",
+            rand::random::<u64>()
+        ));
+
+        with_state_dir(&temp_dir, || {
+            let identity = ensure_local_identity().expect("ensure identity");
+            let share = generate_share_qr(None).expect("generate share qr");
+            let bytes = fs::read(&share.file_path).expect("read qr bytes");
+            let scanned = decode_wayfarer_id_from_qr_bytes(bytes).expect("decode contact card");
+
+            assert!(scanned.signed);
+            assert_eq!(scanned.wayfarer_id, identity.wayfarer_id);
+            assert_eq!(
+                scanned.display_name.as_deref(),
+                Some(identity.device_name.as_str())
+            );
+            assert!(load_contact_keys()
+                .expect("load contact keys")
+                .contains_key(&identity.wayfarer_id));
+            assert!(generate_share_qr(Some("ab".repeat(32))).is_err());
+        });
     }
 
     #[test]
     fn empty_summary_still_produces_request_frame() {
         let summary = crate::aethos_core::gossip_sync::SummaryFrame {
             bloom_filter: vec![0u8; crate::aethos_core::gossip_sync::BLOOM_FILTER_BYTES],
+            bloom_hash_count: None,
             item_count: 0,
             preview_item_ids: None,
             preview_cursor: None,
@@ -5484,6 +7452,7 @@ mod tests {
             .expect("build bloom filter");
         let summary = crate::aethos_core::gossip_sync::SummaryFrame {
             bloom_filter: bloom,
+            bloom_hash_count: None,
             item_count: 1,
             preview_item_ids: Some(vec![wanted_item.clone()]),
             preview_cursor: None,
@@ -5569,6 +7538,8 @@ mod tests {
             .expect("build chat body");
         let pulled = vec![crate::relay::client::EncounterMessagePreview {
             author_wayfarer_id: None,
+            author_pubkey: None,
+            author_device_pubkey: None,
             session_peer: None,
             transport_peer: None,
             item_id: "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".to_string(),
@@ -5604,6 +7575,8 @@ mod tests {
                         "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
                             .to_string(),
                     ),
+                    author_pubkey: None,
+                    author_device_pubkey: None,
                     session_peer: None,
                     transport_peer: None,
                     item_id: "fbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"
@@ -5618,6 +7591,8 @@ mod tests {
                         "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
                             .to_string(),
                     ),
+                    author_pubkey: None,
+                    author_device_pubkey: None,
                     session_peer: None,
                     transport_peer: None,
                     item_id: "abbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"
@@ -5663,6 +7638,8 @@ mod tests {
             let body = build_wayfarer_chat_body("/ping", now_unix_ms()).expect("build chat body");
             let pulled = vec![crate::relay::client::EncounterMessagePreview {
                 author_wayfarer_id: Some(sender.to_string()),
+                author_pubkey: None,
+                author_device_pubkey: None,
                 session_peer: None,
                 transport_peer: None,
                 item_id: "fbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"
@@ -5684,18 +7661,66 @@ mod tests {
         });
     }
 
+    #[test]
+    fn newly_seen_incoming_manifest_ids_reports_only_first_sighting() {
+        let contact = "c".repeat(64);
+        let message = |manifest_id_hex: &str, direction, seen| ChatMessage {
+            msg_id: format!("item-{manifest_id_hex}"),
+            text: "hi".to_string(),
+            timestamp: "1".to_string(),
+            created_at_unix: 1,
+            created_at_unix_ms: 1_000,
+            direction,
+            seen,
+            manifest_id_hex: Some(manifest_id_hex.to_string()),
+            delivered_at: None,
+            read_at: None,
+            outbound_state: None,
+            expires_at_unix_ms: None,
+            last_sync_attempt_unix_ms: None,
+            last_sync_error: None,
+            attachment: None,
+            media: None,
+            author_wayfarer_id: None,
+            edit_history: Vec::new(),
+            deleted_at_unix_ms: None,
+            unencrypted: false,
+        };
+        let mut previous = PersistedChatState::default();
+        previous.threads.insert(
+            contact.clone(),
+            vec![
+                message(&"01".repeat(32), ChatDirection::Incoming, true),
+                message(&"02".repeat(32), ChatDirection::Incoming, false),
+            ],
+        );
+        let mut next = previous.clone();
+        let thread = next.threads.get_mut(&contact).expect("thread");
+        thread[1].seen = true;
+        thread.push(message(&"03".repeat(32), ChatDirection::Outgoing, true));
+
+        let newly_seen = newly_seen_incoming_manifest_ids(&previous, &next);
+        assert_eq!(newly_seen.len(), 1);
+        assert_eq!(newly_seen[&contact], vec!["02".repeat(32)]);
+    }
+
     #[test]
     fn tcp_frame_roundtrip_preserves_summary_frame() {
         let (mut sender, mut receiver) = tcp_pair();
         let frame = GossipSyncFrame::Summary(crate::aethos_core::gossip_sync::SummaryFrame {
             bloom_filter: vec![0u8; crate::aethos_core::gossip_sync::BLOOM_FILTER_BYTES],
+            bloom_hash_count: None,
             item_count: 0,
             preview_item_ids: None,
             preview_cursor: None,
         });
 
-        send_gossip_frame_tcp(&mut sender, &frame).expect("send tcp frame");
-        let decoded = read_gossip_frame_tcp(&mut receiver).expect("read tcp frame");
+        LanSession::plaintext(&mut sender)
+            .send_frame(&frame)
+            .expect("send tcp frame");
+        let decoded = LanSession::plaintext(&mut receiver)
+            .read_frame()
+            .expect("read tcp frame");
         match decoded {
             GossipSyncFrame::Summary(summary) => {
                 assert_eq!(summary.item_count, 0);
@@ -5716,7 +7741,9 @@ mod tests {
             .write_all(&oversized.to_be_bytes())
             .expect("write oversize length");
 
-        let err = read_gossip_frame_tcp(&mut client).expect_err("must reject oversize frame");
+        let err = LanSession::plaintext(&mut client)
+            .read_frame()
+            .expect_err("must reject oversize frame");
         assert!(err.contains("exceeds max"), "unexpected error: {err}");
     }
 
@@ -5748,7 +7775,7 @@ mod tests {
         let target: SocketAddr = receiver.local_addr().expect("receiver addr");
 
         with_state_dir(&temp_dir, || {
-            serve_udp_transfer_for_request(&sender, target, &[item_id.clone()])
+            serve_udp_transfer_for_request(&sender, target, &[item_id.clone()], None, false)
                 .expect("serve udp fallback transfer");
         });
 
@@ -5790,6 +7817,7 @@ mod tests {
         let summary_round_one = crate::aethos_core::gossip_sync::SummaryFrame {
             bloom_filter: crate::aethos_core::gossip_sync::build_bloom_filter(&[item_a.clone()])
                 .expect("build bloom round one"),
+            bloom_hash_count: None,
             item_count: 1,
             preview_item_ids: Some(vec![item_a.clone()]),
             preview_cursor: Some(item_a.clone()),
@@ -5820,6 +7848,7 @@ mod tests {
         let summary_round_two = crate::aethos_core::gossip_sync::SummaryFrame {
             bloom_filter: crate::aethos_core::gossip_sync::build_bloom_filter(&[item_b.clone()])
                 .expect("build bloom round two"),
+            bloom_hash_count: None,
             item_count: 1,
             preview_item_ids: Some(vec![item_b.clone()]),
             preview_cursor: Some(item_b.clone()),
@@ -5918,10 +7947,8 @@ mod tests {
 
         match frame {
             GossipSyncFrame::Hello(hello) => {
-                assert!(hello
-                    .capabilities
-                    .iter()
-                    .any(|capability| capability == LAN_TCP_CAPABILITY));
+                assert!(peer_supports(&hello, GossipCapability::LanTcpTransfer));
+                assert!(peer_supports(&hello, GossipCapability::LanTcpSession));
             }
             other => panic!("expected HELLO, got {other:?}"),
         }
@@ -6013,6 +8040,8 @@ mod tests {
                         .into_iter()
                         .map(|item| crate::relay::client::EncounterMessagePreview {
                             author_wayfarer_id: item.author_wayfarer_id,
+                            author_pubkey: item.author_pubkey,
+                            author_device_pubkey: item.author_device_pubkey,
                             session_peer: item.session_peer,
                             transport_peer: item.transport_peer,
                             item_id: item.item_id,
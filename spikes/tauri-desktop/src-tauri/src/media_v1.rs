@@ -10,14 +10,14 @@ use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
 use crate::aethos_core::gossip_sync::record_local_payload as gossip_record_local_payload;
+use crate::aethos_core::identity_store::profile_state_dir;
 use crate::aethos_core::logging::log_verbose;
-use crate::aethos_core::protocol::{
-    build_envelope_payload_b64_from_utf8, bytes_to_hex_lower, is_valid_wayfarer_id,
-};
+use crate::aethos_core::protocol::{bytes_to_hex_lower, is_valid_wayfarer_id};
 use crate::app_state::{
     load_chat_state, now_unix_ms, save_chat_state, ChatAttachment, ChatDirection,
     ChatMediaTransfer, ChatMessage, MediaTransferStatus, PersistedChatState,
 };
+use crate::build_local_envelope_payload_b64;
 use crate::relay::client::EncounterMessagePreview;
 
 const MEDIA_CAPABILITIES_TYPE: &str = "wayfarer.media.capabilities.v1";
@@ -701,10 +701,14 @@ pub fn maybe_queue_capabilities_for_peer(
         sent_at_unix_ms: now_ms,
     })
     .map_err(|err| format!("failed serializing media capabilities message: {err}"))?;
-    let payload =
-        build_envelope_payload_b64_from_utf8(peer_wayfarer_id, &body, author_signing_seed)?;
     let expiry = now_ms.saturating_add(ttl_seconds_max.saturating_mul(1000));
     let expiry_unix_ms = apply_expiry_authority(expiry, now_ms, ttl_seconds_max);
+    let payload = build_local_envelope_payload_b64(
+        peer_wayfarer_id,
+        body.as_bytes(),
+        author_signing_seed,
+        expiry_unix_ms,
+    )?;
     rate_limit_outbound(payload.len(), now_ms, false, false)?;
     let _ = gossip_record_local_payload(&payload, expiry_unix_ms)?;
     Ok(())
@@ -803,8 +807,13 @@ pub fn send_media_manifest_and_chunks(
 
     let manifest_body = serde_json::to_string(&manifest)
         .map_err(|err| format!("failed serializing media manifest: {err}"))?;
-    let manifest_payload =
-        build_envelope_payload_b64_from_utf8(to_wayfarer_id, &manifest_body, author_signing_seed)?;
+    let manifest_expiry = apply_expiry_authority(expires_at_unix_ms, now_ms, ttl_seconds_max);
+    let manifest_payload = build_local_envelope_payload_b64(
+        to_wayfarer_id,
+        manifest_body.as_bytes(),
+        author_signing_seed,
+        manifest_expiry,
+    )?;
     if manifest_payload.len() > max_item_payload_b64_bytes {
         return Err(format!(
             "media manifest exceeds maxItemPayloadB64Bytes ({} > {})",
@@ -813,7 +822,6 @@ pub fn send_media_manifest_and_chunks(
         ));
     }
     rate_limit_outbound(manifest_payload.len(), now_ms, true, false)?;
-    let manifest_expiry = apply_expiry_authority(expires_at_unix_ms, now_ms, ttl_seconds_max);
     let item_id = gossip_record_local_payload(&manifest_payload, manifest_expiry)?;
 
     let drop_chunk_index = e2e_drop_chunk_index();
@@ -1633,8 +1641,12 @@ fn choose_chunk_payload_bytes(
             expires_at_unix_ms,
         })
         .map_err(|err| format!("failed serializing sample media chunk: {err}"))?;
-        let envelope_payload =
-            build_envelope_payload_b64_from_utf8(to_wayfarer_id, &body, author_signing_seed)?;
+        let envelope_payload = build_local_envelope_payload_b64(
+            to_wayfarer_id,
+            body.as_bytes(),
+            author_signing_seed,
+            expires_at_unix_ms,
+        )?;
         if envelope_payload.len() <= max_item_payload_b64_bytes {
             return Ok(candidate as u32);
         }
@@ -1782,7 +1794,12 @@ fn build_chunk_envelope_payload(
     })
     .map_err(|err| format!("failed serializing media chunk: {err}"))?;
 
-    let payload = build_envelope_payload_b64_from_utf8(to_wayfarer_id, &body, author_signing_seed)?;
+    let payload = build_local_envelope_payload_b64(
+        to_wayfarer_id,
+        body.as_bytes(),
+        author_signing_seed,
+        manifest.expires_at_unix_ms,
+    )?;
     if payload.len() > max_item_payload_b64_bytes {
         return Err(format!(
             "chunk envelope exceeds maxItemPayloadB64Bytes ({} > {})",
@@ -2540,6 +2557,7 @@ fn upsert_manifest_chat_message(
         seen: chat.selected_contact.as_deref() == Some(sender.as_str()),
         manifest_id_hex: pulled.manifest_id_hex.clone(),
         delivered_at: None,
+        read_at: None,
         outbound_state: None,
         expires_at_unix_ms: Some(manifest.expires_at_unix_ms),
         last_sync_attempt_unix_ms: None,
@@ -2563,6 +2581,13 @@ fn upsert_manifest_chat_message(
             error,
             expires_at_unix_ms: Some(manifest.expires_at_unix_ms),
         }),
+        author_wayfarer_id: pulled
+            .author_wayfarer_id
+            .clone()
+            .filter(|value| is_valid_wayfarer_id(value)),
+        edit_history: Vec::new(),
+        deleted_at_unix_ms: None,
+        unencrypted: false,
     });
     true
 }
@@ -2630,16 +2655,17 @@ fn send_missing_request_if_due(
 
     let body = serde_json::to_string(&request)
         .map_err(|err| format!("failed serializing media missing request: {err}"))?;
-    let payload = build_envelope_payload_b64_from_utf8(
+    let expiry = apply_expiry_authority(state.expires_at_unix_ms, now_ms, ttl_seconds_max);
+    let payload = build_local_envelope_payload_b64(
         &state.sender_wayfarer_id,
-        &body,
+        body.as_bytes(),
         author_signing_seed,
+        expiry,
     )?;
     if payload.len() > media_limits().max_item_payload_b64_bytes {
         return Ok(false);
     }
     rate_limit_outbound(payload.len(), now_ms, false, false)?;
-    let expiry = apply_expiry_authority(state.expires_at_unix_ms, now_ms, ttl_seconds_max);
     let item_id = gossip_record_local_payload(&payload, expiry)?;
     let redundancy = missing_fastlane_redundancy();
     for _ in 0..redundancy {
@@ -3583,17 +3609,19 @@ impl Drop for ActiveTransferLock {
 }
 
 fn media_base_dir() -> PathBuf {
+    profile_state_dir(media_state_root_dir()).join("media")
+}
+
+fn media_state_root_dir() -> PathBuf {
     if let Ok(state_dir) = std::env::var("AETHOS_STATE_DIR") {
         if !state_dir.trim().is_empty() {
-            return Path::new(&state_dir).join("media");
+            return PathBuf::from(state_dir);
         }
     }
 
     if let Ok(xdg_state_home) = std::env::var("XDG_STATE_HOME") {
         if !xdg_state_home.trim().is_empty() {
-            return Path::new(&xdg_state_home)
-                .join("aethos-linux")
-                .join("media");
+            return Path::new(&xdg_state_home).join("aethos-linux");
         }
     }
 
@@ -3602,12 +3630,11 @@ fn media_base_dir() -> PathBuf {
             return Path::new(&home)
                 .join(".local")
                 .join("state")
-                .join("aethos-linux")
-                .join("media");
+                .join("aethos-linux");
         }
     }
 
-    std::env::temp_dir().join("aethos-linux").join("media")
+    std::env::temp_dir().join("aethos-linux")
 }
 
 fn media_spool_dir() -> PathBuf {
@@ -3909,6 +3936,8 @@ mod tests {
 
         let _ = EncounterMessagePreview {
             author_wayfarer_id: Some(transfer.sender_wayfarer_id.clone()),
+            author_pubkey: None,
+            author_device_pubkey: None,
             session_peer: None,
             transport_peer: None,
             item_id: "cccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccc".to_string(),
@@ -138,6 +138,7 @@ function createOptimisticOutgoingMessage(localId, text) {
     seen: true,
     manifestIdHex: null,
     deliveredAt: null,
+    readAt: null,
     outboundState: "sending",
     expiresAtUnixMs: null,
     lastSyncAttemptUnixMs: nowMs,
@@ -165,9 +166,12 @@ function formatOutgoingStatus(message) {
   const state = message?.outboundState;
   if (state === "sending") return "Queued";
   if (state === "sent") return "Sent";
+  if (state === "delivered") return "Delivered";
+  if (state === "read") return "Read";
   if (state && typeof state === "object" && state.failed?.error) return `Failed: ${state.failed.error}`;
   if (message?.lastSyncError) return `Failed: ${message.lastSyncError}`;
-  if (message?.deliveredAt) return "Sent";
+  if (message?.readAt) return "Read";
+  if (message?.deliveredAt) return "Delivered";
   return null;
 }
 
@@ -221,7 +225,7 @@ function mediaProgressPercent(media) {
 
 function outgoingMediaProgressPercent(message) {
   const state = String(message?.outboundState || "").trim().toLowerCase();
-  if (state === "sent" || message?.deliveredAt) return 100;
+  if (state === "sent" || state === "delivered" || state === "read" || message?.deliveredAt) return 100;
   if (state === "sending") return 12;
   if (message?.outboundState && typeof message.outboundState === "object" && message.outboundState.failed?.error) {
     return 100;
@@ -294,6 +298,8 @@ export default function App() {
   const [settings, setSettings] = useState(null);
   const [relayEndpointsDraft, setRelayEndpointsDraft] = useState("");
   const [contacts, setContacts] = useState({});
+  const [contactKeys, setContactKeys] = useState({});
+  const [revokedContacts, setRevokedContacts] = useState([]);
   const [chat, setChat] = useState({ selectedContact: null, threads: {}, newContacts: [] });
   const [relayReports, setRelayReports] = useState([]);
   const [isFullscreen, setIsFullscreen] = useState(false);
@@ -321,6 +327,18 @@ export default function App() {
   const [logFilter, setLogFilter] = useState("all");
   const [arrivingMessageIds, setArrivingMessageIds] = useState({});
   const [confirmDialog, setConfirmDialog] = useState(null);
+  const [identityLocked, setIdentityLocked] = useState(false);
+  const [passphraseDraft, setPassphraseDraft] = useState("");
+  const [recoveryPhraseDraft, setRecoveryPhraseDraft] = useState("");
+  const [revocationDraft, setRevocationDraft] = useState("");
+  const [devicePairingDraft, setDevicePairingDraft] = useState("");
+  const [linkedDevices, setLinkedDevices] = useState([]);
+  const [recoveryThreshold, setRecoveryThreshold] = useState(2);
+  const [heldRecoveryShares, setHeldRecoveryShares] = useState([]);
+  const [socialRecovery, setSocialRecovery] = useState(null);
+  const [socialRecoveryDraft, setSocialRecoveryDraft] = useState("");
+  const [identityProfiles, setIdentityProfiles] = useState([]);
+  const [profileDraft, setProfileDraft] = useState("");
   const logContainerRef = useRef(null);
   const threadContainerRef = useRef(null);
   const attachmentInputRef = useRef(null);
@@ -457,9 +475,12 @@ export default function App() {
         invoke("read_app_log", { maxLines: 500 })
       ]);
       setIdentity(boot.identity);
+      setIdentityLocked(Boolean(boot.identityLocked));
       setSettings(boot.settings);
       setRelayEndpointsDraft((boot.settings?.relayEndpoints || []).join("\n"));
       setContacts(boot.contacts || {});
+      setContactKeys(boot.contactKeys || {});
+      setRevokedContacts(boot.revokedContacts || []);
       const initialChat = boot.chat || { selectedContact: null, threads: {}, newContacts: [] };
       if (!initialChat.selectedContact && Object.keys(boot.contacts || {}).length > 0) {
         initialChat.selectedContact = Object.keys(boot.contacts || {})[0];
@@ -470,7 +491,7 @@ export default function App() {
       setRelayHealth(relay);
       setEncounterActivity(encounter);
       setLogTail(log);
-      setStatus("Ready");
+      setStatus(boot.identityLocked ? "Identity locked" : "Ready");
       setNetworkPulseTs(Date.now());
     } catch (error) {
       setStatus(`Bootstrap failed: ${String(error)}`);
@@ -488,6 +509,331 @@ export default function App() {
     runBootstrap();
   }, []);
 
+  const unlockIdentity = async (event) => {
+    event.preventDefault();
+    try {
+      await invoke("unlock_identity", { passphrase: passphraseDraft });
+      setPassphraseDraft("");
+      setIdentityLocked(false);
+      await runBootstrap();
+    } catch (error) {
+      setStatus(`Unlock failed: ${String(error)}`);
+      soundManager.play("error");
+    }
+  };
+
+  const exportIdentityBackup = async () => {
+    try {
+      const exported = await invoke("export_identity_backup", {
+        request: { passphrase: passphraseDraft, includeContacts: true, includeSettings: true }
+      });
+      setStatus(`Encrypted identity backup written to ${exported.filePath}`);
+    } catch (error) {
+      setStatus(`Backup export failed: ${String(error)}`);
+      soundManager.play("error");
+    }
+  };
+
+  const showRecoveryPhrase = async () => {
+    try {
+      const phrase = await invoke("identity_recovery_phrase");
+      requestConfirmation({
+        title: "Recovery phrase",
+        body: `Write these words down and keep them offline. Anyone holding them controls this identity.\n\n${phrase}`,
+        confirmLabel: "Done",
+        onConfirm: () => {}
+      });
+    } catch (error) {
+      setStatus(`Recovery phrase unavailable: ${String(error)}`);
+      soundManager.play("error");
+    }
+  };
+
+  const applyRestoredIdentity = async (restored, source) => {
+    setIdentity(restored);
+    setPassphraseDraft("");
+    setRecoveryPhraseDraft("");
+    await runBootstrap();
+    setStatus(`Restored identity ${tinyId(restored.wayfarerId)} from ${source}`);
+  };
+
+  const restoreIdentityBackupFile = (file) => {
+    if (!file) return;
+    requestConfirmation({
+      title: "Restore identity backup?",
+      body: "This replaces the current Wayfarer ID on this device with the one in the backup.",
+      confirmLabel: "Restore",
+      confirmClassName: "bg-red-600 text-white hover:bg-red-500",
+      onConfirm: async () => {
+        try {
+          const contents = await file.text();
+          const restored = await invoke("restore_identity_backup", { request: { contents, passphrase: passphraseDraft } });
+          await applyRestoredIdentity(restored, "backup");
+        } catch (error) {
+          setStatus(`Backup restore failed: ${String(error)}`);
+          soundManager.play("error");
+        }
+      }
+    });
+  };
+
+  const restoreIdentityFromPhrase = () => {
+    requestConfirmation({
+      title: "Restore from recovery phrase?",
+      body: "This replaces the current Wayfarer ID on this device with the one encoded in the phrase.",
+      confirmLabel: "Restore",
+      confirmClassName: "bg-red-600 text-white hover:bg-red-500",
+      onConfirm: async () => {
+        try {
+          const restored = await invoke("restore_identity_from_phrase", { request: { phrase: recoveryPhraseDraft, expectedWayfarerId: null } });
+          await applyRestoredIdentity(restored, "recovery phrase");
+        } catch (error) {
+          setStatus(`Phrase restore failed: ${String(error)}`);
+          soundManager.play("error");
+        }
+      }
+    });
+  };
+
+  const showRevocationCertificate = async () => {
+    try {
+      const code = await invoke("export_revocation_certificate");
+      requestConfirmation({
+        title: "Revocation certificate",
+        body: `Keep this code somewhere other than this device. If the device is lost or stolen, publish it from any Aethos install and contacts will stop accepting new messages from this Wayfarer ID.\n\n${code}`,
+        confirmLabel: "Done",
+        onConfirm: () => {}
+      });
+    } catch (error) {
+      setStatus(`Revocation certificate unavailable: ${String(error)}`);
+      soundManager.play("error");
+    }
+  };
+
+  const publishRevocation = () => {
+    requestConfirmation({
+      title: "Publish revocation?",
+      body: "Everyone who receives it will permanently stop accepting new messages signed by the revoked key. This cannot be undone.",
+      confirmLabel: "Publish",
+      confirmClassName: "bg-red-600 text-white hover:bg-red-500",
+      onConfirm: async () => {
+        try {
+          const snapshot = await invoke("publish_revocation_certificate", { code: revocationDraft });
+          setRevokedContacts(snapshot.revokedContacts || []);
+          setRevocationDraft("");
+          setStatus("Revocation published");
+        } catch (error) {
+          setStatus(`Revocation failed: ${String(error)}`);
+          soundManager.play("error");
+        }
+      }
+    });
+  };
+
+  const rotateIdentity = () => {
+    requestConfirmation({
+      title: "Rotate Wayfarer ID?",
+      body: "This device gets a new Wayfarer ID. Contacts are sent a notice signed by both keys so their threads follow you, and messages to the old ID still arrive for 30 days.",
+      confirmLabel: "Rotate",
+      onConfirm: async () => {
+        try {
+          const rotated = await invoke("rotate_wayfarer_id");
+          setIdentity(rotated);
+          await runBootstrap();
+          setStatus(`Rotated to ${tinyId(rotated.wayfarerId)}; contacts will be notified`);
+        } catch (error) {
+          setStatus(`Rotation failed: ${String(error)}`);
+          soundManager.play("error");
+        }
+      }
+    });
+  };
+
+  const refreshIdentityProfiles = async () => {
+    try {
+      setIdentityProfiles(await invoke("identity_profiles"));
+    } catch (error) {
+      setStatus(`Profiles unavailable: ${String(error)}`);
+    }
+  };
+
+  useEffect(() => {
+    refreshIdentityProfiles();
+  }, [identity?.wayfarerId]);
+
+  const createIdentityProfile = async () => {
+    try {
+      setIdentityProfiles(await invoke("create_identity_profile", { name: profileDraft }));
+      setStatus(`Profile ${profileDraft.trim()} created`);
+      setProfileDraft("");
+    } catch (error) {
+      setStatus(`Profile creation failed: ${String(error)}`);
+      soundManager.play("error");
+    }
+  };
+
+  const switchIdentityProfile = (name) => {
+    requestConfirmation({
+      title: `Switch to profile ${name}?`,
+      body: "Aethos restarts with that profile's Wayfarer ID, contacts and chats. Nothing is synced for this profile until you switch back.",
+      confirmLabel: "Switch",
+      onConfirm: async () => {
+        try {
+          await invoke("switch_identity_profile", { name });
+        } catch (error) {
+          setStatus(`Profile switch failed: ${String(error)}`);
+          soundManager.play("error");
+        }
+      }
+    });
+  };
+
+  const refreshLinkedDevices = async () => {
+    try {
+      setLinkedDevices(await invoke("linked_devices"));
+    } catch (error) {
+      setStatus(`Linked devices unavailable: ${String(error)}`);
+    }
+  };
+
+  const beginDevicePairing = async () => {
+    try {
+      const code = await invoke("begin_device_pairing", { deviceName: null });
+      requestConfirmation({
+        title: "Link this device",
+        body: `Enter this pairing code on the device that holds your identity, then paste the device code it returns here.\n\n${code}`,
+        confirmLabel: "Done",
+        onConfirm: () => {}
+      });
+    } catch (error) {
+      setStatus(`Device pairing failed: ${String(error)}`);
+      soundManager.play("error");
+    }
+  };
+
+  const submitDevicePairingCode = async () => {
+    const code = devicePairingDraft.trim();
+    if (code.startsWith("aethos-device:")) {
+      requestConfirmation({
+        title: "Finish linking?",
+        body: "This replaces the current Wayfarer ID on this device with a linked device key for the identity that approved it.",
+        confirmLabel: "Link",
+        confirmClassName: "bg-red-600 text-white hover:bg-red-500",
+        onConfirm: async () => {
+          try {
+            const linked = await invoke("complete_device_pairing", { certificateCode: code });
+            setDevicePairingDraft("");
+            await applyRestoredIdentity(linked, "linked device pairing");
+          } catch (error) {
+            setStatus(`Device pairing failed: ${String(error)}`);
+            soundManager.play("error");
+          }
+        }
+      });
+      return;
+    }
+    try {
+      const device = await invoke("approve_device_pairing", { pairingCode: code });
+      setDevicePairingDraft("");
+      await refreshLinkedDevices();
+      requestConfirmation({
+        title: `Linked ${device.deviceName}`,
+        body: `Enter this device code on ${device.deviceName} to finish linking.\n\n${device.certificateCode}`,
+        confirmLabel: "Done",
+        onConfirm: () => {}
+      });
+    } catch (error) {
+      setStatus(`Device approval failed: ${String(error)}`);
+      soundManager.play("error");
+    }
+  };
+
+  const unlinkDevice = async (deviceId) => {
+    try {
+      await invoke("unlink_device", { deviceId });
+      await refreshLinkedDevices();
+      setStatus(`Unlinked device ${tinyId(deviceId)}`);
+    } catch (error) {
+      setStatus(`Unlink failed: ${String(error)}`);
+      soundManager.play("error");
+    }
+  };
+
+  useEffect(() => {
+    if (identity && !identity.linkedDevice) {
+      refreshLinkedDevices();
+    }
+  }, [identity?.wayfarerId, identity?.linkedDevice]);
+
+  const refreshSocialRecovery = async () => {
+    try {
+      const [held, recovery] = await Promise.all([invoke("held_recovery_shares"), invoke("social_recovery_status")]);
+      setHeldRecoveryShares(held);
+      setSocialRecovery(recovery);
+    } catch (error) {
+      setStatus(`Social recovery unavailable: ${String(error)}`);
+    }
+  };
+
+  useEffect(() => {
+    refreshSocialRecovery();
+  }, [identity?.wayfarerId, contactKeys]);
+
+  const distributeRecoveryShares = () => {
+    const verifiedContacts = Object.keys(contactKeys).filter((id) => contactKeys[id]?.verified);
+    requestConfirmation({
+      title: "Split identity among verified contacts?",
+      body: `Each of your ${verifiedContacts.length} verified contacts receives one encrypted share. Any ${recoveryThreshold} of them together can restore this identity, so only choose people you trust.`,
+      confirmLabel: "Send Shares",
+      onConfirm: async () => {
+        try {
+          const sent = await invoke("distribute_recovery_shares", { request: { threshold: Number(recoveryThreshold), contacts: verifiedContacts } });
+          setStatus(`Sent ${sent} recovery shares; ${recoveryThreshold} are needed to recover`);
+        } catch (error) {
+          setStatus(`Recovery shares failed: ${String(error)}`);
+          soundManager.play("error");
+        }
+      }
+    });
+  };
+
+  const startSocialRecovery = async () => {
+    try {
+      setSocialRecovery(await invoke("begin_social_recovery", { wayfarerId: socialRecoveryDraft }));
+      setSocialRecoveryDraft("");
+      setStatus("Social recovery started; share your contact card with the people holding your shares");
+    } catch (error) {
+      setStatus(`Social recovery failed: ${String(error)}`);
+      soundManager.play("error");
+    }
+  };
+
+  const returnRecoveryShare = async (ownerWayfarerId) => {
+    if (!selectedContactId) {
+      setStatus("Select the contact who is recovering first");
+      return;
+    }
+    try {
+      await invoke("return_recovery_share", { request: { ownerWayfarerId, toWayfarerId: selectedContactId } });
+      setStatus(`Returned share for ${tinyId(ownerWayfarerId)} to ${tinyId(selectedContactId)}`);
+    } catch (error) {
+      setStatus(`Returning share failed: ${String(error)}`);
+      soundManager.play("error");
+    }
+  };
+
+  const updateIdentityPassphrase = async (enable) => {
+    try {
+      await invoke(enable ? "set_identity_passphrase" : "remove_identity_passphrase", { passphrase: passphraseDraft });
+      setPassphraseDraft("");
+      setIdentity((current) => (current ? { ...current, passphraseProtected: enable } : current));
+      setStatus(enable ? "Identity passphrase set" : "Identity passphrase removed");
+    } catch (error) {
+      setStatus(`Passphrase update failed: ${String(error)}`);
+      soundManager.play("error");
+    }
+  };
+
   useEffect(() => {
     let cancelled = false;
     const checkForUpdates = async () => {
@@ -539,6 +885,8 @@ export default function App() {
       const snapshot = event.payload || {};
       if (snapshot.chat) setChat(snapshot.chat);
       if (snapshot.contacts) setContacts(snapshot.contacts);
+      if (snapshot.contactKeys) setContactKeys(snapshot.contactKeys);
+      if (snapshot.revokedContacts) setRevokedContacts(snapshot.revokedContacts);
       setNetworkPulseTs(Date.now());
     })
       .then((fn) => {
@@ -856,6 +1204,37 @@ export default function App() {
     }
   };
 
+  const showSafetyNumber = async () => {
+    if (!selectedContactId) return;
+    try {
+      const view = await invoke("contact_safety_number", { wayfarerId: selectedContactId });
+      requestConfirmation({
+        title: view.verified ? "Safety number (verified)" : "Compare safety number",
+        body: `${view.digits}\n\n${view.words}\n\nCompare these with your contact in person or by voice.`,
+        confirmLabel: view.verified ? "Mark Unverified" : "Mark Verified",
+        onConfirm: async () => {
+          const snapshot = await invoke("mark_contact_verified", {
+            wayfarerId: selectedContactId,
+            verified: !view.verified
+          });
+          if (snapshot.contactKeys) setContactKeys(snapshot.contactKeys);
+          setStatus(view.verified ? "Contact marked unverified" : "Contact marked verified");
+        }
+      });
+    } catch (error) {
+      setStatus(`Safety number unavailable: ${String(error)}`);
+    }
+  };
+
+  const dismissNotice = async (manifestIdHex) => {
+    try {
+      const snapshot = await invoke("dismiss_notice", { manifestIdHex });
+      if (snapshot.chat) setChat(snapshot.chat);
+    } catch (error) {
+      setStatus(`Dismiss notice failed: ${String(error)}`);
+    }
+  };
+
   const clearAllMessages = () => {
     requestConfirmation({
       title: "Delete all messages?",
@@ -1105,12 +1484,15 @@ export default function App() {
     if (!file) return;
     try {
       const bytes = Array.from(new Uint8Array(await file.arrayBuffer()));
-      const wayfarerId = await invoke("decode_wayfarer_id_from_qr_bytes", { bytes });
-      const alias = contacts[wayfarerId] || `Contact ${tinyId(wayfarerId)}`;
+      const scanned = await invoke("decode_wayfarer_id_from_qr_bytes", { bytes });
+      const wayfarerId = scanned.wayfarerId;
+      const alias = contacts[wayfarerId] || scanned.displayName || `Contact ${tinyId(wayfarerId)}`;
       const nextContacts = await withNetworkPulse(() => invoke("upsert_contact", { request: { wayfarerId, alias } }));
       setContacts(nextContacts);
       await selectContact(wayfarerId);
-      setStatus(`Imported contact ${tinyId(wayfarerId)}`);
+      setStatus(scanned.signed
+        ? `Imported signed contact card for ${alias}`
+        : `Imported contact ${tinyId(wayfarerId)} (unsigned QR)`);
     } catch (error) {
       setStatus(`QR import failed: ${String(error)}`);
       soundManager.play("error");
@@ -1126,8 +1508,14 @@ export default function App() {
       gossipSyncEnabled: form.get("gossip_sync_enabled") === "on",
       verboseLoggingEnabled: form.get("verbose_logging_enabled") === "on",
       enterToSend: form.get("enter_to_send") === "on",
+      lanDowngradePolicy: form.get("lan_require_encrypted") === "on" ? "require_encrypted" : "allow_plaintext",
+      refuseUnencryptedMessages: form.get("refuse_unencrypted_messages") === "on",
       messageTtlSeconds: Number(form.get("message_ttl_seconds") || settings.messageTtlSeconds),
       relayEndpoints: String(relayEndpointsDraft || "")
+        .split("\n")
+        .map((v) => v.trim())
+        .filter(Boolean),
+      noticeAuthorAllowlist: String(form.get("notice_author_allowlist") || "")
         .split("\n")
         .map((v) => v.trim())
         .filter(Boolean)
@@ -1337,6 +1725,33 @@ export default function App() {
           </Card>
         ) : null}
 
+        {(chat.notices || [])
+          .filter((notice) => !notice.dismissed && notice.expiresAtUnixMs > Date.now())
+          .map((notice) => (
+            <Card
+              key={notice.manifestIdHex}
+              data-testid={`notice-${notice.manifestIdHex}`}
+              className={cn(
+                "mb-2",
+                notice.severity === "critical"
+                  ? "border-red-300/40 bg-red-500/10"
+                  : notice.severity === "warning"
+                    ? "border-amber-300/40 bg-amber-500/10"
+                    : "border-cyan-300/30 bg-cyan-500/8"
+              )}
+            >
+              <CardContent className="flex flex-wrap items-center justify-between gap-2 p-3 text-sm">
+                <div className="min-w-[260px] flex-1">
+                  <p className="font-semibold">{notice.title}</p>
+                  <p className="whitespace-pre-wrap text-xs opacity-85">{notice.body}</p>
+                </div>
+                <Button variant="ghost" className="h-8" onClick={() => dismissNotice(notice.manifestIdHex)}>
+                  Dismiss
+                </Button>
+              </CardContent>
+            </Card>
+          ))}
+
         {tab === "chats" && (
           <div className="grid gap-3 lg:h-[calc(100vh-220px)] lg:grid-cols-[285px_1fr]">
             <Card className="flex min-h-[320px] flex-col lg:h-full">
@@ -1357,7 +1772,7 @@ export default function App() {
                       )}
                       onClick={() => selectContact(id)}
                     >
-                      <div className="flex items-center justify-between gap-2"><span className="truncate font-semibold">{alias || tinyId(id)}</span></div>
+                      <div className="flex items-center justify-between gap-2"><span className="truncate font-semibold">{alias || tinyId(id)}</span>{revokedContacts.includes(id) ? <span className="text-[10px] uppercase tracking-wide text-red-300">Revoked</span> : contactKeys[id]?.verified ? <span className="text-[10px] uppercase tracking-wide text-emerald-300">Verified</span> : null}</div>
                       <p className="mt-1 truncate text-xs text-muted-foreground">{tinyId(id)}</p>
                     </button>
                   );
@@ -1365,7 +1780,14 @@ export default function App() {
               </CardContent>
             </Card>
             <Card className="flex min-h-[360px] flex-col lg:h-full">
-              <CardHeader className="p-3 pb-1"><CardTitle className="text-base">{selectedName}</CardTitle></CardHeader>
+              <CardHeader className="flex flex-row items-center justify-between gap-2 p-3 pb-1">
+                <CardTitle className="text-base">{selectedName}</CardTitle>
+                {selectedContactId ? (
+                  <Button data-testid="chat-safety-number" variant="ghost" className="h-7 px-2 text-xs" onClick={showSafetyNumber}>
+                    {contactKeys[selectedContactId]?.verified ? "Verified" : "Verify"}
+                  </Button>
+                ) : null}
+              </CardHeader>
               <CardContent className="flex min-h-0 flex-1 flex-col p-3 pt-1">
                 <div ref={threadContainerRef} className="mb-1.5 min-h-0 flex-1 space-y-2 overflow-auto rounded-lg border border-border/60 bg-background/40 p-2.5">
                   {selectedThread.length === 0 ? <p className="text-sm text-muted-foreground">No messages in this thread yet.</p> : selectedThread.map((m) => (
@@ -1496,6 +1918,7 @@ export default function App() {
                       <div className="mt-1 flex items-center justify-end gap-2 text-[11px] text-slate-300">
                         <span>{formatMessageTimestamp(m)}</span>
                         {formatOutgoingStatus(m) ? <span className="text-cyan-200/90">{formatOutgoingStatus(m)}</span> : null}
+                        {m.unencrypted ? <span data-testid={`message-unencrypted-${m.msgId}`} className="text-amber-300" title="No key was known for this contact, so the message was sent in cleartext">Unencrypted</span> : null}
                         <Button
                           data-testid={`message-delete-${m.msgId}`}
                           variant="ghost"
@@ -1564,11 +1987,31 @@ export default function App() {
           </div>
         )}
 
+        {identityLocked ? (
+          <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/80 p-4">
+            <form onSubmit={unlockIdentity} className="w-full max-w-md rounded-xl border border-border/70 bg-slate-900 p-4 shadow-xl">
+              <h3 className="text-base font-semibold text-slate-100">Unlock identity</h3>
+              <p className="mt-1 text-sm text-slate-300">Enter the passphrase protecting {tinyId(identity?.wayfarerId || "")}.</p>
+              <Input
+                data-testid="unlock-passphrase"
+                className="mt-3"
+                type="password"
+                autoFocus
+                value={passphraseDraft}
+                onChange={(event) => setPassphraseDraft(event.target.value)}
+              />
+              <div className="mt-4 flex justify-end">
+                <Button type="submit" disabled={!passphraseDraft}>Unlock</Button>
+              </div>
+            </form>
+          </div>
+        ) : null}
+
         {confirmDialog ? (
           <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/65 p-4">
             <div className="w-full max-w-md rounded-xl border border-border/70 bg-slate-900 p-4 shadow-xl">
               <h3 className="text-base font-semibold text-slate-100">{confirmDialog.title}</h3>
-              <p className="mt-1 text-sm text-slate-300">{confirmDialog.body}</p>
+              <p className="mt-1 whitespace-pre-line text-sm text-slate-300">{confirmDialog.body}</p>
               <div className="mt-4 flex justify-end gap-2">
                 <Button variant="ghost" onClick={() => setConfirmDialog(null)}>Cancel</Button>
                 <Button className={confirmDialog.confirmClassName || ""} onClick={handleConfirmDialogProceed}>
@@ -1654,6 +2097,8 @@ export default function App() {
                   <label className="flex items-center gap-2 text-sm"><input data-testid="settings-relay-sync" type="checkbox" name="relay_sync_enabled" defaultChecked={settings.relaySyncEnabled} /> Enable relay sync</label>
                   <label className="flex items-center gap-2 text-sm"><input data-testid="settings-gossip-sync" type="checkbox" name="gossip_sync_enabled" defaultChecked={settings.gossipSyncEnabled} /> Enable LAN gossip sync</label>
                   <label className="flex items-center gap-2 text-sm"><input data-testid="settings-verbose-logging" type="checkbox" name="verbose_logging_enabled" defaultChecked={settings.verboseLoggingEnabled} /> Enable verbose logging</label>
+                  <label className="flex items-center gap-2 text-sm"><input data-testid="settings-lan-require-encrypted" type="checkbox" name="lan_require_encrypted" defaultChecked={settings.lanDowngradePolicy === "require_encrypted"} /> Refuse unencrypted LAN TCP peers</label>
+                  <label className="flex items-center gap-2 text-sm"><input data-testid="settings-refuse-unencrypted-messages" type="checkbox" name="refuse_unencrypted_messages" defaultChecked={settings.refuseUnencryptedMessages === true} /> Refuse to send unencrypted messages to contacts with no known key</label>
                   <label className="flex items-center gap-2 text-sm"><input data-testid="settings-enter-to-send" type="checkbox" name="enter_to_send" defaultChecked={settings.enterToSend !== false} /> Enter sends message (Shift+Enter newline)</label>
                   <Input name="message_ttl_seconds" type="number" defaultValue={settings.messageTtlSeconds} />
                   <Textarea
@@ -1663,6 +2108,135 @@ export default function App() {
                     value={relayEndpointsDraft}
                     onChange={(event) => setRelayEndpointsDraft(event.target.value)}
                   />
+                  <Textarea
+                    data-testid="settings-notice-authors"
+                    name="notice_author_allowlist"
+                    rows={2}
+                    placeholder="Wayfarer IDs allowed to post notices, one per line"
+                    defaultValue={(settings.noticeAuthorAllowlist || []).join("\n")}
+                  />
+                  <div className="flex flex-wrap items-center gap-2">
+                    <Input
+                      data-testid="settings-identity-passphrase"
+                      className="max-w-xs"
+                      type="password"
+                      placeholder={identity?.passphraseProtected ? "Current passphrase" : "New identity passphrase"}
+                      value={passphraseDraft}
+                      onChange={(event) => setPassphraseDraft(event.target.value)}
+                    />
+                    {identity?.passphraseProtected ? (
+                      <Button type="button" variant="secondary" disabled={!passphraseDraft} onClick={() => updateIdentityPassphrase(false)}>Remove Passphrase</Button>
+                    ) : (
+                      <Button type="button" variant="secondary" disabled={!passphraseDraft} onClick={() => updateIdentityPassphrase(true)}>Protect Identity</Button>
+                    )}
+                    <Button type="button" variant="ghost" disabled={!passphraseDraft} onClick={exportIdentityBackup}>Export Backup</Button>
+                    <label className="inline-flex cursor-pointer items-center">
+                      <input className="hidden" type="file" accept="application/json,.json" onChange={(e) => restoreIdentityBackupFile(e.target.files && e.target.files[0])} />
+                      <span className="inline-flex h-10 items-center rounded-md border border-border/70 px-4 text-sm font-medium hover:bg-white/10">Restore Backup</span>
+                    </label>
+                  </div>
+                  <div className="flex flex-wrap items-center gap-2">
+                    <Input
+                      data-testid="settings-recovery-phrase"
+                      className="max-w-md"
+                      placeholder="Recovery phrase (33 words)"
+                      value={recoveryPhraseDraft}
+                      onChange={(event) => setRecoveryPhraseDraft(event.target.value)}
+                    />
+                    <Button type="button" variant="secondary" disabled={!recoveryPhraseDraft.trim()} onClick={restoreIdentityFromPhrase}>Restore From Phrase</Button>
+                    <Button type="button" variant="ghost" onClick={showRecoveryPhrase}>Show Recovery Phrase</Button>
+                    <Button type="button" variant="ghost" disabled={identity?.linkedDevice || identity?.passphraseProtected} onClick={rotateIdentity}>Rotate ID</Button>
+                  </div>
+                  <div className="flex flex-wrap items-center gap-2">
+                    <select
+                      data-testid="settings-identity-profile"
+                      className="h-10 rounded-md border border-border/70 bg-background px-2 text-sm"
+                      value={identityProfiles.find((profile) => profile.active)?.name || ""}
+                      onChange={(event) => switchIdentityProfile(event.target.value)}
+                    >
+                      {identityProfiles.map((profile) => (
+                        <option key={profile.name} value={profile.name}>
+                          {profile.name}{profile.wayfarerId ? ` (${tinyId(profile.wayfarerId)})` : ""}
+                        </option>
+                      ))}
+                    </select>
+                    <Input
+                      className="max-w-xs"
+                      placeholder="New profile name"
+                      value={profileDraft}
+                      onChange={(event) => setProfileDraft(event.target.value)}
+                    />
+                    <Button type="button" variant="secondary" disabled={!profileDraft.trim()} onClick={createIdentityProfile}>Create Profile</Button>
+                  </div>
+                  <div className="flex flex-wrap items-center gap-2">
+                    <Input
+                      data-testid="settings-revocation-code"
+                      className="max-w-md"
+                      placeholder="Revocation code (aethos-revoke:…)"
+                      value={revocationDraft}
+                      onChange={(event) => setRevocationDraft(event.target.value)}
+                    />
+                    <Button type="button" variant="secondary" disabled={!revocationDraft.trim()} onClick={publishRevocation}>Publish Revocation</Button>
+                    <Button type="button" variant="ghost" disabled={identity?.linkedDevice} onClick={showRevocationCertificate}>Show Revocation Certificate</Button>
+                  </div>
+                  <div className="flex flex-wrap items-center gap-2">
+                    <Input
+                      data-testid="settings-device-pairing"
+                      className="max-w-md"
+                      placeholder="Pairing code (aethos-pair:…) or device code (aethos-device:…)"
+                      value={devicePairingDraft}
+                      onChange={(event) => setDevicePairingDraft(event.target.value)}
+                    />
+                    <Button type="button" variant="secondary" disabled={!devicePairingDraft.trim()} onClick={submitDevicePairingCode}>
+                      {devicePairingDraft.trim().startsWith("aethos-device:") ? "Finish Linking" : "Approve Device"}
+                    </Button>
+                    <Button type="button" variant="ghost" onClick={beginDevicePairing}>Link This Device</Button>
+                  </div>
+                  <div className="flex flex-wrap items-center gap-2">
+                    <Input
+                      data-testid="settings-recovery-threshold"
+                      className="w-20"
+                      type="number"
+                      min={2}
+                      value={recoveryThreshold}
+                      onChange={(event) => setRecoveryThreshold(event.target.value)}
+                    />
+                    <Button type="button" variant="secondary" disabled={identity?.linkedDevice} onClick={distributeRecoveryShares}>Split Among Verified Contacts</Button>
+                    <Input
+                      data-testid="settings-social-recovery"
+                      className="max-w-xs"
+                      placeholder="Wayfarer ID to recover"
+                      value={socialRecoveryDraft}
+                      onChange={(event) => setSocialRecoveryDraft(event.target.value)}
+                    />
+                    <Button type="button" variant="ghost" disabled={!socialRecoveryDraft.trim()} onClick={startSocialRecovery}>Start Social Recovery</Button>
+                  </div>
+                  {socialRecovery ? (
+                    <p className="text-xs text-muted-foreground">Recovering {tinyId(socialRecovery.expectedWayfarerId)}: {socialRecovery.collected}{socialRecovery.threshold ? ` of ${socialRecovery.threshold}` : ""} shares collected.</p>
+                  ) : null}
+                  {heldRecoveryShares.length > 0 ? (
+                    <div className="space-y-1">
+                      {heldRecoveryShares.map((ownerWayfarerId) => (
+                        <div key={ownerWayfarerId} className="flex items-center gap-2 text-xs">
+                          <span>Holding a recovery share for {contacts[ownerWayfarerId] || tinyId(ownerWayfarerId)}</span>
+                          <Button type="button" size="sm" variant="ghost" onClick={() => returnRecoveryShare(ownerWayfarerId)}>Return to Selected Contact</Button>
+                        </div>
+                      ))}
+                    </div>
+                  ) : null}
+                  {identity?.linkedDevice ? (
+                    <p className="text-xs text-muted-foreground">This device is linked to {tinyId(identity.wayfarerId)}; it can only message contacts it has received a signed message from.</p>
+                  ) : linkedDevices.length > 0 ? (
+                    <div className="space-y-1">
+                      {linkedDevices.map((device) => (
+                        <div key={device.deviceId} className="flex items-center gap-2 text-xs">
+                          <span className="font-medium">{device.deviceName}</span>
+                          <span className="text-muted-foreground">{tinyId(device.deviceId)}</span>
+                          <Button type="button" size="sm" variant="ghost" onClick={() => unlinkDevice(device.deviceId)}>Unlink</Button>
+                        </div>
+                      ))}
+                    </div>
+                  ) : null}
                   <div className="flex flex-wrap gap-2">
                     <Button data-testid="settings-save" type="submit"><CheckCircle2 className="mr-2 h-4 w-4" />Save Settings</Button>
                     <Button type="button" variant="secondary" onClick={resetRelayEndpoints}>Reset Relay Default</Button>
@@ -0,0 +1,1298 @@
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use ciborium::value::Value;
+
+use crate::aethos_core::canonical_cbor::check_canonical;
+use crate::aethos_core::protocol::{decode_cbor_value_exact, encode_cbor_value_deterministic};
+
+pub const WAYFARER_CHAT_V1: &str = "wayfarer.chat.v1";
+pub const WAYFARER_MEDIA_MANIFEST_V1: &str = "wayfarer.media_manifest.v1";
+pub const WAYFARER_PROFILE_V1: &str = "wayfarer.profile.v1";
+pub const WAYFARER_REACTION_V1: &str = "wayfarer.reaction.v1";
+pub const WAYFARER_MESSAGE_UPDATE_V1: &str = "wayfarer.message_update.v1";
+pub const WAYFARER_STATUS_EVENT_V1: &str = "wayfarer.status_event.v1";
+pub const WAYFARER_NOTICE_V1: &str = "wayfarer.notice.v1";
+pub const WAYFARER_SUCCESSION_V1: &str = "wayfarer.succession.v1";
+pub const WAYFARER_REVOCATION_V1: &str = "wayfarer.revocation.v1";
+pub const WAYFARER_RECOVERY_SHARE_BODY_V1: &str = "wayfarer.recovery_share_body.v1";
+pub const WAYFARER_DEVICE_ANNOUNCE_V1: &str = "wayfarer.device_announce.v1";
+/// Types whose schema is reserved but not yet typed; their fields round-trip untouched.
+pub const RESERVED_TYPES: [&str; 0] = [];
+pub const MEDIA_KINDS: [&str; 4] = ["image", "video", "audio", "file"];
+pub const MAX_REACTION_EMOJI_BYTES: usize = 32;
+pub const MAX_PROFILE_DISPLAY_NAME_BYTES: usize = 64;
+pub const MAX_PROFILE_STATUS_LINE_BYTES: usize = 140;
+pub const MAX_PROFILE_AVATAR_THUMBNAIL_BYTES: usize = 16 * 1024;
+pub const MAX_PROFILE_PREFERRED_RELAYS: usize = 4;
+pub const MAX_STATUS_EVENT_TARGETS: usize = 64;
+pub const MAX_NOTICE_TITLE_BYTES: usize = 120;
+pub const MAX_NOTICE_BODY_BYTES: usize = 2048;
+pub const MAX_SUCCESSION_RECORD_BYTES: usize = 512;
+pub const MAX_REVOCATION_CERTIFICATE_BYTES: usize = 256;
+pub const MAX_RECOVERY_SHARE_BYTES: usize = 512;
+pub const MAX_DEVICE_CERTIFICATE_BYTES: usize = 256;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatBody {
+    pub text: String,
+    pub created_at_unix_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaAsset {
+    pub asset_ref: String,
+    pub mime_type: String,
+    pub byte_length: u64,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaManifestBody {
+    pub transfer_ref: String,
+    pub media_kind: String,
+    pub assets: Vec<MediaAsset>,
+    pub caption: Option<String>,
+    pub created_at_unix_ms: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReactionAction {
+    Add,
+    Remove,
+}
+
+impl ReactionAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Remove => "remove",
+        }
+    }
+}
+
+/// Adds or removes `emoji` on the message whose body hashes to `target_manifest_id_hex`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReactionBody {
+    pub target_manifest_id_hex: String,
+    pub emoji: String,
+    pub action: ReactionAction,
+    pub created_at_unix_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageUpdateAction {
+    Edit { text: String },
+    Delete,
+}
+
+/// Edits or deletes the message whose body hashes to `target_manifest_id_hex`; receivers only
+/// honor it when the envelope author also authored the target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageUpdateBody {
+    pub target_manifest_id_hex: String,
+    pub action: MessageUpdateAction,
+    pub created_at_unix_ms: u64,
+}
+
+/// An author's self-published profile; receivers keep the highest `version` per author.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileBody {
+    pub version: u64,
+    pub display_name: String,
+    pub avatar_thumbnail: Option<Vec<u8>>,
+    pub status_line: Option<String>,
+    pub preferred_relays: Vec<String>,
+    pub created_at_unix_ms: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StatusEventKind {
+    Delivered,
+    Read,
+}
+
+impl StatusEventKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Delivered => "delivered",
+            Self::Read => "read",
+        }
+    }
+}
+
+/// A recipient's delivery or read receipt for one or more messages it received.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusEventBody {
+    pub status: StatusEventKind,
+    pub target_manifest_id_hexes: Vec<String>,
+    pub created_at_unix_ms: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NoticeSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl NoticeSeverity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Critical => "critical",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "info" => Some(Self::Info),
+            "warning" => Some(Self::Warning),
+            "critical" => Some(Self::Critical),
+            _ => None,
+        }
+    }
+}
+
+/// An operator notice shown as a banner until `expires_at_unix_ms`; never rendered as chat.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoticeBody {
+    pub severity: NoticeSeverity,
+    pub title: String,
+    pub body: String,
+    pub expires_at_unix_ms: u64,
+    pub created_at_unix_ms: u64,
+}
+
+/// Carries a signed `wayfarer.succession_record.v1`; receivers verify it separately.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuccessionBody {
+    pub record: Vec<u8>,
+}
+
+/// Carries a signed `wayfarer.revocation_cert.v1`. Sent in cleartext so every carrier can
+/// check it and stop accepting envelopes from the revoked key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevocationBody {
+    pub certificate: Vec<u8>,
+}
+
+/// Carries a signed `wayfarer.recovery_share.v1`, either to the holder or back to the owner's
+/// recovering device. Only ever sent in sealed envelopes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecoveryShareBody {
+    pub share: Vec<u8>,
+}
+
+/// Carries a root-signed `wayfarer.device_cert.v1` so contacts can seal to a linked device
+/// before that device has sent them anything.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceAnnounceBody {
+    pub certificate: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppBody {
+    Chat(ChatBody),
+    MediaManifest(MediaManifestBody),
+    Reaction(ReactionBody),
+    MessageUpdate(MessageUpdateBody),
+    Profile(ProfileBody),
+    StatusEvent(StatusEventBody),
+    Notice(NoticeBody),
+    Succession(SuccessionBody),
+    Revocation(RevocationBody),
+    RecoveryShare(RecoveryShareBody),
+    DeviceAnnounce(DeviceAnnounceBody),
+    Reserved {
+        payload_type: String,
+        fields: BTreeMap<String, Value>,
+    },
+    /// A well-formed body whose `type` has no registered handler; callers skip it.
+    Unknown {
+        payload_type: String,
+    },
+}
+
+impl AppBody {
+    pub fn payload_type(&self) -> &str {
+        match self {
+            Self::Chat(_) => WAYFARER_CHAT_V1,
+            Self::MediaManifest(_) => WAYFARER_MEDIA_MANIFEST_V1,
+            Self::Reaction(_) => WAYFARER_REACTION_V1,
+            Self::MessageUpdate(_) => WAYFARER_MESSAGE_UPDATE_V1,
+            Self::Profile(_) => WAYFARER_PROFILE_V1,
+            Self::StatusEvent(_) => WAYFARER_STATUS_EVENT_V1,
+            Self::Notice(_) => WAYFARER_NOTICE_V1,
+            Self::Succession(_) => WAYFARER_SUCCESSION_V1,
+            Self::Revocation(_) => WAYFARER_REVOCATION_V1,
+            Self::RecoveryShare(_) => WAYFARER_RECOVERY_SHARE_BODY_V1,
+            Self::DeviceAnnounce(_) => WAYFARER_DEVICE_ANNOUNCE_V1,
+            Self::Reserved { payload_type, .. } | Self::Unknown { payload_type } => payload_type,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppBodyError {
+    InvalidCbor(String),
+    NonCanonical,
+    InvalidEnvelope(String),
+    /// The typed decoder for `payload_type` ran and rejected the fields.
+    Malformed {
+        payload_type: String,
+        reason: String,
+    },
+}
+
+impl AppBodyError {
+    pub fn reason(&self) -> String {
+        match self {
+            Self::InvalidCbor(err) => format!("invalid_cbor: {err}"),
+            Self::NonCanonical => "non_deterministic_cbor_encoding".to_string(),
+            Self::InvalidEnvelope(reason) => reason.clone(),
+            Self::Malformed {
+                payload_type,
+                reason,
+            } => format!("malformed_{}: {reason}", payload_type.replace('.', "_")),
+        }
+    }
+}
+
+pub trait AppBodyHandler: Send + Sync {
+    fn payload_type(&self) -> &'static str;
+    fn decode(&self, fields: &BTreeMap<String, Value>) -> Result<AppBody, String>;
+    /// Returns the full top-level map for `body`, including its `type` entry.
+    fn encode(&self, body: &AppBody) -> Result<BTreeMap<String, Value>, String>;
+}
+
+pub struct AppBodyRegistry {
+    handlers: BTreeMap<&'static str, Box<dyn AppBodyHandler>>,
+}
+
+impl AppBodyRegistry {
+    pub fn empty() -> Self {
+        Self {
+            handlers: BTreeMap::new(),
+        }
+    }
+
+    pub fn wayfarer() -> Self {
+        let mut registry = Self::empty();
+        registry.register(Box::new(ChatHandler));
+        registry.register(Box::new(MediaManifestHandler));
+        registry.register(Box::new(ReactionHandler));
+        registry.register(Box::new(MessageUpdateHandler));
+        registry.register(Box::new(ProfileHandler));
+        registry.register(Box::new(StatusEventHandler));
+        registry.register(Box::new(NoticeHandler));
+        registry.register(Box::new(SuccessionHandler));
+        registry.register(Box::new(RevocationHandler));
+        registry.register(Box::new(RecoveryShareHandler));
+        registry.register(Box::new(DeviceAnnounceHandler));
+        for payload_type in RESERVED_TYPES {
+            registry.register(Box::new(ReservedHandler(payload_type)));
+        }
+        registry
+    }
+
+    /// Registers `handler`, replacing any existing handler for the same type.
+    pub fn register(&mut self, handler: Box<dyn AppBodyHandler>) {
+        self.handlers.insert(handler.payload_type(), handler);
+    }
+
+    pub fn handles(&self, payload_type: &str) -> bool {
+        self.handlers.contains_key(payload_type)
+    }
+
+    pub fn decode(&self, body: &[u8]) -> Result<AppBody, AppBodyError> {
+        let decoded = decode_cbor_value_exact(body, "wayfarer app body")
+            .map_err(AppBodyError::InvalidCbor)?;
+        check_canonical(body).map_err(|_| AppBodyError::NonCanonical)?;
+
+        let fields =
+            value_as_text_keyed_map(decoded, "top_level").map_err(AppBodyError::InvalidEnvelope)?;
+        let payload_type = match fields.get("type") {
+            Some(Value::Text(value)) => value.clone(),
+            Some(_) => return Err(AppBodyError::InvalidEnvelope("type_must_be_text".into())),
+            None => return Err(AppBodyError::InvalidEnvelope("missing_type".into())),
+        };
+
+        let Some(handler) = self.handlers.get(payload_type.as_str()) else {
+            return Ok(AppBody::Unknown { payload_type });
+        };
+        handler
+            .decode(&fields)
+            .map_err(|reason| AppBodyError::Malformed {
+                payload_type,
+                reason,
+            })
+    }
+
+    /// Encodes `body` deterministically and checks that it decodes back through its handler.
+    pub fn encode(&self, body: &AppBody) -> Result<Vec<u8>, String> {
+        let payload_type = body.payload_type();
+        let handler = self
+            .handlers
+            .get(payload_type)
+            .ok_or_else(|| format!("no app body handler registered for {payload_type}"))?;
+        let fields = handler.encode(body)?;
+        let encoded = encode_cbor_value_deterministic(&text_keyed_map_value(fields))?;
+        self.decode(&encoded).map_err(|err| {
+            format!(
+                "constructed {payload_type} body failed strict classification: {}",
+                err.reason()
+            )
+        })?;
+        Ok(encoded)
+    }
+}
+
+fn wayfarer_registry() -> &'static AppBodyRegistry {
+    static REGISTRY: OnceLock<AppBodyRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(AppBodyRegistry::wayfarer)
+}
+
+pub fn decode_app_body(body: &[u8]) -> Result<AppBody, AppBodyError> {
+    wayfarer_registry().decode(body)
+}
+
+pub fn encode_app_body(body: &AppBody) -> Result<Vec<u8>, String> {
+    wayfarer_registry().encode(body)
+}
+
+struct ChatHandler;
+
+impl AppBodyHandler for ChatHandler {
+    fn payload_type(&self) -> &'static str {
+        WAYFARER_CHAT_V1
+    }
+
+    fn decode(&self, fields: &BTreeMap<String, Value>) -> Result<AppBody, String> {
+        Ok(AppBody::Chat(ChatBody {
+            text: required_non_empty_text(fields, "text")?,
+            created_at_unix_ms: required_u64_integer(fields, "created_at_unix_ms")?,
+        }))
+    }
+
+    fn encode(&self, body: &AppBody) -> Result<BTreeMap<String, Value>, String> {
+        let AppBody::Chat(chat) = body else {
+            return Err("type_mismatch".to_string());
+        };
+        let mut map = typed_map(WAYFARER_CHAT_V1);
+        map.insert(
+            "created_at_unix_ms".to_string(),
+            Value::Integer(chat.created_at_unix_ms.into()),
+        );
+        map.insert("text".to_string(), Value::Text(chat.text.clone()));
+        Ok(map)
+    }
+}
+
+struct MediaManifestHandler;
+
+impl AppBodyHandler for MediaManifestHandler {
+    fn payload_type(&self) -> &'static str {
+        WAYFARER_MEDIA_MANIFEST_V1
+    }
+
+    fn decode(&self, fields: &BTreeMap<String, Value>) -> Result<AppBody, String> {
+        let transfer_ref = required_non_empty_text(fields, "transfer_ref")?;
+        let media_kind = required_non_empty_text(fields, "media_kind")?;
+        if !MEDIA_KINDS.contains(&media_kind.as_str()) {
+            return Err("media_kind_invalid".to_string());
+        }
+
+        let assets = fields
+            .get("assets")
+            .ok_or_else(|| "assets_required".to_string())?;
+        let Value::Array(assets) = assets else {
+            return Err("assets_must_be_array".to_string());
+        };
+        if assets.is_empty() {
+            return Err("assets_must_be_non_empty".to_string());
+        }
+
+        let mut decoded_assets = Vec::with_capacity(assets.len());
+        for (index, asset) in assets.iter().enumerate() {
+            let context = format!("assets[{index}]");
+            let asset_map = value_as_text_keyed_map(asset.clone(), &context)?;
+            decoded_assets.push(MediaAsset {
+                asset_ref: required_non_empty_text(&asset_map, "asset_ref")?,
+                mime_type: required_non_empty_text(&asset_map, "mime_type")?,
+                byte_length: required_u64_integer(&asset_map, "byte_length")?,
+                name: optional_text(&asset_map, "name")
+                    .map_err(|err| format!("{context}.{err}"))?,
+            });
+        }
+
+        Ok(AppBody::MediaManifest(MediaManifestBody {
+            transfer_ref,
+            media_kind,
+            assets: decoded_assets,
+            caption: optional_text(fields, "caption")?,
+            created_at_unix_ms: required_u64_integer(fields, "created_at_unix_ms")?,
+        }))
+    }
+
+    fn encode(&self, body: &AppBody) -> Result<BTreeMap<String, Value>, String> {
+        let AppBody::MediaManifest(manifest) = body else {
+            return Err("type_mismatch".to_string());
+        };
+        let mut map = typed_map(WAYFARER_MEDIA_MANIFEST_V1);
+        map.insert(
+            "transfer_ref".to_string(),
+            Value::Text(manifest.transfer_ref.clone()),
+        );
+        map.insert(
+            "media_kind".to_string(),
+            Value::Text(manifest.media_kind.clone()),
+        );
+        let assets = manifest
+            .assets
+            .iter()
+            .map(|asset| {
+                let mut asset_map = BTreeMap::new();
+                asset_map.insert(
+                    "asset_ref".to_string(),
+                    Value::Text(asset.asset_ref.clone()),
+                );
+                asset_map.insert(
+                    "mime_type".to_string(),
+                    Value::Text(asset.mime_type.clone()),
+                );
+                asset_map.insert(
+                    "byte_length".to_string(),
+                    Value::Integer(asset.byte_length.into()),
+                );
+                if let Some(name) = asset.name.as_ref() {
+                    asset_map.insert("name".to_string(), Value::Text(name.clone()));
+                }
+                text_keyed_map_value(asset_map)
+            })
+            .collect();
+        map.insert("assets".to_string(), Value::Array(assets));
+        if let Some(caption) = manifest.caption.as_ref() {
+            map.insert("caption".to_string(), Value::Text(caption.clone()));
+        }
+        map.insert(
+            "created_at_unix_ms".to_string(),
+            Value::Integer(manifest.created_at_unix_ms.into()),
+        );
+        Ok(map)
+    }
+}
+
+struct ReactionHandler;
+
+impl AppBodyHandler for ReactionHandler {
+    fn payload_type(&self) -> &'static str {
+        WAYFARER_REACTION_V1
+    }
+
+    fn decode(&self, fields: &BTreeMap<String, Value>) -> Result<AppBody, String> {
+        let target_manifest_id_hex = required_manifest_id_hex(fields, "target_manifest_id_hex")?;
+        let emoji = required_non_empty_text(fields, "emoji")?;
+        if emoji.len() > MAX_REACTION_EMOJI_BYTES {
+            return Err("emoji_too_long".to_string());
+        }
+        let action = match required_non_empty_text(fields, "action")?.as_str() {
+            "add" => ReactionAction::Add,
+            "remove" => ReactionAction::Remove,
+            _ => return Err("action_invalid".to_string()),
+        };
+
+        Ok(AppBody::Reaction(ReactionBody {
+            target_manifest_id_hex,
+            emoji,
+            action,
+            created_at_unix_ms: required_u64_integer(fields, "created_at_unix_ms")?,
+        }))
+    }
+
+    fn encode(&self, body: &AppBody) -> Result<BTreeMap<String, Value>, String> {
+        let AppBody::Reaction(reaction) = body else {
+            return Err("type_mismatch".to_string());
+        };
+        let mut map = typed_map(WAYFARER_REACTION_V1);
+        map.insert(
+            "target_manifest_id_hex".to_string(),
+            Value::Text(reaction.target_manifest_id_hex.clone()),
+        );
+        map.insert("emoji".to_string(), Value::Text(reaction.emoji.clone()));
+        map.insert(
+            "action".to_string(),
+            Value::Text(reaction.action.as_str().to_string()),
+        );
+        map.insert(
+            "created_at_unix_ms".to_string(),
+            Value::Integer(reaction.created_at_unix_ms.into()),
+        );
+        Ok(map)
+    }
+}
+
+struct MessageUpdateHandler;
+
+impl AppBodyHandler for MessageUpdateHandler {
+    fn payload_type(&self) -> &'static str {
+        WAYFARER_MESSAGE_UPDATE_V1
+    }
+
+    fn decode(&self, fields: &BTreeMap<String, Value>) -> Result<AppBody, String> {
+        let target_manifest_id_hex = required_manifest_id_hex(fields, "target_manifest_id_hex")?;
+        let action = match required_non_empty_text(fields, "action")?.as_str() {
+            "edit" => MessageUpdateAction::Edit {
+                text: required_non_empty_text(fields, "text")?,
+            },
+            "delete" => {
+                if fields.contains_key("text") {
+                    return Err("text_not_allowed_for_delete".to_string());
+                }
+                MessageUpdateAction::Delete
+            }
+            _ => return Err("action_invalid".to_string()),
+        };
+
+        Ok(AppBody::MessageUpdate(MessageUpdateBody {
+            target_manifest_id_hex,
+            action,
+            created_at_unix_ms: required_u64_integer(fields, "created_at_unix_ms")?,
+        }))
+    }
+
+    fn encode(&self, body: &AppBody) -> Result<BTreeMap<String, Value>, String> {
+        let AppBody::MessageUpdate(update) = body else {
+            return Err("type_mismatch".to_string());
+        };
+        let mut map = typed_map(WAYFARER_MESSAGE_UPDATE_V1);
+        map.insert(
+            "target_manifest_id_hex".to_string(),
+            Value::Text(update.target_manifest_id_hex.clone()),
+        );
+        let action = match &update.action {
+            MessageUpdateAction::Edit { text } => {
+                map.insert("text".to_string(), Value::Text(text.clone()));
+                "edit"
+            }
+            MessageUpdateAction::Delete => "delete",
+        };
+        map.insert("action".to_string(), Value::Text(action.to_string()));
+        map.insert(
+            "created_at_unix_ms".to_string(),
+            Value::Integer(update.created_at_unix_ms.into()),
+        );
+        Ok(map)
+    }
+}
+
+struct ProfileHandler;
+
+impl AppBodyHandler for ProfileHandler {
+    fn payload_type(&self) -> &'static str {
+        WAYFARER_PROFILE_V1
+    }
+
+    fn decode(&self, fields: &BTreeMap<String, Value>) -> Result<AppBody, String> {
+        let version = required_u64_integer(fields, "version")?;
+        if version == 0 {
+            return Err("version_must_be_positive".to_string());
+        }
+        let display_name = required_non_empty_text(fields, "display_name")?;
+        if display_name.len() > MAX_PROFILE_DISPLAY_NAME_BYTES {
+            return Err("display_name_too_long".to_string());
+        }
+        let avatar_thumbnail = match fields.get("avatar_thumbnail") {
+            None => None,
+            Some(Value::Bytes(bytes)) if bytes.len() <= MAX_PROFILE_AVATAR_THUMBNAIL_BYTES => {
+                Some(bytes.clone())
+            }
+            Some(Value::Bytes(_)) => return Err("avatar_thumbnail_too_large".to_string()),
+            Some(_) => return Err("avatar_thumbnail_must_be_bytes".to_string()),
+        };
+        let status_line = optional_text(fields, "status_line")?;
+        if status_line
+            .as_ref()
+            .is_some_and(|status_line| status_line.len() > MAX_PROFILE_STATUS_LINE_BYTES)
+        {
+            return Err("status_line_too_long".to_string());
+        }
+
+        let preferred_relays = match fields.get("preferred_relays") {
+            None => Vec::new(),
+            Some(Value::Array(relays)) => {
+                if relays.len() > MAX_PROFILE_PREFERRED_RELAYS {
+                    return Err("preferred_relays_too_many".to_string());
+                }
+                relays
+                    .iter()
+                    .map(|relay| match relay {
+                        Value::Text(relay) if !relay.is_empty() => Ok(relay.clone()),
+                        _ => Err("preferred_relays_must_be_non_empty_text".to_string()),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?
+            }
+            Some(_) => return Err("preferred_relays_must_be_array".to_string()),
+        };
+
+        Ok(AppBody::Profile(ProfileBody {
+            version,
+            display_name,
+            avatar_thumbnail,
+            status_line,
+            preferred_relays,
+            created_at_unix_ms: required_u64_integer(fields, "created_at_unix_ms")?,
+        }))
+    }
+
+    fn encode(&self, body: &AppBody) -> Result<BTreeMap<String, Value>, String> {
+        let AppBody::Profile(profile) = body else {
+            return Err("type_mismatch".to_string());
+        };
+        let mut map = typed_map(WAYFARER_PROFILE_V1);
+        map.insert(
+            "version".to_string(),
+            Value::Integer(profile.version.into()),
+        );
+        map.insert(
+            "display_name".to_string(),
+            Value::Text(profile.display_name.clone()),
+        );
+        if let Some(avatar_thumbnail) = profile.avatar_thumbnail.as_ref() {
+            map.insert(
+                "avatar_thumbnail".to_string(),
+                Value::Bytes(avatar_thumbnail.clone()),
+            );
+        }
+        if let Some(status_line) = profile.status_line.as_ref() {
+            map.insert("status_line".to_string(), Value::Text(status_line.clone()));
+        }
+        if !profile.preferred_relays.is_empty() {
+            map.insert(
+                "preferred_relays".to_string(),
+                Value::Array(
+                    profile
+                        .preferred_relays
+                        .iter()
+                        .map(|relay| Value::Text(relay.clone()))
+                        .collect(),
+                ),
+            );
+        }
+        map.insert(
+            "created_at_unix_ms".to_string(),
+            Value::Integer(profile.created_at_unix_ms.into()),
+        );
+        Ok(map)
+    }
+}
+
+struct StatusEventHandler;
+
+impl AppBodyHandler for StatusEventHandler {
+    fn payload_type(&self) -> &'static str {
+        WAYFARER_STATUS_EVENT_V1
+    }
+
+    fn decode(&self, fields: &BTreeMap<String, Value>) -> Result<AppBody, String> {
+        let status = match required_non_empty_text(fields, "status")?.as_str() {
+            "delivered" => StatusEventKind::Delivered,
+            "read" => StatusEventKind::Read,
+            _ => return Err("status_invalid".to_string()),
+        };
+        let targets = fields
+            .get("target_manifest_id_hexes")
+            .ok_or_else(|| "target_manifest_id_hexes_required".to_string())?;
+        let Value::Array(targets) = targets else {
+            return Err("target_manifest_id_hexes_must_be_array".to_string());
+        };
+        if targets.is_empty() || targets.len() > MAX_STATUS_EVENT_TARGETS {
+            return Err("target_manifest_id_hexes_count_invalid".to_string());
+        }
+        let target_manifest_id_hexes = targets
+            .iter()
+            .map(|target| match target {
+                Value::Text(target) if is_manifest_id_hex(target) => Ok(target.clone()),
+                _ => Err("target_manifest_id_hexes_must_be_lowercase_sha256_hex".to_string()),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(AppBody::StatusEvent(StatusEventBody {
+            status,
+            target_manifest_id_hexes,
+            created_at_unix_ms: required_u64_integer(fields, "created_at_unix_ms")?,
+        }))
+    }
+
+    fn encode(&self, body: &AppBody) -> Result<BTreeMap<String, Value>, String> {
+        let AppBody::StatusEvent(event) = body else {
+            return Err("type_mismatch".to_string());
+        };
+        let mut map = typed_map(WAYFARER_STATUS_EVENT_V1);
+        map.insert(
+            "status".to_string(),
+            Value::Text(event.status.as_str().to_string()),
+        );
+        map.insert(
+            "target_manifest_id_hexes".to_string(),
+            Value::Array(
+                event
+                    .target_manifest_id_hexes
+                    .iter()
+                    .map(|target| Value::Text(target.clone()))
+                    .collect(),
+            ),
+        );
+        map.insert(
+            "created_at_unix_ms".to_string(),
+            Value::Integer(event.created_at_unix_ms.into()),
+        );
+        Ok(map)
+    }
+}
+
+struct NoticeHandler;
+
+impl AppBodyHandler for NoticeHandler {
+    fn payload_type(&self) -> &'static str {
+        WAYFARER_NOTICE_V1
+    }
+
+    fn decode(&self, fields: &BTreeMap<String, Value>) -> Result<AppBody, String> {
+        let severity = NoticeSeverity::parse(&required_non_empty_text(fields, "severity")?)
+            .ok_or_else(|| "severity_invalid".to_string())?;
+        let title = required_non_empty_text(fields, "title")?;
+        if title.len() > MAX_NOTICE_TITLE_BYTES {
+            return Err("title_too_long".to_string());
+        }
+        let body = required_non_empty_text(fields, "body")?;
+        if body.len() > MAX_NOTICE_BODY_BYTES {
+            return Err("body_too_long".to_string());
+        }
+        let created_at_unix_ms = required_u64_integer(fields, "created_at_unix_ms")?;
+        let expires_at_unix_ms = required_u64_integer(fields, "expires_at_unix_ms")?;
+        if expires_at_unix_ms <= created_at_unix_ms {
+            return Err("expires_at_must_follow_created_at".to_string());
+        }
+
+        Ok(AppBody::Notice(NoticeBody {
+            severity,
+            title,
+            body,
+            expires_at_unix_ms,
+            created_at_unix_ms,
+        }))
+    }
+
+    fn encode(&self, body: &AppBody) -> Result<BTreeMap<String, Value>, String> {
+        let AppBody::Notice(notice) = body else {
+            return Err("type_mismatch".to_string());
+        };
+        let mut map = typed_map(WAYFARER_NOTICE_V1);
+        map.insert(
+            "severity".to_string(),
+            Value::Text(notice.severity.as_str().to_string()),
+        );
+        map.insert("title".to_string(), Value::Text(notice.title.clone()));
+        map.insert("body".to_string(), Value::Text(notice.body.clone()));
+        map.insert(
+            "expires_at_unix_ms".to_string(),
+            Value::Integer(notice.expires_at_unix_ms.into()),
+        );
+        map.insert(
+            "created_at_unix_ms".to_string(),
+            Value::Integer(notice.created_at_unix_ms.into()),
+        );
+        Ok(map)
+    }
+}
+
+struct SuccessionHandler;
+
+impl AppBodyHandler for SuccessionHandler {
+    fn payload_type(&self) -> &'static str {
+        WAYFARER_SUCCESSION_V1
+    }
+
+    fn decode(&self, fields: &BTreeMap<String, Value>) -> Result<AppBody, String> {
+        let record = match fields.get("record") {
+            Some(Value::Bytes(record)) if !record.is_empty() => record.clone(),
+            Some(_) => return Err("record_must_be_non_empty_bytes".to_string()),
+            None => return Err("record_required".to_string()),
+        };
+        if record.len() > MAX_SUCCESSION_RECORD_BYTES {
+            return Err("record_too_long".to_string());
+        }
+        Ok(AppBody::Succession(SuccessionBody { record }))
+    }
+
+    fn encode(&self, body: &AppBody) -> Result<BTreeMap<String, Value>, String> {
+        let AppBody::Succession(succession) = body else {
+            return Err("type_mismatch".to_string());
+        };
+        let mut map = typed_map(WAYFARER_SUCCESSION_V1);
+        map.insert(
+            "record".to_string(),
+            Value::Bytes(succession.record.clone()),
+        );
+        Ok(map)
+    }
+}
+
+struct RevocationHandler;
+
+impl AppBodyHandler for RevocationHandler {
+    fn payload_type(&self) -> &'static str {
+        WAYFARER_REVOCATION_V1
+    }
+
+    fn decode(&self, fields: &BTreeMap<String, Value>) -> Result<AppBody, String> {
+        let certificate = match fields.get("certificate") {
+            Some(Value::Bytes(certificate)) if !certificate.is_empty() => certificate.clone(),
+            Some(_) => return Err("certificate_must_be_non_empty_bytes".to_string()),
+            None => return Err("certificate_required".to_string()),
+        };
+        if certificate.len() > MAX_REVOCATION_CERTIFICATE_BYTES {
+            return Err("certificate_too_long".to_string());
+        }
+        Ok(AppBody::Revocation(RevocationBody { certificate }))
+    }
+
+    fn encode(&self, body: &AppBody) -> Result<BTreeMap<String, Value>, String> {
+        let AppBody::Revocation(revocation) = body else {
+            return Err("type_mismatch".to_string());
+        };
+        let mut map = typed_map(WAYFARER_REVOCATION_V1);
+        map.insert(
+            "certificate".to_string(),
+            Value::Bytes(revocation.certificate.clone()),
+        );
+        Ok(map)
+    }
+}
+
+struct RecoveryShareHandler;
+
+impl AppBodyHandler for RecoveryShareHandler {
+    fn payload_type(&self) -> &'static str {
+        WAYFARER_RECOVERY_SHARE_BODY_V1
+    }
+
+    fn decode(&self, fields: &BTreeMap<String, Value>) -> Result<AppBody, String> {
+        let share = match fields.get("share") {
+            Some(Value::Bytes(share)) if !share.is_empty() => share.clone(),
+            Some(_) => return Err("share_must_be_non_empty_bytes".to_string()),
+            None => return Err("share_required".to_string()),
+        };
+        if share.len() > MAX_RECOVERY_SHARE_BYTES {
+            return Err("share_too_long".to_string());
+        }
+        Ok(AppBody::RecoveryShare(RecoveryShareBody { share }))
+    }
+
+    fn encode(&self, body: &AppBody) -> Result<BTreeMap<String, Value>, String> {
+        let AppBody::RecoveryShare(recovery_share) = body else {
+            return Err("type_mismatch".to_string());
+        };
+        let mut map = typed_map(WAYFARER_RECOVERY_SHARE_BODY_V1);
+        map.insert(
+            "share".to_string(),
+            Value::Bytes(recovery_share.share.clone()),
+        );
+        Ok(map)
+    }
+}
+
+struct DeviceAnnounceHandler;
+
+impl AppBodyHandler for DeviceAnnounceHandler {
+    fn payload_type(&self) -> &'static str {
+        WAYFARER_DEVICE_ANNOUNCE_V1
+    }
+
+    fn decode(&self, fields: &BTreeMap<String, Value>) -> Result<AppBody, String> {
+        let certificate = match fields.get("certificate") {
+            Some(Value::Bytes(certificate)) if !certificate.is_empty() => certificate.clone(),
+            Some(_) => return Err("certificate_must_be_non_empty_bytes".to_string()),
+            None => return Err("certificate_required".to_string()),
+        };
+        if certificate.len() > MAX_DEVICE_CERTIFICATE_BYTES {
+            return Err("certificate_too_long".to_string());
+        }
+        Ok(AppBody::DeviceAnnounce(DeviceAnnounceBody { certificate }))
+    }
+
+    fn encode(&self, body: &AppBody) -> Result<BTreeMap<String, Value>, String> {
+        let AppBody::DeviceAnnounce(announce) = body else {
+            return Err("type_mismatch".to_string());
+        };
+        let mut map = typed_map(WAYFARER_DEVICE_ANNOUNCE_V1);
+        map.insert(
+            "certificate".to_string(),
+            Value::Bytes(announce.certificate.clone()),
+        );
+        Ok(map)
+    }
+}
+
+struct ReservedHandler(&'static str);
+
+impl AppBodyHandler for ReservedHandler {
+    fn payload_type(&self) -> &'static str {
+        self.0
+    }
+
+    fn decode(&self, fields: &BTreeMap<String, Value>) -> Result<AppBody, String> {
+        Ok(AppBody::Reserved {
+            payload_type: self.0.to_string(),
+            fields: fields.clone(),
+        })
+    }
+
+    fn encode(&self, body: &AppBody) -> Result<BTreeMap<String, Value>, String> {
+        let AppBody::Reserved { fields, .. } = body else {
+            return Err("type_mismatch".to_string());
+        };
+        let mut map = fields.clone();
+        map.insert("type".to_string(), Value::Text(self.0.to_string()));
+        Ok(map)
+    }
+}
+
+fn typed_map(payload_type: &str) -> BTreeMap<String, Value> {
+    let mut map = BTreeMap::new();
+    map.insert("type".to_string(), Value::Text(payload_type.to_string()));
+    map
+}
+
+pub fn text_keyed_map_value(map: BTreeMap<String, Value>) -> Value {
+    Value::Map(
+        map.into_iter()
+            .map(|(key, value)| (Value::Text(key), value))
+            .collect(),
+    )
+}
+
+pub fn value_as_text_keyed_map(
+    value: Value,
+    context: &str,
+) -> Result<BTreeMap<String, Value>, String> {
+    let Value::Map(entries) = value else {
+        return Err(format!("{context}_must_be_map"));
+    };
+
+    let mut out = BTreeMap::new();
+    for (key, value) in entries {
+        let Value::Text(key) = key else {
+            return Err(format!("{context}_keys_must_be_text"));
+        };
+        if out.insert(key, value).is_some() {
+            return Err(format!("{context}_duplicate_keys"));
+        }
+    }
+
+    Ok(out)
+}
+
+pub fn required_non_empty_text(map: &BTreeMap<String, Value>, key: &str) -> Result<String, String> {
+    let value = map.get(key).ok_or_else(|| format!("{key}_required"))?;
+    let Value::Text(text) = value else {
+        return Err(format!("{key}_must_be_text"));
+    };
+    if text.is_empty() {
+        return Err(format!("{key}_must_be_non_empty"));
+    }
+    Ok(text.clone())
+}
+
+pub fn required_manifest_id_hex(
+    map: &BTreeMap<String, Value>,
+    key: &str,
+) -> Result<String, String> {
+    let value = required_non_empty_text(map, key)?;
+    if !is_manifest_id_hex(&value) {
+        return Err(format!("{key}_must_be_lowercase_sha256_hex"));
+    }
+    Ok(value)
+}
+
+fn is_manifest_id_hex(value: &str) -> bool {
+    value.len() == 64
+        && value
+            .chars()
+            .all(|ch| ch.is_ascii_digit() || ('a'..='f').contains(&ch))
+}
+
+pub fn optional_text(map: &BTreeMap<String, Value>, key: &str) -> Result<Option<String>, String> {
+    match map.get(key) {
+        None => Ok(None),
+        Some(Value::Text(text)) => Ok(Some(text.clone())),
+        Some(_) => Err(format!("{key}_must_be_text")),
+    }
+}
+
+pub fn required_u64_integer(map: &BTreeMap<String, Value>, key: &str) -> Result<u64, String> {
+    let value = map.get(key).ok_or_else(|| format!("{key}_required"))?;
+    let Value::Integer(integer) = value else {
+        return Err(format!("{key}_must_be_integer"));
+    };
+    let signed = i128::from(*integer);
+    if signed < 0 {
+        return Err(format!("{key}_must_be_non_negative"));
+    }
+    u64::try_from(signed).map_err(|_| format!("{key}_invalid_integer"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chat_body() -> AppBody {
+        AppBody::Chat(ChatBody {
+            text: "hello wayfarer".to_string(),
+            created_at_unix_ms: 1_735_689_600_000,
+        })
+    }
+
+    #[test]
+    fn every_wayfarer_type_round_trips_through_registry() {
+        let mut bodies = vec![
+            chat_body(),
+            AppBody::MediaManifest(MediaManifestBody {
+                transfer_ref: "transfer-1".to_string(),
+                media_kind: "image".to_string(),
+                assets: vec![MediaAsset {
+                    asset_ref: "asset-1".to_string(),
+                    mime_type: "image/png".to_string(),
+                    byte_length: 2048,
+                    name: Some("photo.png".to_string()),
+                }],
+                caption: Some("sunset".to_string()),
+                created_at_unix_ms: 1_735_689_600_000,
+            }),
+            AppBody::Reaction(ReactionBody {
+                target_manifest_id_hex: "ab".repeat(32),
+                emoji: "\u{1f44d}".to_string(),
+                action: ReactionAction::Remove,
+                created_at_unix_ms: 1_735_689_600_001,
+            }),
+            AppBody::MessageUpdate(MessageUpdateBody {
+                target_manifest_id_hex: "ab".repeat(32),
+                action: MessageUpdateAction::Edit {
+                    text: "hello again".to_string(),
+                },
+                created_at_unix_ms: 1_735_689_600_002,
+            }),
+            AppBody::MessageUpdate(MessageUpdateBody {
+                target_manifest_id_hex: "ab".repeat(32),
+                action: MessageUpdateAction::Delete,
+                created_at_unix_ms: 1_735_689_600_003,
+            }),
+            AppBody::Profile(ProfileBody {
+                version: 3,
+                display_name: "Wayfarer".to_string(),
+                avatar_thumbnail: Some(vec![0x89, b'P', b'N', b'G']),
+                status_line: Some("on the trail".to_string()),
+                preferred_relays: vec!["wss://aethos-relay.network".to_string()],
+                created_at_unix_ms: 1_735_689_600_004,
+            }),
+            AppBody::StatusEvent(StatusEventBody {
+                status: StatusEventKind::Read,
+                target_manifest_id_hexes: vec!["ab".repeat(32), "cd".repeat(32)],
+                created_at_unix_ms: 1_735_689_600_005,
+            }),
+            AppBody::Notice(NoticeBody {
+                severity: NoticeSeverity::Warning,
+                title: "Relay maintenance".to_string(),
+                body: "The relay restarts tonight at 22:00.".to_string(),
+                expires_at_unix_ms: 1_735_776_000_000,
+                created_at_unix_ms: 1_735_689_600_006,
+            }),
+            AppBody::Succession(SuccessionBody {
+                record: vec![0xa1, 0x61, b'x', 0x01],
+            }),
+            AppBody::Revocation(RevocationBody {
+                certificate: vec![0xa1, 0x61, b'y', 0x02],
+            }),
+            AppBody::RecoveryShare(RecoveryShareBody {
+                share: vec![0xa1, 0x61, b'z', 0x03],
+            }),
+            AppBody::DeviceAnnounce(DeviceAnnounceBody {
+                certificate: vec![0xa1, 0x61, b'd', 0x04],
+            }),
+        ];
+        for payload_type in RESERVED_TYPES {
+            let mut fields = BTreeMap::new();
+            fields.insert("type".to_string(), Value::Text(payload_type.to_string()));
+            fields.insert("note".to_string(), Value::Text("kept".to_string()));
+            bodies.push(AppBody::Reserved {
+                payload_type: payload_type.to_string(),
+                fields,
+            });
+        }
+
+        for body in bodies {
+            let encoded = encode_app_body(&body).expect("encode app body");
+            check_canonical(&encoded).expect("app body encoding is canonical");
+            assert_eq!(decode_app_body(&encoded).expect("decode app body"), body);
+        }
+    }
+
+    #[test]
+    fn unknown_types_decode_without_error() {
+        let encoded =
+            encode_cbor_value_deterministic(&text_keyed_map_value(typed_map("wayfarer.chat.v2")))
+                .expect("encode unknown body");
+
+        assert_eq!(
+            decode_app_body(&encoded).expect("unknown type is non-fatal"),
+            AppBody::Unknown {
+                payload_type: "wayfarer.chat.v2".to_string()
+            }
+        );
+        assert!(encode_app_body(&AppBody::Unknown {
+            payload_type: "wayfarer.chat.v2".to_string()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn malformed_and_non_canonical_bodies_are_rejected() {
+        let mut fields = typed_map(WAYFARER_CHAT_V1);
+        fields.insert("text".to_string(), Value::Text(String::new()));
+        fields.insert("created_at_unix_ms".to_string(), Value::Integer(1.into()));
+        let empty_text =
+            encode_cbor_value_deterministic(&text_keyed_map_value(fields)).expect("encode");
+        let err = decode_app_body(&empty_text).expect_err("empty chat text must fail");
+        assert_eq!(
+            err.reason(),
+            "malformed_wayfarer_chat_v1: text_must_be_non_empty"
+        );
+
+        let mut non_canonical = Vec::new();
+        ciborium::ser::into_writer(
+            &Value::Map(vec![
+                (
+                    Value::Text("type".to_string()),
+                    Value::Text(WAYFARER_CHAT_V1.to_string()),
+                ),
+                (
+                    Value::Text("text".to_string()),
+                    Value::Text("hi".to_string()),
+                ),
+            ]),
+            &mut non_canonical,
+        )
+        .expect("encode non-canonical body");
+        assert_eq!(
+            decode_app_body(&non_canonical).expect_err("unsorted keys must fail"),
+            AppBodyError::NonCanonical
+        );
+    }
+
+    #[test]
+    fn reaction_requires_manifest_target_and_known_action() {
+        let mut fields = typed_map(WAYFARER_REACTION_V1);
+        fields.insert(
+            "target_manifest_id_hex".to_string(),
+            Value::Text("AB".repeat(32)),
+        );
+        fields.insert("emoji".to_string(), Value::Text("\u{1f525}".to_string()));
+        fields.insert("action".to_string(), Value::Text("add".to_string()));
+        fields.insert("created_at_unix_ms".to_string(), Value::Integer(1.into()));
+        let uppercase_target =
+            encode_cbor_value_deterministic(&text_keyed_map_value(fields.clone())).expect("encode");
+        assert_eq!(
+            decode_app_body(&uppercase_target)
+                .expect_err("uppercase target must fail")
+                .reason(),
+            "malformed_wayfarer_reaction_v1: target_manifest_id_hex_must_be_lowercase_sha256_hex"
+        );
+
+        fields.insert(
+            "target_manifest_id_hex".to_string(),
+            Value::Text("ab".repeat(32)),
+        );
+        fields.insert("action".to_string(), Value::Text("toggle".to_string()));
+        let unknown_action =
+            encode_cbor_value_deterministic(&text_keyed_map_value(fields)).expect("encode");
+        assert_eq!(
+            decode_app_body(&unknown_action)
+                .expect_err("unknown action must fail")
+                .reason(),
+            "malformed_wayfarer_reaction_v1: action_invalid"
+        );
+    }
+
+    #[test]
+    fn message_update_delete_must_not_carry_text() {
+        let mut fields = typed_map(WAYFARER_MESSAGE_UPDATE_V1);
+        fields.insert(
+            "target_manifest_id_hex".to_string(),
+            Value::Text("ab".repeat(32)),
+        );
+        fields.insert("action".to_string(), Value::Text("delete".to_string()));
+        fields.insert("text".to_string(), Value::Text("leftover".to_string()));
+        fields.insert("created_at_unix_ms".to_string(), Value::Integer(1.into()));
+        let encoded =
+            encode_cbor_value_deterministic(&text_keyed_map_value(fields)).expect("encode");
+
+        assert_eq!(
+            decode_app_body(&encoded)
+                .expect_err("delete with text must fail")
+                .reason(),
+            "malformed_wayfarer_message_update_v1: text_not_allowed_for_delete"
+        );
+    }
+
+    #[test]
+    fn notice_must_expire_after_creation() {
+        let mut fields = typed_map(WAYFARER_NOTICE_V1);
+        fields.insert("severity".to_string(), Value::Text("info".to_string()));
+        fields.insert("title".to_string(), Value::Text("Heads up".to_string()));
+        fields.insert("body".to_string(), Value::Text("Relay moved.".to_string()));
+        fields.insert("created_at_unix_ms".to_string(), Value::Integer(10.into()));
+        fields.insert("expires_at_unix_ms".to_string(), Value::Integer(10.into()));
+        let encoded =
+            encode_cbor_value_deterministic(&text_keyed_map_value(fields)).expect("encode");
+
+        assert_eq!(
+            decode_app_body(&encoded)
+                .expect_err("already-expired notice must fail")
+                .reason(),
+            "malformed_wayfarer_notice_v1: expires_at_must_follow_created_at"
+        );
+    }
+
+    #[test]
+    fn registered_handler_replaces_default_for_its_type() {
+        struct UpperChatHandler;
+
+        impl AppBodyHandler for UpperChatHandler {
+            fn payload_type(&self) -> &'static str {
+                WAYFARER_CHAT_V1
+            }
+
+            fn decode(&self, fields: &BTreeMap<String, Value>) -> Result<AppBody, String> {
+                Ok(AppBody::Chat(ChatBody {
+                    text: required_non_empty_text(fields, "text")?.to_uppercase(),
+                    created_at_unix_ms: required_u64_integer(fields, "created_at_unix_ms")?,
+                }))
+            }
+
+            fn encode(&self, body: &AppBody) -> Result<BTreeMap<String, Value>, String> {
+                ChatHandler.encode(body)
+            }
+        }
+
+        let encoded = encode_app_body(&chat_body()).expect("encode chat");
+        let mut registry = AppBodyRegistry::wayfarer();
+        registry.register(Box::new(UpperChatHandler));
+        let AppBody::Chat(chat) = registry.decode(&encoded).expect("decode chat") else {
+            panic!("expected chat body");
+        };
+        assert_eq!(chat.text, "HELLO WAYFARER");
+        assert!(!AppBodyRegistry::empty().handles(WAYFARER_CHAT_V1));
+    }
+}
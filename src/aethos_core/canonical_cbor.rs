@@ -0,0 +1,298 @@
+use ciborium::value::Value;
+use ciborium::{de::from_reader, ser::into_writer};
+
+const MAJOR_UNSIGNED: u8 = 0;
+const MAJOR_NEGATIVE: u8 = 1;
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_TEXT: u8 = 3;
+const MAJOR_ARRAY: u8 = 4;
+const MAJOR_MAP: u8 = 5;
+const MAJOR_TAG: u8 = 6;
+const MAJOR_SIMPLE: u8 = 7;
+const MAX_NESTING_DEPTH: usize = 128;
+
+/// Encodes `value` in deterministic CBOR: shortest-form heads, definite lengths, and map
+/// entries ordered by their encoded key bytes. Only map keys are encoded separately (to
+/// sort them); values are written straight into the output buffer.
+pub fn encode_canonical(value: &Value) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    write_value(value, &mut out, 0)?;
+    Ok(out)
+}
+
+/// Validates that `raw` is exactly one data item in the form `encode_canonical` produces,
+/// walking the borrowed bytes without decoding them into a `Value`.
+pub fn check_canonical(raw: &[u8]) -> Result<(), String> {
+    let end = check_item(raw, 0, 0)?;
+    if end != raw.len() {
+        return Err("trailing bytes after CBOR data item".to_string());
+    }
+    Ok(())
+}
+
+fn write_value(value: &Value, out: &mut Vec<u8>, depth: usize) -> Result<(), String> {
+    if depth > MAX_NESTING_DEPTH {
+        return Err("CBOR value nesting exceeds limit".to_string());
+    }
+    match value {
+        Value::Integer(integer) => {
+            let integer = i128::from(*integer);
+            if let Ok(unsigned) = u64::try_from(integer) {
+                write_head(MAJOR_UNSIGNED, unsigned, out);
+            } else if let Ok(negative) = u64::try_from(-1 - integer) {
+                write_head(MAJOR_NEGATIVE, negative, out);
+            } else {
+                into_writer(value, &mut *out)
+                    .map_err(|err| format!("CBOR bignum encode failed: {err}"))?;
+            }
+        }
+        Value::Bytes(bytes) => {
+            write_head(MAJOR_BYTES, bytes.len() as u64, out);
+            out.extend_from_slice(bytes);
+        }
+        Value::Text(text) => {
+            write_head(MAJOR_TEXT, text.len() as u64, out);
+            out.extend_from_slice(text.as_bytes());
+        }
+        Value::Array(items) => {
+            write_head(MAJOR_ARRAY, items.len() as u64, out);
+            for item in items {
+                write_value(item, out, depth + 1)?;
+            }
+        }
+        Value::Map(entries) => {
+            let mut encoded_entries = Vec::with_capacity(entries.len());
+            for (key, entry_value) in entries {
+                let mut encoded_key = Vec::new();
+                write_value(key, &mut encoded_key, depth + 1)?;
+                encoded_entries.push((encoded_key, entry_value));
+            }
+            encoded_entries.sort_by(|(left, _), (right, _)| left.cmp(right));
+            if encoded_entries
+                .windows(2)
+                .any(|pair| pair[0].0 == pair[1].0)
+            {
+                return Err("CBOR map contains duplicate keys".to_string());
+            }
+
+            write_head(MAJOR_MAP, encoded_entries.len() as u64, out);
+            for (encoded_key, entry_value) in encoded_entries {
+                out.extend_from_slice(&encoded_key);
+                write_value(entry_value, out, depth + 1)?;
+            }
+        }
+        Value::Tag(tag, inner) => {
+            write_head(MAJOR_TAG, *tag, out);
+            write_value(inner, out, depth + 1)?;
+        }
+        Value::Bool(false) => out.push(0xf4),
+        Value::Bool(true) => out.push(0xf5),
+        Value::Null => out.push(0xf6),
+        Value::Float(_) => {
+            into_writer(value, &mut *out)
+                .map_err(|err| format!("CBOR float encode failed: {err}"))?;
+        }
+        _ => return Err("unsupported CBOR value in canonical encoding".to_string()),
+    }
+    Ok(())
+}
+
+fn write_head(major: u8, argument: u64, out: &mut Vec<u8>) {
+    let major = major << 5;
+    if argument < 24 {
+        out.push(major | argument as u8);
+    } else if argument <= u8::MAX as u64 {
+        out.push(major | 24);
+        out.push(argument as u8);
+    } else if argument <= u16::MAX as u64 {
+        out.push(major | 25);
+        out.extend_from_slice(&(argument as u16).to_be_bytes());
+    } else if argument <= u32::MAX as u64 {
+        out.push(major | 26);
+        out.extend_from_slice(&(argument as u32).to_be_bytes());
+    } else {
+        out.push(major | 27);
+        out.extend_from_slice(&argument.to_be_bytes());
+    }
+}
+
+fn check_item(raw: &[u8], offset: usize, depth: usize) -> Result<usize, String> {
+    if depth > MAX_NESTING_DEPTH {
+        return Err("CBOR value nesting exceeds limit".to_string());
+    }
+    let initial = *raw
+        .get(offset)
+        .ok_or_else(|| "truncated CBOR data item".to_string())?;
+    let major = initial >> 5;
+    if major == MAJOR_SIMPLE {
+        return check_simple(raw, offset);
+    }
+
+    let (argument, mut cursor) = read_head(raw, offset)?;
+    match major {
+        MAJOR_UNSIGNED | MAJOR_NEGATIVE => Ok(cursor),
+        MAJOR_BYTES | MAJOR_TEXT => {
+            let end = usize::try_from(argument)
+                .ok()
+                .and_then(|length| cursor.checked_add(length))
+                .filter(|end| *end <= raw.len())
+                .ok_or_else(|| "truncated CBOR string".to_string())?;
+            if major == MAJOR_TEXT && std::str::from_utf8(&raw[cursor..end]).is_err() {
+                return Err("CBOR text string is not valid UTF-8".to_string());
+            }
+            Ok(end)
+        }
+        MAJOR_ARRAY => {
+            for _ in 0..argument {
+                cursor = check_item(raw, cursor, depth + 1)?;
+            }
+            Ok(cursor)
+        }
+        MAJOR_MAP => {
+            let mut previous_key: Option<&[u8]> = None;
+            for _ in 0..argument {
+                let key_start = cursor;
+                cursor = check_item(raw, cursor, depth + 1)?;
+                let key = &raw[key_start..cursor];
+                if previous_key.is_some_and(|previous| previous >= key) {
+                    return Err("CBOR map keys are not in canonical order".to_string());
+                }
+                previous_key = Some(key);
+                cursor = check_item(raw, cursor, depth + 1)?;
+            }
+            Ok(cursor)
+        }
+        MAJOR_TAG => check_item(raw, cursor, depth + 1),
+        _ => unreachable!("CBOR major type is three bits"),
+    }
+}
+
+fn read_head(raw: &[u8], offset: usize) -> Result<(u64, usize), String> {
+    let additional = raw[offset] & 0x1f;
+    let width = match additional {
+        0..=23 => return Ok((additional as u64, offset + 1)),
+        24 => 1,
+        25 => 2,
+        26 => 4,
+        27 => 8,
+        31 => return Err("indefinite-length CBOR is not canonical".to_string()),
+        _ => return Err("reserved CBOR additional information".to_string()),
+    };
+    let start = offset + 1;
+    let bytes = raw
+        .get(start..start + width)
+        .ok_or_else(|| "truncated CBOR head".to_string())?;
+    let argument = bytes
+        .iter()
+        .fold(0u64, |value, byte| (value << 8) | *byte as u64);
+    let minimal_width = match argument {
+        0..=23 => 0,
+        24..=0xff => 1,
+        0x100..=0xffff => 2,
+        0x1_0000..=0xffff_ffff => 4,
+        _ => 8,
+    };
+    if width != minimal_width {
+        return Err("CBOR head is not in shortest form".to_string());
+    }
+    Ok((argument, start + width))
+}
+
+fn check_simple(raw: &[u8], offset: usize) -> Result<usize, String> {
+    let width = match raw[offset] & 0x1f {
+        20..=22 => return Ok(offset + 1),
+        25 => 2,
+        26 => 4,
+        27 => 8,
+        _ => return Err("unsupported CBOR simple value".to_string()),
+    };
+    let end = offset + 1 + width;
+    let encoded = raw
+        .get(offset..end)
+        .ok_or_else(|| "truncated CBOR float".to_string())?;
+    let float: Value =
+        from_reader(encoded).map_err(|err| format!("CBOR float decode failed: {err}"))?;
+    let mut shortest = Vec::with_capacity(encoded.len());
+    into_writer(&float, &mut shortest).map_err(|err| format!("CBOR float encode failed: {err}"))?;
+    if shortest != encoded {
+        return Err("CBOR float is not in shortest form".to_string());
+    }
+    Ok(end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_canonical, encode_canonical};
+    use ciborium::value::Value;
+
+    fn ciborium_bytes(value: &Value) -> Vec<u8> {
+        let mut out = Vec::new();
+        ciborium::ser::into_writer(value, &mut out).expect("ciborium encode");
+        out
+    }
+
+    #[test]
+    fn canonical_encoder_matches_ciborium_for_sorted_values() {
+        let value = Value::Map(vec![
+            (Value::Integer(7.into()), Value::Bool(true)),
+            (
+                Value::Text("b".to_string()),
+                Value::Array(vec![
+                    Value::Integer(23.into()),
+                    Value::Integer(24.into()),
+                    Value::Integer(65_536.into()),
+                    Value::Integer(u64::MAX.into()),
+                    Value::Integer((-25).into()),
+                    Value::Null,
+                    Value::Float(1.5),
+                ]),
+            ),
+            (
+                Value::Text("aa".to_string()),
+                Value::Tag(42, Box::new(Value::Bytes(vec![0u8; 300]))),
+            ),
+        ]);
+
+        let encoded = encode_canonical(&value).expect("encode canonical");
+        assert_eq!(encoded, ciborium_bytes(&value));
+        check_canonical(&encoded).expect("encoder output is canonical");
+    }
+
+    #[test]
+    fn canonical_encoder_sorts_map_keys_and_rejects_duplicates() {
+        let unsorted = Value::Map(vec![
+            (Value::Text("bb".to_string()), Value::Integer(1.into())),
+            (Value::Text("a".to_string()), Value::Integer(2.into())),
+        ]);
+        let encoded = encode_canonical(&unsorted).expect("encode canonical");
+        assert_eq!(&encoded[..3], &[0xa2, 0x61, b'a']);
+        assert!(check_canonical(&ciborium_bytes(&unsorted))
+            .expect_err("unsorted map must fail")
+            .contains("canonical order"));
+
+        let duplicate = Value::Map(vec![
+            (Value::Text("a".to_string()), Value::Integer(1.into())),
+            (Value::Text("a".to_string()), Value::Integer(2.into())),
+        ]);
+        assert!(encode_canonical(&duplicate).is_err());
+        assert!(check_canonical(&ciborium_bytes(&duplicate)).is_err());
+    }
+
+    #[test]
+    fn canonical_checker_rejects_non_shortest_and_indefinite_forms() {
+        assert!(check_canonical(&[0x18, 0x05])
+            .expect_err("non-minimal integer")
+            .contains("shortest form"));
+        assert!(check_canonical(&[0x5f, 0x41, 0x00, 0xff])
+            .expect_err("indefinite bytes")
+            .contains("indefinite"));
+        assert!(check_canonical(&[0xfb, 0x3f, 0xf8, 0, 0, 0, 0, 0, 0])
+            .expect_err("f64 that fits f16")
+            .contains("shortest form"));
+        assert!(check_canonical(&[0x01, 0x02])
+            .expect_err("trailing bytes")
+            .contains("trailing"));
+        assert!(check_canonical(&[0x62, 0xff, 0xfe]).is_err());
+        assert!(check_canonical(&[0x43, 0x00]).is_err());
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+
+use ciborium::value::Value;
+use ed25519_dalek::SigningKey;
+
+use crate::aethos_core::app_body::{
+    optional_text, required_u64_integer, MAX_PROFILE_DISPLAY_NAME_BYTES,
+    MAX_PROFILE_PREFERRED_RELAYS,
+};
+use crate::aethos_core::identity_store::wayfarer_id_from_pubkey;
+use crate::aethos_core::signed_record::{
+    decode_prefixed_code, issue_signed_record, prefixed_code, required_bytes, verify_signed_record,
+    SignedRecord,
+};
+
+pub const WAYFARER_CONTACT_CARD_V1: &str = "wayfarer.contact_card.v1";
+/// Prefix of the QR text form: the signed card bytes follow as unpadded base64url.
+pub const CONTACT_CARD_QR_PREFIX: &str = "aethos-card:";
+
+/// Self-issued description of an identity, signed by the key it carries so the importer can
+/// derive the wayfarer ID instead of trusting one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContactCard {
+    pub pubkey: [u8; 32],
+    pub display_name: Option<String>,
+    pub preferred_relays: Vec<String>,
+    pub issued_at_unix_ms: u64,
+}
+
+impl ContactCard {
+    pub fn wayfarer_id(&self) -> String {
+        wayfarer_id_from_pubkey(&self.pubkey)
+    }
+}
+
+impl SignedRecord for ContactCard {
+    const PAYLOAD_TYPE: &'static str = WAYFARER_CONTACT_CARD_V1;
+    const SIGNING_DOMAIN: &'static [u8] = b"aethos-contact-card-v1";
+    const LABEL: &'static str = "contact card";
+    const SIGNATURE_FIELDS: &'static [&'static str] = &["sig"];
+
+    fn fields(&self) -> BTreeMap<String, Value> {
+        let mut fields = BTreeMap::new();
+        fields.insert("pubkey".to_string(), Value::Bytes(self.pubkey.to_vec()));
+        if let Some(display_name) = self.display_name.as_ref() {
+            fields.insert(
+                "display_name".to_string(),
+                Value::Text(display_name.clone()),
+            );
+        }
+        if !self.preferred_relays.is_empty() {
+            fields.insert(
+                "preferred_relays".to_string(),
+                Value::Array(
+                    self.preferred_relays
+                        .iter()
+                        .map(|relay| Value::Text(relay.clone()))
+                        .collect(),
+                ),
+            );
+        }
+        fields.insert(
+            "issued_at_unix_ms".to_string(),
+            Value::Integer(self.issued_at_unix_ms.into()),
+        );
+        fields
+    }
+
+    fn from_fields(fields: &BTreeMap<String, Value>) -> Result<Self, String> {
+        let preferred_relays = match fields.get("preferred_relays") {
+            None => Vec::new(),
+            Some(Value::Array(relays)) => relays
+                .iter()
+                .map(|relay| match relay {
+                    Value::Text(relay) if !relay.is_empty() => Ok(relay.clone()),
+                    _ => Err("contact card relays must be non-empty text".to_string()),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            Some(_) => return Err("contact card relays must be an array".to_string()),
+        };
+        let card = Self {
+            pubkey: required_bytes(fields, "pubkey")?,
+            display_name: optional_text(fields, "display_name")?,
+            preferred_relays,
+            issued_at_unix_ms: required_u64_integer(fields, "issued_at_unix_ms")?,
+        };
+        validate_card(&card)?;
+        Ok(card)
+    }
+
+    fn signer_pubkeys(&self) -> Vec<[u8; 32]> {
+        vec![self.pubkey]
+    }
+}
+
+/// Builds a signed card for the identity behind `signing_seed`; `pubkey` is taken from the
+/// seed so a card can only describe its signer.
+pub fn issue_contact_card(
+    signing_seed: &[u8; 32],
+    display_name: Option<&str>,
+    preferred_relays: &[String],
+    issued_at_unix_ms: u64,
+) -> Result<Vec<u8>, String> {
+    let signing_key = SigningKey::from_bytes(signing_seed);
+    let card = ContactCard {
+        pubkey: signing_key.verifying_key().to_bytes(),
+        display_name: display_name
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string),
+        preferred_relays: preferred_relays
+            .iter()
+            .take(MAX_PROFILE_PREFERRED_RELAYS)
+            .cloned()
+            .collect(),
+        issued_at_unix_ms,
+    };
+    validate_card(&card)?;
+    issue_signed_record(&card, &[&signing_key])
+}
+
+/// Decodes `raw` and checks its signature against the embedded key. Any change to a signed
+/// field, or a non-canonical encoding, is rejected.
+pub fn verify_contact_card(raw: &[u8]) -> Result<ContactCard, String> {
+    verify_signed_record(raw)
+}
+
+pub fn contact_card_qr_text(card: &[u8]) -> String {
+    prefixed_code(CONTACT_CARD_QR_PREFIX, card)
+}
+
+/// Returns the card bytes from QR text, or `None` when the text is not a contact card.
+pub fn contact_card_from_qr_text(text: &str) -> Option<Result<Vec<u8>, String>> {
+    text.trim()
+        .starts_with(CONTACT_CARD_QR_PREFIX)
+        .then(|| decode_prefixed_code(text, CONTACT_CARD_QR_PREFIX, "contact card QR payload"))
+}
+
+fn validate_card(card: &ContactCard) -> Result<(), String> {
+    if card
+        .display_name
+        .as_ref()
+        .is_some_and(|name| name.is_empty() || name.len() > MAX_PROFILE_DISPLAY_NAME_BYTES)
+    {
+        return Err("contact card display name is empty or too long".to_string());
+    }
+    if card.preferred_relays.len() > MAX_PROFILE_PREFERRED_RELAYS {
+        return Err("contact card lists too many relays".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signed_card_round_trips_through_qr_text() {
+        let seed = [7u8; 32];
+        let relays = vec!["wss://aethos-relay.network".to_string()];
+        let card = issue_contact_card(&seed, Some("Wayfarer"), &relays, 1_735_689_600_000)
+            .expect("issue card");
+
+        let text = contact_card_qr_text(&card);
+        let decoded = contact_card_from_qr_text(&text)
+            .expect("card prefix")
+            .expect("base64url");
+        let verified = verify_contact_card(&decoded).expect("verify card");
+
+        let pubkey = SigningKey::from_bytes(&seed).verifying_key().to_bytes();
+        assert_eq!(verified.pubkey, pubkey);
+        assert_eq!(verified.wayfarer_id(), wayfarer_id_from_pubkey(&pubkey));
+        assert_eq!(verified.display_name.as_deref(), Some("Wayfarer"));
+        assert_eq!(verified.preferred_relays, relays);
+        assert!(contact_card_from_qr_text(&"ab".repeat(32)).is_none());
+    }
+}
@@ -0,0 +1,211 @@
+use std::collections::BTreeMap;
+
+use ciborium::value::Value;
+use ed25519_dalek::{SigningKey, VerifyingKey};
+
+use crate::aethos_core::app_body::{
+    optional_text, required_u64_integer, text_keyed_map_value, value_as_text_keyed_map,
+};
+use crate::aethos_core::identity_store::wayfarer_id_from_pubkey;
+use crate::aethos_core::protocol::{decode_cbor_value_exact, encode_cbor_value_deterministic};
+use crate::aethos_core::signed_record::{
+    decode_prefixed_code, issue_signed_record, prefixed_code, required_bytes, verify_signed_record,
+    SignedRecord,
+};
+
+pub const WAYFARER_DEVICE_CERT_V1: &str = "wayfarer.device_cert.v1";
+pub const WAYFARER_DEVICE_PAIRING_V1: &str = "wayfarer.device_pairing.v1";
+/// Text form of a pairing request shown by the new device.
+pub const DEVICE_PAIRING_CODE_PREFIX: &str = "aethos-pair:";
+/// Text form of the certificate the root device returns.
+pub const DEVICE_CERT_CODE_PREFIX: &str = "aethos-device:";
+pub const MAX_DEVICE_NAME_BYTES: usize = 64;
+
+/// Root-signed statement that `device_pubkey` may sign envelopes for the root identity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceCertificate {
+    pub root_pubkey: [u8; 32],
+    pub device_pubkey: [u8; 32],
+    pub device_name: String,
+    pub issued_at_unix_ms: u64,
+}
+
+impl DeviceCertificate {
+    pub fn wayfarer_id(&self) -> String {
+        wayfarer_id_from_pubkey(&self.root_pubkey)
+    }
+
+    pub fn device_id(&self) -> String {
+        wayfarer_id_from_pubkey(&self.device_pubkey)
+    }
+}
+
+impl SignedRecord for DeviceCertificate {
+    const PAYLOAD_TYPE: &'static str = WAYFARER_DEVICE_CERT_V1;
+    const SIGNING_DOMAIN: &'static [u8] = b"AETHOS_DEVICE_CERT_V1";
+    const LABEL: &'static str = "device certificate";
+    const SIGNATURE_FIELDS: &'static [&'static str] = &["root_sig"];
+
+    fn fields(&self) -> BTreeMap<String, Value> {
+        let mut fields = BTreeMap::new();
+        fields.insert(
+            "root_pubkey".to_string(),
+            Value::Bytes(self.root_pubkey.to_vec()),
+        );
+        fields.insert(
+            "device_pubkey".to_string(),
+            Value::Bytes(self.device_pubkey.to_vec()),
+        );
+        fields.insert(
+            "device_name".to_string(),
+            Value::Text(self.device_name.clone()),
+        );
+        fields.insert(
+            "issued_at_unix_ms".to_string(),
+            Value::Integer(self.issued_at_unix_ms.into()),
+        );
+        fields
+    }
+
+    fn from_fields(fields: &BTreeMap<String, Value>) -> Result<Self, String> {
+        let certificate = Self {
+            root_pubkey: required_bytes(fields, "root_pubkey")?,
+            device_pubkey: required_bytes(fields, "device_pubkey")?,
+            device_name: optional_text(fields, "device_name")?.unwrap_or_default(),
+            issued_at_unix_ms: required_u64_integer(fields, "issued_at_unix_ms")?,
+        };
+        validate_device_name(&certificate.device_name)?;
+        Ok(certificate)
+    }
+
+    fn signer_pubkeys(&self) -> Vec<[u8; 32]> {
+        vec![self.root_pubkey]
+    }
+}
+
+/// What a new device offers the root device when asking to be linked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DevicePairingRequest {
+    pub device_pubkey: [u8; 32],
+    pub device_name: String,
+}
+
+/// Certifies `device_pubkey` under the root identity behind `root_signing_seed`.
+pub fn issue_device_certificate(
+    root_signing_seed: &[u8; 32],
+    device_pubkey: &[u8; 32],
+    device_name: &str,
+    issued_at_unix_ms: u64,
+) -> Result<Vec<u8>, String> {
+    let root_key = SigningKey::from_bytes(root_signing_seed);
+    let certificate = DeviceCertificate {
+        root_pubkey: root_key.verifying_key().to_bytes(),
+        device_pubkey: *device_pubkey,
+        device_name: device_name.trim().to_string(),
+        issued_at_unix_ms,
+    };
+    if certificate.root_pubkey == certificate.device_pubkey {
+        return Err("device key must differ from the root identity key".to_string());
+    }
+    validate_device_name(&certificate.device_name)?;
+    VerifyingKey::from_bytes(device_pubkey)
+        .map_err(|err| format!("invalid device pubkey: {err}"))?;
+    issue_signed_record(&certificate, &[&root_key])
+}
+
+/// Decodes a certificate and checks the root signature over it.
+pub fn verify_device_certificate(raw: &[u8]) -> Result<DeviceCertificate, String> {
+    verify_signed_record(raw)
+}
+
+pub fn encode_device_pairing_code(request: &DevicePairingRequest) -> Result<String, String> {
+    validate_device_name(&request.device_name)?;
+    let mut fields = BTreeMap::new();
+    fields.insert(
+        "type".to_string(),
+        Value::Text(WAYFARER_DEVICE_PAIRING_V1.to_string()),
+    );
+    fields.insert(
+        "device_pubkey".to_string(),
+        Value::Bytes(request.device_pubkey.to_vec()),
+    );
+    fields.insert(
+        "device_name".to_string(),
+        Value::Text(request.device_name.clone()),
+    );
+    let encoded = encode_cbor_value_deterministic(&text_keyed_map_value(fields))?;
+    Ok(prefixed_code(DEVICE_PAIRING_CODE_PREFIX, &encoded))
+}
+
+pub fn decode_device_pairing_code(code: &str) -> Result<DevicePairingRequest, String> {
+    let raw = decode_prefixed_code(code, DEVICE_PAIRING_CODE_PREFIX, "pairing code")?;
+    let decoded = decode_cbor_value_exact(&raw, "device pairing code")?;
+    let fields = value_as_text_keyed_map(decoded, "device_pairing")?;
+    match fields.get("type") {
+        Some(Value::Text(payload_type)) if payload_type == WAYFARER_DEVICE_PAIRING_V1 => {}
+        _ => return Err("pairing code type is not wayfarer.device_pairing.v1".to_string()),
+    }
+    let request = DevicePairingRequest {
+        device_pubkey: required_bytes(&fields, "device_pubkey")?,
+        device_name: optional_text(&fields, "device_name")?.unwrap_or_default(),
+    };
+    validate_device_name(&request.device_name)?;
+    VerifyingKey::from_bytes(&request.device_pubkey)
+        .map_err(|err| format!("invalid device pubkey: {err}"))?;
+    Ok(request)
+}
+
+pub fn device_certificate_code(certificate: &[u8]) -> String {
+    prefixed_code(DEVICE_CERT_CODE_PREFIX, certificate)
+}
+
+pub fn device_certificate_from_code(code: &str) -> Result<Vec<u8>, String> {
+    decode_prefixed_code(code, DEVICE_CERT_CODE_PREFIX, "device certificate code")
+}
+
+fn validate_device_name(device_name: &str) -> Result<(), String> {
+    if device_name.is_empty() || device_name.len() > MAX_DEVICE_NAME_BYTES {
+        return Err("device name is empty or too long".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pairing_code_and_certificate_round_trip() {
+        let root_seed = [7u8; 32];
+        let device_pubkey = SigningKey::from_bytes(&[9u8; 32])
+            .verifying_key()
+            .to_bytes();
+        let code = encode_device_pairing_code(&DevicePairingRequest {
+            device_pubkey,
+            device_name: "laptop".to_string(),
+        })
+        .expect("encode pairing code");
+        let request = decode_device_pairing_code(&code).expect("decode pairing code");
+
+        let certificate =
+            issue_device_certificate(&root_seed, &request.device_pubkey, &request.device_name, 5)
+                .expect("issue certificate");
+        let raw = device_certificate_from_code(&device_certificate_code(&certificate))
+            .expect("certificate code");
+        let verified = verify_device_certificate(&raw).expect("verify certificate");
+
+        let root_pubkey = SigningKey::from_bytes(&root_seed)
+            .verifying_key()
+            .to_bytes();
+        assert_eq!(
+            verified.wayfarer_id(),
+            wayfarer_id_from_pubkey(&root_pubkey)
+        );
+        assert_eq!(
+            verified.device_id(),
+            wayfarer_id_from_pubkey(&device_pubkey)
+        );
+        assert_eq!(verified.device_name, "laptop");
+        assert!(issue_device_certificate(&root_seed, &root_pubkey, "root", 5).is_err());
+    }
+}
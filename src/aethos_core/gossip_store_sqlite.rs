@@ -10,8 +10,12 @@ use std::cell::RefCell;
 use rusqlite::{params, Connection, OptionalExtension};
 use serde::Deserialize;
 
+use crate::aethos_core::identity_store::profile_state_dir;
 use crate::aethos_core::logging::log_verbose;
 use crate::aethos_core::protocol::decode_envelope_payload_b64;
+use crate::aethos_core::revocation::{
+    revocation_certificate_in, verify_revocation_certificate, REVOCATION_RETENTION_MS,
+};
 
 const SQLITE_STORE_FILE_NAME: &str = "gossip-object-store.sqlite3";
 const LEGACY_JSON_STORE_FILE_NAME: &str = "gossip-object-store.json";
@@ -19,6 +23,9 @@ const BUSY_TIMEOUT_MS: u64 = 5_000;
 const CLOCK_SKEW_TOLERANCE_MS: u64 = 30_000;
 const MIGRATION_META_KEY: &str = "legacy_json_to_sqlite_migrated_v1";
 const SQLITE_MAX_VARIABLES: usize = 999;
+/// Longest a v1 (or undecodable) item is kept after this node first sees it; such items
+/// carry no signed lifetime, so carriers could otherwise refresh them forever.
+pub const MAX_UNSIGNED_ITEM_TTL_MS: u64 = 7 * 24 * 60 * 60 * 1000;
 
 #[derive(Debug, Clone)]
 pub struct StoredItemRecord {
@@ -179,6 +186,7 @@ impl StoreRuntime {
             ",
         )
         .map_err(|err| format!("failed ensuring gossip sqlite schema: {err}"))?;
+        ensure_author_expiry_column(&conn)?;
 
         self.current_db_path = Some(db_path.to_path_buf());
         self.conn = Some(conn);
@@ -234,8 +242,9 @@ impl StoreRuntime {
                             envelope_b64,
                             expiry_unix_ms,
                             hop_count,
-                            recorded_at_unix_ms
-                        ) VALUES (?1, ?2, ?3, ?4, ?5)
+                            recorded_at_unix_ms,
+                            author_expiry_unix_ms
+                        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)
                     ",
                 )
                 .map_err(|err| format!("failed preparing sqlite migration insert: {err}"))?;
@@ -249,12 +258,17 @@ impl StoreRuntime {
                     continue;
                 }
 
+                let ceiling = retention_ceiling_for_envelope(
+                    &legacy.envelope_b64,
+                    legacy.recorded_at_unix_ms,
+                );
                 stmt.execute(params![
                     &legacy.item_id,
                     &legacy.envelope_b64,
-                    legacy.expiry_unix_ms as i64,
+                    legacy.expiry_unix_ms.min(ceiling) as i64,
                     legacy.hop_count as i64,
                     legacy.recorded_at_unix_ms as i64,
+                    ceiling as i64,
                 ])
                 .map_err(|err| format!("failed inserting migrated gossip record: {err}"))?;
                 imported = imported.saturating_add(1);
@@ -295,6 +309,50 @@ impl StoreRuntime {
     }
 }
 
+/// Stores the retention ceiling next to the carrier expiry so pruning can enforce it even for
+/// rows written before the carrier expiry was clamped.
+fn ensure_author_expiry_column(conn: &Connection) -> Result<(), String> {
+    let mut stmt = conn
+        .prepare("PRAGMA table_info(gossip_items)")
+        .map_err(|err| format!("failed reading gossip sqlite columns: {err}"))?;
+    let has_column = stmt
+        .query_map([], |row| row.get::<_, String>(1))
+        .map_err(|err| format!("failed querying gossip sqlite columns: {err}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| format!("failed decoding gossip sqlite columns: {err}"))?
+        .iter()
+        .any(|name| name == "author_expiry_unix_ms");
+    if has_column {
+        return Ok(());
+    }
+    conn.execute_batch(
+        "
+            ALTER TABLE gossip_items ADD COLUMN author_expiry_unix_ms INTEGER;
+            CREATE INDEX IF NOT EXISTS idx_gossip_items_author_expiry
+                ON gossip_items(author_expiry_unix_ms);
+        ",
+    )
+    .map_err(|err| format!("failed adding gossip sqlite author expiry column: {err}"))
+}
+
+/// Latest expiry an item may ever reach: the author-signed expiry for v2 envelopes, otherwise a
+/// fixed TTL from `first_seen_unix_ms` (longer for verified revocation items).
+fn retention_ceiling_for_envelope(envelope_b64: &str, first_seen_unix_ms: u64) -> u64 {
+    let Ok(decoded) = decode_envelope_payload_b64(envelope_b64) else {
+        return first_seen_unix_ms.saturating_add(MAX_UNSIGNED_ITEM_TTL_MS);
+    };
+    if let Some(lifetime) = decoded.lifetime {
+        return lifetime.expires_at_unix_ms;
+    }
+    let is_revocation = revocation_certificate_in(&decoded)
+        .is_some_and(|certificate| verify_revocation_certificate(&certificate).is_ok());
+    first_seen_unix_ms.saturating_add(if is_revocation {
+        REVOCATION_RETENTION_MS
+    } else {
+        MAX_UNSIGNED_ITEM_TTL_MS
+    })
+}
+
 fn legacy_json_migration_is_done(conn: &Connection) -> Result<bool, String> {
     let marker: Option<i64> = conn
         .query_row(
@@ -398,6 +456,35 @@ pub fn eligible_item_ids(now_ms: u64) -> Result<Vec<String>, String> {
     })
 }
 
+/// Up to `limit` stored item IDs after `after` in item ID order, so SUMMARY_PAGE requests
+/// read one page of the index instead of the whole inventory.
+pub fn item_ids_after(
+    now_ms: u64,
+    after: Option<&str>,
+    limit: usize,
+) -> Result<Vec<String>, String> {
+    with_connection("item_ids_after", |conn| {
+        prune_expired(conn, now_ms)?;
+        let mut stmt = conn
+            .prepare(
+                "
+                    SELECT item_id
+                    FROM gossip_items
+                    WHERE ?1 IS NULL OR item_id > ?1
+                    ORDER BY item_id ASC
+                    LIMIT ?2
+                ",
+            )
+            .map_err(|err| format!("sqlite page select prepare failed: {err}"))?;
+        let ids = stmt
+            .query_map(params![after, limit as i64], |row| row.get::<_, String>(0))
+            .map_err(|err| format!("sqlite page select query failed: {err}"))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| format!("sqlite page select row decode failed: {err}"))?;
+        Ok(ids)
+    })
+}
+
 pub fn eligible_relay_ingest_item_ids(
     now_ms: u64,
     max_items: usize,
@@ -452,10 +539,10 @@ pub fn record_local_item(
             .transaction()
             .map_err(|err| format!("sqlite record txn begin failed: {err}"))?;
 
-        let existing: Option<(String, u64, u64)> = tx
+        let existing: Option<(String, u64, u64, Option<u64>)> = tx
             .query_row(
                 "
-                    SELECT envelope_b64, expiry_unix_ms, recorded_at_unix_ms
+                    SELECT envelope_b64, expiry_unix_ms, recorded_at_unix_ms, author_expiry_unix_ms
                     FROM gossip_items
                     WHERE item_id = ?1
                 ",
@@ -465,6 +552,7 @@ pub fn record_local_item(
                         row.get::<_, String>(0)?,
                         row.get::<_, i64>(1)? as u64,
                         row.get::<_, i64>(2)? as u64,
+                        row.get::<_, Option<i64>>(3)?.map(|value| value as u64),
                     ))
                 },
             )
@@ -472,21 +560,40 @@ pub fn record_local_item(
             .map_err(|err| format!("sqlite record existing row lookup failed: {err}"))?;
 
         let outcome = match existing {
-            Some((existing_envelope_b64, existing_expiry, existing_recorded_at)) => {
+            Some((
+                existing_envelope_b64,
+                existing_expiry,
+                existing_recorded_at,
+                stored_ceiling,
+            )) => {
                 if existing_envelope_b64 != envelope_b64 {
                     return Err("existing item_id maps to different envelope bytes".to_string());
                 }
-                let next_expiry = existing_expiry.max(expiry_unix_ms);
+                // The ceiling is fixed when the item is first stored; rows from before it was
+                // tracked get one counted from their recorded time.
+                let ceiling = stored_ceiling.unwrap_or_else(|| {
+                    retention_ceiling_for_envelope(envelope_b64, existing_recorded_at)
+                });
+                let next_expiry = existing_expiry.max(expiry_unix_ms).min(ceiling);
                 let next_recorded_at = existing_recorded_at.max(recorded_at_unix_ms);
-                if next_expiry != existing_expiry || next_recorded_at != existing_recorded_at {
+                if next_expiry != existing_expiry
+                    || next_recorded_at != existing_recorded_at
+                    || stored_ceiling.is_none()
+                {
                     tx.execute(
                         "
                             UPDATE gossip_items
                             SET expiry_unix_ms = ?2,
-                                recorded_at_unix_ms = ?3
+                                recorded_at_unix_ms = ?3,
+                                author_expiry_unix_ms = ?4
                             WHERE item_id = ?1
                         ",
-                        params![item_id, next_expiry as i64, next_recorded_at as i64],
+                        params![
+                            item_id,
+                            next_expiry as i64,
+                            next_recorded_at as i64,
+                            ceiling as i64
+                        ],
                     )
                     .map_err(|err| format!("sqlite record refresh update failed: {err}"))?;
                     RecordPutOutcome::Refreshed {
@@ -497,6 +604,7 @@ pub fn record_local_item(
                 }
             }
             None => {
+                let ceiling = retention_ceiling_for_envelope(envelope_b64, recorded_at_unix_ms);
                 tx.execute(
                     "
                         INSERT INTO gossip_items (
@@ -504,15 +612,17 @@ pub fn record_local_item(
                             envelope_b64,
                             expiry_unix_ms,
                             hop_count,
-                            recorded_at_unix_ms
-                        ) VALUES (?1, ?2, ?3, ?4, ?5)
+                            recorded_at_unix_ms,
+                            author_expiry_unix_ms
+                        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)
                     ",
                     params![
                         item_id,
                         envelope_b64,
-                        expiry_unix_ms as i64,
+                        expiry_unix_ms.min(ceiling) as i64,
                         hop_count as i64,
                         recorded_at_unix_ms as i64,
+                        ceiling as i64,
                     ],
                 )
                 .map_err(|err| format!("sqlite record insert failed: {err}"))?;
@@ -647,20 +757,24 @@ pub fn insert_import_items(items: &[ImportWriteObject], now_ms: u64) -> Result<(
                             envelope_b64,
                             expiry_unix_ms,
                             hop_count,
-                            recorded_at_unix_ms
-                        ) VALUES (?1, ?2, ?3, ?4, ?5)
+                            recorded_at_unix_ms,
+                            author_expiry_unix_ms
+                        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)
                     ",
                 )
                 .map_err(|err| format!("sqlite import insert prepare failed: {err}"))?;
 
             for item in items {
+                let ceiling =
+                    retention_ceiling_for_envelope(&item.envelope_b64, item.recorded_at_unix_ms);
                 insert_stmt
                     .execute(params![
                         &item.item_id,
                         &item.envelope_b64,
-                        item.expiry_unix_ms as i64,
+                        item.expiry_unix_ms.min(ceiling) as i64,
                         item.hop_count as i64,
                         item.recorded_at_unix_ms as i64,
+                        ceiling as i64,
                     ])
                     .map_err(|err| format!("sqlite import insert failed: {err}"))?;
             }
@@ -737,7 +851,12 @@ fn prune_expired(conn: &mut Connection, now_ms: u64) -> Result<usize, String> {
 fn prune_expired_tx(tx: &rusqlite::Transaction<'_>, now_ms: u64) -> Result<usize, String> {
     let min_expiry = now_ms.saturating_add(CLOCK_SKEW_TOLERANCE_MS);
     tx.execute(
-        "DELETE FROM gossip_items WHERE expiry_unix_ms <= ?1 OR envelope_b64 = ''",
+        "
+            DELETE FROM gossip_items
+            WHERE expiry_unix_ms <= ?1
+               OR author_expiry_unix_ms <= ?1
+               OR envelope_b64 = ''
+        ",
         params![min_expiry as i64],
     )
     .map_err(|err| format!("sqlite prune expired failed: {err}"))
@@ -750,24 +869,25 @@ pub fn sqlite_store_path() -> PathBuf {
 
     if let Ok(state_dir) = std::env::var("AETHOS_STATE_DIR") {
         if !state_dir.trim().is_empty() {
-            return Path::new(&state_dir).join(SQLITE_STORE_FILE_NAME);
+            return profile_state_dir(PathBuf::from(state_dir)).join(SQLITE_STORE_FILE_NAME);
         }
     }
 
     if let Ok(xdg_state_home) = std::env::var("XDG_STATE_HOME") {
         if !xdg_state_home.trim().is_empty() {
-            return Path::new(&xdg_state_home)
-                .join("aethos-linux")
+            return profile_state_dir(Path::new(&xdg_state_home).join("aethos-linux"))
                 .join(SQLITE_STORE_FILE_NAME);
         }
     }
 
     if let Ok(home) = std::env::var("HOME") {
-        return Path::new(&home)
-            .join(".local")
-            .join("state")
-            .join("aethos-linux")
-            .join(SQLITE_STORE_FILE_NAME);
+        return profile_state_dir(
+            Path::new(&home)
+                .join(".local")
+                .join("state")
+                .join("aethos-linux"),
+        )
+        .join(SQLITE_STORE_FILE_NAME);
     }
 
     std::env::temp_dir().join(SQLITE_STORE_FILE_NAME)
@@ -780,24 +900,25 @@ fn legacy_json_store_path() -> PathBuf {
 
     if let Ok(state_dir) = std::env::var("AETHOS_STATE_DIR") {
         if !state_dir.trim().is_empty() {
-            return Path::new(&state_dir).join(LEGACY_JSON_STORE_FILE_NAME);
+            return profile_state_dir(PathBuf::from(state_dir)).join(LEGACY_JSON_STORE_FILE_NAME);
         }
     }
 
     if let Ok(xdg_state_home) = std::env::var("XDG_STATE_HOME") {
         if !xdg_state_home.trim().is_empty() {
-            return Path::new(&xdg_state_home)
-                .join("aethos-linux")
+            return profile_state_dir(Path::new(&xdg_state_home).join("aethos-linux"))
                 .join(LEGACY_JSON_STORE_FILE_NAME);
         }
     }
 
     if let Ok(home) = std::env::var("HOME") {
-        return Path::new(&home)
-            .join(".local")
-            .join("state")
-            .join("aethos-linux")
-            .join(LEGACY_JSON_STORE_FILE_NAME);
+        return profile_state_dir(
+            Path::new(&home)
+                .join(".local")
+                .join("state")
+                .join("aethos-linux"),
+        )
+        .join(LEGACY_JSON_STORE_FILE_NAME);
     }
 
     std::env::temp_dir().join(LEGACY_JSON_STORE_FILE_NAME)
@@ -917,6 +1038,82 @@ mod tests {
         });
     }
 
+    #[test]
+    fn sqlite_expiry_never_extends_past_signed_envelope_expiry() {
+        let _lock = test_env_lock()
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner());
+        let state_dir = unique_state_dir("aethos-gossip-sqlite-signed-expiry");
+        with_test_state_dir(&state_dir, || {
+            let now_ms = 1_700_000_000_000u64;
+            let recipient_pubkey = ed25519_dalek::SigningKey::from_bytes(&[0x21u8; 32])
+                .verifying_key()
+                .to_bytes();
+            let payload = crate::aethos_core::protocol::build_envelope_v2_payload_b64(
+                &recipient_pubkey,
+                b"hello",
+                &[7u8; 32],
+                crate::aethos_core::protocol::EnvelopeLifetime::from_ttl(now_ms, 60_000),
+            )
+            .expect("payload");
+            let item_id = crate::aethos_core::protocol::bytes_to_hex_lower(&sha2::Sha256::digest(
+                base64::engine::general_purpose::URL_SAFE_NO_PAD
+                    .decode(&payload)
+                    .expect("decode payload"),
+            ));
+
+            let first = record_local_item(&item_id, &payload, now_ms + 3_600_000, 0, now_ms)
+                .expect("insert item");
+            assert_eq!(first, RecordPutOutcome::Inserted);
+            let second = record_local_item(&item_id, &payload, now_ms + 7_200_000, 0, now_ms + 5)
+                .expect("refresh item");
+            assert_eq!(
+                second,
+                RecordPutOutcome::Refreshed {
+                    refreshed_expiry_unix_ms: now_ms + 60_000
+                }
+            );
+
+            let existing =
+                get_existing_items_for_ids(std::slice::from_ref(&item_id)).expect("lookup");
+            assert_eq!(existing[&item_id].expiry_unix_ms, now_ms + 60_000);
+
+            let _ = eligible_item_ids(now_ms + 60_000).expect("eligible ids after prune");
+            assert!(!has_item(&item_id).expect("item should be pruned at signed expiry"));
+        });
+    }
+
+    #[test]
+    fn sqlite_unsigned_item_expiry_is_capped_from_first_sight() {
+        let _lock = test_env_lock()
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner());
+        let state_dir = unique_state_dir("aethos-gossip-sqlite-unsigned-expiry");
+        with_test_state_dir(&state_dir, || {
+            let now_ms = 1_700_000_000_000u64;
+            let (payload, item_id) = build_legacy_payload_and_item_id([7u8; 32], "hello");
+            let ceiling = now_ms + MAX_UNSIGNED_ITEM_TTL_MS;
+
+            let first = record_local_item(&item_id, &payload, now_ms + 60_000, 0, now_ms)
+                .expect("insert item");
+            assert_eq!(first, RecordPutOutcome::Inserted);
+            // A later sighting with a far-future expiry must not restart the clock.
+            let later_ms = now_ms + MAX_UNSIGNED_ITEM_TTL_MS - 1_000;
+            let second =
+                record_local_item(&item_id, &payload, later_ms + 365 * 86_400_000, 0, later_ms)
+                    .expect("refresh item");
+            assert_eq!(
+                second,
+                RecordPutOutcome::Refreshed {
+                    refreshed_expiry_unix_ms: ceiling
+                }
+            );
+
+            let _ = eligible_item_ids(ceiling).expect("eligible ids after prune");
+            assert!(!has_item(&item_id).expect("item should be pruned at the unsigned cap"));
+        });
+    }
+
     #[test]
     fn sqlite_eligible_relay_ingest_selection_is_ranked() {
         let _lock = test_env_lock()
@@ -1175,4 +1372,41 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn sqlite_store_path_follows_the_active_profile() {
+        use crate::aethos_core::identity_store::{
+            create_profile, switch_profile, DEFAULT_PROFILE_NAME,
+        };
+        use crate::aethos_core::test_env::{self, EnvVarGuard};
+
+        let _lock = test_env::test_env_lock()
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner());
+        let temp_dir = test_env::unique_test_state_dir("aethos-gossip-sqlite-profiles");
+        let _state_guard = EnvVarGuard::set("XDG_STATE_HOME", &temp_dir.join("state"));
+        let _data_guard = EnvVarGuard::set("XDG_DATA_HOME", &temp_dir.join("data"));
+        let _aethos_state_dir_guard = EnvVarGuard::clear("AETHOS_STATE_DIR");
+        let _profile_guard = EnvVarGuard::clear("AETHOS_PROFILE");
+
+        let app_state_dir = temp_dir.join("state").join("aethos-linux");
+        assert_eq!(
+            sqlite_store_path(),
+            app_state_dir.join(SQLITE_STORE_FILE_NAME)
+        );
+        create_profile("work").expect("create profile");
+        switch_profile("work").expect("switch profile");
+        assert_eq!(
+            sqlite_store_path(),
+            app_state_dir
+                .join("profiles")
+                .join("work")
+                .join(SQLITE_STORE_FILE_NAME)
+        );
+        switch_profile(DEFAULT_PROFILE_NAME).expect("switch back");
+        assert_eq!(
+            sqlite_store_path(),
+            app_state_dir.join(SQLITE_STORE_FILE_NAME)
+        );
+    }
 }
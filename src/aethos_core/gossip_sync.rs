@@ -4,9 +4,11 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use base64::Engine;
 use ciborium::value::Value;
 use ciborium::{de::from_reader, ser::into_writer};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
+use crate::aethos_core::canonical_cbor::check_canonical;
 use crate::aethos_core::encounter_scheduler::{
     BudgetProfile as SchedulerBudgetProfile, CargoItem as SchedulerCargoItem, EncounterClass,
     EncounterSchedulerV1, ProximityClass as SchedulerProximityClass,
@@ -14,25 +16,131 @@ use crate::aethos_core::encounter_scheduler::{
 use crate::aethos_core::gossip_store_sqlite::{
     self, ImportWriteObject, RecordPutOutcome, StoredItemRecord,
 };
-use crate::aethos_core::identity_store::ensure_local_identity;
-use crate::aethos_core::logging::log_verbose;
+use crate::aethos_core::identity_store::{
+    ensure_local_identity, load_local_signing_key_seed, load_revoked_identities,
+    record_identity_revocation, retired_identity_keys, RevokedIdentity,
+};
+use crate::aethos_core::lan_session::LAN_SESSION_CAPABILITY;
+use crate::aethos_core::logging::{log_info, log_verbose};
 use crate::aethos_core::protocol::{
-    bytes_to_hex_lower, decode_cbor_value_exact, decode_envelope_payload_b64,
-    decode_envelope_payload_text_preview, encode_cbor_value_deterministic, is_valid_payload_b64,
-    to_cbor_value,
+    body_manifest_id_hex, bytes_to_hex_lower, decode_body_text_preview, decode_cbor_value_exact,
+    decode_envelope_payload_b64, encode_cbor_value_deterministic, envelope_matches_recipient,
+    is_valid_payload_b64, open_envelope_body, to_cbor_value, DecodedEnvelopeV1,
 };
+use crate::aethos_core::revocation::{revocation_certificate_in, REVOCATION_RETENTION_MS};
 
 pub const GOSSIP_VERSION: u64 = 1;
+/// Oldest and newest gossip versions this node speaks. HELLO `version` carries the oldest so
+/// strict GossipV1 peers still parse it; a higher ceiling rides along in HELLO `max_version`.
+pub const GOSSIP_MIN_VERSION: u64 = GOSSIP_VERSION;
+pub const GOSSIP_MAX_VERSION: u64 = GOSSIP_VERSION;
 pub const GOSSIP_LAN_PORT: u16 = 47_655;
 pub const MAX_FRAME_BYTES: usize = 1_048_576;
+/// Smallest frame limit a HELLO may advertise, so the largest sized-bloom SUMMARY still fits.
+pub const MIN_FRAME_BYTES: usize = 64 * 1024;
 pub const MAX_WANT_ITEMS: usize = 256;
 pub const MAX_TRANSFER_ITEMS: usize = 32;
 pub const MAX_TRANSFER_BYTES: u64 = 524_288;
 pub const BLOOM_FILTER_BYTES: usize = 2048;
 pub const BLOOM_HASH_COUNT: u8 = 4;
+/// HELLO capability for peers that accept a SUMMARY bloom sized to the sender's inventory.
+pub const SIZED_BLOOM_CAPABILITY: &str = "sized_bloom_v1";
+/// Keeps a sized SUMMARY inside a single UDP datagram alongside its preview IDs.
+pub const MAX_SIZED_BLOOM_BYTES: usize = 32 * 1024;
+pub const MAX_BLOOM_HASH_COUNT: u8 = 16;
+/// About a 1% false-positive rate at the optimal hash count.
+const SIZED_BLOOM_BITS_PER_ITEM: usize = 10;
 pub const CLOCK_SKEW_TOLERANCE_MS: u64 = 30_000;
 pub const MAX_SUMMARY_PREVIEW_ITEMS: usize = 64;
+/// HELLO capability for peers that answer SUMMARY_PAGE_REQUEST with the next SUMMARY_PAGE.
+pub const SUMMARY_PAGING_CAPABILITY: &str = "summary_paging_v1";
+/// HELLO capability for peers that answer a HELLO_CHALLENGE with a HELLO_PROOF.
+pub const HELLO_AUTH_CAPABILITY: &str = "hello_auth";
+pub const HELLO_CHALLENGE_NONCE_BYTES: usize = 32;
+const HELLO_AUTH_SIGNING_DOMAIN: &[u8] = b"AETHOS_GOSSIP_HELLO_AUTH_V1";
 const RELAY_INGEST_MAX_ITEMS_DEFAULT: usize = MAX_WANT_ITEMS;
+pub const RELAY_INGEST_CAPABILITY: &str = "relay_ingest";
+/// HELLO capability for peers listening for LAN TCP encounters on the gossip port.
+pub const LAN_TCP_TRANSFER_CAPABILITY: &str = "lan_tcp_transfer_v1";
+
+/// Optional protocol features a peer can advertise in HELLO `capabilities`. Wire strings this
+/// build does not know are ignored rather than rejected, so new features roll out peer by peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GossipCapability {
+    RelayIngest,
+    HelloAuth,
+    SizedBloom,
+    SummaryPaging,
+    LanTcpTransfer,
+    LanTcpSession,
+}
+
+impl GossipCapability {
+    pub const ALL: [GossipCapability; 6] = [
+        GossipCapability::RelayIngest,
+        GossipCapability::HelloAuth,
+        GossipCapability::SizedBloom,
+        GossipCapability::SummaryPaging,
+        GossipCapability::LanTcpTransfer,
+        GossipCapability::LanTcpSession,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GossipCapability::RelayIngest => RELAY_INGEST_CAPABILITY,
+            GossipCapability::HelloAuth => HELLO_AUTH_CAPABILITY,
+            GossipCapability::SizedBloom => SIZED_BLOOM_CAPABILITY,
+            GossipCapability::SummaryPaging => SUMMARY_PAGING_CAPABILITY,
+            GossipCapability::LanTcpTransfer => LAN_TCP_TRANSFER_CAPABILITY,
+            GossipCapability::LanTcpSession => LAN_SESSION_CAPABILITY,
+        }
+    }
+
+    pub fn from_wire(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|capability| capability.as_str() == value)
+    }
+}
+
+/// Capabilities every HELLO from this node advertises; transports may add their own.
+const LOCAL_CAPABILITIES: [GossipCapability; 4] = [
+    GossipCapability::RelayIngest,
+    GossipCapability::HelloAuth,
+    GossipCapability::SizedBloom,
+    GossipCapability::SummaryPaging,
+];
+
+/// What one session may use, agreed from both HELLOs: the highest common version, the
+/// capabilities both sides advertised, and the tighter of each side's limits. Frame builders take
+/// this rather than the raw peer HELLO so nothing is sent that the peer did not negotiate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GossipSessionProfile {
+    pub version: u64,
+    pub features: BTreeSet<GossipCapability>,
+    pub max_want: usize,
+    pub max_transfer: usize,
+    pub max_transfer_bytes: u64,
+    pub max_frame_bytes: usize,
+}
+
+impl GossipSessionProfile {
+    /// Plain GossipV1 with no optional features, for traffic sent before any HELLO is known.
+    pub fn baseline() -> Self {
+        Self {
+            version: GOSSIP_VERSION,
+            features: BTreeSet::new(),
+            max_want: MAX_WANT_ITEMS,
+            max_transfer: MAX_TRANSFER_ITEMS,
+            max_transfer_bytes: MAX_TRANSFER_BYTES,
+            max_frame_bytes: MAX_FRAME_BYTES,
+        }
+    }
+
+    pub fn supports(&self, capability: GossipCapability) -> bool {
+        self.features.contains(&capability)
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", content = "payload")]
@@ -49,10 +157,19 @@ pub enum GossipSyncFrame {
     Receipt(ReceiptFrame),
     #[serde(rename = "RELAY_INGEST")]
     RelayIngest(RelayIngestFrame),
+    #[serde(rename = "HELLO_CHALLENGE")]
+    HelloChallenge(HelloChallengeFrame),
+    #[serde(rename = "HELLO_PROOF")]
+    HelloProof(HelloProofFrame),
+    #[serde(rename = "SUMMARY_PAGE_REQUEST")]
+    SummaryPageRequest(SummaryPageRequestFrame),
+    #[serde(rename = "SUMMARY_PAGE")]
+    SummaryPage(SummaryPageFrame),
 }
 
+/// Unknown fields are rejected unless the sender advertises a `max_version` above ours; see
+/// `decode_hello_payload`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct HelloFrame {
     pub version: u64,
     pub node_id: String,
@@ -61,6 +178,30 @@ pub struct HelloFrame {
     pub propagation_class: String,
     pub max_want: u64,
     pub max_transfer: u64,
+    /// Newest gossip version the sender speaks; absent means `version` is the only one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_version: Option<u64>,
+    /// Largest decoded TRANSFER payload the sender accepts; absent means `MAX_TRANSFER_BYTES`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_transfer_bytes: Option<u64>,
+    /// Largest encoded frame the sender accepts; absent means `MAX_FRAME_BYTES`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_frame_bytes: Option<u64>,
+}
+
+/// Fresh nonce the receiver must sign with the key behind its HELLO `node_pubkey`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HelloChallengeFrame {
+    #[serde(with = "serde_bytes")]
+    pub nonce: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HelloProofFrame {
+    #[serde(with = "serde_bytes")]
+    pub signature: Vec<u8>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -68,6 +209,9 @@ pub struct HelloFrame {
 pub struct SummaryFrame {
     #[serde(with = "serde_bytes")]
     pub bloom_filter: Vec<u8>,
+    /// Present only on sized blooms; absent means the fixed v1 bloom with `BLOOM_HASH_COUNT`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bloom_hash_count: Option<u8>,
     pub item_count: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub preview_item_ids: Option<Vec<String>>,
@@ -75,6 +219,23 @@ pub struct SummaryFrame {
     pub preview_cursor: Option<String>,
 }
 
+/// Asks for the next page of inventory IDs strictly after `after`; absent starts from the lowest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SummaryPageRequestFrame {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+}
+
+/// One page of inventory IDs in bytewise order. `next_cursor` is absent on the last page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SummaryPageFrame {
+    pub item_ids: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct RequestFrame {
@@ -112,12 +273,17 @@ pub struct RelayIngestFrame {
 pub struct ImportedEnvelope {
     pub item_id: String,
     pub author_wayfarer_id: Option<String>,
+    pub author_pubkey: Option<[u8; 32]>,
+    /// Linked-device key that signed the envelope under a verified root certificate.
+    pub author_device_pubkey: Option<[u8; 32]>,
     pub transport_peer: Option<String>,
     pub session_peer: Option<String>,
     pub body_bytes: Vec<u8>,
     pub text: String,
     pub received_at_unix: i64,
     pub manifest_id_hex: Option<String>,
+    /// Local identity the envelope matched, which may be one of several listed recipients.
+    pub recipient_wayfarer_id: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -135,7 +301,16 @@ pub struct RejectedItem {
     pub message: String,
 }
 
+/// Encodes a frame for traffic sent before any HELLO is known, under the default frame limit.
 pub fn serialize_frame(frame: &GossipSyncFrame) -> Result<Vec<u8>, String> {
+    serialize_frame_for_peer(frame, &GossipSessionProfile::baseline())
+}
+
+/// Encodes a frame for a negotiated session, refusing anything over its `max_frame_bytes`.
+pub fn serialize_frame_for_peer(
+    frame: &GossipSyncFrame,
+    profile: &GossipSessionProfile,
+) -> Result<Vec<u8>, String> {
     let (frame_type, payload) = frame_type_and_payload(frame)?;
     let envelope = Value::Map(vec![
         (
@@ -146,29 +321,41 @@ pub fn serialize_frame(frame: &GossipSyncFrame) -> Result<Vec<u8>, String> {
     ]);
     let raw = encode_cbor_value_deterministic(&envelope)
         .map_err(|err| format!("serialize gossip frame: {err}"))?;
-    if raw.len() > MAX_FRAME_BYTES {
-        return Err("frame exceeds MAX_FRAME_BYTES".to_string());
+    if raw.len() > profile.max_frame_bytes {
+        return Err(format!(
+            "frame exceeds negotiated max_frame_bytes: {} > {}",
+            raw.len(),
+            profile.max_frame_bytes
+        ));
     }
     Ok(raw)
 }
 
+/// Parses a frame received before any HELLO is known, under the default frame limit.
 pub fn parse_frame(raw: &[u8]) -> Result<GossipSyncFrame, String> {
-    if raw.len() > MAX_FRAME_BYTES {
-        return Err("frame exceeds MAX_FRAME_BYTES".to_string());
+    parse_frame_for_peer(raw, &GossipSessionProfile::baseline())
+}
+
+/// Parses a frame from a negotiated session, rejecting anything over its `max_frame_bytes`.
+pub fn parse_frame_for_peer(
+    raw: &[u8],
+    profile: &GossipSessionProfile,
+) -> Result<GossipSyncFrame, String> {
+    if raw.len() > profile.max_frame_bytes {
+        return Err(format!(
+            "frame exceeds negotiated max_frame_bytes: {} > {}",
+            raw.len(),
+            profile.max_frame_bytes
+        ));
     }
 
+    if require_canonical_inbound_frame() && check_canonical(raw).is_err() {
+        return Err(
+            "parse gossip frame cbor: frame is not deterministic canonical CBOR".to_string(),
+        );
+    }
     let envelope = decode_cbor_value_exact(raw, "gossip frame")
         .map_err(|err| classify_frame_parse_error(&err))?;
-    if require_canonical_inbound_frame() {
-        let canonical = encode_cbor_value_deterministic(&envelope).map_err(|err| {
-            classify_frame_parse_error(&format!("canonical frame encode failed: {err}"))
-        })?;
-        if canonical.as_slice() != raw {
-            return Err(
-                "parse gossip frame cbor: frame is not deterministic canonical CBOR".to_string(),
-            );
-        }
-    }
 
     let frame =
         frame_from_envelope_value(envelope).map_err(|err| classify_frame_parse_error(&err))?;
@@ -214,6 +401,25 @@ pub fn validate_frame(frame: &GossipSyncFrame) -> Result<(), String> {
         GossipSyncFrame::RelayIngest(ingest) => {
             validate_unique_item_ids(&ingest.item_ids, "RELAY_INGEST.item_ids")
         }
+        GossipSyncFrame::HelloChallenge(challenge) => {
+            if challenge.nonce.len() != HELLO_CHALLENGE_NONCE_BYTES {
+                return Err("HELLO_CHALLENGE nonce length mismatch".to_string());
+            }
+            Ok(())
+        }
+        GossipSyncFrame::HelloProof(proof) => {
+            if proof.signature.len() != 64 {
+                return Err("HELLO_PROOF signature length mismatch".to_string());
+            }
+            Ok(())
+        }
+        GossipSyncFrame::SummaryPageRequest(request) => match &request.after {
+            Some(after) if !is_valid_item_id(after) => {
+                Err("SUMMARY_PAGE_REQUEST after format invalid".to_string())
+            }
+            _ => Ok(()),
+        },
+        GossipSyncFrame::SummaryPage(page) => validate_summary_page(page),
     }
 }
 
@@ -222,25 +428,261 @@ pub fn build_hello_frame(
     node_pubkey_b64url: &str,
 ) -> Result<GossipSyncFrame, String> {
     let frame = GossipSyncFrame::Hello(HelloFrame {
-        version: GOSSIP_VERSION,
+        version: GOSSIP_MIN_VERSION,
         node_id: node_id.to_string(),
         node_pubkey: node_pubkey_b64url.to_string(),
-        capabilities: vec!["relay_ingest".to_string()],
+        capabilities: LOCAL_CAPABILITIES
+            .iter()
+            .map(|capability| capability.as_str().to_string())
+            .collect(),
         propagation_class: "interactive".to_string(),
         max_want: MAX_WANT_ITEMS as u64,
         max_transfer: MAX_TRANSFER_ITEMS as u64,
+        max_version: Some(GOSSIP_MAX_VERSION),
+        max_transfer_bytes: Some(MAX_TRANSFER_BYTES),
+        max_frame_bytes: Some(MAX_FRAME_BYTES as u64),
+    });
+    validate_frame(&frame)?;
+    Ok(frame)
+}
+
+/// Adds a transport-specific capability to a HELLO built by `build_hello_frame`.
+pub fn advertise_capability(hello: &mut HelloFrame, capability: GossipCapability) {
+    if !peer_supports(hello, capability) {
+        hello.capabilities.push(capability.as_str().to_string());
+    }
+}
+
+/// Known capabilities advertised in a HELLO; unknown wire strings are skipped.
+pub fn hello_capabilities(hello: &HelloFrame) -> BTreeSet<GossipCapability> {
+    hello
+        .capabilities
+        .iter()
+        .filter_map(|capability| GossipCapability::from_wire(capability))
+        .collect()
+}
+
+pub fn peer_supports(hello: &HelloFrame, capability: GossipCapability) -> bool {
+    hello
+        .capabilities
+        .iter()
+        .any(|advertised| advertised == capability.as_str())
+}
+
+/// Inclusive range of gossip versions a HELLO advertises.
+pub fn hello_version_range(hello: &HelloFrame) -> Result<(u64, u64), String> {
+    let max_version = hello.max_version.unwrap_or(hello.version);
+    if max_version < hello.version {
+        return Err("HELLO max_version below version".to_string());
+    }
+    Ok((hello.version, max_version))
+}
+
+/// Agrees the session profile for a peer from the local HELLO we sent and the one it sent back.
+/// Fails only when the two version ranges do not overlap.
+pub fn negotiate_session_profile(
+    local: &HelloFrame,
+    peer: &HelloFrame,
+) -> Result<GossipSessionProfile, String> {
+    let (local_min, local_max) = hello_version_range(local)?;
+    let (peer_min, peer_max) = hello_version_range(peer)?;
+    let version = local_max.min(peer_max);
+    if version < local_min.max(peer_min) {
+        return Err(format!(
+            "HELLO version ranges do not overlap: local {local_min}..={local_max} peer {peer_min}..={peer_max}"
+        ));
+    }
+    let max_frame_bytes = hello_max_frame_bytes(local).min(hello_max_frame_bytes(peer));
+    Ok(GossipSessionProfile {
+        version,
+        features: hello_capabilities(local)
+            .intersection(&hello_capabilities(peer))
+            .copied()
+            .collect(),
+        max_want: local.max_want.min(peer.max_want) as usize,
+        max_transfer: local.max_transfer.min(peer.max_transfer) as usize,
+        // Half the frame limit leaves room for base64 and CBOR overhead, as the defaults do.
+        max_transfer_bytes: hello_max_transfer_bytes(local)
+            .min(hello_max_transfer_bytes(peer))
+            .min(max_frame_bytes as u64 / 2),
+        max_frame_bytes,
+    })
+}
+
+fn hello_max_transfer_bytes(hello: &HelloFrame) -> u64 {
+    hello.max_transfer_bytes.unwrap_or(MAX_TRANSFER_BYTES)
+}
+
+fn hello_max_frame_bytes(hello: &HelloFrame) -> usize {
+    hello
+        .max_frame_bytes
+        .map_or(MAX_FRAME_BYTES, |max_frame_bytes| max_frame_bytes as usize)
+}
+
+/// Returns a fresh challenge and the nonce to keep for checking the peer's proof.
+pub fn build_hello_challenge_frame() -> ([u8; HELLO_CHALLENGE_NONCE_BYTES], GossipSyncFrame) {
+    use chacha20poly1305::aead::rand_core::RngCore;
+
+    let mut nonce = [0u8; HELLO_CHALLENGE_NONCE_BYTES];
+    chacha20poly1305::aead::OsRng.fill_bytes(&mut nonce);
+    let frame = GossipSyncFrame::HelloChallenge(HelloChallengeFrame {
+        nonce: nonce.to_vec(),
+    });
+    (nonce, frame)
+}
+
+/// Answers a peer's challenge. `local_nonce` is the challenge we sent that peer; the signature
+/// covers both nonces and both HELLOs, so it is only good for this pair of nodes and session.
+pub fn build_hello_proof_frame(
+    signing_seed: &[u8; 32],
+    local: &HelloFrame,
+    peer: &HelloFrame,
+    local_nonce: &[u8; HELLO_CHALLENGE_NONCE_BYTES],
+    challenge: &HelloChallengeFrame,
+) -> Result<GossipSyncFrame, String> {
+    let message = hello_auth_message(local, peer, &challenge.nonce, local_nonce)?;
+    let frame = GossipSyncFrame::HelloProof(HelloProofFrame {
+        signature: SigningKey::from_bytes(signing_seed)
+            .sign(&message)
+            .to_bytes()
+            .to_vec(),
     });
     validate_frame(&frame)?;
     Ok(frame)
 }
 
+/// Checks that `peer` holds the key behind its HELLO `node_pubkey`. `local_nonce` is the
+/// challenge we sent it and `peer_challenge` the one it sent us; a proof the peer made for any
+/// other node or session fails. `node_id == SHA-256(node_pubkey)` is already enforced when the
+/// HELLO is parsed.
+pub fn verify_hello_proof(
+    local: &HelloFrame,
+    peer: &HelloFrame,
+    local_nonce: &[u8; HELLO_CHALLENGE_NONCE_BYTES],
+    peer_challenge: &HelloChallengeFrame,
+    proof: &HelloProofFrame,
+) -> Result<(), String> {
+    validate_hello(peer)?;
+    let pubkey: [u8; 32] = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(&peer.node_pubkey)
+        .map_err(|err| format!("HELLO node_pubkey decode failed: {err}"))?
+        .try_into()
+        .map_err(|_| "HELLO node_pubkey must be 32 bytes".to_string())?;
+    let signature: [u8; 64] = proof
+        .signature
+        .as_slice()
+        .try_into()
+        .map_err(|_| "HELLO_PROOF signature length mismatch".to_string())?;
+    VerifyingKey::from_bytes(&pubkey)
+        .map_err(|err| format!("HELLO node_pubkey invalid: {err}"))?
+        .verify(
+            &hello_auth_message(peer, local, local_nonce, &peer_challenge.nonce)?,
+            &Signature::from_bytes(&signature),
+        )
+        .map_err(|_| "HELLO_PROOF signature is invalid".to_string())
+}
+
+/// Runs the HELLO_CHALLENGE/HELLO_PROOF exchange on a session transport once both HELLOs are
+/// through. Returns false, without sending anything, when the peer does not advertise
+/// `hello_auth`; a proof that fails to verify is an error.
+pub fn complete_hello_auth<T>(
+    transport: &mut T,
+    local: &HelloFrame,
+    peer: &HelloFrame,
+    signing_seed: &[u8; 32],
+    send: impl Fn(&mut T, &GossipSyncFrame) -> Result<(), String>,
+    recv: impl Fn(&mut T) -> Result<GossipSyncFrame, String>,
+) -> Result<bool, String> {
+    if !peer_supports(peer, GossipCapability::HelloAuth) {
+        return Ok(false);
+    }
+    let (nonce, challenge) = build_hello_challenge_frame();
+    send(transport, &challenge)?;
+
+    // The peer sends its challenge before its proof, so the proof can be checked against both.
+    let mut peer_challenge = None;
+    let mut verified = false;
+    while !(peer_challenge.is_some() && verified) {
+        match recv(transport)? {
+            GossipSyncFrame::HelloChallenge(challenge) if peer_challenge.is_none() => {
+                send(
+                    transport,
+                    &build_hello_proof_frame(signing_seed, local, peer, &nonce, &challenge)?,
+                )?;
+                peer_challenge = Some(challenge);
+            }
+            GossipSyncFrame::HelloProof(proof) if !verified => {
+                let Some(challenge) = peer_challenge.as_ref() else {
+                    return Err("HELLO_PROOF arrived before the peer's HELLO_CHALLENGE".to_string());
+                };
+                verify_hello_proof(local, peer, &nonce, challenge, &proof)?;
+                verified = true;
+            }
+            other => {
+                let (frame_type, _) = frame_type_and_payload(&other)?;
+                return Err(format!(
+                    "HELLO authentication got unexpected {frame_type} frame"
+                ));
+            }
+        }
+    }
+    Ok(true)
+}
+
+/// What a HELLO_PROOF signs: the verifier's nonce, the prover's nonce, both `node_id`s and a
+/// digest of each HELLO, prover first. The digests cover the HELLO fields this build knows, as
+/// it decoded them.
+fn hello_auth_message(
+    prover: &HelloFrame,
+    verifier: &HelloFrame,
+    verifier_nonce: &[u8],
+    prover_nonce: &[u8],
+) -> Result<Vec<u8>, String> {
+    if verifier_nonce.len() != HELLO_CHALLENGE_NONCE_BYTES
+        || prover_nonce.len() != HELLO_CHALLENGE_NONCE_BYTES
+    {
+        return Err("HELLO_CHALLENGE nonce length mismatch".to_string());
+    }
+    let mut message = HELLO_AUTH_SIGNING_DOMAIN.to_vec();
+    message.extend_from_slice(verifier_nonce);
+    message.extend_from_slice(prover_nonce);
+    message.extend_from_slice(prover.node_id.as_bytes());
+    message.extend_from_slice(verifier.node_id.as_bytes());
+    for hello in [prover, verifier] {
+        let encoded = encode_cbor_value_deterministic(&to_cbor_value(hello)?)
+            .map_err(|err| format!("encode HELLO for authentication: {err}"))?;
+        message.extend_from_slice(&Sha256::digest(encoded));
+    }
+    Ok(message)
+}
+
+/// Builds the fixed-size v1 SUMMARY every GossipV1 peer can parse.
 pub fn build_summary_frame(now_ms: u64) -> Result<GossipSyncFrame, String> {
+    build_summary_frame_for_peer(now_ms, &GossipSessionProfile::baseline())
+}
+
+/// Builds a SUMMARY for a negotiated session. Sessions with `sized_bloom_v1` get a bloom sized to
+/// the local inventory; everyone else gets the fixed v1 bloom, which v1 peers reject any other
+/// shape of.
+pub fn build_summary_frame_for_peer(
+    now_ms: u64,
+    profile: &GossipSessionProfile,
+) -> Result<GossipSyncFrame, String> {
     let item_ids = eligible_item_ids(now_ms)?;
-    let bloom_filter = build_bloom_filter(&item_ids)?;
+    let (bloom_filter, bloom_hash_count) = if profile.supports(GossipCapability::SizedBloom) {
+        let (bloom_bytes, hash_count) = sized_bloom_parameters(item_ids.len());
+        (
+            build_sized_bloom_filter(&item_ids, bloom_bytes, hash_count)?,
+            Some(hash_count),
+        )
+    } else {
+        (build_bloom_filter(&item_ids)?, None)
+    };
     let preview_item_ids = build_summary_preview_item_ids(now_ms)?;
     let preview_cursor = preview_item_ids.last().cloned();
     let frame = GossipSyncFrame::Summary(SummaryFrame {
         bloom_filter,
+        bloom_hash_count,
         item_count: item_ids.len() as u64,
         preview_item_ids: (!preview_item_ids.is_empty()).then_some(preview_item_ids),
         preview_cursor,
@@ -249,6 +691,101 @@ pub fn build_summary_frame(now_ms: u64) -> Result<GossipSyncFrame, String> {
     Ok(frame)
 }
 
+/// True when the SUMMARY counts more items than its preview lists, so paging can reveal more.
+pub fn summary_has_unpreviewed_items(summary: &SummaryFrame) -> bool {
+    let previewed = summary.preview_item_ids.as_ref().map_or(0, Vec::len) as u64;
+    summary.item_count > previewed
+}
+
+pub fn build_summary_page_request_frame(
+    after: Option<&str>,
+    profile: &GossipSessionProfile,
+) -> Result<GossipSyncFrame, String> {
+    require_feature(profile, GossipCapability::SummaryPaging)?;
+    let frame = GossipSyncFrame::SummaryPageRequest(SummaryPageRequestFrame {
+        after: after.map(str::to_string),
+    });
+    validate_frame(&frame)?;
+    Ok(frame)
+}
+
+/// Answers a SUMMARY_PAGE_REQUEST with up to `MAX_SUMMARY_PREVIEW_ITEMS` advertisable IDs after
+/// the cursor. Pages walk the inventory in ID order rather than the SUMMARY preview ranking so a
+/// cursor always identifies a stable position.
+pub fn build_summary_page_frame(
+    now_ms: u64,
+    after: Option<&str>,
+    profile: &GossipSessionProfile,
+) -> Result<GossipSyncFrame, String> {
+    require_feature(profile, GossipCapability::SummaryPaging)?;
+    if after.is_some_and(|after| !is_valid_item_id(after)) {
+        return Err("SUMMARY_PAGE_REQUEST after format invalid".to_string());
+    }
+    // Item IDs are lowercase hex, so the store's text order is the wire's byte order. Batches
+    // are read past the cursor until a full page survives the self-addressed filter.
+    let mut item_ids = Vec::new();
+    let mut cursor = after.map(str::to_string);
+    let has_more = loop {
+        let batch = gossip_store_sqlite::item_ids_after(
+            now_ms,
+            cursor.as_deref(),
+            MAX_SUMMARY_PREVIEW_ITEMS + 1,
+        )?;
+        let exhausted = batch.len() <= MAX_SUMMARY_PREVIEW_ITEMS;
+        cursor = batch.last().cloned();
+        item_ids.extend(filter_non_self_advertisable_item_ids(batch)?);
+        if item_ids.len() > MAX_SUMMARY_PREVIEW_ITEMS {
+            break true;
+        }
+        if exhausted {
+            break false;
+        }
+    };
+    item_ids.truncate(MAX_SUMMARY_PREVIEW_ITEMS);
+    let next_cursor = if has_more {
+        item_ids.last().cloned()
+    } else {
+        None
+    };
+    let frame = GossipSyncFrame::SummaryPage(SummaryPageFrame {
+        item_ids,
+        next_cursor,
+    });
+    validate_frame(&frame)?;
+    Ok(frame)
+}
+
+fn require_feature(
+    profile: &GossipSessionProfile,
+    capability: GossipCapability,
+) -> Result<(), String> {
+    if profile.supports(capability) {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} was not negotiated for this session",
+            capability.as_str()
+        ))
+    }
+}
+
+/// Picks the page IDs this node lacks, skipping ones already requested this encounter.
+pub fn select_request_item_ids_from_summary_page(
+    page: &SummaryPageFrame,
+    max_want: usize,
+    already_requested: &BTreeSet<String>,
+) -> Result<Vec<String>, String> {
+    let candidates = page
+        .item_ids
+        .iter()
+        .filter(|item_id| !already_requested.contains(*item_id))
+        .cloned()
+        .collect::<Vec<_>>();
+    let mut want = missing_item_ids(&candidates)?;
+    want.truncate(max_want.min(MAX_WANT_ITEMS));
+    Ok(want)
+}
+
 #[allow(dead_code)]
 pub fn select_request_item_ids_from_summary(
     summary: &SummaryFrame,
@@ -292,7 +829,7 @@ fn select_request_item_ids_from_summary_with_context(
         if !preview_seen.insert(item_id.clone()) {
             continue;
         }
-        if bloom_might_contain(&summary.bloom_filter, item_id)? {
+        if summary_might_contain(summary, item_id)? {
             preview_eligible.push(item_id.clone());
         }
     }
@@ -306,17 +843,14 @@ fn select_request_item_ids_from_summary_with_context(
         if !candidate_seen.insert(item_id.clone()) {
             continue;
         }
-        if bloom_might_contain(&summary.bloom_filter, item_id)? {
+        if summary_might_contain(summary, item_id)? {
             candidate_eligible.push(item_id.clone());
         }
     }
 
     let mut selected = Vec::new();
     let mut selected_seen = BTreeSet::new();
-    for item_id in preview_eligible
-        .into_iter()
-        .chain(candidate_eligible.into_iter())
-    {
+    for item_id in preview_eligible.into_iter().chain(candidate_eligible) {
         if selected.len() >= request_cap {
             break;
         }
@@ -403,12 +937,19 @@ fn filter_non_self_advertisable_item_ids(item_ids: Vec<String>) -> Result<Vec<St
     let existing = gossip_store_sqlite::get_existing_items_for_ids(&item_ids)?;
     let mut filtered = Vec::with_capacity(item_ids.len());
     let mut dropped = 0usize;
+    let mut local_signing_key_seed = None;
     for item_id in item_ids {
         let Some(record) = existing.get(&item_id) else {
             continue;
         };
         match decode_envelope_payload_b64(&record.envelope_b64) {
-            Ok(decoded) if decoded.to_wayfarer_id_hex == local_wayfarer_id => {
+            Ok(decoded)
+                if is_local_recipient(
+                    &decoded,
+                    &local_wayfarer_id,
+                    &mut local_signing_key_seed,
+                ) =>
+            {
                 dropped = dropped.saturating_add(1);
             }
             Ok(_) => filtered.push(item_id),
@@ -437,6 +978,9 @@ pub fn record_local_payload(payload_b64: &str, expiry_unix_ms: u64) -> Result<St
         return Err("invalid payload_b64 format for gossip storage".to_string());
     }
     let now = now_unix_ms();
+    let expiry_unix_ms = decode_envelope_payload_b64(payload_b64)
+        .map(|decoded| decoded.bounded_expiry_unix_ms(expiry_unix_ms))
+        .unwrap_or(expiry_unix_ms);
     if now + CLOCK_SKEW_TOLERANCE_MS >= expiry_unix_ms {
         return Err("cannot store already-expired object".to_string());
     }
@@ -485,6 +1029,7 @@ pub fn transfer_items_for_request(
         max_bytes,
         now_ms,
         None,
+        false,
     )
 }
 
@@ -505,12 +1050,15 @@ pub struct TransferSelectionOutcome {
     pub telemetry: TransferSelectionTelemetry,
 }
 
+/// `peer_authenticated` is false when the peer's `node_id` was not proven by a HELLO_PROOF;
+/// such a peer is scheduled like any other carrier, never as the destination.
 pub fn transfer_items_for_request_with_shadow_context_and_diagnostics(
     requested_item_ids: &[String],
     max_items: u32,
     max_bytes: u64,
     now_ms: u64,
     peer_wayfarer_id: Option<&str>,
+    peer_authenticated: bool,
 ) -> Result<TransferSelectionOutcome, String> {
     let planner = "encounter-scheduler-v1";
     log_verbose(&format!(
@@ -522,8 +1070,14 @@ pub fn transfer_items_for_request_with_shadow_context_and_diagnostics(
     ));
     let candidates =
         gossip_store_sqlite::transfer_candidates_for_request(requested_item_ids, now_ms)?;
-    let scheduler_plan =
-        build_scheduler_transfer_plan(&candidates, max_items, max_bytes, now_ms, peer_wayfarer_id)?;
+    let scheduler_plan = build_scheduler_transfer_plan(
+        &candidates,
+        max_items,
+        max_bytes,
+        now_ms,
+        peer_wayfarer_id,
+        peer_authenticated,
+    )?;
 
     transfer_legacy_debug::maybe_log_scheduler_vs_legacy_diff(
         &candidates,
@@ -532,6 +1086,7 @@ pub fn transfer_items_for_request_with_shadow_context_and_diagnostics(
         max_bytes,
         now_ms,
         peer_wayfarer_id,
+        peer_authenticated,
     );
 
     if should_use_legacy_transfer_fallback() {
@@ -616,6 +1171,7 @@ pub fn transfer_items_for_request_with_shadow_context(
     max_bytes: u64,
     now_ms: u64,
     peer_wayfarer_id: Option<&str>,
+    peer_authenticated: bool,
 ) -> Result<Vec<TransferObject>, String> {
     transfer_items_for_request_with_shadow_context_and_diagnostics(
         requested_item_ids,
@@ -623,6 +1179,7 @@ pub fn transfer_items_for_request_with_shadow_context(
         max_bytes,
         now_ms,
         peer_wayfarer_id,
+        peer_authenticated,
     )
     .map(|outcome| outcome.objects)
 }
@@ -640,13 +1197,14 @@ fn build_scheduler_transfer_plan(
     max_bytes: u64,
     now_ms: u64,
     peer_wayfarer_id: Option<&str>,
+    peer_authenticated: bool,
 ) -> Result<SchedulerTransferPlan, String> {
     let mut scheduler_items = Vec::with_capacity(candidates.len());
     let mut item_to_stored = BTreeMap::<String, &StoredItemRecord>::new();
     let mut item_to_wire_size = BTreeMap::<String, u64>::new();
     for candidate in candidates {
         let (profile, scheduler_item) =
-            shadow_profile_from_stored(candidate, now_ms, peer_wayfarer_id)?;
+            shadow_profile_from_stored(candidate, now_ms, peer_wayfarer_id, peer_authenticated)?;
         item_to_stored.insert(profile.item_id.clone(), candidate);
         item_to_wire_size.insert(profile.item_id, profile.decoded_wire_bytes as u64);
         scheduler_items.push(scheduler_item);
@@ -915,6 +1473,7 @@ mod transfer_legacy_debug {
         max_bytes: u64,
         now_ms: u64,
         peer_wayfarer_id: Option<&str>,
+        peer_authenticated: bool,
     ) {
         if !scheduler_shadow_mode_enabled() {
             return;
@@ -940,11 +1499,15 @@ mod transfer_legacy_debug {
         };
 
         for candidate in candidates {
-            let (profile, scheduler_item) =
-                match shadow_profile_from_stored(candidate, now_ms, peer_wayfarer_id) {
-                    Ok(profile) => profile,
-                    Err(_) => continue,
-                };
+            let (profile, scheduler_item) = match shadow_profile_from_stored(
+                candidate,
+                now_ms,
+                peer_wayfarer_id,
+                peer_authenticated,
+            ) {
+                Ok(profile) => profile,
+                Err(_) => continue,
+            };
             candidate_profiles.push(profile);
             scheduler_items.push(scheduler_item);
         }
@@ -1079,6 +1642,7 @@ fn shadow_profile_from_stored(
     stored: &StoredItemRecord,
     now_ms: u64,
     peer_wayfarer_id: Option<&str>,
+    peer_authenticated: bool,
 ) -> Result<
     (
         transfer_legacy_debug::SchedulerCandidateProfile,
@@ -1093,7 +1657,7 @@ fn shadow_profile_from_stored(
         .map_err(|err| format!("decode transfer candidate envelope bytes failed: {err}"))?
         .len();
     let proximity = if let Some(peer) = peer_wayfarer_id {
-        if decoded.to_wayfarer_id_hex == peer {
+        if peer_authenticated && decoded.is_addressed_to(peer) {
             SchedulerProximityClass::DestinationPeer
         } else {
             SchedulerProximityClass::LikelyCloser
@@ -1190,6 +1754,9 @@ pub fn import_transfer_items(
     let mut new_messages = Vec::new();
     let mut pending_new_records = BTreeMap::<String, ImportWriteObject>::new();
     let mut pending_new_inserts = Vec::new();
+    let mut local_signing_key_seed = None;
+    let mut retired_identities = None;
+    let mut revoked_identities = None;
     let existing = gossip_store_sqlite::get_existing_items_for_ids(
         &objects
             .iter()
@@ -1220,6 +1787,50 @@ pub fn import_transfer_items(
             });
             continue;
         }
+        if let Some(lifetime) = parsed.lifetime {
+            if now_ms + CLOCK_SKEW_TOLERANCE_MS >= lifetime.expires_at_unix_ms {
+                rejected_items.push(RejectedItem {
+                    item_id: object.item_id.clone(),
+                    code: "EXPIRED".to_string(),
+                    message: "object is past its signed envelope expiry".to_string(),
+                });
+                continue;
+            }
+            if lifetime.created_at_unix_ms > now_ms + CLOCK_SKEW_TOLERANCE_MS {
+                rejected_items.push(RejectedItem {
+                    item_id: object.item_id.clone(),
+                    code: "NOT_YET_VALID".to_string(),
+                    message: "object signed creation time is in the future".to_string(),
+                });
+                continue;
+            }
+        }
+
+        // Carriers check revocation items themselves and keep them well past normal TTLs.
+        let mut revocation_retention_ms = None;
+        if let Some(certificate) = revocation_certificate_in(&parsed) {
+            match record_identity_revocation(&certificate, now_ms) {
+                Ok((revoked, newly_revoked)) => {
+                    if newly_revoked {
+                        log_info(&format!(
+                            "identity_revocation_recorded: item_id={} revoked={}",
+                            object.item_id,
+                            revoked.wayfarer_id()
+                        ));
+                        revoked_identities = None;
+                    }
+                    revocation_retention_ms = Some(now_ms.saturating_add(REVOCATION_RETENTION_MS));
+                }
+                Err(err) => {
+                    rejected_items.push(RejectedItem {
+                        item_id: object.item_id.clone(),
+                        code: "INVALID_REVOCATION".to_string(),
+                        message: err,
+                    });
+                    continue;
+                }
+            }
+        }
 
         let existing_item = if let Some(pending) = pending_new_records.get(&object.item_id) {
             Some(StoredItemRecord {
@@ -1233,6 +1844,12 @@ pub fn import_transfer_items(
             existing.get(&object.item_id).cloned()
         };
 
+        // Signed creation times are not consulted: whoever holds a stolen key can backdate
+        // them, so every envelope first seen after the revocation is dropped.
+        let revoked_author = existing_item.is_none()
+            && revocation_retention_ms.is_none()
+            && is_revoked_author(&parsed, &mut revoked_identities)?;
+
         match existing_item {
             Some(existing_item) if existing_item.envelope_b64 != object.envelope_b64 => {
                 rejected_items.push(RejectedItem {
@@ -1251,34 +1868,81 @@ pub fn import_transfer_items(
             Some(_) => {
                 accepted_item_ids.push(object.item_id.clone());
             }
+            None if revoked_author => {
+                rejected_items.push(RejectedItem {
+                    item_id: object.item_id.clone(),
+                    code: "REVOKED_AUTHOR".to_string(),
+                    message: "envelope author key has been revoked".to_string(),
+                });
+            }
             None => {
+                let expiry_unix_ms = parsed.bounded_expiry_unix_ms(object.expiry_unix_ms);
                 let insert = ImportWriteObject {
                     item_id: object.item_id.clone(),
                     envelope_b64: object.envelope_b64.clone(),
-                    expiry_unix_ms: object.expiry_unix_ms,
+                    expiry_unix_ms: revocation_retention_ms
+                        .map_or(expiry_unix_ms, |retain_until| {
+                            expiry_unix_ms.max(retain_until)
+                        }),
                     hop_count: object.hop_count,
                     recorded_at_unix_ms: now_ms,
                 };
                 pending_new_records.insert(object.item_id.clone(), insert.clone());
                 pending_new_inserts.push(insert);
                 accepted_item_ids.push(object.item_id.clone());
-                if parsed.to_wayfarer_id_hex == local_wayfarer_id {
-                    let preview_text = decode_envelope_payload_text_preview(&object.envelope_b64)
-                        .unwrap_or_default();
+                let local_recipient = local_recipient_for(
+                    &parsed,
+                    local_wayfarer_id,
+                    &mut local_signing_key_seed,
+                    &mut retired_identities,
+                    now_ms,
+                );
+                if let Some(local_recipient) = local_recipient {
+                    let opened = match &local_recipient {
+                        LocalRecipient::Current => {
+                            open_local_envelope_body(&parsed, &mut local_signing_key_seed)
+                        }
+                        LocalRecipient::Retired {
+                            signing_key_seed, ..
+                        } => open_envelope_body(&parsed, signing_key_seed),
+                    };
+                    let body_bytes = match opened {
+                        Ok(body_bytes) => body_bytes,
+                        Err(err) => {
+                            log_verbose(&format!(
+                                "transfer_import_open_failed: item_id={} error={}",
+                                object.item_id, err
+                            ));
+                            continue;
+                        }
+                    };
+                    let preview_text = decode_body_text_preview(&body_bytes).unwrap_or_default();
+                    let manifest_id_hex = body_manifest_id_hex(&body_bytes);
                     new_messages.push(ImportedEnvelope {
                         item_id: object.item_id.clone(),
                         author_wayfarer_id: Some(parsed.author_wayfarer_id_hex.clone()),
+                        author_pubkey: Some(parsed.author_pubkey),
+                        author_device_pubkey: parsed.author_device_pubkey,
                         transport_peer: transport_peer.map(|value| value.to_string()),
                         session_peer: session_peer_wayfarer_id.map(|value| value.to_string()),
-                        body_bytes: parsed.body,
+                        body_bytes,
                         text: preview_text,
                         received_at_unix: (now_ms / 1000) as i64,
-                        manifest_id_hex: Some(parsed.manifest_id_hex),
+                        recipient_wayfarer_id: match local_recipient {
+                            LocalRecipient::Current => parsed
+                                .matched_recipient(&[local_wayfarer_id])
+                                .or(parsed.is_sealed().then_some(local_wayfarer_id))
+                                .map(str::to_string),
+                            LocalRecipient::Retired { wayfarer_id, .. } => Some(wayfarer_id),
+                        },
+                        manifest_id_hex: Some(manifest_id_hex),
                     });
                 } else {
                     log_verbose(&format!(
                         "transfer_import_stored_nonlocal: item_id={} to={} local={}",
-                        object.item_id, parsed.to_wayfarer_id_hex, local_wayfarer_id
+                        object.item_id,
+                        parsed.recipient_wayfarer_ids_hex.join(","),
+                        local_wayfarer_id
                     ));
                 }
             }
@@ -1299,58 +1963,199 @@ pub fn import_transfer_items(
     })
 }
 
+/// Errors when the revocation list cannot be read: an unknown revocation state must not let
+/// envelopes from a revoked key through.
+fn is_revoked_author(
+    parsed: &DecodedEnvelopeV1,
+    revoked_identities: &mut Option<BTreeMap<String, RevokedIdentity>>,
+) -> Result<bool, String> {
+    let revoked = match revoked_identities {
+        Some(revoked) => revoked,
+        None => revoked_identities.insert(load_revoked_identities().map_err(|err| {
+            log_info(&format!("transfer_import_revocations_unavailable: {}", err));
+            format!("revocation list unavailable: {err}")
+        })?),
+    };
+    Ok(revoked.contains_key(&parsed.author_wayfarer_id_hex))
+}
+
+/// Which local key an imported envelope is addressed to.
+enum LocalRecipient {
+    Current,
+    /// A rotated-away identity still inside its succession grace window.
+    Retired {
+        wayfarer_id: String,
+        signing_key_seed: [u8; 32],
+    },
+}
+
+fn local_recipient_for(
+    parsed: &DecodedEnvelopeV1,
+    local_wayfarer_id: &str,
+    local_signing_key_seed: &mut Option<[u8; 32]>,
+    retired_identities: &mut Option<Vec<(String, [u8; 32])>>,
+    now_ms: u64,
+) -> Option<LocalRecipient> {
+    if is_local_recipient(parsed, local_wayfarer_id, local_signing_key_seed) {
+        return Some(LocalRecipient::Current);
+    }
+    retired_identities
+        .get_or_insert_with(|| {
+            retired_identity_keys(now_ms).unwrap_or_else(|err| {
+                log_verbose(&format!(
+                    "transfer_import_retired_keys_unavailable: {}",
+                    err
+                ));
+                Vec::new()
+            })
+        })
+        .iter()
+        .find(|(wayfarer_id, seed)| {
+            parsed.is_addressed_to(wayfarer_id)
+                || (parsed.is_sealed() && envelope_matches_recipient(parsed, seed))
+        })
+        .map(|(wayfarer_id, seed)| LocalRecipient::Retired {
+            wayfarer_id: wayfarer_id.clone(),
+            signing_key_seed: *seed,
+        })
+}
+
+/// Blinded envelopes carry no cleartext recipient, so each one is scanned by recomputing
+/// the recipient tag with the local identity key.
+fn is_local_recipient(
+    parsed: &DecodedEnvelopeV1,
+    local_wayfarer_id: &str,
+    local_signing_key_seed: &mut Option<[u8; 32]>,
+) -> bool {
+    if parsed.is_addressed_to(local_wayfarer_id) {
+        return true;
+    }
+    // Sealed envelopes may name this install by a blinded tag or, on a linked device, by the
+    // device key hash rather than the shared wayfarer ID.
+    if !parsed.is_sealed() {
+        return false;
+    }
+    match cached_local_signing_key_seed(local_signing_key_seed) {
+        Ok(seed) => envelope_matches_recipient(parsed, &seed),
+        Err(err) => {
+            log_verbose(&format!(
+                "transfer_import_blinded_scan_unavailable: {}",
+                err
+            ));
+            false
+        }
+    }
+}
+
+fn open_local_envelope_body(
+    parsed: &DecodedEnvelopeV1,
+    local_signing_key_seed: &mut Option<[u8; 32]>,
+) -> Result<Vec<u8>, String> {
+    if !parsed.is_sealed() {
+        return Ok(parsed.body.clone());
+    }
+    let seed = cached_local_signing_key_seed(local_signing_key_seed)?;
+    open_envelope_body(parsed, &seed)
+}
+
+fn cached_local_signing_key_seed(cache: &mut Option<[u8; 32]>) -> Result<[u8; 32], String> {
+    if let Some(seed) = cache {
+        return Ok(*seed);
+    }
+    let seed = load_local_signing_key_seed()?;
+    *cache = Some(seed);
+    Ok(seed)
+}
+
 pub fn build_bloom_filter(item_ids: &[String]) -> Result<Vec<u8>, String> {
-    let mut bloom = vec![0u8; BLOOM_FILTER_BYTES];
+    build_sized_bloom_filter(item_ids, BLOOM_FILTER_BYTES, BLOOM_HASH_COUNT)
+}
+
+pub fn bloom_might_contain(bloom_filter: &[u8], item_id: &str) -> Result<bool, String> {
+    if bloom_filter.len() != BLOOM_FILTER_BYTES {
+        return Err("invalid bloom filter length".to_string());
+    }
+    sized_bloom_might_contain(bloom_filter, BLOOM_HASH_COUNT, item_id)
+}
+
+/// Bloom size and hash count for `item_count` items at `SIZED_BLOOM_BITS_PER_ITEM`, never
+/// smaller than the v1 bloom and never larger than `MAX_SIZED_BLOOM_BYTES`.
+pub fn sized_bloom_parameters(item_count: usize) -> (usize, u8) {
+    let bloom_bytes = item_count
+        .saturating_mul(SIZED_BLOOM_BITS_PER_ITEM)
+        .div_ceil(8)
+        .clamp(BLOOM_FILTER_BYTES, MAX_SIZED_BLOOM_BYTES);
+    let bits_per_item = (bloom_bytes * 8) as f64 / item_count.max(1) as f64;
+    let hash_count = (bits_per_item * std::f64::consts::LN_2).round() as u8;
+    (bloom_bytes, hash_count.clamp(1, MAX_BLOOM_HASH_COUNT))
+}
+
+pub fn build_sized_bloom_filter(
+    item_ids: &[String],
+    bloom_bytes: usize,
+    hash_count: u8,
+) -> Result<Vec<u8>, String> {
+    let mut bloom = vec![0u8; bloom_bytes];
     for item_id in item_ids {
         let item_bytes = decode_item_id(item_id)?;
-        for hash_idx in 0..BLOOM_HASH_COUNT {
-            let mut hasher = Sha256::new();
-            hasher.update(&item_bytes);
-            hasher.update([hash_idx]);
-            let digest = hasher.finalize();
-            let mut n = [0u8; 8];
-            n.copy_from_slice(&digest[..8]);
-            let bit_index = (u64::from_be_bytes(n) % (BLOOM_FILTER_BYTES as u64 * 8)) as usize;
-            let byte_index = bit_index / 8;
-            let bit_offset = bit_index % 8;
-            bloom[byte_index] |= 1 << bit_offset;
+        for hash_idx in 0..hash_count {
+            let bit_index = bloom_bit_index(&item_bytes, hash_idx, bloom_bytes);
+            bloom[bit_index / 8] |= 1 << (bit_index % 8);
         }
     }
     Ok(bloom)
 }
 
-pub fn bloom_might_contain(bloom_filter: &[u8], item_id: &str) -> Result<bool, String> {
-    if bloom_filter.len() != BLOOM_FILTER_BYTES {
+pub fn sized_bloom_might_contain(
+    bloom_filter: &[u8],
+    hash_count: u8,
+    item_id: &str,
+) -> Result<bool, String> {
+    if bloom_filter.is_empty() {
         return Err("invalid bloom filter length".to_string());
     }
     let item_bytes = decode_item_id(item_id)?;
-    for hash_idx in 0..BLOOM_HASH_COUNT {
-        let mut hasher = Sha256::new();
-        hasher.update(&item_bytes);
-        hasher.update([hash_idx]);
-        let digest = hasher.finalize();
-        let mut n = [0u8; 8];
-        n.copy_from_slice(&digest[..8]);
-        let bit_index = (u64::from_be_bytes(n) % (BLOOM_FILTER_BYTES as u64 * 8)) as usize;
-        let byte_index = bit_index / 8;
-        let bit_offset = bit_index % 8;
-        if bloom_filter[byte_index] & (1 << bit_offset) == 0 {
+    for hash_idx in 0..hash_count {
+        let bit_index = bloom_bit_index(&item_bytes, hash_idx, bloom_filter.len());
+        if bloom_filter[bit_index / 8] & (1 << (bit_index % 8)) == 0 {
             return Ok(false);
         }
     }
     Ok(true)
 }
 
+/// Checks an item against whichever bloom shape the SUMMARY carries.
+pub fn summary_might_contain(summary: &SummaryFrame, item_id: &str) -> Result<bool, String> {
+    match summary.bloom_hash_count {
+        Some(hash_count) => sized_bloom_might_contain(&summary.bloom_filter, hash_count, item_id),
+        None => bloom_might_contain(&summary.bloom_filter, item_id),
+    }
+}
+
+fn bloom_bit_index(item_bytes: &[u8], hash_idx: u8, bloom_bytes: usize) -> usize {
+    let mut hasher = Sha256::new();
+    hasher.update(item_bytes);
+    hasher.update([hash_idx]);
+    let digest = hasher.finalize();
+    let mut n = [0u8; 8];
+    n.copy_from_slice(&digest[..8]);
+    (u64::from_be_bytes(n) % (bloom_bytes as u64 * 8)) as usize
+}
+
 fn validate_hello(hello: &HelloFrame) -> Result<(), String> {
-    if hello.version != GOSSIP_VERSION {
-        return Err("HELLO version mismatch".to_string());
+    if hello.version == 0 {
+        return Err("HELLO version must be positive".to_string());
     }
+    hello_version_range(hello)?;
     if !is_valid_item_id(&hello.node_id) {
         return Err("HELLO node_id format invalid".to_string());
     }
     let pubkey = base64::engine::general_purpose::URL_SAFE_NO_PAD
         .decode(&hello.node_pubkey)
         .map_err(|err| format!("HELLO node_pubkey decode failed: {err}"))?;
+    if pubkey.len() != 32 {
+        return Err("HELLO node_pubkey must be 32 bytes".to_string());
+    }
     let derived = bytes_to_hex_lower(&Sha256::digest(pubkey));
     if derived != hello.node_id {
         return Err("HELLO node_id does not match SHA-256(node_pubkey)".to_string());
@@ -1361,12 +2166,33 @@ fn validate_hello(hello: &HelloFrame) -> Result<(), String> {
     if hello.max_transfer == 0 || hello.max_transfer > MAX_TRANSFER_ITEMS as u64 {
         return Err("HELLO max_transfer out of range".to_string());
     }
+    if hello.max_transfer_bytes.is_some_and(|max_transfer_bytes| {
+        max_transfer_bytes == 0 || max_transfer_bytes > MAX_TRANSFER_BYTES
+    }) {
+        return Err("HELLO max_transfer_bytes out of range".to_string());
+    }
+    if hello.max_frame_bytes.is_some_and(|max_frame_bytes| {
+        !(MIN_FRAME_BYTES as u64..=MAX_FRAME_BYTES as u64).contains(&max_frame_bytes)
+    }) {
+        return Err("HELLO max_frame_bytes out of range".to_string());
+    }
     Ok(())
 }
 
 fn validate_summary(summary: &SummaryFrame) -> Result<(), String> {
-    if summary.bloom_filter.len() != BLOOM_FILTER_BYTES {
-        return Err("SUMMARY bloom_filter length mismatch".to_string());
+    match summary.bloom_hash_count {
+        None if summary.bloom_filter.len() != BLOOM_FILTER_BYTES => {
+            return Err("SUMMARY bloom_filter length mismatch".to_string());
+        }
+        Some(hash_count) => {
+            if !(BLOOM_FILTER_BYTES..=MAX_SIZED_BLOOM_BYTES).contains(&summary.bloom_filter.len()) {
+                return Err("SUMMARY sized bloom_filter length out of range".to_string());
+            }
+            if hash_count == 0 || hash_count > MAX_BLOOM_HASH_COUNT {
+                return Err("SUMMARY bloom_hash_count out of range".to_string());
+            }
+        }
+        None => {}
     }
 
     if let Some(preview_item_ids) = &summary.preview_item_ids {
@@ -1398,10 +2224,24 @@ fn validate_summary(summary: &SummaryFrame) -> Result<(), String> {
     Ok(())
 }
 
+fn validate_summary_page(page: &SummaryPageFrame) -> Result<(), String> {
+    if page.item_ids.len() > MAX_SUMMARY_PREVIEW_ITEMS {
+        return Err("SUMMARY_PAGE item_ids exceeds MAX_SUMMARY_PREVIEW_ITEMS".to_string());
+    }
+    validate_sorted_unique_item_ids(&page.item_ids, "SUMMARY_PAGE.item_ids")?;
+    if let Some(next_cursor) = &page.next_cursor {
+        if page.item_ids.last() != Some(next_cursor) {
+            return Err("SUMMARY_PAGE next_cursor must equal last item_ids element".to_string());
+        }
+    }
+    Ok(())
+}
+
 fn build_summary_preview_item_ids(now_ms: u64) -> Result<Vec<String>, String> {
     let local_wayfarer_id = ensure_local_identity()
         .ok()
         .map(|identity| identity.wayfarer_id);
+    let mut local_signing_key_seed = None;
     let mut ranked = gossip_store_sqlite::summary_preview_candidates(now_ms)?
         .into_iter()
         .filter(|item| {
@@ -1409,7 +2249,9 @@ fn build_summary_preview_item_ids(now_ms: u64) -> Result<Vec<String>, String> {
                 return true;
             };
             decode_envelope_payload_b64(&item.envelope_b64)
-                .map(|decoded| decoded.to_wayfarer_id_hex != local_wayfarer_id)
+                .map(|decoded| {
+                    !is_local_recipient(&decoded, local_wayfarer_id, &mut local_signing_key_seed)
+                })
                 .unwrap_or(true)
         })
         .map(|item| {
@@ -1543,6 +2385,14 @@ fn frame_type_and_payload(frame: &GossipSyncFrame) -> Result<(&'static str, Valu
         GossipSyncFrame::Transfer(payload) => Ok(("TRANSFER", to_cbor_value(payload)?)),
         GossipSyncFrame::Receipt(payload) => Ok(("RECEIPT", to_cbor_value(payload)?)),
         GossipSyncFrame::RelayIngest(payload) => Ok(("RELAY_INGEST", to_cbor_value(payload)?)),
+        GossipSyncFrame::HelloChallenge(payload) => {
+            Ok(("HELLO_CHALLENGE", to_cbor_value(payload)?))
+        }
+        GossipSyncFrame::HelloProof(payload) => Ok(("HELLO_PROOF", to_cbor_value(payload)?)),
+        GossipSyncFrame::SummaryPageRequest(payload) => {
+            Ok(("SUMMARY_PAGE_REQUEST", to_cbor_value(payload)?))
+        }
+        GossipSyncFrame::SummaryPage(payload) => Ok(("SUMMARY_PAGE", to_cbor_value(payload)?)),
     }
 }
 
@@ -1581,85 +2431,89 @@ fn frame_from_envelope_value(envelope: Value) -> Result<GossipSyncFrame, String>
     })?;
 
     match frame_type.as_str() {
-        "HELLO" => decode_payload_frame(payload, GossipSyncFrame::Hello),
+        "HELLO" => decode_hello_payload(payload),
         "SUMMARY" => decode_payload_frame(payload, GossipSyncFrame::Summary),
         "REQUEST" => decode_payload_frame(payload, GossipSyncFrame::Request),
         "TRANSFER" => decode_payload_frame(payload, GossipSyncFrame::Transfer),
         "RECEIPT" => decode_payload_frame(payload, GossipSyncFrame::Receipt),
         "RELAY_INGEST" => decode_payload_frame(payload, GossipSyncFrame::RelayIngest),
+        "HELLO_CHALLENGE" => decode_payload_frame(payload, GossipSyncFrame::HelloChallenge),
+        "HELLO_PROOF" => decode_payload_frame(payload, GossipSyncFrame::HelloProof),
+        "SUMMARY_PAGE_REQUEST" => {
+            decode_payload_frame(payload, GossipSyncFrame::SummaryPageRequest)
+        }
+        "SUMMARY_PAGE" => decode_payload_frame(payload, GossipSyncFrame::SummaryPage),
         _ => Err(format!(
             "parse gossip frame cbor: unsupported frame type `{frame_type}`"
         )),
     }
 }
 
-fn decode_payload_frame<T, F>(payload: Value, wrap: F) -> Result<GossipSyncFrame, String>
+/// HELLO fields this build understands.
+const HELLO_FIELDS: [&str; 10] = [
+    "version",
+    "node_id",
+    "node_pubkey",
+    "capabilities",
+    "propagation_class",
+    "max_want",
+    "max_transfer",
+    "max_version",
+    "max_transfer_bytes",
+    "max_frame_bytes",
+];
+
+/// A peer that speaks a newer gossip version may add HELLO fields this build has never heard
+/// of, so those are dropped for it; from anyone else an unknown field is still rejected.
+fn decode_hello_payload(payload: Value) -> Result<GossipSyncFrame, String> {
+    let Value::Map(entries) = payload else {
+        return Err("payload decode failed: HELLO payload must be a map".to_string());
+    };
+    let (known, unknown): (Vec<_>, Vec<_>) = entries.into_iter().partition(
+        |(key, _)| matches!(key, Value::Text(field) if HELLO_FIELDS.contains(&field.as_str())),
+    );
+    let hello: HelloFrame = decode_payload(Value::Map(known))?;
+    if let Some((key, _)) = unknown.first() {
+        if hello.max_version.unwrap_or(hello.version) <= GOSSIP_MAX_VERSION {
+            let field = match key {
+                Value::Text(field) => field.clone(),
+                other => format!("{other:?}"),
+            };
+            return Err(format!("payload decode failed: unknown field `{field}`"));
+        }
+    }
+    Ok(GossipSyncFrame::Hello(hello))
+}
+
+fn decode_payload_frame<T, F>(payload: Value, wrap: F) -> Result<GossipSyncFrame, String>
 where
     T: for<'de> serde::Deserialize<'de>,
     F: FnOnce(T) -> GossipSyncFrame,
+{
+    Ok(wrap(decode_payload(payload)?))
+}
+
+fn decode_payload<T>(payload: Value) -> Result<T, String>
+where
+    T: for<'de> serde::Deserialize<'de>,
 {
     let mut raw = Vec::new();
     into_writer(&payload, &mut raw).map_err(|err| format!("payload re-encode failed: {err}"))?;
-    let payload_value: T =
-        from_reader(raw.as_slice()).map_err(|err| format!("payload decode failed: {err}"))?;
-    Ok(wrap(payload_value))
+    from_reader(raw.as_slice()).map_err(|err| format!("payload decode failed: {err}"))
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::aethos_core::app_body::{
+        decode_app_body, encode_app_body, AppBody, ReactionAction, ReactionBody,
+    };
     use crate::aethos_core::encounter_scheduler::EncounterTieBreakReason;
+    use crate::aethos_core::protocol::EnvelopeLifetime;
+    use crate::aethos_core::test_env::{test_env_lock, unique_test_state_dir, EnvVarGuard};
     use crate::aethos_core::vectors::load_envelope_vectors;
 
     use super::*;
     use base64::Engine;
-    use std::sync::atomic::{AtomicU64, Ordering};
-    use std::sync::{Mutex, OnceLock};
-
-    static TEST_ENV_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
-    static TEST_DIR_COUNTER: AtomicU64 = AtomicU64::new(0);
-
-    fn test_env_lock() -> &'static Mutex<()> {
-        TEST_ENV_LOCK.get_or_init(|| Mutex::new(()))
-    }
-
-    fn unique_test_state_dir(prefix: &str) -> std::path::PathBuf {
-        let nanos = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|duration| duration.as_nanos())
-            .unwrap_or(0);
-        let counter = TEST_DIR_COUNTER.fetch_add(1, Ordering::SeqCst);
-        std::env::temp_dir().join(format!("{prefix}-{nanos}-{counter}"))
-    }
-
-    struct EnvVarGuard {
-        key: &'static str,
-        previous: Option<String>,
-    }
-
-    impl EnvVarGuard {
-        fn set(key: &'static str, value: &std::path::Path) -> Self {
-            let previous = std::env::var(key).ok();
-            std::env::set_var(key, value);
-            Self { key, previous }
-        }
-
-        fn clear(key: &'static str) -> Self {
-            let previous = std::env::var(key).ok();
-            std::env::remove_var(key);
-            Self { key, previous }
-        }
-    }
-
-    impl Drop for EnvVarGuard {
-        fn drop(&mut self) {
-            if let Some(previous) = self.previous.as_ref() {
-                std::env::set_var(self.key, previous);
-            } else {
-                std::env::remove_var(self.key);
-            }
-        }
-    }
-
     fn item(fill: u8) -> String {
         format!("{:02x}", fill).repeat(32)
     }
@@ -1667,6 +2521,7 @@ mod tests {
     fn summary_with_bloom_for(items: &[String]) -> SummaryFrame {
         SummaryFrame {
             bloom_filter: build_bloom_filter(items).expect("build bloom"),
+            bloom_hash_count: None,
             item_count: items.len() as u64,
             preview_item_ids: None,
             preview_cursor: None,
@@ -1695,6 +2550,7 @@ mod tests {
         let summary = SummaryFrame {
             bloom_filter: build_bloom_filter(&[preview_a.clone(), preview_b.clone()])
                 .expect("bloom"),
+            bloom_hash_count: None,
             item_count: 2,
             preview_item_ids: Some(vec![preview_a.clone(), preview_b.clone()]),
             preview_cursor: Some(preview_b.clone()),
@@ -1717,6 +2573,7 @@ mod tests {
                 candidate.clone(),
             ])
             .expect("bloom"),
+            bloom_hash_count: None,
             item_count: 3,
             preview_item_ids: Some(vec![preview_b.clone(), preview_a.clone()]),
             preview_cursor: Some(preview_a.clone()),
@@ -1738,6 +2595,185 @@ mod tests {
         assert_eq!(first, second);
     }
 
+    fn numbered_item(index: u32) -> String {
+        bytes_to_hex_lower(&Sha256::digest(index.to_be_bytes()))
+    }
+
+    #[test]
+    fn sized_bloom_keeps_false_positives_low_for_large_inventories() {
+        let held = (0..5_000).map(numbered_item).collect::<Vec<_>>();
+        let (bloom_bytes, hash_count) = sized_bloom_parameters(held.len());
+        assert!(bloom_bytes > BLOOM_FILTER_BYTES);
+        let summary = SummaryFrame {
+            bloom_filter: build_sized_bloom_filter(&held, bloom_bytes, hash_count)
+                .expect("sized bloom"),
+            bloom_hash_count: Some(hash_count),
+            item_count: held.len() as u64,
+            preview_item_ids: None,
+            preview_cursor: None,
+        };
+        validate_frame(&GossipSyncFrame::Summary(summary.clone())).expect("valid summary");
+        assert!(held
+            .iter()
+            .all(|item_id| summary_might_contain(&summary, item_id).expect("lookup")));
+
+        let false_positives = (5_000..10_000)
+            .map(numbered_item)
+            .filter(|item_id| summary_might_contain(&summary, item_id).expect("lookup"))
+            .count();
+        assert!(false_positives < 150, "false positives: {false_positives}");
+        let v1_bloom = build_bloom_filter(&held).expect("v1 bloom");
+        let v1_false_positives = (5_000..10_000)
+            .map(numbered_item)
+            .filter(|item_id| bloom_might_contain(&v1_bloom, item_id).expect("lookup"))
+            .take(150)
+            .count();
+        assert_eq!(v1_false_positives, 150);
+    }
+
+    #[test]
+    fn sized_bloom_parameters_stay_within_frame_bounds() {
+        assert_eq!(sized_bloom_parameters(0).0, BLOOM_FILTER_BYTES);
+        assert_eq!(sized_bloom_parameters(0).1, MAX_BLOOM_HASH_COUNT);
+        let (bloom_bytes, hash_count) = sized_bloom_parameters(10_000_000);
+        assert_eq!(bloom_bytes, MAX_SIZED_BLOOM_BYTES);
+        assert_eq!(hash_count, 1);
+    }
+
+    #[test]
+    fn summary_validation_checks_bloom_shape_against_hash_count() {
+        let mut summary = summary_with_bloom_for(&[item(0x01)]);
+        summary.bloom_filter = vec![0u8; BLOOM_FILTER_BYTES * 2];
+        assert!(validate_frame(&GossipSyncFrame::Summary(summary.clone())).is_err());
+        summary.bloom_hash_count = Some(7);
+        validate_frame(&GossipSyncFrame::Summary(summary.clone())).expect("sized summary");
+        summary.bloom_hash_count = Some(MAX_BLOOM_HASH_COUNT + 1);
+        assert!(validate_frame(&GossipSyncFrame::Summary(summary.clone())).is_err());
+        summary.bloom_hash_count = Some(7);
+        summary.bloom_filter = vec![0u8; MAX_SIZED_BLOOM_BYTES + 1];
+        assert!(validate_frame(&GossipSyncFrame::Summary(summary)).is_err());
+    }
+
+    #[test]
+    fn v1_summary_encoding_omits_bloom_hash_count() {
+        let frame = GossipSyncFrame::Summary(summary_with_bloom_for(&[item(0x01)]));
+        let raw = serialize_frame(&frame).expect("serialize");
+        assert!(!raw
+            .windows(b"bloom_hash_count".len())
+            .any(|window| window == b"bloom_hash_count"));
+        match parse_frame(&raw).expect("parse") {
+            GossipSyncFrame::Summary(summary) => assert_eq!(summary.bloom_hash_count, None),
+            other => panic!("expected SUMMARY, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn summary_pages_walk_the_whole_inventory_from_a_cursor() {
+        let _lock = test_env_lock().lock().expect("lock test env");
+        let temp_dir = unique_test_state_dir("aethos-gossip-summary-pages");
+        let _state_guard = EnvVarGuard::set("XDG_STATE_HOME", &temp_dir.join("state"));
+        let _data_guard = EnvVarGuard::set("XDG_DATA_HOME", &temp_dir.join("data"));
+        let _aethos_state_dir_guard = EnvVarGuard::clear("AETHOS_STATE_DIR");
+
+        let local = ensure_local_identity().expect("local identity");
+        let now_ms = now_unix_ms();
+        let payload_for = |to_wayfarer_id: &str| {
+            crate::aethos_core::protocol::build_envelope_payload_b64_from_utf8(
+                to_wayfarer_id,
+                "page me",
+                &[9u8; 32],
+            )
+            .expect("payload")
+        };
+        let for_other = payload_for(&"bb".repeat(32));
+        let for_local = payload_for(&local.wayfarer_id);
+        let mut held = Vec::new();
+        let mut records = Vec::new();
+        for index in 0..72 {
+            let item_id = numbered_item(index);
+            let envelope_b64 = if index % 16 == 0 {
+                for_local.clone()
+            } else {
+                held.push(item_id.clone());
+                for_other.clone()
+            };
+            records.push(ImportWriteObject {
+                item_id,
+                envelope_b64,
+                expiry_unix_ms: now_ms + 60_000,
+                hop_count: 0,
+                recorded_at_unix_ms: now_ms,
+            });
+        }
+        gossip_store_sqlite::insert_import_items(&records, now_ms).expect("record items");
+
+        let mut profile = GossipSessionProfile::baseline();
+        profile.features.insert(GossipCapability::SummaryPaging);
+        let page_after =
+            |after: Option<&str>| match build_summary_page_frame(now_ms, after, &profile)
+                .expect("page")
+            {
+                GossipSyncFrame::SummaryPage(page) => page,
+                other => panic!("expected SUMMARY_PAGE, got {other:?}"),
+            };
+        let mut seen = Vec::new();
+        let mut cursor: Option<String> = None;
+        let mut pages = 0;
+        loop {
+            let page = page_after(cursor.as_deref());
+            pages += 1;
+            seen.extend(page.item_ids.iter().cloned());
+            match page.next_cursor {
+                Some(next_cursor) => cursor = Some(next_cursor),
+                None => break,
+            }
+        }
+        assert_eq!(pages, 2);
+        held.sort_by_key(|item_id| decode_item_id(item_id).expect("item id"));
+        assert_eq!(seen, held);
+
+        let last = page_after(held.last().map(String::as_str));
+        assert!(last.item_ids.is_empty());
+        assert_eq!(last.next_cursor, None);
+    }
+
+    #[test]
+    fn summary_page_frames_round_trip_and_reject_bad_cursors() {
+        let mut profile = GossipSessionProfile::baseline();
+        assert!(build_summary_page_request_frame(None, &profile).is_err());
+        profile.features.insert(GossipCapability::SummaryPaging);
+        let request =
+            build_summary_page_request_frame(Some(&item(0x10)), &profile).expect("request");
+        match parse_frame(&serialize_frame(&request).expect("serialize")).expect("parse") {
+            GossipSyncFrame::SummaryPageRequest(frame) => {
+                assert_eq!(frame.after, Some(item(0x10)))
+            }
+            other => panic!("expected SUMMARY_PAGE_REQUEST, got {other:?}"),
+        }
+        assert!(build_summary_page_request_frame(Some("not-an-item-id"), &profile).is_err());
+
+        let page = SummaryPageFrame {
+            item_ids: vec![item(0x01), item(0x02)],
+            next_cursor: Some(item(0x01)),
+        };
+        assert!(validate_frame(&GossipSyncFrame::SummaryPage(page)).is_err());
+        let page = SummaryPageFrame {
+            item_ids: vec![item(0x02), item(0x01)],
+            next_cursor: None,
+        };
+        assert!(validate_frame(&GossipSyncFrame::SummaryPage(page)).is_err());
+    }
+
+    #[test]
+    fn summary_reports_unpreviewed_items_only_past_the_preview() {
+        let mut summary = summary_with_bloom_for(&[item(0x01), item(0x02)]);
+        summary.item_count = 2;
+        summary.preview_item_ids = Some(vec![item(0x01), item(0x02)]);
+        assert!(!summary_has_unpreviewed_items(&summary));
+        summary.item_count = 3;
+        assert!(summary_has_unpreviewed_items(&summary));
+    }
+
     #[test]
     fn parse_frame_accepts_unknown_top_level_envelope_key() {
         let pubkey = [0x11u8; 32];
@@ -1763,10 +2799,366 @@ mod tests {
         ));
     }
 
+    fn hello_for_seed(seed: [u8; 32]) -> HelloFrame {
+        let pubkey = ed25519_dalek::SigningKey::from_bytes(&seed)
+            .verifying_key()
+            .to_bytes();
+        let node_id = bytes_to_hex_lower(&Sha256::digest(pubkey));
+        match build_hello_frame(
+            &node_id,
+            &base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(pubkey),
+        )
+        .expect("hello")
+        {
+            GossipSyncFrame::Hello(hello) => hello,
+            other => panic!("expected HELLO, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn session_profile_keeps_only_shared_capabilities_and_tighter_limits() {
+        let local = hello_for_seed([1u8; 32]);
+        let mut peer = hello_for_seed([2u8; 32]);
+        peer.capabilities
+            .retain(|capability| capability != SIZED_BLOOM_CAPABILITY);
+        peer.capabilities.push("future_feature_v9".to_string());
+        peer.max_want = 64;
+        validate_frame(&GossipSyncFrame::Hello(peer.clone())).expect("unknown capability is fine");
+
+        let profile = negotiate_session_profile(&local, &peer).expect("negotiate");
+        assert_eq!(profile.version, GOSSIP_VERSION);
+        assert!(profile.supports(GossipCapability::SummaryPaging));
+        assert!(!profile.supports(GossipCapability::SizedBloom));
+        assert!(!profile.supports(GossipCapability::LanTcpSession));
+        assert_eq!(profile.max_want, 64);
+        assert_eq!(profile.max_transfer, MAX_TRANSFER_ITEMS);
+        assert_eq!(profile.max_transfer_bytes, MAX_TRANSFER_BYTES);
+        assert_eq!(profile.max_frame_bytes, MAX_FRAME_BYTES);
+        assert_eq!(
+            negotiate_session_profile(&peer, &local).expect("symmetric"),
+            profile
+        );
+    }
+
+    #[test]
+    fn session_profile_takes_the_tighter_advertised_byte_limits() {
+        let local = hello_for_seed([1u8; 32]);
+        let mut peer = hello_for_seed([2u8; 32]);
+        peer.max_transfer_bytes = Some(100_000);
+        let profile = negotiate_session_profile(&local, &peer).expect("negotiate");
+        assert_eq!(profile.max_transfer_bytes, 100_000);
+        assert_eq!(profile.max_frame_bytes, MAX_FRAME_BYTES);
+
+        peer.max_transfer_bytes = None;
+        peer.max_frame_bytes = Some(128 * 1024);
+        let (a, b) = negotiate_over_channel(&local, &peer);
+        let (a, b) = (a.expect("local end"), b.expect("peer end"));
+        assert_eq!(a, b);
+        assert_eq!(a.max_frame_bytes, 128 * 1024);
+        assert_eq!(a.max_transfer_bytes, 64 * 1024);
+
+        peer.max_frame_bytes = Some(MIN_FRAME_BYTES as u64 - 1);
+        assert!(validate_frame(&GossipSyncFrame::Hello(peer.clone())).is_err());
+        peer.max_frame_bytes = None;
+        peer.max_transfer_bytes = Some(MAX_TRANSFER_BYTES + 1);
+        assert!(validate_frame(&GossipSyncFrame::Hello(peer)).is_err());
+    }
+
+    #[test]
+    fn session_profile_picks_highest_shared_version() {
+        let mut local = hello_for_seed([1u8; 32]);
+        local.max_version = Some(2);
+        let mut peer = hello_for_seed([2u8; 32]);
+        peer.max_version = Some(3);
+        assert_eq!(
+            negotiate_session_profile(&local, &peer)
+                .expect("overlap")
+                .version,
+            2
+        );
+
+        peer.version = 3;
+        let err = negotiate_session_profile(&local, &peer).expect_err("no overlap");
+        assert!(err.contains("do not overlap"), "{err}");
+
+        peer.max_version = Some(2);
+        assert!(validate_frame(&GossipSyncFrame::Hello(peer)).is_err());
+    }
+
+    #[test]
+    fn hello_advertises_local_version_range_and_limits() {
+        let hello = hello_for_seed([3u8; 32]);
+        assert_eq!(hello.version, GOSSIP_MIN_VERSION);
+        assert_eq!(hello.max_version, Some(GOSSIP_MAX_VERSION));
+        assert_eq!(hello.max_transfer_bytes, Some(MAX_TRANSFER_BYTES));
+        assert_eq!(hello.max_frame_bytes, Some(MAX_FRAME_BYTES as u64));
+        assert_eq!(
+            hello_version_range(&hello),
+            Ok((GOSSIP_MIN_VERSION, GOSSIP_MAX_VERSION))
+        );
+    }
+
+    fn hello_frame_with_extra_field(hello: HelloFrame) -> Vec<u8> {
+        let raw = serialize_frame(&GossipSyncFrame::Hello(hello)).expect("serialize");
+        let mut value = decode_cbor_value_exact(&raw, "frame").expect("decode");
+        let Value::Map(entries) = &mut value else {
+            panic!("expected envelope map")
+        };
+        let Some((_, Value::Map(fields))) = entries
+            .iter_mut()
+            .find(|(key, _)| *key == Value::Text("payload".to_string()))
+        else {
+            panic!("expected HELLO payload map")
+        };
+        fields.push((
+            Value::Text("x_future_field".to_string()),
+            Value::Integer(7.into()),
+        ));
+        encode_cbor_value_deterministic(&value).expect("encode")
+    }
+
+    #[test]
+    fn hello_unknown_fields_are_tolerated_only_from_newer_peers() {
+        let current = hello_for_seed([3u8; 32]);
+        assert!(parse_frame(&hello_frame_with_extra_field(current.clone()))
+            .expect_err("same-version HELLO must not carry unknown fields")
+            .contains("unknown field `x_future_field`"));
+
+        let mut newer = current;
+        newer.max_version = Some(GOSSIP_MAX_VERSION + 1);
+        let GossipSyncFrame::Hello(parsed) =
+            parse_frame(&hello_frame_with_extra_field(newer)).expect("newer peer HELLO")
+        else {
+            panic!("expected HELLO")
+        };
+        assert_eq!(parsed.max_version, Some(GOSSIP_MAX_VERSION + 1));
+    }
+
+    type FrameChannel = (
+        std::sync::mpsc::Sender<Vec<u8>>,
+        std::sync::mpsc::Receiver<Vec<u8>>,
+    );
+
+    fn send_over_channel(
+        channel: &mut FrameChannel,
+        frame: &GossipSyncFrame,
+    ) -> Result<(), String> {
+        channel
+            .0
+            .send(serialize_frame(frame)?)
+            .map_err(|err| err.to_string())
+    }
+
+    fn recv_over_channel(channel: &mut FrameChannel) -> Result<GossipSyncFrame, String> {
+        parse_frame(&channel.1.recv().map_err(|err| err.to_string())?)
+    }
+
+    /// Sends each HELLO across a channel and negotiates on both ends from what arrived.
+    fn negotiate_over_channel(
+        hello_a: &HelloFrame,
+        hello_b: &HelloFrame,
+    ) -> (
+        Result<GossipSessionProfile, String>,
+        Result<GossipSessionProfile, String>,
+    ) {
+        let (a_tx, b_rx) = std::sync::mpsc::channel();
+        let (b_tx, a_rx) = std::sync::mpsc::channel();
+        let mut side_a: FrameChannel = (a_tx, a_rx);
+        let mut side_b: FrameChannel = (b_tx, b_rx);
+        send_over_channel(&mut side_a, &GossipSyncFrame::Hello(hello_a.clone())).expect("send a");
+        send_over_channel(&mut side_b, &GossipSyncFrame::Hello(hello_b.clone())).expect("send b");
+        let negotiate =
+            |channel: &mut FrameChannel, local: &HelloFrame| match recv_over_channel(channel)? {
+                GossipSyncFrame::Hello(peer) => negotiate_session_profile(local, &peer),
+                other => Err(format!("expected HELLO, got {other:?}")),
+            };
+        (
+            negotiate(&mut side_a, hello_a),
+            negotiate(&mut side_b, hello_b),
+        )
+    }
+
+    #[test]
+    fn differing_version_ranges_negotiate_the_same_version_on_both_ends() {
+        let mut older = hello_for_seed([1u8; 32]);
+        let mut newer = hello_for_seed([2u8; 32]);
+        newer.max_version = Some(3);
+        let (a, b) = negotiate_over_channel(&older, &newer);
+        assert_eq!(a.expect("older end").version, 1);
+        assert_eq!(b.expect("newer end").version, 1);
+
+        older.max_version = Some(2);
+        let (a, b) = negotiate_over_channel(&older, &newer);
+        let (a, b) = (a.expect("older end"), b.expect("newer end"));
+        assert_eq!(a.version, 2);
+        assert_eq!(a, b);
+
+        newer.version = 3;
+        let (a, b) = negotiate_over_channel(&older, &newer);
+        assert!(a.expect_err("older end").contains("do not overlap"));
+        assert!(b.expect_err("newer end").contains("do not overlap"));
+    }
+
+    #[test]
+    fn hello_auth_exchange_authenticates_both_peers() {
+        let (to_b, from_a) = std::sync::mpsc::channel();
+        let (to_a, from_b) = std::sync::mpsc::channel();
+        let (hello_a, hello_b) = (hello_for_seed([1u8; 32]), hello_for_seed([2u8; 32]));
+
+        let peer_b = std::thread::spawn({
+            let (hello_a, hello_b) = (hello_a.clone(), hello_b.clone());
+            move || {
+                complete_hello_auth(
+                    &mut (to_a, from_a),
+                    &hello_b,
+                    &hello_a,
+                    &[2u8; 32],
+                    send_over_channel,
+                    recv_over_channel,
+                )
+            }
+        });
+        let a_verified_b = complete_hello_auth(
+            &mut (to_b, from_b),
+            &hello_a,
+            &hello_b,
+            &[1u8; 32],
+            send_over_channel,
+            recv_over_channel,
+        )
+        .expect("a authenticates b");
+        let b_verified_a = peer_b.join().expect("join").expect("b authenticates a");
+        assert!(a_verified_b && b_verified_a);
+    }
+
+    fn hello_challenge() -> ([u8; HELLO_CHALLENGE_NONCE_BYTES], HelloChallengeFrame) {
+        let (nonce, challenge) = build_hello_challenge_frame();
+        let GossipSyncFrame::HelloChallenge(challenge) = challenge else {
+            panic!("expected HELLO_CHALLENGE")
+        };
+        (nonce, challenge)
+    }
+
+    fn hello_proof(
+        signing_seed: [u8; 32],
+        prover: &HelloFrame,
+        verifier: &HelloFrame,
+        prover_nonce: &[u8; HELLO_CHALLENGE_NONCE_BYTES],
+        challenge: &HelloChallengeFrame,
+    ) -> HelloProofFrame {
+        match build_hello_proof_frame(&signing_seed, prover, verifier, prover_nonce, challenge)
+            .expect("proof")
+        {
+            GossipSyncFrame::HelloProof(proof) => proof,
+            other => panic!("expected HELLO_PROOF, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn hello_proof_from_another_key_is_rejected() {
+        let (victim, verifier) = (hello_for_seed([1u8; 32]), hello_for_seed([2u8; 32]));
+        let (verifier_nonce, challenge) = hello_challenge();
+        let (prover_nonce, prover_challenge) = hello_challenge();
+
+        let forged = hello_proof([9u8; 32], &victim, &verifier, &prover_nonce, &challenge);
+        assert_eq!(
+            verify_hello_proof(
+                &verifier,
+                &victim,
+                &verifier_nonce,
+                &prover_challenge,
+                &forged
+            )
+            .unwrap_err(),
+            "HELLO_PROOF signature is invalid"
+        );
+
+        let genuine = hello_proof([1u8; 32], &victim, &verifier, &prover_nonce, &challenge);
+        assert!(verify_hello_proof(
+            &verifier,
+            &victim,
+            &verifier_nonce,
+            &prover_challenge,
+            &genuine
+        )
+        .is_ok());
+        assert!(
+            verify_hello_proof(&verifier, &victim, &[0u8; 32], &prover_challenge, &genuine)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn hello_proof_made_for_another_challenger_is_rejected() {
+        // A relaying attacker forwards the victim's challenge to an honest prover and replays
+        // the proof; it must not verify for anyone but the node the prover was talking to.
+        let prover = hello_for_seed([1u8; 32]);
+        let (attacker, victim) = (hello_for_seed([3u8; 32]), hello_for_seed([2u8; 32]));
+        let (victim_nonce, victim_challenge) = hello_challenge();
+        let (prover_nonce, prover_challenge) = hello_challenge();
+
+        let relayed = hello_proof(
+            [1u8; 32],
+            &prover,
+            &attacker,
+            &prover_nonce,
+            &victim_challenge,
+        );
+        assert_eq!(
+            verify_hello_proof(&victim, &prover, &victim_nonce, &prover_challenge, &relayed)
+                .unwrap_err(),
+            "HELLO_PROOF signature is invalid"
+        );
+
+        // Same pair of nodes, but the proof answered a different session's prover nonce.
+        let (_, other_session_challenge) = hello_challenge();
+        let genuine = hello_proof(
+            [1u8; 32],
+            &prover,
+            &victim,
+            &prover_nonce,
+            &victim_challenge,
+        );
+        assert!(verify_hello_proof(
+            &victim,
+            &prover,
+            &victim_nonce,
+            &other_session_challenge,
+            &genuine
+        )
+        .is_err());
+        assert!(
+            verify_hello_proof(&victim, &prover, &victim_nonce, &prover_challenge, &genuine)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn hello_auth_is_skipped_for_peers_without_capability() {
+        let mut legacy = hello_for_seed([1u8; 32]);
+        legacy
+            .capabilities
+            .retain(|capability| capability != HELLO_AUTH_CAPABILITY);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let mut channel = (sender, receiver);
+        let authenticated = complete_hello_auth(
+            &mut channel,
+            &hello_for_seed([2u8; 32]),
+            &legacy,
+            &[2u8; 32],
+            send_over_channel,
+            recv_over_channel,
+        )
+        .expect("skip auth");
+        assert!(!authenticated);
+        assert!(channel.1.try_recv().is_err());
+    }
+
     #[test]
     fn parse_frame_rejects_summary_unknown_payload_key() {
         let summary = GossipSyncFrame::Summary(SummaryFrame {
             bloom_filter: vec![0u8; BLOOM_FILTER_BYTES],
+            bloom_hash_count: None,
             item_count: 0,
             preview_item_ids: None,
             preview_cursor: None,
@@ -1903,6 +3295,547 @@ mod tests {
         }
     }
 
+    #[test]
+    fn import_opens_sealed_envelope_addressed_to_local_identity() {
+        let _lock = test_env_lock().lock().expect("lock test env");
+        let temp_dir = unique_test_state_dir("aethos-gossip-import-sealed");
+        let _state_guard = EnvVarGuard::set("XDG_STATE_HOME", &temp_dir.join("state"));
+        let _data_guard = EnvVarGuard::set("XDG_DATA_HOME", &temp_dir.join("data"));
+        let _aethos_state_dir_guard = EnvVarGuard::clear("AETHOS_STATE_DIR");
+
+        let local = ensure_local_identity().expect("local identity");
+        let local_seed = load_local_signing_key_seed().expect("local seed");
+        let local_pubkey = ed25519_dalek::SigningKey::from_bytes(&local_seed)
+            .verifying_key()
+            .to_bytes();
+        let chat_body = encode_cbor_value_deterministic(&Value::Map(vec![
+            (
+                Value::Text("type".to_string()),
+                Value::Text("wayfarer.chat.v1".to_string()),
+            ),
+            (
+                Value::Text("text".to_string()),
+                Value::Text("sealed hello".to_string()),
+            ),
+            (
+                Value::Text("created_at_unix_ms".to_string()),
+                Value::Integer(1_735_689_600_000u64.into()),
+            ),
+        ]))
+        .expect("encode chat body");
+        let now_ms = now_unix_ms();
+        let payload = crate::aethos_core::protocol::build_envelope_v2_payload_b64(
+            &local_pubkey,
+            &chat_body,
+            &[9u8; 32],
+            EnvelopeLifetime::from_ttl(now_ms, 600_000),
+        )
+        .expect("sealed payload");
+        let item_id = super::item_id_from_envelope_bytes(
+            &base64::engine::general_purpose::URL_SAFE_NO_PAD
+                .decode(&payload)
+                .expect("decode sealed payload"),
+        );
+
+        let imported = import_transfer_items(
+            &local.wayfarer_id,
+            None,
+            None,
+            &[TransferObject {
+                item_id: item_id.clone(),
+                envelope_b64: payload,
+                expiry_unix_ms: now_ms + 60_000,
+                hop_count: 1,
+            }],
+            now_ms,
+        )
+        .expect("import sealed transfer");
+
+        assert_eq!(imported.accepted_item_ids, vec![item_id]);
+        assert_eq!(imported.new_messages.len(), 1);
+        assert_eq!(imported.new_messages[0].text, "sealed hello");
+        assert_eq!(imported.new_messages[0].body_bytes, chat_body);
+    }
+
+    #[test]
+    fn import_opens_envelopes_for_rotated_identity_during_grace_window() {
+        let _lock = test_env_lock().lock().expect("lock test env");
+        let temp_dir = unique_test_state_dir("aethos-gossip-import-retired");
+        let _state_guard = EnvVarGuard::set("XDG_STATE_HOME", &temp_dir.join("state"));
+        let _data_guard = EnvVarGuard::set("XDG_DATA_HOME", &temp_dir.join("data"));
+        let _aethos_state_dir_guard = EnvVarGuard::clear("AETHOS_STATE_DIR");
+
+        let previous = ensure_local_identity().expect("local identity");
+        let previous_pubkey = ed25519_dalek::SigningKey::from_bytes(
+            &load_local_signing_key_seed().expect("local seed"),
+        )
+        .verifying_key()
+        .to_bytes();
+        let now_ms = now_unix_ms();
+        let rotation = crate::aethos_core::identity_store::rotate_local_identity(now_ms)
+            .expect("rotate identity");
+        assert_eq!(rotation.previous_wayfarer_id, previous.wayfarer_id);
+
+        let chat_body = encode_app_body(&AppBody::Chat(crate::aethos_core::app_body::ChatBody {
+            text: "sent to the old key".to_string(),
+            created_at_unix_ms: now_ms,
+        }))
+        .expect("encode chat body");
+        let payload = crate::aethos_core::protocol::build_envelope_v2_payload_b64(
+            &previous_pubkey,
+            &chat_body,
+            &[9u8; 32],
+            EnvelopeLifetime::from_ttl(now_ms, 600_000),
+        )
+        .expect("sealed payload");
+        let item_id = super::item_id_from_envelope_bytes(
+            &base64::engine::general_purpose::URL_SAFE_NO_PAD
+                .decode(&payload)
+                .expect("decode sealed payload"),
+        );
+
+        let imported = import_transfer_items(
+            &rotation.identity.wayfarer_id,
+            None,
+            None,
+            &[TransferObject {
+                item_id,
+                envelope_b64: payload,
+                expiry_unix_ms: now_ms + 60_000,
+                hop_count: 1,
+            }],
+            now_ms,
+        )
+        .expect("import sealed transfer");
+
+        assert_eq!(imported.new_messages.len(), 1);
+        assert_eq!(imported.new_messages[0].text, "sent to the old key");
+        assert_eq!(
+            imported.new_messages[0].recipient_wayfarer_id.as_deref(),
+            Some(previous.wayfarer_id.as_str())
+        );
+        assert!(retired_identity_keys(
+            now_ms + crate::aethos_core::succession::SUCCESSION_GRACE_WINDOW_MS
+        )
+        .expect("retired keys")
+        .is_empty());
+    }
+
+    #[test]
+    fn rotating_a_passphrase_identity_keeps_retired_keys_wrapped() {
+        use crate::aethos_core::identity_store::{
+            identity_passphrase_enabled, lock_local_identity, remove_identity_passphrase,
+            rotate_local_identity, set_identity_passphrase, unlock_local_identity,
+        };
+
+        let _lock = test_env_lock().lock().expect("lock test env");
+        let temp_dir = unique_test_state_dir("aethos-gossip-rotate-passphrase");
+        let _state_guard = EnvVarGuard::set("XDG_STATE_HOME", &temp_dir.join("state"));
+        let _data_guard = EnvVarGuard::set("XDG_DATA_HOME", &temp_dir.join("data"));
+        let _aethos_state_dir_guard = EnvVarGuard::clear("AETHOS_STATE_DIR");
+
+        let now_ms = now_unix_ms();
+        ensure_local_identity().expect("local identity");
+        set_identity_passphrase("correct horse battery").expect("set passphrase");
+        let rotation = rotate_local_identity(now_ms).expect("rotate while unlocked");
+        assert!(identity_passphrase_enabled().expect("passphrase state"));
+
+        let retired_file = std::fs::read_to_string(
+            temp_dir
+                .join("data")
+                .join("aethos-linux")
+                .join("retired-identities.json"),
+        )
+        .expect("read retired identities");
+        let previous_seed_b64 =
+            base64::engine::general_purpose::STANDARD.encode(rotation.previous_signing_seed);
+        assert!(!retired_file.contains(&previous_seed_b64));
+        assert_eq!(
+            retired_identity_keys(now_ms).expect("retired keys while unlocked"),
+            vec![(
+                rotation.previous_wayfarer_id.clone(),
+                rotation.previous_signing_seed
+            )]
+        );
+
+        lock_local_identity();
+        assert!(retired_identity_keys(now_ms).is_err());
+        assert!(rotate_local_identity(now_ms).is_err());
+        assert_eq!(
+            unlock_local_identity("correct horse battery")
+                .expect("unlock rotated identity")
+                .wayfarer_id,
+            rotation.identity.wayfarer_id
+        );
+        assert_eq!(
+            retired_identity_keys(now_ms).expect("retired keys").len(),
+            1
+        );
+
+        remove_identity_passphrase("correct horse battery").expect("remove passphrase");
+        lock_local_identity();
+        assert_eq!(
+            retired_identity_keys(now_ms).expect("plaintext retired keys"),
+            vec![(
+                rotation.previous_wayfarer_id,
+                rotation.previous_signing_seed
+            )]
+        );
+    }
+
+    #[test]
+    fn revocation_item_is_retained_and_blocks_new_envelopes_from_revoked_key() {
+        let _lock = test_env_lock().lock().expect("lock test env");
+        let temp_dir = unique_test_state_dir("aethos-gossip-import-revocation");
+        let _state_guard = EnvVarGuard::set("XDG_STATE_HOME", &temp_dir.join("state"));
+        let _data_guard = EnvVarGuard::set("XDG_DATA_HOME", &temp_dir.join("data"));
+        let _aethos_state_dir_guard = EnvVarGuard::clear("AETHOS_STATE_DIR");
+
+        let now_ms = now_unix_ms();
+        let local = ensure_local_identity().expect("local identity");
+        let revoked_seed = [21u8; 32];
+        let revoked_wayfarer_id = crate::aethos_core::identity_store::wayfarer_id_from_pubkey(
+            &ed25519_dalek::SigningKey::from_bytes(&revoked_seed)
+                .verifying_key()
+                .to_bytes(),
+        );
+        let transfer_object = |payload: String| TransferObject {
+            item_id: super::item_id_from_envelope_bytes(
+                &base64::engine::general_purpose::URL_SAFE_NO_PAD
+                    .decode(&payload)
+                    .expect("decode payload"),
+            ),
+            envelope_b64: payload,
+            expiry_unix_ms: now_ms + 60_000,
+            hop_count: 1,
+        };
+        let chat_from_revoked_key = |text: &str| {
+            let body = encode_app_body(&AppBody::Chat(crate::aethos_core::app_body::ChatBody {
+                text: text.to_string(),
+                created_at_unix_ms: now_ms,
+            }))
+            .expect("encode chat body");
+            transfer_object(
+                crate::aethos_core::protocol::build_envelope_payload_b64(
+                    &local.wayfarer_id,
+                    &body,
+                    &revoked_seed,
+                )
+                .expect("chat payload"),
+            )
+        };
+
+        let before = import_transfer_items(
+            &local.wayfarer_id,
+            None,
+            None,
+            &[chat_from_revoked_key("before")],
+            now_ms,
+        )
+        .expect("import before revocation");
+        assert_eq!(before.new_messages.len(), 1);
+
+        let certificate =
+            crate::aethos_core::revocation::issue_revocation_certificate(&revoked_seed, now_ms)
+                .expect("issue certificate");
+        let revocation_body = encode_app_body(&AppBody::Revocation(
+            crate::aethos_core::app_body::RevocationBody { certificate },
+        ))
+        .expect("encode revocation body");
+        // Published by a different key, as a user would from a replacement device.
+        let revocation_item = transfer_object(
+            crate::aethos_core::protocol::build_envelope_payload_b64(
+                &revoked_wayfarer_id,
+                &revocation_body,
+                &[22u8; 32],
+            )
+            .expect("revocation payload"),
+        );
+        let after = import_transfer_items(
+            &local.wayfarer_id,
+            None,
+            None,
+            &[revocation_item.clone(), chat_from_revoked_key("after")],
+            now_ms,
+        )
+        .expect("import revocation");
+
+        assert_eq!(
+            after.accepted_item_ids,
+            vec![revocation_item.item_id.clone()]
+        );
+        assert_eq!(after.rejected_items.len(), 1);
+        assert_eq!(after.rejected_items[0].code, "REVOKED_AUTHOR");
+        assert!(after.new_messages.is_empty());
+        let stored = gossip_store_sqlite::get_existing_items_for_ids(std::slice::from_ref(
+            &revocation_item.item_id,
+        ))
+        .expect("stored revocation");
+        assert!(
+            stored[&revocation_item.item_id].expiry_unix_ms >= now_ms + REVOCATION_RETENTION_MS
+        );
+        assert!(load_revoked_identities()
+            .expect("revoked identities")
+            .contains_key(&revoked_wayfarer_id));
+
+        std::fs::write(
+            temp_dir
+                .join("data")
+                .join("aethos-linux")
+                .join("revoked-identities.json"),
+            "{not json",
+        )
+        .expect("corrupt revocation list");
+        assert!(import_transfer_items(
+            &local.wayfarer_id,
+            None,
+            None,
+            &[chat_from_revoked_key("while unreadable")],
+            now_ms,
+        )
+        .unwrap_err()
+        .starts_with("revocation list unavailable"));
+    }
+
+    #[test]
+    fn import_round_trips_reaction_body_without_chat_preview() {
+        let _lock = test_env_lock().lock().expect("lock test env");
+        let temp_dir = unique_test_state_dir("aethos-gossip-import-reaction");
+        let _state_guard = EnvVarGuard::set("XDG_STATE_HOME", &temp_dir.join("state"));
+        let _data_guard = EnvVarGuard::set("XDG_DATA_HOME", &temp_dir.join("data"));
+        let _aethos_state_dir_guard = EnvVarGuard::clear("AETHOS_STATE_DIR");
+
+        let local = ensure_local_identity().expect("local identity");
+        let reaction = ReactionBody {
+            target_manifest_id_hex: "ef".repeat(32),
+            emoji: "\u{1f44d}".to_string(),
+            action: ReactionAction::Add,
+            created_at_unix_ms: 1_735_689_600_000,
+        };
+        let reaction_body =
+            encode_app_body(&AppBody::Reaction(reaction.clone())).expect("encode reaction");
+        let payload = crate::aethos_core::protocol::build_envelope_payload_b64(
+            &local.wayfarer_id,
+            &reaction_body,
+            &[5u8; 32],
+        )
+        .expect("reaction payload");
+        let item_id = super::item_id_from_envelope_bytes(
+            &base64::engine::general_purpose::URL_SAFE_NO_PAD
+                .decode(&payload)
+                .expect("decode reaction payload"),
+        );
+
+        let now_ms = now_unix_ms();
+        let imported = import_transfer_items(
+            &local.wayfarer_id,
+            None,
+            None,
+            &[TransferObject {
+                item_id: item_id.clone(),
+                envelope_b64: payload,
+                expiry_unix_ms: now_ms + 60_000,
+                hop_count: 1,
+            }],
+            now_ms,
+        )
+        .expect("import reaction transfer");
+
+        assert_eq!(imported.accepted_item_ids, vec![item_id]);
+        assert_eq!(imported.new_messages.len(), 1);
+        assert!(imported.new_messages[0].text.is_empty());
+        assert_eq!(
+            decode_app_body(&imported.new_messages[0].body_bytes).expect("decode reaction"),
+            AppBody::Reaction(reaction)
+        );
+    }
+
+    #[test]
+    fn import_scans_blinded_envelopes_for_local_recipient_tags() {
+        let _lock = test_env_lock().lock().expect("lock test env");
+        let temp_dir = unique_test_state_dir("aethos-gossip-import-blinded");
+        let _state_guard = EnvVarGuard::set("XDG_STATE_HOME", &temp_dir.join("state"));
+        let _data_guard = EnvVarGuard::set("XDG_DATA_HOME", &temp_dir.join("data"));
+        let _aethos_state_dir_guard = EnvVarGuard::clear("AETHOS_STATE_DIR");
+
+        let local = ensure_local_identity().expect("local identity");
+        let local_seed = load_local_signing_key_seed().expect("local seed");
+        let local_pubkey = ed25519_dalek::SigningKey::from_bytes(&local_seed)
+            .verifying_key()
+            .to_bytes();
+        let other_pubkey = ed25519_dalek::SigningKey::from_bytes(&[0x33u8; 32])
+            .verifying_key()
+            .to_bytes();
+        let now_ms = now_unix_ms();
+        let transfer_for = |recipient_pubkey: &[u8; 32]| {
+            let payload = crate::aethos_core::protocol::build_blinded_envelope_v2_payload_b64(
+                recipient_pubkey,
+                b"blinded hello",
+                &[9u8; 32],
+                EnvelopeLifetime::from_ttl(now_ms, 600_000),
+            )
+            .expect("blinded payload");
+            TransferObject {
+                item_id: super::item_id_from_envelope_bytes(
+                    &base64::engine::general_purpose::URL_SAFE_NO_PAD
+                        .decode(&payload)
+                        .expect("decode blinded payload"),
+                ),
+                envelope_b64: payload,
+                expiry_unix_ms: now_ms + 60_000,
+                hop_count: 1,
+            }
+        };
+        let for_local = transfer_for(&local_pubkey);
+        let for_other = transfer_for(&other_pubkey);
+
+        let imported = import_transfer_items(
+            &local.wayfarer_id,
+            None,
+            None,
+            &[for_local.clone(), for_other.clone()],
+            now_ms,
+        )
+        .expect("import blinded transfers");
+
+        assert_eq!(
+            imported.accepted_item_ids,
+            vec![for_local.item_id.clone(), for_other.item_id.clone()]
+        );
+        assert_eq!(imported.new_messages.len(), 1);
+        assert_eq!(imported.new_messages[0].item_id, for_local.item_id);
+        assert_eq!(imported.new_messages[0].body_bytes, b"blinded hello");
+
+        let GossipSyncFrame::Summary(summary) = build_summary_frame(now_ms).expect("summary")
+        else {
+            panic!("expected SUMMARY frame")
+        };
+        assert_eq!(summary.preview_item_ids, Some(vec![for_other.item_id]));
+    }
+
+    #[test]
+    fn import_enforces_signed_envelope_lifetime() {
+        let _lock = test_env_lock().lock().expect("lock test env");
+        let temp_dir = unique_test_state_dir("aethos-gossip-import-signed-lifetime");
+        let _state_guard = EnvVarGuard::set("XDG_STATE_HOME", &temp_dir);
+        let _aethos_state_dir_guard = EnvVarGuard::clear("AETHOS_STATE_DIR");
+
+        let recipient_pubkey = ed25519_dalek::SigningKey::from_bytes(&[0x33u8; 32])
+            .verifying_key()
+            .to_bytes();
+        let now_ms = now_unix_ms();
+        let transfer_with = |lifetime: EnvelopeLifetime| {
+            let payload = crate::aethos_core::protocol::build_envelope_v2_payload_b64(
+                &recipient_pubkey,
+                b"hello",
+                &[9u8; 32],
+                lifetime,
+            )
+            .expect("payload");
+            TransferObject {
+                item_id: super::item_id_from_envelope_bytes(
+                    &base64::engine::general_purpose::URL_SAFE_NO_PAD
+                        .decode(&payload)
+                        .expect("decode payload"),
+                ),
+                envelope_b64: payload,
+                expiry_unix_ms: now_ms + 86_400_000,
+                hop_count: 1,
+            }
+        };
+        let live = transfer_with(EnvelopeLifetime::from_ttl(now_ms, 600_000));
+        let expired = transfer_with(EnvelopeLifetime::from_ttl(now_ms - 600_000, 60_000));
+        let future = transfer_with(EnvelopeLifetime::from_ttl(now_ms + 3_600_000, 600_000));
+
+        let imported = import_transfer_items(
+            &item(0x77),
+            None,
+            None,
+            &[live.clone(), expired, future],
+            now_ms,
+        )
+        .expect("import transfers");
+
+        assert_eq!(imported.accepted_item_ids, vec![live.item_id.clone()]);
+        let codes = imported
+            .rejected_items
+            .iter()
+            .map(|rejected| rejected.code.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(codes, vec!["EXPIRED", "NOT_YET_VALID"]);
+        let stored =
+            gossip_store_sqlite::get_existing_items_for_ids(std::slice::from_ref(&live.item_id))
+                .expect("stored items");
+        assert_eq!(stored[&live.item_id].expiry_unix_ms, now_ms + 600_000);
+    }
+
+    #[test]
+    fn multi_recipient_envelope_imports_for_listed_identity_and_ranks_each_as_destination() {
+        let _lock = test_env_lock().lock().expect("lock test env");
+        let temp_dir = unique_test_state_dir("aethos-gossip-import-multi-recipient");
+        let _state_guard = EnvVarGuard::set("XDG_STATE_HOME", &temp_dir.join("state"));
+        let _data_guard = EnvVarGuard::set("XDG_DATA_HOME", &temp_dir.join("data"));
+        let _aethos_state_dir_guard = EnvVarGuard::clear("AETHOS_STATE_DIR");
+
+        let local = ensure_local_identity().expect("local identity");
+        let local_seed = load_local_signing_key_seed().expect("local seed");
+        let local_pubkey = ed25519_dalek::SigningKey::from_bytes(&local_seed)
+            .verifying_key()
+            .to_bytes();
+        let teammate_pubkey = ed25519_dalek::SigningKey::from_bytes(&[0x44u8; 32])
+            .verifying_key()
+            .to_bytes();
+        let teammate_id = bytes_to_hex_lower(&Sha256::digest(teammate_pubkey));
+        let now_ms = now_unix_ms();
+        let payload =
+            crate::aethos_core::protocol::build_wayfarer_chat_multi_recipient_envelope_payload_b64(
+                &[teammate_pubkey, local_pubkey],
+                "team hello",
+                &[9u8; 32],
+                now_ms as i64,
+                600_000,
+            )
+            .expect("multi-recipient payload");
+        let transfer = TransferObject {
+            item_id: super::item_id_from_envelope_bytes(
+                &base64::engine::general_purpose::URL_SAFE_NO_PAD
+                    .decode(&payload)
+                    .expect("decode payload"),
+            ),
+            envelope_b64: payload.clone(),
+            expiry_unix_ms: now_ms + 600_000,
+            hop_count: 1,
+        };
+
+        let imported = import_transfer_items(&local.wayfarer_id, None, None, &[transfer], now_ms)
+            .expect("import multi-recipient transfer");
+        assert_eq!(imported.new_messages.len(), 1);
+        assert_eq!(imported.new_messages[0].text, "team hello");
+        assert_eq!(
+            imported.new_messages[0].recipient_wayfarer_id.as_deref(),
+            Some(local.wayfarer_id.as_str())
+        );
+
+        let stored = StoredItemRecord {
+            item_id: imported.accepted_item_ids[0].clone(),
+            envelope_b64: payload,
+            expiry_unix_ms: now_ms + 600_000,
+            hop_count: 1,
+            recorded_at_unix_ms: now_ms,
+        };
+        for peer in [teammate_id.as_str(), local.wayfarer_id.as_str()] {
+            let (profile, _) = shadow_profile_from_stored(&stored, now_ms, Some(peer), true)
+                .expect("shadow profile");
+            assert_eq!(profile.proximity, SchedulerProximityClass::DestinationPeer);
+        }
+        let (profile, _) = shadow_profile_from_stored(&stored, now_ms, Some(&item(0x99)), true)
+            .expect("shadow profile");
+        assert_eq!(profile.proximity, SchedulerProximityClass::LikelyCloser);
+        let (profile, _) = shadow_profile_from_stored(&stored, now_ms, Some(&teammate_id), false)
+            .expect("shadow profile");
+        assert_eq!(profile.proximity, SchedulerProximityClass::LikelyCloser);
+    }
+
     #[test]
     fn parse_and_import_transfer_allows_mixed_validity_objects() {
         let _lock = test_env_lock().lock().expect("lock test env");
@@ -2301,7 +4234,7 @@ mod tests {
             let mut scheduler_items = Vec::new();
             for candidate in &candidates {
                 let (profile, scheduler_item) =
-                    shadow_profile_from_stored(candidate, fixture.now_unix_ms, None)
+                    shadow_profile_from_stored(candidate, fixture.now_unix_ms, None, false)
                         .expect("shadow profile");
                 profiles.push(profile);
                 scheduler_items.push(scheduler_item);
@@ -2392,7 +4325,7 @@ mod tests {
         let mut scheduler_items = Vec::new();
         for candidate in &candidates {
             let (profile, scheduler_item) =
-                shadow_profile_from_stored(candidate, now_ms, Some(&direct_peer))
+                shadow_profile_from_stored(candidate, now_ms, Some(&direct_peer), true)
                     .expect("shadow profile");
             profiles.push(profile);
             scheduler_items.push(scheduler_item);
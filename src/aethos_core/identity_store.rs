@@ -2,7 +2,9 @@ use std::collections::BTreeMap;
 use std::fmt::Write;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
+use argon2::{Algorithm, Argon2, Params, Version};
 use base64::Engine;
 use chacha20poly1305::aead::{Aead, KeyInit, OsRng};
 use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
@@ -10,10 +12,65 @@ use ed25519_dalek::{SigningKey, VerifyingKey};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
+use crate::aethos_core::app_body::ProfileBody;
+use crate::aethos_core::device_link::{
+    decode_device_pairing_code, device_certificate_from_code, encode_device_pairing_code,
+    issue_device_certificate, verify_device_certificate, DeviceCertificate, DevicePairingRequest,
+};
+use crate::aethos_core::protocol::is_valid_wayfarer_id;
+use crate::aethos_core::recovery_phrase::{recovery_phrase_from_seed, seed_from_recovery_phrase};
+use crate::aethos_core::revocation::{
+    issue_revocation_certificate, verify_revocation_certificate, RevocationCertificate,
+};
+use crate::aethos_core::social_recovery::{
+    recover_from_shares, split_signing_seed, verify_recovery_share, RecoveryShare,
+};
+use crate::aethos_core::succession::{
+    issue_succession_record, SuccessionRecord, SUCCESSION_GRACE_WINDOW_MS,
+};
+
 const APP_DIR_NAME: &str = "aethos-linux";
 const IDENTITY_FILE_NAME: &str = "identity.json";
 const SESSION_CACHE_FILE_NAME: &str = "session-cache.enc.json";
 const CONTACT_ALIASES_FILE_NAME: &str = "contact-aliases.json";
+const CONTACT_PROFILES_FILE_NAME: &str = "contact-profiles.json";
+const CONTACT_KEYS_FILE_NAME: &str = "contact-keys.json";
+const DEVICE_PAIRING_FILE_NAME: &str = "device-pairing.json";
+const LINKED_DEVICES_FILE_NAME: &str = "linked-devices.json";
+const RETIRED_IDENTITIES_FILE_NAME: &str = "retired-identities.json";
+const CONTACT_SUCCESSIONS_FILE_NAME: &str = "contact-successions.json";
+const REVOKED_IDENTITIES_FILE_NAME: &str = "revoked-identities.json";
+const HELD_RECOVERY_SHARES_FILE_NAME: &str = "held-recovery-shares.json";
+const SOCIAL_RECOVERY_FILE_NAME: &str = "social-recovery.json";
+const PROFILES_FILE_NAME: &str = "profiles.json";
+const PROFILES_DIR_NAME: &str = "profiles";
+/// Profile whose files live directly in the app directories, as they did before profiles.
+pub const DEFAULT_PROFILE_NAME: &str = "default";
+const MAX_PROFILE_NAME_CHARS: usize = 32;
+/// Bound on following old-to-new contact ID chains, in case a saved map contains a cycle.
+const MAX_SUCCESSION_CHAIN: usize = 16;
+const PASSPHRASE_KDF_ARGON2ID: &str = "argon2id";
+const PASSPHRASE_SALT_BYTES: usize = 16;
+pub const MIN_IDENTITY_PASSPHRASE_CHARS: usize = 8;
+#[cfg(not(test))]
+const PASSPHRASE_KDF_MEMORY_KIB: u32 = 64 * 1024;
+#[cfg(test)]
+const PASSPHRASE_KDF_MEMORY_KIB: u32 = 1024;
+const PASSPHRASE_KDF_ITERATIONS: u32 = 3;
+const PASSPHRASE_KDF_LANES: u32 = 1;
+/// Upper bounds on KDF parameters read back from disk or a backup file, so a crafted file
+/// cannot make the key derivation allocate or spin without limit.
+const MAX_PASSPHRASE_KDF_MEMORY_KIB: u32 = 1024 * 1024;
+const MAX_PASSPHRASE_KDF_ITERATIONS: u32 = 16;
+const MAX_PASSPHRASE_KDF_LANES: u32 = 8;
+const IDENTITY_BACKUP_FORMAT_V1: &str = "aethos-identity-backup-v1";
+
+/// Signing seed of a passphrase-protected identity once unlocked, keyed by wayfarer ID so a
+/// state-dir switch never hands out the wrong key.
+static UNLOCKED_SIGNING_SEED: Mutex<Option<UnlockedIdentity>> = Mutex::new(None);
+/// Active profile name, keyed by the profiles file it was read from so a data-dir change
+/// re-reads it.
+static ACTIVE_PROFILE: Mutex<Option<(PathBuf, String)>> = Mutex::new(None);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct StoredIdentity {
@@ -21,11 +78,159 @@ struct StoredIdentity {
     wayfarer_id: String,
     #[serde(default)]
     device_id: String,
+    /// Plaintext seed; empty when the identity is passphrase-protected.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     signing_key_b64: String,
+    /// Public key kept alongside a wrapped seed so the identity can be reconciled while locked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    verifying_key_b64: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    wrapped_signing_key: Option<PassphraseSealed>,
+    /// Root-issued certificate when this install is a linked device of another identity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    device_certificate_b64: Option<String>,
     device_name: String,
     platform: String,
 }
 
+/// Identity replaced by `rotate_local_identity`, kept so messages still addressed to it can
+/// be opened until `grace_until_unix_ms`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RetiredIdentity {
+    wayfarer_id: String,
+    /// Plaintext seed; empty when the local identity is passphrase-protected.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    signing_key_b64: String,
+    /// Seed wrapped under the same passphrase key as the local identity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    wrapped_signing_key: Option<PassphraseSealed>,
+    succession_record_b64: String,
+    retired_at_unix_ms: u64,
+    grace_until_unix_ms: u64,
+}
+
+/// Outcome of a rotation. The caller announces `succession_record` to contacts in envelopes
+/// signed with `previous_signing_seed`.
+#[derive(Debug, Clone)]
+pub struct IdentityRotation {
+    pub identity: LocalIdentitySummary,
+    pub previous_wayfarer_id: String,
+    pub previous_signing_seed: [u8; 32],
+    pub succession_record: Vec<u8>,
+}
+
+/// Identity key revoked by a verified revocation certificate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevokedIdentity {
+    pub pubkey_b64: String,
+    pub certificate_b64: String,
+    /// When this install first saw the certificate; envelopes it imports from then on are
+    /// dropped.
+    pub revoked_at_unix_ms: u64,
+}
+
+/// Shares collected by `begin_social_recovery` until enough arrive to rebuild the identity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SocialRecoverySession {
+    expected_wayfarer_id: String,
+    shares_b64: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SocialRecoveryProgress {
+    pub expected_wayfarer_id: String,
+    pub collected: usize,
+    /// Shares needed, known once the first one arrives.
+    pub threshold: Option<u8>,
+    /// Set when the last needed share arrived and the recovered identity was installed.
+    pub restored: Option<LocalIdentitySummary>,
+}
+
+/// Device key generated by `begin_device_pairing`, kept until the root device certifies it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PendingDevicePairing {
+    signing_key_b64: String,
+    device_name: String,
+}
+
+/// A device the local root identity has certified.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkedDevice {
+    pub device_id: String,
+    pub device_name: String,
+    pub device_pubkey_b64: String,
+    pub certificate_b64: String,
+    pub linked_at_unix_ms: u64,
+}
+
+impl LinkedDevice {
+    pub fn certificate(&self) -> Result<Vec<u8>, String> {
+        base64::engine::general_purpose::STANDARD
+            .decode(&self.certificate_b64)
+            .map_err(|err| format!("failed to decode device certificate: {err}"))
+    }
+}
+
+/// Payload sealed with ChaCha20-Poly1305 under an Argon2id passphrase key; used for the
+/// wrapped signing seed and for identity backups.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct PassphraseSealed {
+    kdf: String,
+    memory_kib: u32,
+    iterations: u32,
+    lanes: u32,
+    salt_b64: String,
+    nonce_b64: String,
+    ciphertext_b64: String,
+}
+
+/// Argon2id output for one `PassphraseSealed` salt, kept while unlocked so retired seeds and a
+/// rotated identity can be wrapped without asking for the passphrase again.
+#[derive(Clone)]
+struct PassphraseKey {
+    key: [u8; 32],
+    memory_kib: u32,
+    iterations: u32,
+    lanes: u32,
+    salt_b64: String,
+}
+
+struct UnlockedIdentity {
+    wayfarer_id: String,
+    seed: [u8; 32],
+    passphrase_key: PassphraseKey,
+}
+
+/// On-disk backup: the cleartext header names the identity, everything else is sealed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IdentityBackupFile {
+    format: String,
+    wayfarer_id: String,
+    created_at_unix_ms: u64,
+    sealed: PassphraseSealed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IdentityBackupPayload {
+    wayfarer_id: String,
+    signing_key_b64: String,
+    device_name: String,
+    #[serde(default)]
+    contacts: Option<BTreeMap<String, String>>,
+    #[serde(default)]
+    contact_keys: Option<BTreeMap<String, ContactKey>>,
+    #[serde(default)]
+    settings: Option<serde_json::Value>,
+}
+
+/// Result of restoring a backup; `settings` is returned as-is for the app layer to apply.
+#[derive(Debug, Clone)]
+pub struct RestoredIdentityBackup {
+    pub identity: LocalIdentitySummary,
+    pub contacts_restored: usize,
+    pub settings: Option<serde_json::Value>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct EncryptedEnvelope {
     nonce_b64: String,
@@ -38,12 +243,71 @@ pub struct RelaySessionCache {
     pub secondary_status: String,
 }
 
+/// Latest `wayfarer.profile.v1` record seen for a wayfarer ID, shown next to the local alias.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContactProfile {
+    pub version: u64,
+    pub display_name: String,
+    #[serde(default)]
+    pub avatar_thumbnail_b64: Option<String>,
+    #[serde(default)]
+    pub status_line: Option<String>,
+    #[serde(default)]
+    pub preferred_relays: Vec<String>,
+    pub updated_at_unix_ms: u64,
+}
+
+impl ContactProfile {
+    pub fn from_body(profile: &ProfileBody) -> Self {
+        Self {
+            version: profile.version,
+            display_name: profile.display_name.clone(),
+            avatar_thumbnail_b64: profile
+                .avatar_thumbnail
+                .as_ref()
+                .map(|bytes| base64::engine::general_purpose::STANDARD.encode(bytes)),
+            status_line: profile.status_line.clone(),
+            preferred_relays: profile.preferred_relays.clone(),
+            updated_at_unix_ms: profile.created_at_unix_ms,
+        }
+    }
+}
+
+/// A contact's signing key as learned from a verified envelope, plus whether the user has
+/// confirmed the pairwise safety number out of band.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContactKey {
+    pub pubkey_b64: String,
+    #[serde(default)]
+    pub verified: bool,
+    #[serde(default)]
+    pub verified_at_unix_ms: Option<u64>,
+    /// Linked-device keys seen signing for this contact under a valid root certificate.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub device_pubkeys_b64: Vec<String>,
+}
+
+impl ContactKey {
+    pub fn pubkey(&self) -> Result<[u8; 32], String> {
+        decode_pubkey_b64(&self.pubkey_b64, "contact pubkey")
+    }
+
+    pub fn device_pubkeys(&self) -> Result<Vec<[u8; 32]>, String> {
+        self.device_pubkeys_b64
+            .iter()
+            .map(|pubkey_b64| decode_pubkey_b64(pubkey_b64, "contact device pubkey"))
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct LocalIdentitySummary {
     pub wayfarer_id: String,
     pub device_id: String,
     pub verifying_key_b64: String,
     pub device_name: String,
+    /// True when this install signs with a device key certified by another root device.
+    pub linked_device: bool,
 }
 
 pub fn ensure_local_identity() -> Result<LocalIdentitySummary, String> {
@@ -59,32 +323,107 @@ pub fn ensure_local_identity() -> Result<LocalIdentitySummary, String> {
 pub fn regenerate_local_identity() -> Result<LocalIdentitySummary, String> {
     let mut csprng = OsRng;
     let signing_key = SigningKey::generate(&mut csprng);
-    let signing_key_b64 = base64::engine::general_purpose::STANDARD.encode(signing_key.to_bytes());
-    let verifying = signing_key.verifying_key();
-    let wayfarer_id = sha256_hex_lower(&verifying.to_bytes());
-    let device_id = sha256_hex_lower(&verifying.to_bytes());
+    install_signing_seed(&signing_key.to_bytes(), infer_device_name(), None)
+}
 
-    let identity = StoredIdentity {
-        wayfarer_id,
-        device_id,
-        signing_key_b64,
-        device_name: infer_device_name(),
-        platform: "linux".to_string(),
+pub fn load_local_signing_key_seed() -> Result<[u8; 32], String> {
+    let identity = ensure_stored_identity()?;
+    signing_seed_from_identity(&identity)
+}
+
+/// Whether the stored identity's signing key is wrapped under a passphrase.
+pub fn identity_passphrase_enabled() -> Result<bool, String> {
+    Ok(load_identity()?.is_some_and(|identity| identity.wrapped_signing_key.is_some()))
+}
+
+/// True while a passphrase-protected identity has not been unlocked in this process.
+pub fn local_identity_locked() -> Result<bool, String> {
+    let Some(identity) = load_identity()? else {
+        return Ok(false);
     };
+    Ok(identity.wrapped_signing_key.is_some() && cached_signing_seed(&identity).is_none())
+}
 
-    persist_identity(&identity)?;
+/// Unwraps the signing key with `passphrase` and keeps it in memory for this process.
+pub fn unlock_local_identity(passphrase: &str) -> Result<LocalIdentitySummary, String> {
+    let identity = ensure_stored_identity()?;
+    let Some(wrapped) = identity.wrapped_signing_key.as_ref() else {
+        return summary_from_identity(&identity);
+    };
+    let passphrase_key = passphrase_key_for_sealed(wrapped, passphrase)?;
+    let seed = open_seed_with_key(wrapped, &passphrase_key, &identity.wayfarer_id)?;
+    if SigningKey::from_bytes(&seed).verifying_key()
+        != decode_verifying_key_from_identity(&identity)?
+    {
+        return Err("unwrapped signing key does not match the stored identity".to_string());
+    }
+    remember_unlocked_identity(identity.wayfarer_id.clone(), seed, passphrase_key)?;
     summary_from_identity(&identity)
 }
 
-pub fn load_local_signing_key_seed() -> Result<[u8; 32], String> {
-    let identity = ensure_stored_identity()?;
-    let signing_bytes = base64::engine::general_purpose::STANDARD
-        .decode(&identity.signing_key_b64)
-        .map_err(|err| format!("failed to decode signing key: {err}"))?;
+pub fn lock_local_identity() {
+    forget_unlocked_signing_seed();
+}
 
-    signing_bytes
-        .try_into()
-        .map_err(|_| "decoded signing key had invalid length".to_string())
+/// Wraps the signing key and any retired keys under `passphrase`, migrating a plaintext
+/// identity in place or re-wrapping an unlocked one. The wayfarer ID is unchanged.
+pub fn set_identity_passphrase(passphrase: &str) -> Result<(), String> {
+    if passphrase.chars().count() < MIN_IDENTITY_PASSPHRASE_CHARS {
+        return Err(format!(
+            "passphrase must be at least {MIN_IDENTITY_PASSPHRASE_CHARS} characters"
+        ));
+    }
+    let mut identity = ensure_stored_identity()?;
+    let seed = signing_seed_from_identity(&identity)?;
+    let mut retired = load_retired_identities()?;
+    let retired_seeds = retired
+        .iter()
+        .map(|entry| retired_signing_seed(entry, unlocked_passphrase_key(&identity).as_ref()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let passphrase_key = new_passphrase_key(passphrase)?;
+    for (entry, retired_seed) in retired.iter_mut().zip(retired_seeds) {
+        entry.wrapped_signing_key = Some(seal_with_key(
+            &retired_seed,
+            &passphrase_key,
+            entry.wayfarer_id.as_bytes(),
+        )?);
+        entry.signing_key_b64.clear();
+    }
+    identity.wrapped_signing_key = Some(seal_with_key(
+        &seed,
+        &passphrase_key,
+        identity.wayfarer_id.as_bytes(),
+    )?);
+    identity.verifying_key_b64 = Some(
+        base64::engine::general_purpose::STANDARD
+            .encode(SigningKey::from_bytes(&seed).verifying_key().to_bytes()),
+    );
+    identity.signing_key_b64.clear();
+    persist_identity(&identity)?;
+    save_retired_identities(&retired)?;
+    remember_unlocked_identity(identity.wayfarer_id, seed, passphrase_key)
+}
+
+/// Returns a passphrase-protected identity and its retired keys to plaintext storage after
+/// checking `passphrase`.
+pub fn remove_identity_passphrase(passphrase: &str) -> Result<(), String> {
+    let mut identity = ensure_stored_identity()?;
+    let Some(wrapped) = identity.wrapped_signing_key.take() else {
+        return Ok(());
+    };
+    let passphrase_key = passphrase_key_for_sealed(&wrapped, passphrase)?;
+    let seed = open_seed_with_key(&wrapped, &passphrase_key, &identity.wayfarer_id)?;
+    let mut retired = load_retired_identities()?;
+    for entry in &mut retired {
+        let retired_seed = retired_signing_seed(entry, Some(&passphrase_key))?;
+        entry.signing_key_b64 = base64::engine::general_purpose::STANDARD.encode(retired_seed);
+        entry.wrapped_signing_key = None;
+    }
+    identity.signing_key_b64 = base64::engine::general_purpose::STANDARD.encode(seed);
+    identity.verifying_key_b64 = None;
+    persist_identity(&identity)?;
+    save_retired_identities(&retired)
 }
 
 pub fn delete_wayfarer_id() -> Result<(), String> {
@@ -108,9 +447,728 @@ pub fn delete_wayfarer_id() -> Result<(), String> {
         })?;
     }
 
+    let retired_path = retired_identities_file_path();
+    if retired_path.exists() {
+        fs::remove_file(&retired_path).map_err(|err| {
+            format!(
+                "failed to delete retired identities file at {}: {err}",
+                retired_path.display()
+            )
+        })?;
+    }
+
+    forget_unlocked_signing_seed();
     Ok(())
 }
 
+/// Encodes the local signing seed as a recovery phrase; errors while the identity is locked.
+pub fn identity_recovery_phrase() -> Result<String, String> {
+    let identity = ensure_stored_identity()?;
+    require_root_identity(&identity)?;
+    Ok(recovery_phrase_from_seed(&signing_seed_from_identity(
+        &identity,
+    )?))
+}
+
+/// Replaces the local identity with the one encoded in `phrase`. When `expected_wayfarer_id`
+/// is given the phrase must reproduce it.
+pub fn restore_identity_from_recovery_phrase(
+    phrase: &str,
+    expected_wayfarer_id: Option<&str>,
+) -> Result<LocalIdentitySummary, String> {
+    let seed = seed_from_recovery_phrase(phrase)?;
+    if let Some(expected) = expected_wayfarer_id {
+        check_seed_matches_wayfarer_id(&seed, expected)?;
+    }
+    install_signing_seed(&seed, infer_device_name(), None)
+}
+
+/// Seals the local identity, and optionally contacts and app settings, into a backup file
+/// body under `passphrase`.
+pub fn export_identity_backup(
+    passphrase: &str,
+    include_contacts: bool,
+    settings: Option<serde_json::Value>,
+    now_unix_ms: u64,
+) -> Result<String, String> {
+    if passphrase.chars().count() < MIN_IDENTITY_PASSPHRASE_CHARS {
+        return Err(format!(
+            "passphrase must be at least {MIN_IDENTITY_PASSPHRASE_CHARS} characters"
+        ));
+    }
+    let identity = ensure_stored_identity()?;
+    require_root_identity(&identity)?;
+    let seed = signing_seed_from_identity(&identity)?;
+    let payload = IdentityBackupPayload {
+        wayfarer_id: identity.wayfarer_id.clone(),
+        signing_key_b64: base64::engine::general_purpose::STANDARD.encode(seed),
+        device_name: identity.device_name.clone(),
+        contacts: include_contacts.then(load_contact_aliases).transpose()?,
+        contact_keys: include_contacts.then(load_contact_keys).transpose()?,
+        settings,
+    };
+    let serialized = serde_json::to_vec(&payload)
+        .map_err(|err| format!("failed to serialize identity backup payload: {err}"))?;
+    let backup = IdentityBackupFile {
+        format: IDENTITY_BACKUP_FORMAT_V1.to_string(),
+        sealed: seal_with_passphrase(
+            &serialized,
+            passphrase,
+            &backup_associated_data(&identity.wayfarer_id),
+        )?,
+        wayfarer_id: identity.wayfarer_id,
+        created_at_unix_ms: now_unix_ms,
+    };
+    serde_json::to_string_pretty(&backup)
+        .map_err(|err| format!("failed to serialize identity backup: {err}"))
+}
+
+/// Opens a backup made by [`export_identity_backup`], checks that its signing key reproduces
+/// the recorded wayfarer ID, and installs it. Backed-up contacts fill in entries missing locally.
+pub fn restore_identity_backup(
+    raw: &str,
+    passphrase: &str,
+) -> Result<RestoredIdentityBackup, String> {
+    let backup: IdentityBackupFile = serde_json::from_str(raw)
+        .map_err(|err| format!("failed to parse identity backup: {err}"))?;
+    if backup.format != IDENTITY_BACKUP_FORMAT_V1 {
+        return Err(format!(
+            "unsupported identity backup format {}",
+            backup.format
+        ));
+    }
+    let plaintext = open_with_passphrase(
+        &backup.sealed,
+        passphrase,
+        &backup_associated_data(&backup.wayfarer_id),
+    )?;
+    let payload: IdentityBackupPayload = serde_json::from_slice(&plaintext)
+        .map_err(|err| format!("failed to parse identity backup payload: {err}"))?;
+    if payload.wayfarer_id != backup.wayfarer_id {
+        return Err("identity backup header does not match its payload".to_string());
+    }
+    let seed: [u8; 32] = base64::engine::general_purpose::STANDARD
+        .decode(&payload.signing_key_b64)
+        .map_err(|err| format!("failed to decode backed-up signing key: {err}"))?
+        .try_into()
+        .map_err(|_| "backed-up signing key had invalid length".to_string())?;
+    check_seed_matches_wayfarer_id(&seed, &payload.wayfarer_id)?;
+
+    let identity = install_signing_seed(&seed, payload.device_name, None)?;
+    let mut contacts_restored = 0usize;
+    if let Some(backed_up) = payload.contacts {
+        let mut contacts = load_contact_aliases()?;
+        for (wayfarer_id, alias) in backed_up {
+            if let std::collections::btree_map::Entry::Vacant(entry) = contacts.entry(wayfarer_id) {
+                entry.insert(alias);
+                contacts_restored += 1;
+            }
+        }
+        save_contact_aliases(&contacts)?;
+    }
+    if let Some(backed_up) = payload.contact_keys {
+        let mut keys = load_contact_keys()?;
+        for (wayfarer_id, key) in backed_up {
+            keys.entry(wayfarer_id).or_insert(key);
+        }
+        save_contact_keys(&keys)?;
+    }
+
+    Ok(RestoredIdentityBackup {
+        identity,
+        contacts_restored,
+        settings: payload.settings,
+    })
+}
+
+/// Replaces the local identity with a fresh key and signs a succession record with both keys.
+/// The old key is retired rather than deleted so messages sent to it during the grace window
+/// can still be opened. A passphrase-protected identity must be unlocked; the new and retired
+/// keys stay wrapped under the same passphrase.
+pub fn rotate_local_identity(now_unix_ms: u64) -> Result<IdentityRotation, String> {
+    let identity = ensure_stored_identity()?;
+    require_root_identity(&identity)?;
+    let previous_signing_seed = signing_seed_from_identity(&identity)?;
+    let passphrase_key = unlocked_passphrase_key(&identity);
+    let new_signing_seed = SigningKey::generate(&mut OsRng).to_bytes();
+    let succession_record =
+        issue_succession_record(&previous_signing_seed, &new_signing_seed, now_unix_ms)?;
+
+    let mut retired = load_retired_identities()?;
+    retired.retain(|entry| {
+        entry.grace_until_unix_ms > now_unix_ms && entry.wayfarer_id != identity.wayfarer_id
+    });
+    let (signing_key_b64, wrapped_signing_key) = match passphrase_key.as_ref() {
+        Some(key) => (
+            String::new(),
+            Some(seal_with_key(
+                &previous_signing_seed,
+                key,
+                identity.wayfarer_id.as_bytes(),
+            )?),
+        ),
+        None => (
+            base64::engine::general_purpose::STANDARD.encode(previous_signing_seed),
+            None,
+        ),
+    };
+    retired.push(RetiredIdentity {
+        wayfarer_id: identity.wayfarer_id.clone(),
+        signing_key_b64,
+        wrapped_signing_key,
+        succession_record_b64: base64::engine::general_purpose::STANDARD.encode(&succession_record),
+        retired_at_unix_ms: now_unix_ms,
+        grace_until_unix_ms: now_unix_ms.saturating_add(SUCCESSION_GRACE_WINDOW_MS),
+    });
+    save_retired_identities(&retired)?;
+
+    let summary = match passphrase_key {
+        Some(key) => {
+            let mut rotated =
+                stored_identity_from_seed(&new_signing_seed, identity.device_name, None)?;
+            rotated.wrapped_signing_key = Some(seal_with_key(
+                &new_signing_seed,
+                &key,
+                rotated.wayfarer_id.as_bytes(),
+            )?);
+            rotated.verifying_key_b64 = Some(
+                base64::engine::general_purpose::STANDARD.encode(
+                    SigningKey::from_bytes(&new_signing_seed)
+                        .verifying_key()
+                        .to_bytes(),
+                ),
+            );
+            rotated.signing_key_b64.clear();
+            persist_identity(&rotated)?;
+            remember_unlocked_identity(rotated.wayfarer_id.clone(), new_signing_seed, key)?;
+            summary_from_identity(&rotated)?
+        }
+        None => install_signing_seed(&new_signing_seed, identity.device_name, None)?,
+    };
+    Ok(IdentityRotation {
+        identity: summary,
+        previous_wayfarer_id: identity.wayfarer_id,
+        previous_signing_seed,
+        succession_record,
+    })
+}
+
+/// Wayfarer IDs and signing seeds of rotated-away identities still inside their grace window.
+/// Wrapped seeds need the local identity to be unlocked.
+pub fn retired_identity_keys(now_unix_ms: u64) -> Result<Vec<(String, [u8; 32])>, String> {
+    let passphrase_key = load_identity()?.as_ref().and_then(unlocked_passphrase_key);
+    load_retired_identities()?
+        .into_iter()
+        .filter(|entry| entry.grace_until_unix_ms > now_unix_ms)
+        .map(|entry| {
+            let seed = retired_signing_seed(&entry, passphrase_key.as_ref())?;
+            Ok((entry.wayfarer_id, seed))
+        })
+        .collect()
+}
+
+fn retired_signing_seed(
+    entry: &RetiredIdentity,
+    passphrase_key: Option<&PassphraseKey>,
+) -> Result<[u8; 32], String> {
+    if let Some(wrapped) = entry.wrapped_signing_key.as_ref() {
+        let key = passphrase_key
+            .ok_or_else(|| "identity is locked; unlock it with the passphrase".to_string())?;
+        return open_seed_with_key(wrapped, key, &entry.wayfarer_id);
+    }
+    base64::engine::general_purpose::STANDARD
+        .decode(&entry.signing_key_b64)
+        .map_err(|err| format!("failed to decode retired signing key: {err}"))?
+        .try_into()
+        .map_err(|_| "retired signing key had invalid length".to_string())
+}
+
+/// Old-to-new contact IDs learned from verified succession records.
+pub fn load_contact_successions() -> Result<BTreeMap<String, String>, String> {
+    let path = contact_successions_file_path();
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+
+    let content = fs::read_to_string(&path).map_err(|err| {
+        format!(
+            "failed to read contact successions file at {}: {err}",
+            path.display()
+        )
+    })?;
+
+    serde_json::from_str(&content).map_err(|err| {
+        format!(
+            "failed to parse contact successions file at {}: {err}",
+            path.display()
+        )
+    })
+}
+
+/// Follows `successions` from `wayfarer_id` to the contact's current ID.
+pub fn resolve_contact_succession(
+    successions: &BTreeMap<String, String>,
+    wayfarer_id: &str,
+) -> String {
+    let mut current = wayfarer_id;
+    for _ in 0..MAX_SUCCESSION_CHAIN {
+        match successions.get(current) {
+            Some(next) => current = next,
+            None => break,
+        }
+    }
+    current.to_string()
+}
+
+/// Moves a contact's alias, key and profile from the record's old ID to its new one. Only
+/// call this with a verified record that arrived in an envelope signed by the old key. A
+/// verified old key carries its verification over, since it vouched for the new one.
+pub fn apply_contact_succession(record: &SuccessionRecord) -> Result<bool, String> {
+    let mut aliases = load_contact_aliases()?;
+    let mut keys = load_contact_keys()?;
+    let mut profiles = load_contact_profiles()?;
+    let mut successions = load_contact_successions()?;
+    if !migrate_contact_succession(
+        &mut aliases,
+        &mut keys,
+        &mut profiles,
+        &mut successions,
+        record,
+    )? {
+        return Ok(false);
+    }
+    save_contact_aliases(&aliases)?;
+    save_contact_keys(&keys)?;
+    save_contact_profiles(&profiles)?;
+    let serialized = serde_json::to_string_pretty(&successions)
+        .map_err(|err| format!("failed to serialize contact successions payload: {err}"))?;
+    write_app_file(&contact_successions_file_path(), serialized.as_bytes())?;
+    Ok(true)
+}
+
+fn migrate_contact_succession(
+    aliases: &mut BTreeMap<String, String>,
+    keys: &mut BTreeMap<String, ContactKey>,
+    profiles: &mut BTreeMap<String, ContactProfile>,
+    successions: &mut BTreeMap<String, String>,
+    record: &SuccessionRecord,
+) -> Result<bool, String> {
+    let old_wayfarer_id = record.old_wayfarer_id();
+    let new_wayfarer_id = record.new_wayfarer_id();
+    if successions.get(&old_wayfarer_id) == Some(&new_wayfarer_id) {
+        return Ok(false);
+    }
+    if let Some(known) = keys.get(&old_wayfarer_id) {
+        if known.pubkey()? != record.old_pubkey {
+            return Err("succession record does not match the recorded contact key".to_string());
+        }
+    }
+
+    if let Some(alias) = aliases.remove(&old_wayfarer_id) {
+        aliases.entry(new_wayfarer_id.clone()).or_insert(alias);
+    }
+    let old_key = keys.remove(&old_wayfarer_id);
+    record_contact_pubkey(keys, &new_wayfarer_id, &record.new_pubkey)?;
+    if let (Some(old_key), Some(new_key)) = (old_key, keys.get_mut(&new_wayfarer_id)) {
+        if old_key.verified && !new_key.verified {
+            new_key.verified = true;
+            new_key.verified_at_unix_ms = old_key.verified_at_unix_ms;
+        }
+    }
+    if let Some(profile) = profiles.remove(&old_wayfarer_id) {
+        profiles.entry(new_wayfarer_id.clone()).or_insert(profile);
+    }
+    successions.insert(old_wayfarer_id, new_wayfarer_id);
+    Ok(true)
+}
+
+/// Signs a revocation certificate for the local identity, meant to be stored off-device and
+/// published if the key is ever lost or stolen.
+pub fn local_revocation_certificate(now_unix_ms: u64) -> Result<Vec<u8>, String> {
+    let identity = ensure_stored_identity()?;
+    require_root_identity(&identity)?;
+    issue_revocation_certificate(&signing_seed_from_identity(&identity)?, now_unix_ms)
+}
+
+/// Revoked identity keys by wayfarer ID.
+pub fn load_revoked_identities() -> Result<BTreeMap<String, RevokedIdentity>, String> {
+    let path = revoked_identities_file_path();
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+
+    let content = fs::read_to_string(&path).map_err(|err| {
+        format!(
+            "failed to read revoked identities file at {}: {err}",
+            path.display()
+        )
+    })?;
+
+    serde_json::from_str(&content).map_err(|err| {
+        format!(
+            "failed to parse revoked identities file at {}: {err}",
+            path.display()
+        )
+    })
+}
+
+/// Verifies `certificate` and records the key it revokes. Returns the certificate and whether
+/// the key was newly revoked.
+pub fn record_identity_revocation(
+    certificate: &[u8],
+    now_unix_ms: u64,
+) -> Result<(RevocationCertificate, bool), String> {
+    let verified = verify_revocation_certificate(certificate)?;
+    let mut revoked = load_revoked_identities()?;
+    let wayfarer_id = verified.wayfarer_id();
+    if revoked.contains_key(&wayfarer_id) {
+        return Ok((verified, false));
+    }
+    revoked.insert(
+        wayfarer_id,
+        RevokedIdentity {
+            pubkey_b64: base64::engine::general_purpose::STANDARD.encode(verified.pubkey),
+            certificate_b64: base64::engine::general_purpose::STANDARD.encode(certificate),
+            revoked_at_unix_ms: now_unix_ms,
+        },
+    );
+    let serialized = serde_json::to_string_pretty(&revoked)
+        .map_err(|err| format!("failed to serialize revoked identities payload: {err}"))?;
+    write_app_file(&revoked_identities_file_path(), serialized.as_bytes())?;
+    Ok((verified, true))
+}
+
+/// Splits the local signing seed into `count` signed shares for trusted contacts, any
+/// `threshold` of which rebuild it.
+pub fn split_identity_recovery_shares(threshold: u8, count: u8) -> Result<Vec<Vec<u8>>, String> {
+    use chacha20poly1305::aead::rand_core::RngCore;
+
+    let identity = ensure_stored_identity()?;
+    require_root_identity(&identity)?;
+    let mut split_id = [0u8; 16];
+    OsRng.fill_bytes(&mut split_id);
+    split_signing_seed(
+        &signing_seed_from_identity(&identity)?,
+        threshold,
+        count,
+        split_id,
+        || (OsRng.next_u32() & 0xff) as u8,
+    )
+}
+
+/// Keeps a share a contact entrusted to this install. Only the share's owner may hand it
+/// over, and a newer share from the same owner replaces the old one.
+pub fn store_held_recovery_share(
+    sender_wayfarer_id: &str,
+    raw_share: &[u8],
+) -> Result<RecoveryShare, String> {
+    let share = verify_recovery_share(raw_share)?;
+    if share.owner_wayfarer_id() != sender_wayfarer_id {
+        return Err("recovery share was not sent by its owner".to_string());
+    }
+    let mut held = load_held_recovery_shares()?;
+    held.insert(
+        sender_wayfarer_id.to_string(),
+        base64::engine::general_purpose::STANDARD.encode(raw_share),
+    );
+    let serialized = serde_json::to_string_pretty(&held)
+        .map_err(|err| format!("failed to serialize held recovery shares payload: {err}"))?;
+    write_app_file(&held_recovery_shares_file_path(), serialized.as_bytes())?;
+    Ok(share)
+}
+
+/// Base64 shares held for other identities, keyed by owner wayfarer ID.
+pub fn load_held_recovery_shares() -> Result<BTreeMap<String, String>, String> {
+    let path = held_recovery_shares_file_path();
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+
+    let content = fs::read_to_string(&path).map_err(|err| {
+        format!(
+            "failed to read held recovery shares file at {}: {err}",
+            path.display()
+        )
+    })?;
+
+    serde_json::from_str(&content).map_err(|err| {
+        format!(
+            "failed to parse held recovery shares file at {}: {err}",
+            path.display()
+        )
+    })
+}
+
+/// Starts collecting returned shares for `expected_wayfarer_id`, discarding any earlier
+/// recovery attempt.
+pub fn begin_social_recovery(expected_wayfarer_id: &str) -> Result<SocialRecoveryProgress, String> {
+    let expected_wayfarer_id = expected_wayfarer_id.trim();
+    if !is_valid_wayfarer_id(expected_wayfarer_id) {
+        return Err("wayfarer_id must be 64 lowercase hex characters".to_string());
+    }
+    let session = SocialRecoverySession {
+        expected_wayfarer_id: expected_wayfarer_id.to_string(),
+        shares_b64: Vec::new(),
+    };
+    save_social_recovery_session(&session)?;
+    social_recovery_progress(&session, None)
+}
+
+pub fn social_recovery_status() -> Result<Option<SocialRecoveryProgress>, String> {
+    load_social_recovery_session()?
+        .map(|session| social_recovery_progress(&session, None))
+        .transpose()
+}
+
+/// Adds a returned share to the active recovery. Once a split reaches its threshold the
+/// rebuilt key is checked against the expected ID and installed as this device's identity.
+pub fn add_social_recovery_share(raw_share: &[u8]) -> Result<SocialRecoveryProgress, String> {
+    let mut session = load_social_recovery_session()?
+        .ok_or_else(|| "no social recovery in progress".to_string())?;
+    let share = verify_recovery_share(raw_share)?;
+    if share.owner_wayfarer_id() != session.expected_wayfarer_id {
+        return Err("recovery share belongs to a different identity".to_string());
+    }
+    let encoded = base64::engine::general_purpose::STANDARD.encode(raw_share);
+    if !session.shares_b64.contains(&encoded) {
+        session.shares_b64.push(encoded);
+    }
+
+    let shares = decode_session_shares(&session)?;
+    let Some(recovered) = recover_from_shares(&shares) else {
+        save_social_recovery_session(&session)?;
+        return social_recovery_progress(&session, None);
+    };
+    let seed = recovered?;
+    let restored = install_signing_seed(&seed, infer_device_name(), None)?;
+    let path = social_recovery_file_path();
+    fs::remove_file(&path).map_err(|err| {
+        format!(
+            "failed to delete social recovery file at {}: {err}",
+            path.display()
+        )
+    })?;
+    social_recovery_progress(&session, Some(restored))
+}
+
+fn social_recovery_progress(
+    session: &SocialRecoverySession,
+    restored: Option<LocalIdentitySummary>,
+) -> Result<SocialRecoveryProgress, String> {
+    let shares = decode_session_shares(session)?;
+    Ok(SocialRecoveryProgress {
+        expected_wayfarer_id: session.expected_wayfarer_id.clone(),
+        collected: shares.len(),
+        threshold: shares.iter().map(|share| share.threshold).min(),
+        restored,
+    })
+}
+
+fn decode_session_shares(session: &SocialRecoverySession) -> Result<Vec<RecoveryShare>, String> {
+    session
+        .shares_b64
+        .iter()
+        .map(|share_b64| {
+            let raw = base64::engine::general_purpose::STANDARD
+                .decode(share_b64)
+                .map_err(|err| format!("failed to decode collected recovery share: {err}"))?;
+            verify_recovery_share(&raw)
+        })
+        .collect()
+}
+
+fn load_social_recovery_session() -> Result<Option<SocialRecoverySession>, String> {
+    let path = social_recovery_file_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&path).map_err(|err| {
+        format!(
+            "failed to read social recovery file at {}: {err}",
+            path.display()
+        )
+    })?;
+
+    serde_json::from_str(&content).map(Some).map_err(|err| {
+        format!(
+            "failed to parse social recovery file at {}: {err}",
+            path.display()
+        )
+    })
+}
+
+fn save_social_recovery_session(session: &SocialRecoverySession) -> Result<(), String> {
+    let serialized = serde_json::to_string_pretty(session)
+        .map_err(|err| format!("failed to serialize social recovery payload: {err}"))?;
+    write_app_file(&social_recovery_file_path(), serialized.as_bytes())
+}
+
+fn load_retired_identities() -> Result<Vec<RetiredIdentity>, String> {
+    let path = retired_identities_file_path();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path).map_err(|err| {
+        format!(
+            "failed to read retired identities file at {}: {err}",
+            path.display()
+        )
+    })?;
+
+    serde_json::from_str(&content).map_err(|err| {
+        format!(
+            "failed to parse retired identities file at {}: {err}",
+            path.display()
+        )
+    })
+}
+
+fn save_retired_identities(retired: &[RetiredIdentity]) -> Result<(), String> {
+    let serialized = serde_json::to_string_pretty(retired)
+        .map_err(|err| format!("failed to serialize retired identities payload: {err}"))?;
+    write_app_file(&retired_identities_file_path(), serialized.as_bytes())
+}
+
+/// Starts linking this install to an existing identity: generates a device key, keeps it
+/// pending, and returns the pairing code to enter on the root device.
+pub fn begin_device_pairing(device_name: &str) -> Result<String, String> {
+    let signing_key = SigningKey::generate(&mut OsRng);
+    let request = DevicePairingRequest {
+        device_pubkey: signing_key.verifying_key().to_bytes(),
+        device_name: device_name.trim().to_string(),
+    };
+    let code = encode_device_pairing_code(&request)?;
+    let pending = PendingDevicePairing {
+        signing_key_b64: base64::engine::general_purpose::STANDARD.encode(signing_key.to_bytes()),
+        device_name: request.device_name,
+    };
+    let serialized = serde_json::to_string_pretty(&pending)
+        .map_err(|err| format!("failed to serialize device pairing payload: {err}"))?;
+    write_app_file(&device_pairing_file_path(), serialized.as_bytes())?;
+    Ok(code)
+}
+
+/// Certifies the device behind `pairing_code` with the local root key and records it.
+/// Linked devices cannot certify further devices.
+pub fn approve_device_pairing(
+    pairing_code: &str,
+    now_unix_ms: u64,
+) -> Result<LinkedDevice, String> {
+    let identity = ensure_stored_identity()?;
+    require_root_identity(&identity)?;
+    let seed = signing_seed_from_identity(&identity)?;
+    let request = decode_device_pairing_code(pairing_code)?;
+    let certificate = issue_device_certificate(
+        &seed,
+        &request.device_pubkey,
+        &request.device_name,
+        now_unix_ms,
+    )?;
+    let linked = LinkedDevice {
+        device_id: wayfarer_id_from_pubkey(&request.device_pubkey),
+        device_name: request.device_name,
+        device_pubkey_b64: base64::engine::general_purpose::STANDARD.encode(request.device_pubkey),
+        certificate_b64: base64::engine::general_purpose::STANDARD.encode(certificate),
+        linked_at_unix_ms: now_unix_ms,
+    };
+    let mut devices = load_linked_devices()?;
+    devices.retain(|device| device.device_id != linked.device_id);
+    devices.push(linked.clone());
+    save_linked_devices(&devices)?;
+    Ok(linked)
+}
+
+/// Finishes pairing with the certificate code from the root device. The local identity is
+/// replaced by the pending device key, which now speaks for the root wayfarer ID.
+pub fn complete_device_pairing(certificate_code: &str) -> Result<LocalIdentitySummary, String> {
+    let path = device_pairing_file_path();
+    if !path.exists() {
+        return Err("no device pairing is in progress".to_string());
+    }
+    let content = fs::read_to_string(&path).map_err(|err| {
+        format!(
+            "failed to read device pairing file at {}: {err}",
+            path.display()
+        )
+    })?;
+    let pending: PendingDevicePairing = serde_json::from_str(&content).map_err(|err| {
+        format!(
+            "failed to parse device pairing file at {}: {err}",
+            path.display()
+        )
+    })?;
+    let seed: [u8; 32] = base64::engine::general_purpose::STANDARD
+        .decode(&pending.signing_key_b64)
+        .map_err(|err| format!("failed to decode pending device key: {err}"))?
+        .try_into()
+        .map_err(|_| "pending device key had invalid length".to_string())?;
+
+    let certificate = device_certificate_from_code(certificate_code)?;
+    let summary = install_signing_seed(&seed, pending.device_name, Some(&certificate))?;
+    fs::remove_file(&path).map_err(|err| {
+        format!(
+            "failed to delete device pairing file at {}: {err}",
+            path.display()
+        )
+    })?;
+    Ok(summary)
+}
+
+/// Certificate of the local device key, or `None` when this install holds the root key.
+pub fn load_local_device_certificate() -> Result<Option<Vec<u8>>, String> {
+    let identity = ensure_stored_identity()?;
+    identity
+        .device_certificate_b64
+        .as_deref()
+        .map(|certificate_b64| {
+            base64::engine::general_purpose::STANDARD
+                .decode(certificate_b64)
+                .map_err(|err| format!("failed to decode device certificate: {err}"))
+        })
+        .transpose()
+}
+
+pub fn load_linked_devices() -> Result<Vec<LinkedDevice>, String> {
+    let path = linked_devices_file_path();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path).map_err(|err| {
+        format!(
+            "failed to read linked devices file at {}: {err}",
+            path.display()
+        )
+    })?;
+
+    serde_json::from_str(&content).map_err(|err| {
+        format!(
+            "failed to parse linked devices file at {}: {err}",
+            path.display()
+        )
+    })
+}
+
+/// Forgets a linked device. Certificates already issued stay valid for anyone holding them.
+pub fn unlink_device(device_id: &str) -> Result<bool, String> {
+    let mut devices = load_linked_devices()?;
+    let before = devices.len();
+    devices.retain(|device| device.device_id != device_id);
+    if devices.len() == before {
+        return Ok(false);
+    }
+    save_linked_devices(&devices)?;
+    Ok(true)
+}
+
+fn save_linked_devices(devices: &[LinkedDevice]) -> Result<(), String> {
+    let serialized = serde_json::to_string_pretty(devices)
+        .map_err(|err| format!("failed to serialize linked devices payload: {err}"))?;
+    write_app_file(&linked_devices_file_path(), serialized.as_bytes())
+}
+
 #[allow(dead_code)]
 pub fn delete_wayfair_id() -> Result<(), String> {
     delete_wayfarer_id()
@@ -217,24 +1275,364 @@ pub fn load_contact_aliases() -> Result<BTreeMap<String, String>, String> {
         )
     })?;
 
-    Ok(aliases)
+    Ok(aliases)
+}
+
+pub fn save_contact_aliases(aliases: &BTreeMap<String, String>) -> Result<(), String> {
+    let path = contact_aliases_file_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| {
+            format!(
+                "failed to create aliases directory at {}: {err}",
+                parent.display()
+            )
+        })?;
+    }
+
+    let serialized = serde_json::to_string_pretty(aliases)
+        .map_err(|err| format!("failed to serialize contact aliases payload: {err}"))?;
+
+    write_secure_file(&path, serialized.as_bytes())
+}
+
+pub fn load_contact_profiles() -> Result<BTreeMap<String, ContactProfile>, String> {
+    let path = contact_profiles_file_path();
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+
+    let content = fs::read_to_string(&path).map_err(|err| {
+        format!(
+            "failed to read contact profiles file at {}: {err}",
+            path.display()
+        )
+    })?;
+
+    serde_json::from_str(&content).map_err(|err| {
+        format!(
+            "failed to parse contact profiles file at {}: {err}",
+            path.display()
+        )
+    })
+}
+
+pub fn save_contact_profiles(profiles: &BTreeMap<String, ContactProfile>) -> Result<(), String> {
+    let path = contact_profiles_file_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| {
+            format!(
+                "failed to create profiles directory at {}: {err}",
+                parent.display()
+            )
+        })?;
+    }
+
+    let serialized = serde_json::to_string_pretty(profiles)
+        .map_err(|err| format!("failed to serialize contact profiles payload: {err}"))?;
+
+    write_secure_file(&path, serialized.as_bytes())
+}
+
+/// Stores `profile` for `wayfarer_id` if it is newer than the stored version. Returns whether
+/// the stored profile changed.
+pub fn apply_contact_profile(
+    profiles: &mut BTreeMap<String, ContactProfile>,
+    wayfarer_id: &str,
+    profile: &ProfileBody,
+) -> bool {
+    if profiles
+        .get(wayfarer_id)
+        .is_some_and(|existing| existing.version >= profile.version)
+    {
+        return false;
+    }
+    profiles.insert(wayfarer_id.to_string(), ContactProfile::from_body(profile));
+    true
+}
+
+pub fn load_contact_keys() -> Result<BTreeMap<String, ContactKey>, String> {
+    let path = contact_keys_file_path();
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+
+    let content = fs::read_to_string(&path).map_err(|err| {
+        format!(
+            "failed to read contact keys file at {}: {err}",
+            path.display()
+        )
+    })?;
+
+    serde_json::from_str(&content).map_err(|err| {
+        format!(
+            "failed to parse contact keys file at {}: {err}",
+            path.display()
+        )
+    })
+}
+
+pub fn save_contact_keys(keys: &BTreeMap<String, ContactKey>) -> Result<(), String> {
+    let path = contact_keys_file_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| {
+            format!(
+                "failed to create contact keys directory at {}: {err}",
+                parent.display()
+            )
+        })?;
+    }
+
+    let serialized = serde_json::to_string_pretty(keys)
+        .map_err(|err| format!("failed to serialize contact keys payload: {err}"))?;
+
+    write_secure_file(&path, serialized.as_bytes())
+}
+
+/// Records `pubkey` for `wayfarer_id` the first time it is seen. The ID is a hash of the key,
+/// so a mismatched pair is rejected rather than stored. Returns whether the map changed.
+pub fn record_contact_pubkey(
+    keys: &mut BTreeMap<String, ContactKey>,
+    wayfarer_id: &str,
+    pubkey: &[u8; 32],
+) -> Result<bool, String> {
+    if wayfarer_id_from_pubkey(pubkey) != wayfarer_id {
+        return Err("contact pubkey does not hash to its wayfarer ID".to_string());
+    }
+    if keys.contains_key(wayfarer_id) {
+        return Ok(false);
+    }
+    keys.insert(
+        wayfarer_id.to_string(),
+        ContactKey {
+            pubkey_b64: base64::engine::general_purpose::STANDARD.encode(pubkey),
+            verified: false,
+            verified_at_unix_ms: None,
+            device_pubkeys_b64: Vec::new(),
+        },
+    );
+    Ok(true)
+}
+
+/// Records a linked-device key for `wayfarer_id`. Only call this with keys taken from a
+/// verified device certificate chain. Returns whether the map changed.
+pub fn record_contact_device(
+    keys: &mut BTreeMap<String, ContactKey>,
+    wayfarer_id: &str,
+    root_pubkey: &[u8; 32],
+    device_pubkey: &[u8; 32],
+) -> Result<bool, String> {
+    let mut changed = record_contact_pubkey(keys, wayfarer_id, root_pubkey)?;
+    let key = keys
+        .get_mut(wayfarer_id)
+        .ok_or_else(|| "contact pubkey is not known yet".to_string())?;
+    if key.pubkey()? != *root_pubkey {
+        return Err("device certificate root does not match the recorded contact key".to_string());
+    }
+    let device_pubkey_b64 = base64::engine::general_purpose::STANDARD.encode(device_pubkey);
+    if !key.device_pubkeys_b64.contains(&device_pubkey_b64) {
+        key.device_pubkeys_b64.push(device_pubkey_b64);
+        changed = true;
+    }
+    Ok(changed)
+}
+
+/// Records the device behind a root-signed certificate announced by `wayfarer_id`. The
+/// certificate must verify and name `wayfarer_id` as its root. Returns whether the map changed.
+pub fn record_contact_device_certificate(
+    keys: &mut BTreeMap<String, ContactKey>,
+    wayfarer_id: &str,
+    certificate: &[u8],
+) -> Result<bool, String> {
+    let certificate = verify_device_certificate(certificate)?;
+    if certificate.wayfarer_id() != wayfarer_id {
+        return Err("device certificate was issued for a different identity".to_string());
+    }
+    record_contact_device(
+        keys,
+        wayfarer_id,
+        &certificate.root_pubkey,
+        &certificate.device_pubkey,
+    )
+}
+
+pub fn set_contact_verified(
+    keys: &mut BTreeMap<String, ContactKey>,
+    wayfarer_id: &str,
+    verified: bool,
+    now_unix_ms: u64,
+) -> Result<(), String> {
+    let key = keys
+        .get_mut(wayfarer_id)
+        .ok_or_else(|| "contact pubkey is not known yet".to_string())?;
+    key.verified = verified;
+    key.verified_at_unix_ms = verified.then_some(now_unix_ms);
+    Ok(())
+}
+
+pub fn wayfarer_id_from_pubkey(pubkey: &[u8; 32]) -> String {
+    sha256_hex_lower(pubkey)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdentityProfile {
+    pub name: String,
+    pub active: bool,
+    /// None until the profile has been opened once and generated its identity.
+    pub wayfarer_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct StoredProfiles {
+    #[serde(default)]
+    active: String,
+}
+
+/// Profile pinned for this process by `AETHOS_PROFILE`, if any. Errors when the name is not an
+/// existing profile, so callers can refuse to start rather than run some other profile.
+pub fn pinned_profile() -> Result<Option<String>, String> {
+    let Ok(name) = std::env::var("AETHOS_PROFILE") else {
+        return Ok(None);
+    };
+    let name = name.trim();
+    if name.is_empty() {
+        return Ok(None);
+    }
+    validate_profile_name(name)?;
+    if !profile_exists(name) {
+        return Err(format!(
+            "profile {name} pinned by AETHOS_PROFILE does not exist; create it first"
+        ));
+    }
+    Ok(Some(name.to_string()))
+}
+
+/// Profile whose identity, contacts and state dirs are in use. `AETHOS_PROFILE` pins it for
+/// one process, so two profiles can run side by side. Startup is expected to have checked the
+/// pin with [`pinned_profile`]; an unusable pin is never honoured here.
+pub fn active_profile() -> String {
+    if let Ok(Some(name)) = pinned_profile() {
+        return name;
+    }
+
+    let path = profiles_file_path();
+    let mut slot = ACTIVE_PROFILE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some((cached_path, name)) = slot.as_ref() {
+        if *cached_path == path {
+            return name.clone();
+        }
+    }
+
+    let name = fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<StoredProfiles>(&content).ok())
+        .map(|stored| stored.active)
+        .filter(|name| profile_exists(name))
+        .unwrap_or_else(|| DEFAULT_PROFILE_NAME.to_string());
+    *slot = Some((path, name.clone()));
+    name
 }
 
-pub fn save_contact_aliases(aliases: &BTreeMap<String, String>) -> Result<(), String> {
-    let path = contact_aliases_file_path();
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).map_err(|err| {
+pub fn list_profiles() -> Result<Vec<IdentityProfile>, String> {
+    let mut names = vec![DEFAULT_PROFILE_NAME.to_string()];
+    let profiles_dir = base_data_dir().join(APP_DIR_NAME).join(PROFILES_DIR_NAME);
+    if profiles_dir.exists() {
+        let entries = fs::read_dir(&profiles_dir).map_err(|err| {
             format!(
-                "failed to create aliases directory at {}: {err}",
-                parent.display()
+                "failed to read profiles directory at {}: {err}",
+                profiles_dir.display()
             )
         })?;
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.path().is_dir()
+                && name != DEFAULT_PROFILE_NAME
+                && validate_profile_name(&name).is_ok()
+            {
+                names.push(name);
+            }
+        }
     }
+    names[1..].sort();
+
+    let active = active_profile();
+    names
+        .into_iter()
+        .map(|name| {
+            let path = identity_file_path_for(app_data_dir_for(base_data_dir(), &name));
+            let wayfarer_id = load_identity_from(&path)?.map(|identity| identity.wayfarer_id);
+            Ok(IdentityProfile {
+                active: name == active,
+                name,
+                wayfarer_id,
+            })
+        })
+        .collect()
+}
 
-    let serialized = serde_json::to_string_pretty(aliases)
-        .map_err(|err| format!("failed to serialize contact aliases payload: {err}"))?;
+/// Creates an empty profile; its identity is generated the first time it is active.
+pub fn create_profile(name: &str) -> Result<(), String> {
+    let name = name.trim();
+    validate_profile_name(name)?;
+    if profile_exists(name) {
+        return Err(format!("profile {name} already exists"));
+    }
+    let dir = app_data_dir_for(base_data_dir(), name);
+    fs::create_dir_all(&dir).map_err(|err| {
+        format!(
+            "failed to create profile directory at {}: {err}",
+            dir.display()
+        )
+    })
+}
 
-    write_secure_file(&path, serialized.as_bytes())
+/// Makes `name` the active profile. Callers must restart anything holding the previous
+/// profile's identity or stores, such as the gossip workers.
+pub fn switch_profile(name: &str) -> Result<(), String> {
+    let name = name.trim();
+    validate_profile_name(name)?;
+    if !profile_exists(name) {
+        return Err(format!("profile {name} does not exist"));
+    }
+    let path = profiles_file_path();
+    let content = serde_json::to_vec_pretty(&StoredProfiles {
+        active: name.to_string(),
+    })
+    .map_err(|err| format!("failed to serialize profiles: {err}"))?;
+    write_app_file(&path, &content)?;
+    forget_unlocked_signing_seed();
+    *ACTIVE_PROFILE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some((path, name.to_string()));
+    Ok(())
+}
+
+/// Directory for per-profile runtime state under `state_base_dir`, e.g. the gossip store,
+/// chat history and app log.
+pub fn profile_state_dir(state_base_dir: PathBuf) -> PathBuf {
+    profile_dir_for(state_base_dir, &active_profile())
+}
+
+fn profile_exists(name: &str) -> bool {
+    name == DEFAULT_PROFILE_NAME
+        || (validate_profile_name(name).is_ok() && app_data_dir_for(base_data_dir(), name).is_dir())
+}
+
+fn validate_profile_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.chars().count() > MAX_PROFILE_NAME_CHARS {
+        return Err(format!(
+            "profile name must be 1 to {MAX_PROFILE_NAME_CHARS} characters"
+        ));
+    }
+    if !name
+        .chars()
+        .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-' || ch == '_')
+        || name.starts_with(['-', '_'])
+    {
+        return Err("profile name may only use lowercase letters, digits, '-' and '_'".to_string());
+    }
+    Ok(())
 }
 
 fn persist_identity(identity: &StoredIdentity) -> Result<(), String> {
@@ -255,12 +1653,15 @@ fn persist_identity(identity: &StoredIdentity) -> Result<(), String> {
 }
 
 fn load_identity() -> Result<Option<StoredIdentity>, String> {
-    let path = identity_file_path();
+    load_identity_from(&identity_file_path())
+}
+
+fn load_identity_from(path: &Path) -> Result<Option<StoredIdentity>, String> {
     if !path.exists() {
         return Ok(None);
     }
 
-    let content = fs::read_to_string(&path)
+    let content = fs::read_to_string(path)
         .map_err(|err| format!("failed to read identity file at {}: {err}", path.display()))?;
 
     let identity: StoredIdentity = serde_json::from_str(&content)
@@ -287,13 +1688,27 @@ fn summary_from_identity(identity: &StoredIdentity) -> Result<LocalIdentitySumma
         device_id: identity.device_id.clone(),
         verifying_key_b64: base64::engine::general_purpose::STANDARD.encode(verifying.to_bytes()),
         device_name: identity.device_name.clone(),
+        linked_device: identity.device_certificate_b64.is_some(),
     })
 }
 
 fn reconcile_identity(mut identity: StoredIdentity) -> Result<StoredIdentity, String> {
     let verifying = decode_verifying_key_from_identity(&identity)?;
-    let expected_wayfarer_id = sha256_hex_lower(&verifying.to_bytes());
-    let expected_device_id = sha256_hex_lower(&verifying.to_bytes());
+    let (expected_wayfarer_id, expected_device_id) =
+        match device_certificate_from_identity(&identity)? {
+            Some(certificate) => {
+                if certificate.device_pubkey != verifying.to_bytes() {
+                    return Err(
+                        "device certificate does not certify the stored signing key".to_string()
+                    );
+                }
+                (certificate.wayfarer_id(), certificate.device_id())
+            }
+            None => (
+                sha256_hex_lower(&verifying.to_bytes()),
+                sha256_hex_lower(&verifying.to_bytes()),
+            ),
+        };
 
     let needs_migration = identity.wayfarer_id != expected_wayfarer_id
         || identity.device_id != expected_device_id
@@ -310,6 +1725,16 @@ fn reconcile_identity(mut identity: StoredIdentity) -> Result<StoredIdentity, St
 }
 
 fn decode_verifying_key_from_identity(identity: &StoredIdentity) -> Result<VerifyingKey, String> {
+    if identity.wrapped_signing_key.is_some() {
+        let verifying_bytes: [u8; 32] = base64::engine::general_purpose::STANDARD
+            .decode(identity.verifying_key_b64.as_deref().unwrap_or_default())
+            .map_err(|err| format!("failed to decode verifying key: {err}"))?
+            .try_into()
+            .map_err(|_| "decoded verifying key had invalid length".to_string())?;
+        return VerifyingKey::from_bytes(&verifying_bytes)
+            .map_err(|err| format!("invalid stored verifying key: {err}"));
+    }
+
     let signing_bytes = base64::engine::general_purpose::STANDARD
         .decode(&identity.signing_key_b64)
         .map_err(|err| format!("failed to decode signing key: {err}"))?;
@@ -321,6 +1746,297 @@ fn decode_verifying_key_from_identity(identity: &StoredIdentity) -> Result<Verif
     Ok(SigningKey::from_bytes(&signing_arr).verifying_key())
 }
 
+fn install_signing_seed(
+    seed: &[u8; 32],
+    device_name: String,
+    device_certificate: Option<&[u8]>,
+) -> Result<LocalIdentitySummary, String> {
+    let identity = stored_identity_from_seed(seed, device_name, device_certificate)?;
+    persist_identity(&identity)?;
+    forget_unlocked_signing_seed();
+    summary_from_identity(&identity)
+}
+
+fn stored_identity_from_seed(
+    seed: &[u8; 32],
+    device_name: String,
+    device_certificate: Option<&[u8]>,
+) -> Result<StoredIdentity, String> {
+    let verifying = SigningKey::from_bytes(seed).verifying_key();
+    let mut identity = StoredIdentity {
+        wayfarer_id: sha256_hex_lower(&verifying.to_bytes()),
+        device_id: sha256_hex_lower(&verifying.to_bytes()),
+        signing_key_b64: base64::engine::general_purpose::STANDARD.encode(seed),
+        verifying_key_b64: None,
+        wrapped_signing_key: None,
+        device_certificate_b64: device_certificate
+            .map(|certificate| base64::engine::general_purpose::STANDARD.encode(certificate)),
+        device_name,
+        platform: "linux".to_string(),
+    };
+    if let Some(certificate) = device_certificate_from_identity(&identity)? {
+        if certificate.device_pubkey != verifying.to_bytes() {
+            return Err("device certificate does not certify the pending device key".to_string());
+        }
+        identity.wayfarer_id = certificate.wayfarer_id();
+        identity.device_id = certificate.device_id();
+    }
+    Ok(identity)
+}
+
+fn device_certificate_from_identity(
+    identity: &StoredIdentity,
+) -> Result<Option<DeviceCertificate>, String> {
+    let Some(certificate_b64) = identity.device_certificate_b64.as_deref() else {
+        return Ok(None);
+    };
+    let raw = base64::engine::general_purpose::STANDARD
+        .decode(certificate_b64)
+        .map_err(|err| format!("failed to decode device certificate: {err}"))?;
+    verify_device_certificate(&raw).map(Some)
+}
+
+fn require_root_identity(identity: &StoredIdentity) -> Result<(), String> {
+    if identity.device_certificate_b64.is_some() {
+        return Err("this is a linked device; use the root device instead".to_string());
+    }
+    Ok(())
+}
+
+fn decode_pubkey_b64(pubkey_b64: &str, context: &str) -> Result<[u8; 32], String> {
+    base64::engine::general_purpose::STANDARD
+        .decode(pubkey_b64)
+        .map_err(|err| format!("failed to decode {context}: {err}"))?
+        .try_into()
+        .map_err(|_| format!("decoded {context} had invalid length"))
+}
+
+fn check_seed_matches_wayfarer_id(seed: &[u8; 32], wayfarer_id: &str) -> Result<(), String> {
+    let derived = wayfarer_id_from_pubkey(&SigningKey::from_bytes(seed).verifying_key().to_bytes());
+    if derived != wayfarer_id.trim().to_ascii_lowercase() {
+        return Err(format!(
+            "restored signing key belongs to {derived}, not the recorded wayfarer ID {wayfarer_id}"
+        ));
+    }
+    Ok(())
+}
+
+fn backup_associated_data(wayfarer_id: &str) -> Vec<u8> {
+    format!("{IDENTITY_BACKUP_FORMAT_V1}:{wayfarer_id}").into_bytes()
+}
+
+fn signing_seed_from_identity(identity: &StoredIdentity) -> Result<[u8; 32], String> {
+    if identity.wrapped_signing_key.is_some() {
+        return cached_signing_seed(identity)
+            .ok_or_else(|| "identity is locked; unlock it with the passphrase".to_string());
+    }
+
+    base64::engine::general_purpose::STANDARD
+        .decode(&identity.signing_key_b64)
+        .map_err(|err| format!("failed to decode signing key: {err}"))?
+        .try_into()
+        .map_err(|_| "decoded signing key had invalid length".to_string())
+}
+
+fn cached_signing_seed(identity: &StoredIdentity) -> Option<[u8; 32]> {
+    let unlocked = UNLOCKED_SIGNING_SEED.lock().ok()?;
+    unlocked
+        .as_ref()
+        .filter(|unlocked| unlocked.wayfarer_id == identity.wayfarer_id)
+        .map(|unlocked| unlocked.seed)
+}
+
+/// Passphrase key of the unlocked identity; `None` for plaintext or locked identities.
+fn unlocked_passphrase_key(identity: &StoredIdentity) -> Option<PassphraseKey> {
+    identity.wrapped_signing_key.as_ref()?;
+    let unlocked = UNLOCKED_SIGNING_SEED.lock().ok()?;
+    unlocked
+        .as_ref()
+        .filter(|unlocked| unlocked.wayfarer_id == identity.wayfarer_id)
+        .map(|unlocked| unlocked.passphrase_key.clone())
+}
+
+fn remember_unlocked_identity(
+    wayfarer_id: String,
+    seed: [u8; 32],
+    passphrase_key: PassphraseKey,
+) -> Result<(), String> {
+    *UNLOCKED_SIGNING_SEED
+        .lock()
+        .map_err(|_| "identity unlock state is poisoned".to_string())? = Some(UnlockedIdentity {
+        wayfarer_id,
+        seed,
+        passphrase_key,
+    });
+    Ok(())
+}
+
+fn forget_unlocked_signing_seed() {
+    if let Ok(mut unlocked) = UNLOCKED_SIGNING_SEED.lock() {
+        *unlocked = None;
+    }
+}
+
+fn passphrase_key(
+    passphrase: &str,
+    salt: &[u8],
+    memory_kib: u32,
+    iterations: u32,
+    lanes: u32,
+) -> Result<[u8; 32], String> {
+    let params = Params::new(memory_kib, iterations, lanes, Some(32))
+        .map_err(|err| format!("invalid passphrase kdf parameters: {err}"))?;
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|err| format!("failed to derive passphrase key: {err}"))?;
+    Ok(key)
+}
+
+fn seal_with_passphrase(
+    plaintext: &[u8],
+    passphrase: &str,
+    associated_data: &[u8],
+) -> Result<PassphraseSealed, String> {
+    seal_with_key(plaintext, &new_passphrase_key(passphrase)?, associated_data)
+}
+
+fn open_with_passphrase(
+    sealed: &PassphraseSealed,
+    passphrase: &str,
+    associated_data: &[u8],
+) -> Result<Vec<u8>, String> {
+    open_with_key(
+        sealed,
+        &passphrase_key_for_sealed(sealed, passphrase)?,
+        associated_data,
+    )
+}
+
+fn new_passphrase_key(passphrase: &str) -> Result<PassphraseKey, String> {
+    use chacha20poly1305::aead::rand_core::RngCore;
+
+    let mut salt = [0u8; PASSPHRASE_SALT_BYTES];
+    OsRng.fill_bytes(&mut salt);
+    Ok(PassphraseKey {
+        key: passphrase_key(
+            passphrase,
+            &salt,
+            PASSPHRASE_KDF_MEMORY_KIB,
+            PASSPHRASE_KDF_ITERATIONS,
+            PASSPHRASE_KDF_LANES,
+        )?,
+        memory_kib: PASSPHRASE_KDF_MEMORY_KIB,
+        iterations: PASSPHRASE_KDF_ITERATIONS,
+        lanes: PASSPHRASE_KDF_LANES,
+        salt_b64: base64::engine::general_purpose::STANDARD.encode(salt),
+    })
+}
+
+/// Re-derives the key `sealed` was made with from its recorded salt and KDF parameters.
+fn passphrase_key_for_sealed(
+    sealed: &PassphraseSealed,
+    passphrase: &str,
+) -> Result<PassphraseKey, String> {
+    if sealed.kdf != PASSPHRASE_KDF_ARGON2ID {
+        return Err(format!("unsupported passphrase kdf {}", sealed.kdf));
+    }
+    if !(1..=MAX_PASSPHRASE_KDF_LANES).contains(&sealed.lanes)
+        || !(1..=MAX_PASSPHRASE_KDF_ITERATIONS).contains(&sealed.iterations)
+        || !(8 * sealed.lanes..=MAX_PASSPHRASE_KDF_MEMORY_KIB).contains(&sealed.memory_kib)
+    {
+        return Err(format!(
+            "passphrase kdf parameters out of range: memory_kib={} iterations={} lanes={}",
+            sealed.memory_kib, sealed.iterations, sealed.lanes
+        ));
+    }
+    let salt = base64::engine::general_purpose::STANDARD
+        .decode(&sealed.salt_b64)
+        .map_err(|err| format!("failed to decode passphrase salt: {err}"))?;
+    Ok(PassphraseKey {
+        key: passphrase_key(
+            passphrase,
+            &salt,
+            sealed.memory_kib,
+            sealed.iterations,
+            sealed.lanes,
+        )?,
+        memory_kib: sealed.memory_kib,
+        iterations: sealed.iterations,
+        lanes: sealed.lanes,
+        salt_b64: sealed.salt_b64.clone(),
+    })
+}
+
+fn seal_with_key(
+    plaintext: &[u8],
+    key: &PassphraseKey,
+    associated_data: &[u8],
+) -> Result<PassphraseSealed, String> {
+    use chacha20poly1305::aead::rand_core::RngCore;
+    use chacha20poly1305::aead::Payload;
+
+    let mut nonce_bytes = [0u8; 12];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let ciphertext = ChaCha20Poly1305::new(Key::from_slice(&key.key))
+        .encrypt(
+            Nonce::from_slice(&nonce_bytes),
+            Payload {
+                msg: plaintext,
+                aad: associated_data,
+            },
+        )
+        .map_err(|err| format!("failed to seal with passphrase: {err}"))?;
+
+    Ok(PassphraseSealed {
+        kdf: PASSPHRASE_KDF_ARGON2ID.to_string(),
+        memory_kib: key.memory_kib,
+        iterations: key.iterations,
+        lanes: key.lanes,
+        salt_b64: key.salt_b64.clone(),
+        nonce_b64: base64::engine::general_purpose::STANDARD.encode(nonce_bytes),
+        ciphertext_b64: base64::engine::general_purpose::STANDARD.encode(ciphertext),
+    })
+}
+
+fn open_with_key(
+    sealed: &PassphraseSealed,
+    key: &PassphraseKey,
+    associated_data: &[u8],
+) -> Result<Vec<u8>, String> {
+    use chacha20poly1305::aead::Payload;
+
+    let nonce_bytes = base64::engine::general_purpose::STANDARD
+        .decode(&sealed.nonce_b64)
+        .map_err(|err| format!("failed to decode sealed nonce: {err}"))?;
+    if nonce_bytes.len() != 12 {
+        return Err("sealed nonce had invalid length".to_string());
+    }
+    let ciphertext = base64::engine::general_purpose::STANDARD
+        .decode(&sealed.ciphertext_b64)
+        .map_err(|err| format!("failed to decode sealed payload: {err}"))?;
+
+    ChaCha20Poly1305::new(Key::from_slice(&key.key))
+        .decrypt(
+            Nonce::from_slice(&nonce_bytes),
+            Payload {
+                msg: &ciphertext,
+                aad: associated_data,
+            },
+        )
+        .map_err(|_| "incorrect passphrase".to_string())
+}
+
+fn open_seed_with_key(
+    sealed: &PassphraseSealed,
+    key: &PassphraseKey,
+    wayfarer_id: &str,
+) -> Result<[u8; 32], String> {
+    open_with_key(sealed, key, wayfarer_id.as_bytes())?
+        .try_into()
+        .map_err(|_| "unwrapped signing key had invalid length".to_string())
+}
+
 fn sha256_hex_lower(input: &[u8]) -> String {
     let digest = Sha256::digest(input);
     let mut out = String::with_capacity(digest.len() * 2);
@@ -331,9 +2047,8 @@ fn sha256_hex_lower(input: &[u8]) -> String {
 }
 
 fn cipher_from_identity(identity: &StoredIdentity) -> Result<ChaCha20Poly1305, String> {
-    let signing_seed = base64::engine::general_purpose::STANDARD
-        .decode(&identity.signing_key_b64)
-        .map_err(|err| format!("failed to decode signing key for cache cipher: {err}"))?;
+    let signing_seed = signing_seed_from_identity(identity)
+        .map_err(|err| format!("failed to load signing key for cache cipher: {err}"))?;
 
     let mut hasher = Sha256::new();
     hasher.update(signing_seed);
@@ -369,16 +2084,84 @@ fn write_secure_file(path: &Path, content: &[u8]) -> Result<(), String> {
     Ok(())
 }
 
+fn write_app_file(path: &Path, content: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| {
+            format!(
+                "failed to create app data directory at {}: {err}",
+                parent.display()
+            )
+        })?;
+    }
+    write_secure_file(path, content)
+}
+
 fn identity_file_path() -> PathBuf {
-    identity_file_path_for(base_data_dir())
+    identity_file_path_for(app_data_dir())
 }
 
 fn session_cache_file_path() -> PathBuf {
-    session_cache_file_path_for(base_data_dir())
+    session_cache_file_path_for(app_data_dir())
 }
 
 fn contact_aliases_file_path() -> PathBuf {
-    contact_aliases_file_path_for(base_data_dir())
+    contact_aliases_file_path_for(app_data_dir())
+}
+
+fn contact_profiles_file_path() -> PathBuf {
+    contact_profiles_file_path_for(app_data_dir())
+}
+
+fn contact_keys_file_path() -> PathBuf {
+    contact_keys_file_path_for(app_data_dir())
+}
+
+fn device_pairing_file_path() -> PathBuf {
+    app_data_dir().join(DEVICE_PAIRING_FILE_NAME)
+}
+
+fn linked_devices_file_path() -> PathBuf {
+    app_data_dir().join(LINKED_DEVICES_FILE_NAME)
+}
+
+fn retired_identities_file_path() -> PathBuf {
+    app_data_dir().join(RETIRED_IDENTITIES_FILE_NAME)
+}
+
+fn contact_successions_file_path() -> PathBuf {
+    app_data_dir().join(CONTACT_SUCCESSIONS_FILE_NAME)
+}
+
+fn held_recovery_shares_file_path() -> PathBuf {
+    app_data_dir().join(HELD_RECOVERY_SHARES_FILE_NAME)
+}
+
+fn social_recovery_file_path() -> PathBuf {
+    app_data_dir().join(SOCIAL_RECOVERY_FILE_NAME)
+}
+
+fn revoked_identities_file_path() -> PathBuf {
+    app_data_dir().join(REVOKED_IDENTITIES_FILE_NAME)
+}
+
+fn profiles_file_path() -> PathBuf {
+    base_data_dir().join(APP_DIR_NAME).join(PROFILES_FILE_NAME)
+}
+
+fn app_data_dir() -> PathBuf {
+    app_data_dir_for(base_data_dir(), &active_profile())
+}
+
+fn app_data_dir_for(base_dir: PathBuf, profile: &str) -> PathBuf {
+    profile_dir_for(base_dir.join(APP_DIR_NAME), profile)
+}
+
+fn profile_dir_for(app_dir: PathBuf, profile: &str) -> PathBuf {
+    if profile == DEFAULT_PROFILE_NAME {
+        app_dir
+    } else {
+        app_dir.join(PROFILES_DIR_NAME).join(profile)
+    }
 }
 
 fn base_data_dir() -> PathBuf {
@@ -395,44 +2178,159 @@ fn base_data_dir() -> PathBuf {
     std::env::temp_dir()
 }
 
-fn identity_file_path_for(base_dir: PathBuf) -> PathBuf {
-    base_dir.join(APP_DIR_NAME).join(IDENTITY_FILE_NAME)
+fn identity_file_path_for(app_dir: PathBuf) -> PathBuf {
+    app_dir.join(IDENTITY_FILE_NAME)
+}
+
+fn session_cache_file_path_for(app_dir: PathBuf) -> PathBuf {
+    app_dir.join(SESSION_CACHE_FILE_NAME)
+}
+
+fn contact_aliases_file_path_for(app_dir: PathBuf) -> PathBuf {
+    app_dir.join(CONTACT_ALIASES_FILE_NAME)
 }
 
-fn session_cache_file_path_for(base_dir: PathBuf) -> PathBuf {
-    base_dir.join(APP_DIR_NAME).join(SESSION_CACHE_FILE_NAME)
+fn contact_profiles_file_path_for(app_dir: PathBuf) -> PathBuf {
+    app_dir.join(CONTACT_PROFILES_FILE_NAME)
 }
 
-fn contact_aliases_file_path_for(base_dir: PathBuf) -> PathBuf {
-    base_dir.join(APP_DIR_NAME).join(CONTACT_ALIASES_FILE_NAME)
+fn contact_keys_file_path_for(app_dir: PathBuf) -> PathBuf {
+    app_dir.join(CONTACT_KEYS_FILE_NAME)
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        cipher_from_identity, contact_aliases_file_path_for, decode_verifying_key_from_identity,
-        identity_file_path_for, session_cache_file_path_for, sha256_hex_lower, StoredIdentity,
+        active_profile, create_profile, ensure_local_identity, list_profiles, pinned_profile,
+        restore_identity_backup, switch_profile,
+    };
+    use super::{
+        app_data_dir_for, apply_contact_profile, check_seed_matches_wayfarer_id,
+        cipher_from_identity, contact_aliases_file_path_for, contact_profiles_file_path_for,
+        decode_verifying_key_from_identity, identity_file_path_for, migrate_contact_succession,
+        open_with_passphrase, profile_dir_for, reconcile_identity, record_contact_device,
+        record_contact_device_certificate, record_contact_pubkey, recovery_phrase_from_seed,
+        require_root_identity, resolve_contact_succession, seal_with_passphrase,
+        seed_from_recovery_phrase, session_cache_file_path_for, set_contact_verified,
+        sha256_hex_lower, validate_profile_name, wayfarer_id_from_pubkey, ContactKey,
+        StoredIdentity, DEFAULT_PROFILE_NAME, MAX_PROFILE_NAME_CHARS,
     };
+    use crate::aethos_core::app_body::ProfileBody;
+    use crate::aethos_core::device_link::issue_device_certificate;
+    use crate::aethos_core::succession::{issue_succession_record, verify_succession_record};
+    use crate::aethos_core::test_env::{test_env_lock, unique_test_state_dir, EnvVarGuard};
     use base64::Engine;
     use chacha20poly1305::aead::Aead;
     use chacha20poly1305::{ChaCha20Poly1305, Nonce};
     use ed25519_dalek::SigningKey;
+    use std::collections::BTreeMap;
     use std::path::PathBuf;
 
     #[test]
     fn identity_file_path_uses_app_subdir() {
         let base = PathBuf::from("/tmp/test-data");
-        let path = identity_file_path_for(base);
+        let path = identity_file_path_for(app_data_dir_for(base, DEFAULT_PROFILE_NAME));
         assert_eq!(
             path,
             PathBuf::from("/tmp/test-data/aethos-linux/identity.json")
         );
     }
 
+    #[test]
+    fn named_profile_files_live_under_profiles_subdir() {
+        let base = PathBuf::from("/tmp/test-data");
+        let path = identity_file_path_for(app_data_dir_for(base, "work"));
+        assert_eq!(
+            path,
+            PathBuf::from("/tmp/test-data/aethos-linux/profiles/work/identity.json")
+        );
+        assert_eq!(
+            profile_dir_for(PathBuf::from("/tmp/state/aethos-linux"), "work"),
+            PathBuf::from("/tmp/state/aethos-linux/profiles/work")
+        );
+    }
+
+    #[test]
+    fn profile_names_are_restricted_to_path_safe_characters() {
+        assert!(validate_profile_name("work-2").is_ok());
+        assert!(validate_profile_name("").is_err());
+        assert!(validate_profile_name("../escape").is_err());
+        assert!(validate_profile_name("Work").is_err());
+        assert!(validate_profile_name("-flag").is_err());
+        assert!(validate_profile_name(&"a".repeat(MAX_PROFILE_NAME_CHARS + 1)).is_err());
+    }
+
+    #[test]
+    fn switching_profiles_swaps_identity() {
+        let _lock = test_env_lock().lock().expect("lock test env");
+        let temp_dir = unique_test_state_dir("aethos-identity-profiles");
+        let _data_guard = EnvVarGuard::set("XDG_DATA_HOME", &temp_dir.join("data"));
+        let _profile_guard = EnvVarGuard::clear("AETHOS_PROFILE");
+
+        assert_eq!(active_profile(), DEFAULT_PROFILE_NAME);
+        let default_identity = ensure_local_identity().expect("default identity");
+
+        create_profile("work").expect("create profile");
+        assert!(create_profile("work").is_err());
+        switch_profile("work").expect("switch profile");
+        let work_identity = ensure_local_identity().expect("work identity");
+        assert_ne!(work_identity.wayfarer_id, default_identity.wayfarer_id);
+
+        let profiles = list_profiles().expect("list profiles");
+        assert_eq!(profiles.len(), 2);
+        assert_eq!(profiles[0].name, DEFAULT_PROFILE_NAME);
+        assert_eq!(
+            profiles[0].wayfarer_id.as_deref(),
+            Some(default_identity.wayfarer_id.as_str())
+        );
+        assert!(profiles[1].active);
+        assert_eq!(
+            profiles[1].wayfarer_id.as_deref(),
+            Some(work_identity.wayfarer_id.as_str())
+        );
+
+        switch_profile(DEFAULT_PROFILE_NAME).expect("switch back");
+        assert_eq!(
+            ensure_local_identity()
+                .expect("default identity")
+                .wayfarer_id,
+            default_identity.wayfarer_id
+        );
+        assert!(switch_profile("missing").is_err());
+    }
+
+    #[test]
+    fn aethos_profile_pins_only_existing_profiles() {
+        let _lock = test_env_lock().lock().expect("lock test env");
+        let temp_dir = unique_test_state_dir("aethos-identity-profile-env");
+        let _data_guard = EnvVarGuard::set("XDG_DATA_HOME", &temp_dir.join("data"));
+        let _profile_guard = EnvVarGuard::set("AETHOS_PROFILE", std::path::Path::new("work"));
+
+        assert!(pinned_profile()
+            .unwrap_err()
+            .starts_with("profile work pinned by AETHOS_PROFILE does not exist"));
+        create_profile("work").expect("create profile");
+        assert_eq!(
+            pinned_profile().expect("pinned profile").as_deref(),
+            Some("work")
+        );
+        assert_eq!(active_profile(), "work");
+
+        let _missing_guard = EnvVarGuard::set("AETHOS_PROFILE", std::path::Path::new("missing"));
+        assert!(pinned_profile().is_err());
+        assert_eq!(active_profile(), DEFAULT_PROFILE_NAME);
+        assert!(!temp_dir
+            .join("data")
+            .join("aethos-linux")
+            .join("profiles")
+            .join("missing")
+            .exists());
+    }
+
     #[test]
     fn session_cache_path_uses_app_subdir() {
         let base = PathBuf::from("/tmp/test-data");
-        let path = session_cache_file_path_for(base);
+        let path = session_cache_file_path_for(app_data_dir_for(base, DEFAULT_PROFILE_NAME));
         assert_eq!(
             path,
             PathBuf::from("/tmp/test-data/aethos-linux/session-cache.enc.json")
@@ -442,13 +2340,62 @@ mod tests {
     #[test]
     fn contact_aliases_path_uses_app_subdir() {
         let base = PathBuf::from("/tmp/test-data");
-        let path = contact_aliases_file_path_for(base);
+        let path = contact_aliases_file_path_for(app_data_dir_for(base, DEFAULT_PROFILE_NAME));
         assert_eq!(
             path,
             PathBuf::from("/tmp/test-data/aethos-linux/contact-aliases.json")
         );
     }
 
+    #[test]
+    fn contact_profiles_path_uses_app_subdir() {
+        let base = PathBuf::from("/tmp/test-data");
+        let path = contact_profiles_file_path_for(app_data_dir_for(base, DEFAULT_PROFILE_NAME));
+        assert_eq!(
+            path,
+            PathBuf::from("/tmp/test-data/aethos-linux/contact-profiles.json")
+        );
+    }
+
+    #[test]
+    fn contact_profile_newer_version_wins() {
+        let contact = "a".repeat(64);
+        let profile = |version, display_name: &str| ProfileBody {
+            version,
+            display_name: display_name.to_string(),
+            avatar_thumbnail: None,
+            status_line: None,
+            preferred_relays: Vec::new(),
+            created_at_unix_ms: version * 1_000,
+        };
+
+        let mut profiles = BTreeMap::new();
+        assert!(apply_contact_profile(
+            &mut profiles,
+            &contact,
+            &profile(2, "Robin")
+        ));
+        assert!(!apply_contact_profile(
+            &mut profiles,
+            &contact,
+            &profile(1, "Rob")
+        ));
+        assert!(!apply_contact_profile(
+            &mut profiles,
+            &contact,
+            &profile(2, "Rob")
+        ));
+        assert_eq!(profiles[&contact].display_name, "Robin");
+
+        assert!(apply_contact_profile(
+            &mut profiles,
+            &contact,
+            &profile(3, "Robin H")
+        ));
+        assert_eq!(profiles[&contact].version, 3);
+        assert_eq!(profiles[&contact].display_name, "Robin H");
+    }
+
     #[test]
     fn sha256_hex_encoding_is_lowercase_and_fixed_width() {
         let digest = sha256_hex_lower(b"abc");
@@ -468,6 +2415,9 @@ mod tests {
             wayfarer_id: digest.clone(),
             device_id: digest,
             signing_key_b64: base64::engine::general_purpose::STANDARD.encode(seed),
+            verifying_key_b64: None,
+            wrapped_signing_key: None,
+            device_certificate_b64: None,
             device_name: "fixture-device".to_string(),
             platform: "linux".to_string(),
         }
@@ -495,4 +2445,223 @@ mod tests {
 
         let _type_check: &ChaCha20Poly1305 = &cipher_a;
     }
+
+    #[test]
+    fn contact_pubkey_must_match_id_before_it_can_be_verified() {
+        let pubkey = SigningKey::from_bytes(&[9u8; 32])
+            .verifying_key()
+            .to_bytes();
+        let wayfarer_id = wayfarer_id_from_pubkey(&pubkey);
+        let mut keys = BTreeMap::new();
+
+        assert!(set_contact_verified(&mut keys, &wayfarer_id, true, 1).is_err());
+        assert!(record_contact_pubkey(&mut keys, &"0".repeat(64), &pubkey).is_err());
+        assert!(record_contact_pubkey(&mut keys, &wayfarer_id, &pubkey).expect("record"));
+        assert!(!record_contact_pubkey(&mut keys, &wayfarer_id, &pubkey).expect("repeat"));
+
+        set_contact_verified(&mut keys, &wayfarer_id, true, 42).expect("verify");
+        assert!(keys[&wayfarer_id].verified);
+        assert_eq!(keys[&wayfarer_id].verified_at_unix_ms, Some(42));
+        assert_eq!(keys[&wayfarer_id].pubkey().expect("decode pubkey"), pubkey);
+    }
+
+    #[test]
+    fn linked_device_identity_speaks_for_the_root_wayfarer_id() {
+        let root = fixture_identity();
+        let device_seed = [9u8; 32];
+        let device_pubkey = SigningKey::from_bytes(&device_seed)
+            .verifying_key()
+            .to_bytes();
+        let certificate = issue_device_certificate(&[7u8; 32], &device_pubkey, "desktop", 1)
+            .expect("issue certificate");
+        let linked = StoredIdentity {
+            device_id: wayfarer_id_from_pubkey(&device_pubkey),
+            signing_key_b64: base64::engine::general_purpose::STANDARD.encode(device_seed),
+            device_certificate_b64: Some(
+                base64::engine::general_purpose::STANDARD.encode(&certificate),
+            ),
+            ..root.clone()
+        };
+        let reconciled = reconcile_identity(linked.clone()).expect("reconcile linked identity");
+        assert_eq!(reconciled.wayfarer_id, root.wayfarer_id);
+        assert_ne!(reconciled.device_id, root.device_id);
+        assert!(require_root_identity(&reconciled).is_err());
+
+        let foreign = StoredIdentity {
+            signing_key_b64: base64::engine::general_purpose::STANDARD.encode([10u8; 32]),
+            ..linked
+        };
+        assert!(reconcile_identity(foreign).is_err());
+
+        let root_pubkey = SigningKey::from_bytes(&[7u8; 32])
+            .verifying_key()
+            .to_bytes();
+        let mut keys = BTreeMap::new();
+        assert!(
+            record_contact_device(&mut keys, &root.wayfarer_id, &root_pubkey, &device_pubkey)
+                .expect("record device")
+        );
+        assert!(
+            !record_contact_device(&mut keys, &root.wayfarer_id, &root_pubkey, &device_pubkey)
+                .expect("repeat device")
+        );
+        assert_eq!(
+            keys[&root.wayfarer_id]
+                .device_pubkeys()
+                .expect("device keys"),
+            vec![device_pubkey]
+        );
+
+        let mut announced = BTreeMap::new();
+        assert!(
+            record_contact_device_certificate(&mut announced, &"0".repeat(64), &certificate)
+                .is_err()
+        );
+        assert!(
+            record_contact_device_certificate(&mut announced, &root.wayfarer_id, &certificate)
+                .expect("record announced device")
+        );
+        assert_eq!(announced, keys);
+    }
+
+    #[test]
+    fn contact_succession_moves_alias_key_and_verification() {
+        let raw = issue_succession_record(&[9u8; 32], &[10u8; 32], 5).expect("issue record");
+        let record = verify_succession_record(&raw).expect("verify record");
+        let old_wayfarer_id = record.old_wayfarer_id();
+        let new_wayfarer_id = record.new_wayfarer_id();
+
+        let mut aliases = BTreeMap::from([(old_wayfarer_id.clone(), "Sam".to_string())]);
+        let mut keys = BTreeMap::new();
+        record_contact_pubkey(&mut keys, &old_wayfarer_id, &record.old_pubkey).expect("record");
+        set_contact_verified(&mut keys, &old_wayfarer_id, true, 3).expect("verify");
+        let mut profiles = BTreeMap::new();
+        let mut successions = BTreeMap::new();
+
+        assert!(migrate_contact_succession(
+            &mut aliases,
+            &mut keys,
+            &mut profiles,
+            &mut successions,
+            &record
+        )
+        .expect("migrate"));
+        assert_eq!(
+            aliases.get(&new_wayfarer_id).map(String::as_str),
+            Some("Sam")
+        );
+        assert!(!aliases.contains_key(&old_wayfarer_id));
+        assert_eq!(
+            keys[&new_wayfarer_id].pubkey().expect("new key"),
+            record.new_pubkey
+        );
+        assert!(keys[&new_wayfarer_id].verified);
+        assert_eq!(
+            resolve_contact_succession(&successions, &old_wayfarer_id),
+            new_wayfarer_id
+        );
+        assert!(!migrate_contact_succession(
+            &mut aliases,
+            &mut keys,
+            &mut profiles,
+            &mut successions,
+            &record
+        )
+        .expect("repeat"));
+
+        let mut keys = BTreeMap::new();
+        let other_pubkey = SigningKey::from_bytes(&[11u8; 32])
+            .verifying_key()
+            .to_bytes();
+        keys.insert(
+            old_wayfarer_id,
+            ContactKey {
+                pubkey_b64: base64::engine::general_purpose::STANDARD.encode(other_pubkey),
+                verified: false,
+                verified_at_unix_ms: None,
+                device_pubkeys_b64: Vec::new(),
+            },
+        );
+        assert!(migrate_contact_succession(
+            &mut BTreeMap::new(),
+            &mut keys,
+            &mut BTreeMap::new(),
+            &mut BTreeMap::new(),
+            &record
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn backup_with_oversized_kdf_parameters_is_rejected_before_derivation() {
+        let identity = fixture_identity();
+        let sealed = seal_with_passphrase(b"{}", "correct horse", b"unused").expect("seal");
+        for (memory_kib, iterations, lanes) in [
+            (4_000_000_000, sealed.iterations, sealed.lanes),
+            (sealed.memory_kib, u32::MAX, sealed.lanes),
+            (sealed.memory_kib, sealed.iterations, 0),
+        ] {
+            let backup = serde_json::json!({
+                "format": super::IDENTITY_BACKUP_FORMAT_V1,
+                "wayfarer_id": identity.wayfarer_id,
+                "created_at_unix_ms": 0,
+                "sealed": super::PassphraseSealed {
+                    memory_kib,
+                    iterations,
+                    lanes,
+                    ..sealed.clone()
+                },
+            });
+            let err = restore_identity_backup(&backup.to_string(), "correct horse").unwrap_err();
+            assert!(
+                err.starts_with("passphrase kdf parameters out of range"),
+                "{err}"
+            );
+        }
+    }
+
+    #[test]
+    fn wrapped_signing_key_needs_passphrase_and_keeps_wayfarer_id() {
+        let plaintext = fixture_identity();
+        let seed = [7u8; 32];
+        let id = plaintext.wayfarer_id.as_bytes();
+        let wrapped = seal_with_passphrase(&seed, "correct horse", id).expect("wrap signing key");
+
+        assert_eq!(
+            open_with_passphrase(&wrapped, "correct horse", id).expect("unwrap signing key"),
+            seed
+        );
+        assert_eq!(
+            open_with_passphrase(&wrapped, "wrong horse", id).unwrap_err(),
+            "incorrect passphrase"
+        );
+        assert!(
+            open_with_passphrase(&wrapped, "correct horse", "0".repeat(64).as_bytes()).is_err()
+        );
+
+        let locked = StoredIdentity {
+            signing_key_b64: String::new(),
+            verifying_key_b64: Some(
+                base64::engine::general_purpose::STANDARD.encode(
+                    decode_verifying_key_from_identity(&plaintext)
+                        .unwrap()
+                        .to_bytes(),
+                ),
+            ),
+            wrapped_signing_key: Some(wrapped),
+            ..plaintext.clone()
+        };
+        let reconciled = reconcile_identity(locked).expect("reconcile locked identity");
+        assert_eq!(reconciled.wayfarer_id, plaintext.wayfarer_id);
+        assert!(cipher_from_identity(&reconciled).is_err());
+    }
+
+    #[test]
+    fn recovery_phrase_restores_only_the_matching_wayfarer_id() {
+        let identity = fixture_identity();
+        let restored = seed_from_recovery_phrase(&recovery_phrase_from_seed(&[7u8; 32]))
+            .expect("parse phrase");
+        check_seed_matches_wayfarer_id(&restored, &identity.wayfarer_id).expect("matching id");
+        assert!(check_seed_matches_wayfarer_id(&[8u8; 32], &identity.wayfarer_id).is_err());
+    }
 }
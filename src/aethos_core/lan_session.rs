@@ -0,0 +1,585 @@
+use std::io::{Read, Write};
+
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::aethos_core::gossip_sync::{
+    parse_frame_for_peer, serialize_frame_for_peer, GossipSessionProfile, GossipSyncFrame,
+};
+use crate::aethos_core::identity_store::wayfarer_id_from_pubkey;
+
+/// HELLO capability for peers whose LAN TCP listener accepts encrypted sessions.
+pub const LAN_SESSION_CAPABILITY: &str = "lan_tcp_session_v1";
+/// Sent by the initiator before its ephemeral key. A plaintext peer reads the first four bytes
+/// as a frame length far above `MAX_FRAME_BYTES`, so the two can never be confused.
+pub const LAN_SESSION_MAGIC: [u8; 8] = *b"AETHLS01";
+const LAN_SESSION_PROTOCOL_NAME: &[u8] = b"AETHOS_LAN_SESSION_V1_XX_25519_CHACHAPOLY_SHA256";
+const LAN_SESSION_CHAIN_KEY_DOMAIN: &[u8] = b"AETHOS_LAN_SESSION_V1_CK";
+const LAN_SESSION_CIPHER_KEY_DOMAIN: &[u8] = b"AETHOS_LAN_SESSION_V1_K";
+const TAG_BYTES: usize = 16;
+const KEY_BYTES: usize = 32;
+const HANDSHAKE_RESPONSE_BYTES: usize = KEY_BYTES + (KEY_BYTES + TAG_BYTES) + TAG_BYTES;
+const HANDSHAKE_FINISH_BYTES: usize = (KEY_BYTES + TAG_BYTES) + TAG_BYTES;
+
+/// What a LAN TCP endpoint does with a peer that cannot run the encrypted session handshake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LanDowngradePolicy {
+    /// Talk plaintext GossipV1 framing to peers that do not advertise `lan_tcp_session_v1`.
+    #[default]
+    AllowPlaintext,
+    /// Refuse any LAN TCP session that is not encrypted.
+    RequireEncrypted,
+}
+
+impl LanDowngradePolicy {
+    pub fn allows_plaintext(self) -> bool {
+        self == Self::AllowPlaintext
+    }
+}
+
+/// Identity the remote end proved during the handshake.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanSessionPeer {
+    pub node_pubkey: [u8; 32],
+    pub node_id: String,
+}
+
+/// Length-prefixed GossipV1 frames over a LAN stream, sealed with per-direction keys when
+/// the session was set up with the handshake.
+///
+/// The handshake follows the Noise XX pattern (`-> e`, `<- e, ee, s, es`, `-> s, se`) with
+/// the Ed25519 identity keys as statics, mapped to X25519 the same way sealed envelopes are,
+/// and SHA-256 chaining in place of HKDF. Both sides end up knowing the other's identity key
+/// and nobody on the path sees inventory or envelopes.
+pub struct LanSession<S> {
+    stream: S,
+    transport: Option<TransportKeys>,
+    remote: Option<LanSessionPeer>,
+    pending_length_prefix: Option<[u8; 4]>,
+    profile: GossipSessionProfile,
+}
+
+struct TransportKeys {
+    send: ChaCha20Poly1305,
+    send_counter: u64,
+    recv: ChaCha20Poly1305,
+    recv_counter: u64,
+}
+
+impl<S: Read + Write> LanSession<S> {
+    pub fn plaintext(stream: S) -> Self {
+        Self {
+            stream,
+            transport: None,
+            remote: None,
+            pending_length_prefix: None,
+            profile: GossipSessionProfile::baseline(),
+        }
+    }
+
+    /// Runs the initiator side of the handshake with the identity behind `signing_seed`.
+    pub fn initiate(mut stream: S, signing_seed: &[u8; 32]) -> Result<Self, String> {
+        let local_static = SigningKey::from_bytes(signing_seed);
+        let ephemeral = SigningKey::generate(&mut OsRng);
+        let ephemeral_pubkey = ephemeral.verifying_key().to_bytes();
+        let mut handshake = HandshakeState::new();
+
+        let mut hello = LAN_SESSION_MAGIC.to_vec();
+        hello.extend_from_slice(&ephemeral_pubkey);
+        handshake.mix_hash(&ephemeral_pubkey);
+        write_all(&mut stream, &hello, "handshake hello")?;
+
+        let mut response = [0u8; HANDSHAKE_RESPONSE_BYTES];
+        read_exact(&mut stream, &mut response, "handshake response")?;
+        let (remote_ephemeral, rest) = response.split_at(KEY_BYTES);
+        let remote_ephemeral: [u8; 32] = remote_ephemeral.try_into().expect("split at 32");
+        let (encrypted_static, encrypted_payload) = rest.split_at(KEY_BYTES + TAG_BYTES);
+        handshake.mix_hash(&remote_ephemeral);
+        handshake.mix_key(&diffie_hellman(&ephemeral, &remote_ephemeral)?);
+        let remote_static: [u8; 32] = handshake
+            .decrypt_and_hash(encrypted_static)?
+            .try_into()
+            .map_err(|_| "lan session remote static key length mismatch".to_string())?;
+        handshake.mix_key(&diffie_hellman(&ephemeral, &remote_static)?);
+        handshake.decrypt_and_hash(encrypted_payload)?;
+
+        let mut finish = handshake.encrypt_and_hash(&local_static.verifying_key().to_bytes())?;
+        handshake.mix_key(&diffie_hellman(&local_static, &remote_ephemeral)?);
+        finish.extend(handshake.encrypt_and_hash(&[])?);
+        write_all(&mut stream, &finish, "handshake finish")?;
+
+        let (initiator_to_responder, responder_to_initiator) = handshake.split();
+        Ok(Self {
+            stream,
+            transport: Some(TransportKeys::new(
+                initiator_to_responder,
+                responder_to_initiator,
+            )),
+            remote: Some(LanSessionPeer::from_pubkey(remote_static)),
+            pending_length_prefix: None,
+            profile: GossipSessionProfile::baseline(),
+        })
+    }
+
+    /// Waits for the first bytes from an inbound connection and runs the responder side of the
+    /// handshake if they are the session magic. Anything else is a plaintext GossipV1 peer,
+    /// accepted only when `policy` allows it.
+    pub fn accept(
+        mut stream: S,
+        signing_seed: &[u8; 32],
+        policy: LanDowngradePolicy,
+    ) -> Result<Self, String> {
+        let mut prefix = [0u8; 4];
+        read_exact(&mut stream, &mut prefix, "session prefix")?;
+        if prefix != LAN_SESSION_MAGIC[..4] {
+            if !policy.allows_plaintext() {
+                return Err("lan session peer did not start an encrypted session".to_string());
+            }
+            return Ok(Self {
+                stream,
+                transport: None,
+                remote: None,
+                pending_length_prefix: Some(prefix),
+                profile: GossipSessionProfile::baseline(),
+            });
+        }
+
+        let mut hello = [0u8; 4 + KEY_BYTES];
+        read_exact(&mut stream, &mut hello, "handshake hello")?;
+        if hello[..4] != LAN_SESSION_MAGIC[4..] {
+            return Err("lan session magic is not supported".to_string());
+        }
+        let remote_ephemeral: [u8; 32] = hello[4..].try_into().expect("32-byte tail");
+
+        let local_static = SigningKey::from_bytes(signing_seed);
+        let ephemeral = SigningKey::generate(&mut OsRng);
+        let ephemeral_pubkey = ephemeral.verifying_key().to_bytes();
+        let mut handshake = HandshakeState::new();
+        handshake.mix_hash(&remote_ephemeral);
+
+        let mut response = ephemeral_pubkey.to_vec();
+        handshake.mix_hash(&ephemeral_pubkey);
+        handshake.mix_key(&diffie_hellman(&ephemeral, &remote_ephemeral)?);
+        response.extend(handshake.encrypt_and_hash(&local_static.verifying_key().to_bytes())?);
+        handshake.mix_key(&diffie_hellman(&local_static, &remote_ephemeral)?);
+        response.extend(handshake.encrypt_and_hash(&[])?);
+        write_all(&mut stream, &response, "handshake response")?;
+
+        let mut finish = [0u8; HANDSHAKE_FINISH_BYTES];
+        read_exact(&mut stream, &mut finish, "handshake finish")?;
+        let (encrypted_static, encrypted_payload) = finish.split_at(KEY_BYTES + TAG_BYTES);
+        let remote_static: [u8; 32] = handshake
+            .decrypt_and_hash(encrypted_static)?
+            .try_into()
+            .map_err(|_| "lan session remote static key length mismatch".to_string())?;
+        handshake.mix_key(&diffie_hellman(&ephemeral, &remote_static)?);
+        handshake.decrypt_and_hash(encrypted_payload)?;
+
+        let (initiator_to_responder, responder_to_initiator) = handshake.split();
+        Ok(Self {
+            stream,
+            transport: Some(TransportKeys::new(
+                responder_to_initiator,
+                initiator_to_responder,
+            )),
+            remote: Some(LanSessionPeer::from_pubkey(remote_static)),
+            pending_length_prefix: None,
+            profile: GossipSessionProfile::baseline(),
+        })
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.transport.is_some()
+    }
+
+    /// The handshake-authenticated remote identity; `None` for plaintext sessions.
+    pub fn remote_peer(&self) -> Option<&LanSessionPeer> {
+        self.remote.as_ref()
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// Holds every later frame, both ways, to the limits negotiated from the two HELLOs. Until
+    /// this is called the session uses the GossipV1 defaults.
+    pub fn apply_session_profile(&mut self, profile: &GossipSessionProfile) {
+        self.profile = profile.clone();
+    }
+
+    pub fn send_frame(&mut self, frame: &GossipSyncFrame) -> Result<(), String> {
+        let payload = serialize_frame_for_peer(frame, &self.profile)?;
+        let record = match self.transport.as_mut() {
+            Some(keys) => keys.seal(&payload)?,
+            None => payload,
+        };
+        let len = u32::try_from(record.len()).map_err(|_| "tcp frame too large".to_string())?;
+        write_all(&mut self.stream, &len.to_be_bytes(), "length")?;
+        write_all(&mut self.stream, &record, "payload")
+    }
+
+    pub fn read_frame(&mut self) -> Result<GossipSyncFrame, String> {
+        let len_buf = match self.pending_length_prefix.take() {
+            Some(prefix) => prefix,
+            None => {
+                let mut len_buf = [0u8; 4];
+                read_exact(&mut self.stream, &mut len_buf, "length")?;
+                len_buf
+            }
+        };
+        let len = u32::from_be_bytes(len_buf) as usize;
+        let max_len = match self.transport {
+            Some(_) => self.profile.max_frame_bytes + TAG_BYTES,
+            None => self.profile.max_frame_bytes,
+        };
+        if len > max_len {
+            return Err(format!("tcp frame length exceeds max: {len}"));
+        }
+        let mut record = vec![0u8; len];
+        read_exact(&mut self.stream, &mut record, "payload")?;
+        let payload = match self.transport.as_mut() {
+            Some(keys) => keys.open(&record)?,
+            None => record,
+        };
+        parse_frame_for_peer(&payload, &self.profile)
+    }
+}
+
+impl LanSessionPeer {
+    fn from_pubkey(node_pubkey: [u8; 32]) -> Self {
+        Self {
+            node_id: wayfarer_id_from_pubkey(&node_pubkey),
+            node_pubkey,
+        }
+    }
+}
+
+impl TransportKeys {
+    fn new(send: [u8; 32], recv: [u8; 32]) -> Self {
+        Self {
+            send: ChaCha20Poly1305::new(Key::from_slice(&send)),
+            send_counter: 0,
+            recv: ChaCha20Poly1305::new(Key::from_slice(&recv)),
+            recv_counter: 0,
+        }
+    }
+
+    fn seal(&mut self, payload: &[u8]) -> Result<Vec<u8>, String> {
+        let nonce = counter_nonce(self.send_counter)?;
+        self.send_counter += 1;
+        self.send
+            .encrypt(Nonce::from_slice(&nonce), payload)
+            .map_err(|err| format!("lan session encrypt failed: {err}"))
+    }
+
+    fn open(&mut self, record: &[u8]) -> Result<Vec<u8>, String> {
+        let nonce = counter_nonce(self.recv_counter)?;
+        self.recv_counter += 1;
+        self.recv
+            .decrypt(Nonce::from_slice(&nonce), record)
+            .map_err(|_| "lan session record failed authentication".to_string())
+    }
+}
+
+/// Noise symmetric state: chaining key, handshake hash, and the current handshake key.
+struct HandshakeState {
+    chaining_key: [u8; 32],
+    handshake_hash: [u8; 32],
+    key: Option<[u8; 32]>,
+    counter: u64,
+}
+
+impl HandshakeState {
+    fn new() -> Self {
+        let protocol_hash: [u8; 32] = Sha256::digest(LAN_SESSION_PROTOCOL_NAME).into();
+        let mut state = Self {
+            chaining_key: protocol_hash,
+            handshake_hash: protocol_hash,
+            key: None,
+            counter: 0,
+        };
+        state.mix_hash(&LAN_SESSION_MAGIC);
+        state
+    }
+
+    fn mix_hash(&mut self, data: &[u8]) {
+        let mut hasher = Sha256::new();
+        hasher.update(self.handshake_hash);
+        hasher.update(data);
+        self.handshake_hash = hasher.finalize().into();
+    }
+
+    fn mix_key(&mut self, shared_secret: &[u8; 32]) {
+        let (chaining_key, key) = derive_pair(&self.chaining_key, shared_secret);
+        self.chaining_key = chaining_key;
+        self.key = Some(key);
+        self.counter = 0;
+    }
+
+    fn encrypt_and_hash(&mut self, plaintext: &[u8]) -> Result<Vec<u8>, String> {
+        let ciphertext = self
+            .cipher()?
+            .encrypt(
+                Nonce::from_slice(&counter_nonce(self.counter)?),
+                Payload {
+                    msg: plaintext,
+                    aad: &self.handshake_hash,
+                },
+            )
+            .map_err(|err| format!("lan session handshake encrypt failed: {err}"))?;
+        self.counter += 1;
+        self.mix_hash(&ciphertext);
+        Ok(ciphertext)
+    }
+
+    fn decrypt_and_hash(&mut self, ciphertext: &[u8]) -> Result<Vec<u8>, String> {
+        let plaintext = self
+            .cipher()?
+            .decrypt(
+                Nonce::from_slice(&counter_nonce(self.counter)?),
+                Payload {
+                    msg: ciphertext,
+                    aad: &self.handshake_hash,
+                },
+            )
+            .map_err(|_| "lan session handshake failed authentication".to_string())?;
+        self.counter += 1;
+        self.mix_hash(ciphertext);
+        Ok(plaintext)
+    }
+
+    fn cipher(&self) -> Result<ChaCha20Poly1305, String> {
+        let key = self
+            .key
+            .ok_or_else(|| "lan session handshake key not established".to_string())?;
+        Ok(ChaCha20Poly1305::new(Key::from_slice(&key)))
+    }
+
+    /// Returns the initiator-to-responder and responder-to-initiator transport keys.
+    fn split(self) -> ([u8; 32], [u8; 32]) {
+        derive_pair(&self.chaining_key, &[0u8; 32])
+    }
+}
+
+fn derive_pair(chaining_key: &[u8; 32], input: &[u8; 32]) -> ([u8; 32], [u8; 32]) {
+    let derive = |domain: &[u8]| -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(domain);
+        hasher.update(chaining_key);
+        hasher.update(input);
+        hasher.finalize().into()
+    };
+    (
+        derive(LAN_SESSION_CHAIN_KEY_DOMAIN),
+        derive(LAN_SESSION_CIPHER_KEY_DOMAIN),
+    )
+}
+
+fn diffie_hellman(local: &SigningKey, remote_pubkey: &[u8; 32]) -> Result<[u8; 32], String> {
+    let shared_secret = VerifyingKey::from_bytes(remote_pubkey)
+        .map_err(|err| format!("lan session peer key invalid: {err}"))?
+        .to_montgomery()
+        .mul_clamped(local.to_scalar_bytes())
+        .to_bytes();
+    if shared_secret.iter().all(|byte| *byte == 0) {
+        return Err("lan session key agreement produced a low-order shared secret".to_string());
+    }
+    Ok(shared_secret)
+}
+
+fn counter_nonce(counter: u64) -> Result<[u8; 12], String> {
+    if counter == u64::MAX {
+        return Err("lan session nonce counter exhausted".to_string());
+    }
+    let mut nonce = [0u8; 12];
+    nonce[4..].copy_from_slice(&counter.to_le_bytes());
+    Ok(nonce)
+}
+
+fn write_all<W: Write>(stream: &mut W, bytes: &[u8], what: &str) -> Result<(), String> {
+    stream
+        .write_all(bytes)
+        .map_err(|err| format!("tcp write {what} failed: {err}"))
+}
+
+fn read_exact<R: Read>(stream: &mut R, buf: &mut [u8], what: &str) -> Result<(), String> {
+    stream.read_exact(buf).map_err(|err| match err.kind() {
+        std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock => {
+            format!("tcp read {what} timeout: {err}")
+        }
+        std::io::ErrorKind::UnexpectedEof => format!("tcp read {what} eof: {err}"),
+        _ => format!("tcp read {what} failed: {err}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aethos_core::gossip_sync::{
+        serialize_frame, ReceiptFrame, SummaryFrame, BLOOM_FILTER_BYTES, MAX_FRAME_BYTES,
+        MIN_FRAME_BYTES,
+    };
+    use std::net::{TcpListener, TcpStream};
+
+    fn tcp_pair() -> (TcpStream, TcpStream) {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).expect("bind test listener");
+        let client = TcpStream::connect(listener.local_addr().expect("addr")).expect("connect");
+        let (server, _) = listener.accept().expect("accept");
+        (client, server)
+    }
+
+    fn pubkey_for_seed(seed: [u8; 32]) -> [u8; 32] {
+        SigningKey::from_bytes(&seed).verifying_key().to_bytes()
+    }
+
+    fn summary_frame() -> GossipSyncFrame {
+        GossipSyncFrame::Summary(SummaryFrame {
+            bloom_filter: vec![0u8; BLOOM_FILTER_BYTES],
+            bloom_hash_count: None,
+            item_count: 0,
+            preview_item_ids: None,
+            preview_cursor: None,
+        })
+    }
+
+    #[test]
+    fn handshake_authenticates_both_identities_and_carries_frames() {
+        let (client, server) = tcp_pair();
+        let responder = std::thread::spawn(move || {
+            let mut session =
+                LanSession::accept(server, &[2u8; 32], LanDowngradePolicy::RequireEncrypted)
+                    .expect("accept");
+            let frame = session.read_frame().expect("read summary");
+            session
+                .send_frame(&GossipSyncFrame::Receipt(ReceiptFrame {
+                    received: Vec::new(),
+                }))
+                .expect("send receipt");
+            (session.remote_peer().cloned(), frame)
+        });
+
+        let mut session = LanSession::initiate(client, &[1u8; 32]).expect("initiate");
+        assert!(session.is_encrypted());
+        assert_eq!(
+            session.remote_peer().map(|peer| peer.node_pubkey),
+            Some(pubkey_for_seed([2u8; 32]))
+        );
+        session.send_frame(&summary_frame()).expect("send summary");
+        assert!(matches!(
+            session.read_frame().expect("read receipt"),
+            GossipSyncFrame::Receipt(_)
+        ));
+
+        let (initiator, frame) = responder.join().expect("responder thread");
+        let initiator = initiator.expect("initiator identity");
+        assert_eq!(initiator.node_pubkey, pubkey_for_seed([1u8; 32]));
+        assert_eq!(
+            initiator.node_id,
+            wayfarer_id_from_pubkey(&pubkey_for_seed([1u8; 32]))
+        );
+        assert!(matches!(frame, GossipSyncFrame::Summary(_)));
+    }
+
+    #[test]
+    fn encrypted_records_hide_frame_bytes_and_reject_tampering() {
+        let mut wire = Vec::new();
+        let (client, server) = tcp_pair();
+        let responder = std::thread::spawn(move || {
+            LanSession::accept(server, &[2u8; 32], LanDowngradePolicy::AllowPlaintext)
+                .expect("accept")
+        });
+        let mut initiator = LanSession::initiate(client, &[1u8; 32]).expect("initiate");
+        let mut responder = responder.join().expect("responder thread");
+
+        let plaintext = serialize_frame(&summary_frame()).expect("serialize");
+        let record = initiator
+            .transport
+            .as_mut()
+            .expect("keys")
+            .seal(&plaintext)
+            .expect("seal");
+        assert!(!record
+            .windows(b"SUMMARY".len())
+            .any(|window| window == b"SUMMARY"));
+        wire.extend_from_slice(&(record.len() as u32).to_be_bytes());
+        wire.extend_from_slice(&record);
+        let last = wire.len() - 1;
+        wire[last] ^= 0x01;
+        initiator.stream.write_all(&wire).expect("write tampered");
+
+        let err = responder
+            .read_frame()
+            .expect_err("tampered record must fail");
+        assert!(err.contains("authentication"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn plaintext_peer_is_accepted_only_when_policy_allows() {
+        let (client, server) = tcp_pair();
+        LanSession::plaintext(client)
+            .send_frame(&summary_frame())
+            .expect("send plaintext");
+        let mut session =
+            LanSession::accept(server, &[2u8; 32], LanDowngradePolicy::AllowPlaintext)
+                .expect("accept plaintext");
+        assert!(!session.is_encrypted());
+        assert!(session.remote_peer().is_none());
+        assert!(matches!(
+            session.read_frame().expect("read plaintext"),
+            GossipSyncFrame::Summary(_)
+        ));
+
+        let (client, server) = tcp_pair();
+        LanSession::plaintext(client)
+            .send_frame(&summary_frame())
+            .expect("send plaintext");
+        let err = LanSession::accept(server, &[2u8; 32], LanDowngradePolicy::RequireEncrypted)
+            .err()
+            .expect("plaintext must be refused");
+        assert!(err.contains("encrypted"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn plaintext_session_rejects_oversize_length_prefix() {
+        let (client, mut server) = tcp_pair();
+        server
+            .write_all(&((MAX_FRAME_BYTES as u32) + 1).to_be_bytes())
+            .expect("write oversize length");
+        let err = LanSession::plaintext(client)
+            .read_frame()
+            .expect_err("must reject oversize frame");
+        assert!(err.contains("exceeds max"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn negotiated_frame_limit_replaces_the_default() {
+        let profile = GossipSessionProfile {
+            max_frame_bytes: MIN_FRAME_BYTES,
+            ..GossipSessionProfile::baseline()
+        };
+        let (client, mut server) = tcp_pair();
+        server
+            .write_all(&((MIN_FRAME_BYTES as u32) + 1).to_be_bytes())
+            .expect("write length over the negotiated limit");
+        let mut session = LanSession::plaintext(client);
+        session.apply_session_profile(&profile);
+        let err = session
+            .read_frame()
+            .expect_err("must reject frame over the negotiated limit");
+        assert!(err.contains("exceeds max"), "unexpected error: {err}");
+
+        let oversized = GossipSyncFrame::Receipt(ReceiptFrame {
+            received: (0..MIN_FRAME_BYTES / 64)
+                .map(|index| format!("{index:064x}"))
+                .collect(),
+        });
+        let err = session
+            .send_frame(&oversized)
+            .expect_err("must refuse to send over the negotiated limit");
+        assert!(err.contains("max_frame_bytes"), "unexpected error: {err}");
+    }
+}
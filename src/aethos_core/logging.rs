@@ -7,6 +7,8 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use serde_json::json;
 
+use crate::aethos_core::identity_store::profile_state_dir;
+
 const APP_LOG_FILE_NAME: &str = "aethos-linux.log";
 
 static VERBOSE_LOGGING_ENABLED: AtomicBool = AtomicBool::new(false);
@@ -34,18 +36,19 @@ pub fn log_verbose(message: &str) {
 pub fn app_log_file_path() -> PathBuf {
     if let Ok(xdg_state_home) = std::env::var("XDG_STATE_HOME") {
         if !xdg_state_home.trim().is_empty() {
-            return Path::new(&xdg_state_home)
-                .join("aethos-linux")
+            return profile_state_dir(Path::new(&xdg_state_home).join("aethos-linux"))
                 .join(APP_LOG_FILE_NAME);
         }
     }
 
     if let Ok(home) = std::env::var("HOME") {
-        return Path::new(&home)
-            .join(".local")
-            .join("state")
-            .join("aethos-linux")
-            .join(APP_LOG_FILE_NAME);
+        return profile_state_dir(
+            Path::new(&home)
+                .join(".local")
+                .join("state")
+                .join("aethos-linux"),
+        )
+        .join(APP_LOG_FILE_NAME);
     }
 
     std::env::temp_dir().join(APP_LOG_FILE_NAME)
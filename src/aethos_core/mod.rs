@@ -1,10 +1,23 @@
+pub mod app_body;
 pub mod ble_discovery;
+pub mod canonical_cbor;
+pub mod contact_card;
+pub mod device_link;
 pub mod encounter_orchestration;
 pub mod encounter_scheduler;
 pub mod gossip_store_sqlite;
 pub mod gossip_sync;
 pub mod identity_store;
+pub mod lan_session;
 pub mod logging;
 pub mod protocol;
+pub mod recovery_phrase;
+pub mod revocation;
+pub mod safety_number;
+pub mod signed_record;
+pub mod social_recovery;
+pub mod succession;
+#[cfg(test)]
+pub mod test_env;
 #[cfg(test)]
 pub mod vectors;
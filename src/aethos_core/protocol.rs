@@ -2,6 +2,8 @@ use std::collections::BTreeMap;
 use std::io::Cursor;
 
 use base64::Engine;
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
 use ciborium::value::Value;
 use ciborium::{de::from_reader, ser::into_writer};
 use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
@@ -9,7 +11,21 @@ use serde::Serialize;
 use serde_bytes::ByteBuf;
 use sha2::{Digest, Sha256};
 
+use crate::aethos_core::app_body::{decode_app_body, encode_app_body, AppBody, ChatBody};
+use crate::aethos_core::canonical_cbor::{check_canonical, encode_canonical};
+use crate::aethos_core::device_link::verify_device_certificate;
+
 const ENVELOPE_V1_SIGNING_DOMAIN: &[u8] = b"AETHOS_ENVELOPE_V1";
+const ENVELOPE_V2_SIGNING_DOMAIN: &[u8] = b"AETHOS_ENVELOPE_V2";
+const ENVELOPE_V2_BODY_KEY_DOMAIN: &[u8] = b"AETHOS_ENVELOPE_V2_BODY_KEY";
+const ENVELOPE_V2_RECIPIENT_TAG_DOMAIN: &[u8] = b"AETHOS_ENVELOPE_V2_RECIPIENT_TAG";
+const ENVELOPE_V2_CONTENT_KEY_DOMAIN: &[u8] = b"AETHOS_ENVELOPE_V2_CONTENT_KEY";
+const ENVELOPE_V2_KEY_WRAP_DOMAIN: &[u8] = b"AETHOS_ENVELOPE_V2_KEY_WRAP";
+const ENVELOPE_V2_MANIFEST_ID_DOMAIN: &[u8] = b"AETHOS_ENVELOPE_V2_MANIFEST_ID";
+pub const ENVELOPE_VERSION_V2: u64 = 2;
+pub const RECIPIENT_TAG_EPOCH_MS: u64 = 86_400_000;
+pub const MAX_ENVELOPE_RECIPIENTS: usize = 32;
+const WRAPPED_CONTENT_KEY_BYTES: usize = 48;
 
 pub fn is_valid_wayfarer_id(value: &str) -> bool {
     value.len() == 64
@@ -34,12 +50,125 @@ pub struct EnvelopeV1 {
     pub author_sig: [u8; 64],
 }
 
+/// Envelope whose app body is sealed to the recipient's identity key. Carriers can
+/// verify `author_sig` and derive `item_id` without being able to read the body.
+/// `manifest_id` is keyed by the body key, so it cannot be used to confirm a guessed body.
+#[derive(Debug, Clone)]
+pub struct EnvelopeV2 {
+    pub recipient: EnvelopeRecipientV2,
+    pub lifetime: EnvelopeLifetime,
+    pub manifest_id: Vec<u8>,
+    pub ephemeral_pubkey: [u8; 32],
+    pub ciphertext: Vec<u8>,
+    /// Root-signed certificate for `author_pubkey` when a linked device authored the envelope.
+    pub device_cert: Option<Vec<u8>>,
+    pub author_pubkey: [u8; 32],
+    pub author_sig: [u8; 64],
+}
+
+/// How a v2 envelope names its recipient on the wire. Blinded envelopes carry a tag
+/// that only the author and recipient can derive, rotating every `RECIPIENT_TAG_EPOCH_MS`.
+/// Multi-recipient envelopes share one body ciphertext and wrap its key per recipient.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvelopeRecipientV2 {
+    WayfarerId([u8; 32]),
+    Blinded(RecipientTagV2),
+    Multiple(Vec<RecipientKeyWrapV2>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecipientKeyWrapV2 {
+    pub to_wayfarer_id: [u8; 32],
+    pub wrapped_key: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecipientTagV2 {
+    pub tag: [u8; 32],
+    pub epoch: u64,
+}
+
+/// Author-signed creation time and hard expiry. Carriers may shorten an item's
+/// transfer expiry hop by hop, but never keep it past `expires_at_unix_ms`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnvelopeLifetime {
+    pub created_at_unix_ms: u64,
+    pub expires_at_unix_ms: u64,
+}
+
+impl EnvelopeLifetime {
+    pub fn from_ttl(created_at_unix_ms: u64, ttl_ms: u64) -> Self {
+        Self {
+            created_at_unix_ms,
+            expires_at_unix_ms: created_at_unix_ms.saturating_add(ttl_ms),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DecodedEnvelopeV1 {
+    /// Empty for blinded and multi-recipient v2 envelopes; use `is_addressed_to` or
+    /// `envelope_matches_recipient` instead of comparing this field directly.
     pub to_wayfarer_id_hex: String,
+    /// Every cleartext recipient, including `to_wayfarer_id_hex` for single-recipient forms.
+    pub recipient_wayfarer_ids_hex: Vec<String>,
+    /// SHA-256 of the body for v1 envelopes; for sealed v2 envelopes a hash keyed by the
+    /// body key. Use `body_manifest_id_hex` on the opened body to name the message.
     pub manifest_id_hex: String,
     pub author_wayfarer_id_hex: String,
+    /// Signature-verified author identity key; `author_wayfarer_id_hex` is its SHA-256.
+    /// For device-signed envelopes this is the certificate's root key.
+    pub author_pubkey: [u8; 32],
+    /// Linked-device key that signed the envelope, after its certificate chain was checked.
+    pub author_device_pubkey: Option<[u8; 32]>,
+    /// Plaintext app body for v1 envelopes; the AEAD ciphertext for sealed v2 envelopes.
     pub body: Vec<u8>,
+    pub sealed_body: Option<SealedBodyV2>,
+    /// Signed lifetime; always present for v2, `None` for v1 envelopes, which cannot carry one.
+    pub lifetime: Option<EnvelopeLifetime>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SealedBodyV2 {
+    pub ephemeral_pubkey: [u8; 32],
+    pub recipient_tag: Option<RecipientTagV2>,
+    key_wraps: Vec<RecipientKeyWrapV2>,
+    author_pubkey: [u8; 32],
+    manifest_id: Vec<u8>,
+}
+
+impl DecodedEnvelopeV1 {
+    pub fn is_sealed(&self) -> bool {
+        self.sealed_body.is_some()
+    }
+
+    pub fn is_blinded(&self) -> bool {
+        self.sealed_body
+            .as_ref()
+            .is_some_and(|sealed| sealed.recipient_tag.is_some())
+    }
+
+    pub fn is_addressed_to(&self, wayfarer_id_hex: &str) -> bool {
+        self.recipient_wayfarer_ids_hex
+            .iter()
+            .any(|recipient| recipient == wayfarer_id_hex)
+    }
+
+    /// Returns the first of `local_wayfarer_ids` listed as a recipient of this envelope.
+    pub fn matched_recipient<'a>(&self, local_wayfarer_ids: &[&'a str]) -> Option<&'a str> {
+        local_wayfarer_ids
+            .iter()
+            .copied()
+            .find(|local_wayfarer_id| self.is_addressed_to(local_wayfarer_id))
+    }
+
+    /// Clamps a carrier-supplied expiry to the author-signed limit, if the envelope has one.
+    pub fn bounded_expiry_unix_ms(&self, expiry_unix_ms: u64) -> u64 {
+        match self.lifetime {
+            Some(lifetime) => expiry_unix_ms.min(lifetime.expires_at_unix_ms),
+            None => expiry_unix_ms,
+        }
+    }
 }
 
 impl EnvelopeV1 {
@@ -68,6 +197,99 @@ impl EnvelopeV1 {
     }
 }
 
+impl EnvelopeV2 {
+    fn signed_entries(&self) -> Vec<(Value, Value)> {
+        let mut entries = vec![(
+            Value::Text("version".to_string()),
+            Value::Integer(ENVELOPE_VERSION_V2.into()),
+        )];
+        match &self.recipient {
+            EnvelopeRecipientV2::WayfarerId(to_wayfarer_id) => entries.push((
+                Value::Text("to_wayfarer_id".to_string()),
+                Value::Bytes(to_wayfarer_id.to_vec()),
+            )),
+            EnvelopeRecipientV2::Blinded(recipient_tag) => {
+                entries.push((
+                    Value::Text("recipient_tag".to_string()),
+                    Value::Bytes(recipient_tag.tag.to_vec()),
+                ));
+                entries.push((
+                    Value::Text("recipient_epoch".to_string()),
+                    Value::Integer(recipient_tag.epoch.into()),
+                ));
+            }
+            EnvelopeRecipientV2::Multiple(key_wraps) => entries.push((
+                Value::Text("recipients".to_string()),
+                Value::Array(
+                    key_wraps
+                        .iter()
+                        .map(|key_wrap| {
+                            Value::Map(vec![
+                                (
+                                    Value::Text("to_wayfarer_id".to_string()),
+                                    Value::Bytes(key_wrap.to_wayfarer_id.to_vec()),
+                                ),
+                                (
+                                    Value::Text("wrapped_key".to_string()),
+                                    Value::Bytes(key_wrap.wrapped_key.clone()),
+                                ),
+                            ])
+                        })
+                        .collect(),
+                ),
+            )),
+        }
+        entries.push((
+            Value::Text("created_at_unix_ms".to_string()),
+            Value::Integer(self.lifetime.created_at_unix_ms.into()),
+        ));
+        entries.push((
+            Value::Text("expires_at_unix_ms".to_string()),
+            Value::Integer(self.lifetime.expires_at_unix_ms.into()),
+        ));
+        entries.push((
+            Value::Text("manifest_id".to_string()),
+            Value::Bytes(self.manifest_id.clone()),
+        ));
+        entries.push((
+            Value::Text("ephemeral_pubkey".to_string()),
+            Value::Bytes(self.ephemeral_pubkey.to_vec()),
+        ));
+        entries.push((
+            Value::Text("ciphertext".to_string()),
+            Value::Bytes(self.ciphertext.clone()),
+        ));
+        if let Some(device_cert) = self.device_cert.as_ref() {
+            entries.push((
+                Value::Text("device_cert".to_string()),
+                Value::Bytes(device_cert.clone()),
+            ));
+        }
+        entries
+    }
+
+    fn signing_digest_v2(&self) -> Result<[u8; 32], String> {
+        let signing_payload = encode_cbor_value_deterministic(&Value::Map(self.signed_entries()))?;
+        let mut hasher = Sha256::new();
+        hasher.update(ENVELOPE_V2_SIGNING_DOMAIN);
+        hasher.update(signing_payload);
+        Ok(hasher.finalize().into())
+    }
+
+    pub fn canonical_bytes_v2(&self) -> Result<Vec<u8>, String> {
+        let mut entries = self.signed_entries();
+        entries.push((
+            Value::Text("author_pubkey".to_string()),
+            Value::Bytes(self.author_pubkey.to_vec()),
+        ));
+        entries.push((
+            Value::Text("author_sig".to_string()),
+            Value::Bytes(self.author_sig.to_vec()),
+        ));
+        encode_cbor_value_deterministic(&Value::Map(entries))
+    }
+}
+
 #[allow(dead_code)]
 pub fn build_envelope_payload_b64_from_utf8(
     to_wayfarer_id_hex: &str,
@@ -87,6 +309,35 @@ pub fn build_wayfarer_chat_envelope_payload_b64(
     author_signing_key_seed: &[u8; 32],
     created_at_unix_ms: i64,
 ) -> Result<String, String> {
+    let wayfarer_chat_body = encode_wayfarer_chat_body(chat_text, created_at_unix_ms)?;
+
+    build_envelope_payload_b64(
+        to_wayfarer_id_hex,
+        &wayfarer_chat_body,
+        author_signing_key_seed,
+    )
+}
+
+/// Builds a single sealed `wayfarer.chat.v1` envelope for every pubkey in
+/// `recipient_pubkeys`, valid for `ttl_ms` from `created_at_unix_ms`.
+pub fn build_wayfarer_chat_multi_recipient_envelope_payload_b64(
+    recipient_pubkeys: &[[u8; 32]],
+    chat_text: &str,
+    author_signing_key_seed: &[u8; 32],
+    created_at_unix_ms: i64,
+    ttl_ms: u64,
+) -> Result<String, String> {
+    let wayfarer_chat_body = encode_wayfarer_chat_body(chat_text, created_at_unix_ms)?;
+
+    build_multi_recipient_envelope_v2_payload_b64(
+        recipient_pubkeys,
+        &wayfarer_chat_body,
+        author_signing_key_seed,
+        EnvelopeLifetime::from_ttl(created_at_unix_ms as u64, ttl_ms),
+    )
+}
+
+fn encode_wayfarer_chat_body(chat_text: &str, created_at_unix_ms: i64) -> Result<Vec<u8>, String> {
     if chat_text.trim().is_empty() {
         return Err("chat text must be non-empty".to_string());
     }
@@ -94,26 +345,10 @@ pub fn build_wayfarer_chat_envelope_payload_b64(
         return Err("created_at_unix_ms must be non-negative".to_string());
     }
 
-    let wayfarer_chat_body = encode_cbor_value_deterministic(&Value::Map(vec![
-        (
-            Value::Text("type".to_string()),
-            Value::Text("wayfarer.chat.v1".to_string()),
-        ),
-        (
-            Value::Text("text".to_string()),
-            Value::Text(chat_text.to_string()),
-        ),
-        (
-            Value::Text("created_at_unix_ms".to_string()),
-            Value::Integer(created_at_unix_ms.into()),
-        ),
-    ]))?;
-
-    build_envelope_payload_b64(
-        to_wayfarer_id_hex,
-        &wayfarer_chat_body,
-        author_signing_key_seed,
-    )
+    encode_app_body(&AppBody::Chat(ChatBody {
+        text: chat_text.to_string(),
+        created_at_unix_ms: created_at_unix_ms as u64,
+    }))
 }
 
 pub fn build_envelope_payload_b64(
@@ -139,6 +374,328 @@ pub fn build_envelope_payload_b64(
     Ok(base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(envelope.canonical_bytes_v1()?))
 }
 
+/// Builds a v2 envelope whose body is encrypted to `recipient_pubkey`. The recipient's
+/// wayfarer ID is derived from the pubkey, so callers cannot address a mismatched key.
+pub fn build_envelope_v2_payload_b64(
+    recipient_pubkey: &[u8; 32],
+    body: &[u8],
+    author_signing_key_seed: &[u8; 32],
+    lifetime: EnvelopeLifetime,
+) -> Result<String, String> {
+    build_envelope_v2_payload_b64_with_ephemeral_seed(
+        recipient_pubkey,
+        body,
+        author_signing_key_seed,
+        &random_ephemeral_seed(),
+        None,
+        lifetime,
+    )
+}
+
+/// Builds a v2 envelope like `build_envelope_v2_payload_b64`, but replaces the cleartext
+/// `to_wayfarer_id` with a recipient tag for the epoch containing the creation time.
+pub fn build_blinded_envelope_v2_payload_b64(
+    recipient_pubkey: &[u8; 32],
+    body: &[u8],
+    author_signing_key_seed: &[u8; 32],
+    lifetime: EnvelopeLifetime,
+) -> Result<String, String> {
+    build_envelope_v2_payload_b64_with_ephemeral_seed(
+        recipient_pubkey,
+        body,
+        author_signing_key_seed,
+        &random_ephemeral_seed(),
+        Some(lifetime.created_at_unix_ms / RECIPIENT_TAG_EPOCH_MS),
+        lifetime,
+    )
+}
+
+/// Builds one v2 envelope for several recipients. The body is encrypted once under a
+/// content key, and that key is wrapped separately for each recipient pubkey.
+pub fn build_multi_recipient_envelope_v2_payload_b64(
+    recipient_pubkeys: &[[u8; 32]],
+    body: &[u8],
+    author_signing_key_seed: &[u8; 32],
+    lifetime: EnvelopeLifetime,
+) -> Result<String, String> {
+    build_multi_recipient_envelope_v2_payload_b64_with_ephemeral_seed(
+        recipient_pubkeys,
+        body,
+        author_signing_key_seed,
+        &random_ephemeral_seed(),
+        lifetime,
+        None,
+    )
+}
+
+/// Builds a multi-recipient v2 envelope signed by a linked device. `device_cert` must
+/// certify the key behind `device_signing_key_seed`; recipients attribute the envelope
+/// to the certificate's root identity.
+pub fn build_device_signed_envelope_v2_payload_b64(
+    recipient_pubkeys: &[[u8; 32]],
+    body: &[u8],
+    device_signing_key_seed: &[u8; 32],
+    device_cert: &[u8],
+    lifetime: EnvelopeLifetime,
+) -> Result<String, String> {
+    let certificate = verify_device_certificate(device_cert)?;
+    if certificate.device_pubkey
+        != SigningKey::from_bytes(device_signing_key_seed)
+            .verifying_key()
+            .to_bytes()
+    {
+        return Err("device certificate does not certify this signing key".to_string());
+    }
+    build_multi_recipient_envelope_v2_payload_b64_with_ephemeral_seed(
+        recipient_pubkeys,
+        body,
+        device_signing_key_seed,
+        &random_ephemeral_seed(),
+        lifetime,
+        Some(device_cert),
+    )
+}
+
+fn random_ephemeral_seed() -> [u8; 32] {
+    use chacha20poly1305::aead::rand_core::RngCore;
+    let mut ephemeral_seed = [0u8; 32];
+    OsRng.fill_bytes(&mut ephemeral_seed);
+    ephemeral_seed
+}
+
+fn build_envelope_v2_payload_b64_with_ephemeral_seed(
+    recipient_pubkey: &[u8; 32],
+    body: &[u8],
+    author_signing_key_seed: &[u8; 32],
+    ephemeral_seed: &[u8; 32],
+    blinded_epoch: Option<u64>,
+    lifetime: EnvelopeLifetime,
+) -> Result<String, String> {
+    validate_envelope_lifetime(&lifetime)?;
+    let recipient_key = VerifyingKey::from_bytes(recipient_pubkey)
+        .map_err(|err| format!("invalid recipient pubkey: {err}"))?;
+    let signing_key = SigningKey::from_bytes(author_signing_key_seed);
+    let author_pubkey = signing_key.verifying_key().to_bytes();
+
+    let recipient = match blinded_epoch {
+        Some(epoch) => {
+            let static_secret = recipient_key
+                .to_montgomery()
+                .mul_clamped(signing_key.to_scalar_bytes())
+                .to_bytes();
+            EnvelopeRecipientV2::Blinded(RecipientTagV2 {
+                tag: recipient_tag_v2(&static_secret, epoch)?,
+                epoch,
+            })
+        }
+        None => EnvelopeRecipientV2::WayfarerId(Sha256::digest(recipient_pubkey).into()),
+    };
+
+    let ephemeral_key = SigningKey::from_bytes(ephemeral_seed);
+    let ephemeral_pubkey = ephemeral_key.verifying_key().to_bytes();
+    let shared_secret = recipient_key
+        .to_montgomery()
+        .mul_clamped(ephemeral_key.to_scalar_bytes())
+        .to_bytes();
+    let body_key = envelope_v2_body_key(&shared_secret, &ephemeral_pubkey, recipient_pubkey)?;
+    let manifest_id = envelope_v2_manifest_id(&body_key, body);
+    let ciphertext = ChaCha20Poly1305::new(Key::from_slice(&body_key))
+        .encrypt(
+            Nonce::from_slice(&[0u8; 12]),
+            Payload {
+                msg: body,
+                aad: &envelope_v2_body_aad(&author_pubkey, &manifest_id),
+            },
+        )
+        .map_err(|err| format!("failed to encrypt envelope body: {err}"))?;
+
+    let mut envelope = EnvelopeV2 {
+        recipient,
+        lifetime,
+        manifest_id,
+        ephemeral_pubkey,
+        ciphertext,
+        device_cert: None,
+        author_pubkey,
+        author_sig: [0u8; 64],
+    };
+    envelope.author_sig = signing_key.sign(&envelope.signing_digest_v2()?).to_bytes();
+    Ok(base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(envelope.canonical_bytes_v2()?))
+}
+
+fn build_multi_recipient_envelope_v2_payload_b64_with_ephemeral_seed(
+    recipient_pubkeys: &[[u8; 32]],
+    body: &[u8],
+    author_signing_key_seed: &[u8; 32],
+    ephemeral_seed: &[u8; 32],
+    lifetime: EnvelopeLifetime,
+    device_cert: Option<&[u8]>,
+) -> Result<String, String> {
+    if recipient_pubkeys.is_empty() || recipient_pubkeys.len() > MAX_ENVELOPE_RECIPIENTS {
+        return Err(format!(
+            "multi-recipient envelope needs between 1 and {MAX_ENVELOPE_RECIPIENTS} recipients"
+        ));
+    }
+    validate_envelope_lifetime(&lifetime)?;
+    let signing_key = SigningKey::from_bytes(author_signing_key_seed);
+    let author_pubkey = signing_key.verifying_key().to_bytes();
+    let ephemeral_key = SigningKey::from_bytes(ephemeral_seed);
+    let ephemeral_pubkey = ephemeral_key.verifying_key().to_bytes();
+
+    let mut hasher = Sha256::new();
+    hasher.update(ENVELOPE_V2_CONTENT_KEY_DOMAIN);
+    hasher.update(ephemeral_seed);
+    let content_key: [u8; 32] = hasher.finalize().into();
+    let manifest_id = envelope_v2_manifest_id(&content_key, body);
+
+    let mut key_wraps = Vec::with_capacity(recipient_pubkeys.len());
+    for recipient_pubkey in recipient_pubkeys {
+        let to_wayfarer_id: [u8; 32] = Sha256::digest(recipient_pubkey).into();
+        if key_wraps
+            .iter()
+            .any(|key_wrap: &RecipientKeyWrapV2| key_wrap.to_wayfarer_id == to_wayfarer_id)
+        {
+            return Err("multi-recipient envelope lists a recipient twice".to_string());
+        }
+        let recipient_key = VerifyingKey::from_bytes(recipient_pubkey)
+            .map_err(|err| format!("invalid recipient pubkey: {err}"))?;
+        let shared_secret = recipient_key
+            .to_montgomery()
+            .mul_clamped(ephemeral_key.to_scalar_bytes())
+            .to_bytes();
+        let wrapped_key =
+            envelope_v2_key_wrap_cipher(&shared_secret, &ephemeral_pubkey, recipient_pubkey)?
+                .encrypt(
+                    Nonce::from_slice(&[0u8; 12]),
+                    Payload {
+                        msg: &content_key,
+                        aad: &to_wayfarer_id,
+                    },
+                )
+                .map_err(|err| format!("failed to wrap envelope content key: {err}"))?;
+        key_wraps.push(RecipientKeyWrapV2 {
+            to_wayfarer_id,
+            wrapped_key,
+        });
+    }
+
+    let ciphertext = ChaCha20Poly1305::new(Key::from_slice(&content_key))
+        .encrypt(
+            Nonce::from_slice(&[0u8; 12]),
+            Payload {
+                msg: body,
+                aad: &envelope_v2_body_aad(&author_pubkey, &manifest_id),
+            },
+        )
+        .map_err(|err| format!("failed to encrypt envelope body: {err}"))?;
+
+    let mut envelope = EnvelopeV2 {
+        recipient: EnvelopeRecipientV2::Multiple(key_wraps),
+        lifetime,
+        manifest_id,
+        ephemeral_pubkey,
+        ciphertext,
+        device_cert: device_cert.map(<[u8]>::to_vec),
+        author_pubkey,
+        author_sig: [0u8; 64],
+    };
+    envelope.author_sig = signing_key.sign(&envelope.signing_digest_v2()?).to_bytes();
+    Ok(base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(envelope.canonical_bytes_v2()?))
+}
+
+/// Returns whether the identity holding `recipient_signing_key_seed` is the envelope's
+/// recipient. Blinded envelopes are matched by recomputing the tag from `author_pubkey`.
+pub fn envelope_matches_recipient(
+    decoded: &DecodedEnvelopeV1,
+    recipient_signing_key_seed: &[u8; 32],
+) -> bool {
+    let recipient_key = SigningKey::from_bytes(recipient_signing_key_seed);
+    let Some(recipient_tag) = decoded
+        .sealed_body
+        .as_ref()
+        .and_then(|sealed| sealed.recipient_tag.map(|tag| (sealed.author_pubkey, tag)))
+    else {
+        let recipient_pubkey = recipient_key.verifying_key().to_bytes();
+        return decoded.is_addressed_to(&bytes_to_hex_lower(&Sha256::digest(recipient_pubkey)));
+    };
+
+    let (author_pubkey, recipient_tag) = recipient_tag;
+    let Ok(author_key) = VerifyingKey::from_bytes(&author_pubkey) else {
+        return false;
+    };
+    let static_secret = author_key
+        .to_montgomery()
+        .mul_clamped(recipient_key.to_scalar_bytes())
+        .to_bytes();
+    recipient_tag_v2(&static_secret, recipient_tag.epoch)
+        .is_ok_and(|expected_tag| expected_tag == recipient_tag.tag)
+}
+
+/// Decrypts the body of a sealed envelope with the recipient's signing key seed and
+/// checks it against the signed, keyed `manifest_id`. Unsealed envelopes return their
+/// body as-is.
+pub fn open_envelope_body(
+    decoded: &DecodedEnvelopeV1,
+    recipient_signing_key_seed: &[u8; 32],
+) -> Result<Vec<u8>, String> {
+    let Some(sealed) = decoded.sealed_body.as_ref() else {
+        return Ok(decoded.body.clone());
+    };
+
+    if !envelope_matches_recipient(decoded, recipient_signing_key_seed) {
+        return Err("envelope is not addressed to this identity".to_string());
+    }
+    let recipient_key = SigningKey::from_bytes(recipient_signing_key_seed);
+    let recipient_pubkey = recipient_key.verifying_key().to_bytes();
+
+    let ephemeral_key = VerifyingKey::from_bytes(&sealed.ephemeral_pubkey)
+        .map_err(|err| format!("invalid envelope ephemeral_pubkey: {err}"))?;
+    let shared_secret = ephemeral_key
+        .to_montgomery()
+        .mul_clamped(recipient_key.to_scalar_bytes())
+        .to_bytes();
+    let body_key = if sealed.key_wraps.is_empty() {
+        envelope_v2_body_key(&shared_secret, &sealed.ephemeral_pubkey, &recipient_pubkey)?
+    } else {
+        let to_wayfarer_id: [u8; 32] = Sha256::digest(recipient_pubkey).into();
+        let key_wrap = sealed
+            .key_wraps
+            .iter()
+            .find(|key_wrap| key_wrap.to_wayfarer_id == to_wayfarer_id)
+            .ok_or_else(|| "envelope is not addressed to this identity".to_string())?;
+        let content_key = envelope_v2_key_wrap_cipher(
+            &shared_secret,
+            &sealed.ephemeral_pubkey,
+            &recipient_pubkey,
+        )?
+        .decrypt(
+            Nonce::from_slice(&[0u8; 12]),
+            Payload {
+                msg: &key_wrap.wrapped_key,
+                aad: &to_wayfarer_id,
+            },
+        )
+        .map_err(|_| "failed to unwrap envelope content key".to_string())?;
+        content_key
+            .try_into()
+            .map_err(|_| "unwrapped envelope content key has the wrong length".to_string())?
+    };
+    let body = ChaCha20Poly1305::new(Key::from_slice(&body_key))
+        .decrypt(
+            Nonce::from_slice(&[0u8; 12]),
+            Payload {
+                msg: &decoded.body,
+                aad: &envelope_v2_body_aad(&sealed.author_pubkey, &sealed.manifest_id),
+            },
+        )
+        .map_err(|_| "failed to decrypt envelope body".to_string())?;
+
+    if envelope_v2_manifest_id(&body_key, &body) != sealed.manifest_id {
+        return Err("decrypted envelope body does not match manifest_id".to_string());
+    }
+    Ok(body)
+}
+
 pub fn decode_envelope_payload_b64(payload_b64: &str) -> Result<DecodedEnvelopeV1, String> {
     let raw = base64::engine::general_purpose::URL_SAFE_NO_PAD
         .decode(payload_b64)
@@ -148,17 +705,21 @@ pub fn decode_envelope_payload_b64(payload_b64: &str) -> Result<DecodedEnvelopeV
 }
 
 fn parse_envelope_cbor(raw: &[u8]) -> Result<DecodedEnvelopeV1, String> {
+    check_canonical(raw)
+        .map_err(|err| format!("envelope is not canonical CBOR encoding: {err}"))?;
     let fields = decode_cbor_value_exact(raw, "envelope")?;
-    let canonical = encode_cbor_value_deterministic(&fields)
-        .map_err(|err| format!("envelope cbor canonical re-encode failed: {err}"))?;
-    if canonical.as_slice() != raw {
-        return Err("envelope is not canonical CBOR encoding".to_string());
-    }
 
     let Value::Map(map_entries) = fields else {
         return Err("envelope cbor root must be a map".to_string());
     };
 
+    if map_entries
+        .iter()
+        .any(|(key, _)| matches!(key, Value::Text(key_text) if key_text == "version"))
+    {
+        return parse_envelope_v2_entries(map_entries);
+    }
+
     let mut field_map = BTreeMap::<String, Vec<u8>>::new();
     for (key, value) in map_entries {
         let Value::Text(key_text) = key else {
@@ -246,10 +807,209 @@ fn parse_envelope_cbor(raw: &[u8]) -> Result<DecodedEnvelopeV1, String> {
         to_wayfarer_id_hex: bytes_to_hex_lower(&to_wayfarer_id_arr),
         manifest_id_hex: bytes_to_hex_lower(&manifest_id),
         author_wayfarer_id_hex,
+        author_pubkey: author_pubkey_arr,
+        author_device_pubkey: None,
         body,
+        recipient_wayfarer_ids_hex: vec![bytes_to_hex_lower(&to_wayfarer_id_arr)],
+        sealed_body: None,
+        lifetime: None,
+    })
+}
+
+fn parse_envelope_v2_entries(
+    map_entries: Vec<(Value, Value)>,
+) -> Result<DecodedEnvelopeV1, String> {
+    let mut field_map = BTreeMap::<String, Value>::new();
+    for (key, value) in map_entries {
+        let Value::Text(key_text) = key else {
+            return Err("envelope cbor keys must be UTF-8 strings".to_string());
+        };
+        if field_map.insert(key_text, value).is_some() {
+            return Err("envelope cbor contains duplicate keys".to_string());
+        }
+    }
+    if take_envelope_u64(&mut field_map, "version")? != ENVELOPE_VERSION_V2 {
+        return Err("unsupported envelope version".to_string());
+    }
+
+    let recipient = if let Some(recipients) = field_map.remove("recipients") {
+        EnvelopeRecipientV2::Multiple(parse_recipient_key_wraps(recipients)?)
+    } else if field_map.contains_key("recipient_tag") {
+        EnvelopeRecipientV2::Blinded(RecipientTagV2 {
+            tag: take_envelope_bytes(&mut field_map, "recipient_tag")?
+                .try_into()
+                .map_err(|_| "invalid recipient_tag length in envelope".to_string())?,
+            epoch: take_envelope_u64(&mut field_map, "recipient_epoch")?,
+        })
+    } else {
+        EnvelopeRecipientV2::WayfarerId(
+            take_envelope_bytes(&mut field_map, "to_wayfarer_id")?
+                .try_into()
+                .map_err(|_| "invalid to_wayfarer_id length in envelope".to_string())?,
+        )
+    };
+    let lifetime = EnvelopeLifetime {
+        created_at_unix_ms: take_envelope_u64(&mut field_map, "created_at_unix_ms")?,
+        expires_at_unix_ms: take_envelope_u64(&mut field_map, "expires_at_unix_ms")?,
+    };
+    validate_envelope_lifetime(&lifetime)?;
+    let manifest_id = take_envelope_bytes(&mut field_map, "manifest_id")?;
+    if manifest_id.len() != 32 {
+        return Err("invalid manifest_id length in envelope".to_string());
+    }
+    let envelope = EnvelopeV2 {
+        recipient,
+        lifetime,
+        manifest_id,
+        ephemeral_pubkey: take_envelope_bytes(&mut field_map, "ephemeral_pubkey")?
+            .try_into()
+            .map_err(|_| "invalid ephemeral_pubkey length in envelope".to_string())?,
+        ciphertext: take_envelope_bytes(&mut field_map, "ciphertext")?,
+        device_cert: if field_map.contains_key("device_cert") {
+            Some(take_envelope_bytes(&mut field_map, "device_cert")?)
+        } else {
+            None
+        },
+        author_pubkey: take_envelope_bytes(&mut field_map, "author_pubkey")?
+            .try_into()
+            .map_err(|_| "invalid author_pubkey length in envelope".to_string())?,
+        author_sig: take_envelope_bytes(&mut field_map, "author_sig")?
+            .try_into()
+            .map_err(|_| "invalid author_sig length in envelope".to_string())?,
+    };
+    if !field_map.is_empty() {
+        return Err("envelope cbor must contain exactly required keys".to_string());
+    }
+
+    let verifying_key = VerifyingKey::from_bytes(&envelope.author_pubkey)
+        .map_err(|err| format!("invalid author_pubkey: {err}"))?;
+    verifying_key
+        .verify(
+            &envelope.signing_digest_v2()?,
+            &Signature::from_bytes(&envelope.author_sig),
+        )
+        .map_err(|_| "invalid envelope signature".to_string())?;
+    let (identity_pubkey, author_device_pubkey) = match envelope.device_cert.as_deref() {
+        Some(device_cert) => {
+            let certificate = verify_device_certificate(device_cert)?;
+            if certificate.device_pubkey != envelope.author_pubkey {
+                return Err("envelope device certificate does not match author_pubkey".to_string());
+            }
+            (certificate.root_pubkey, Some(envelope.author_pubkey))
+        }
+        None => (envelope.author_pubkey, None),
+    };
+
+    let (to_wayfarer_id_hex, recipient_tag, key_wraps) = match envelope.recipient {
+        EnvelopeRecipientV2::WayfarerId(to_wayfarer_id) => {
+            (bytes_to_hex_lower(&to_wayfarer_id), None, Vec::new())
+        }
+        EnvelopeRecipientV2::Blinded(recipient_tag) => {
+            (String::new(), Some(recipient_tag), Vec::new())
+        }
+        EnvelopeRecipientV2::Multiple(key_wraps) => (String::new(), None, key_wraps),
+    };
+    let recipient_wayfarer_ids_hex = if to_wayfarer_id_hex.is_empty() {
+        key_wraps
+            .iter()
+            .map(|key_wrap| bytes_to_hex_lower(&key_wrap.to_wayfarer_id))
+            .collect()
+    } else {
+        vec![to_wayfarer_id_hex.clone()]
+    };
+    Ok(DecodedEnvelopeV1 {
+        to_wayfarer_id_hex,
+        recipient_wayfarer_ids_hex,
+        manifest_id_hex: bytes_to_hex_lower(&envelope.manifest_id),
+        author_wayfarer_id_hex: bytes_to_hex_lower(&Sha256::digest(identity_pubkey)),
+        author_pubkey: identity_pubkey,
+        author_device_pubkey,
+        body: envelope.ciphertext,
+        sealed_body: Some(SealedBodyV2 {
+            ephemeral_pubkey: envelope.ephemeral_pubkey,
+            recipient_tag,
+            key_wraps,
+            author_pubkey: envelope.author_pubkey,
+            manifest_id: envelope.manifest_id,
+        }),
+        lifetime: Some(envelope.lifetime),
     })
 }
 
+fn validate_envelope_lifetime(lifetime: &EnvelopeLifetime) -> Result<(), String> {
+    if lifetime.expires_at_unix_ms <= lifetime.created_at_unix_ms {
+        return Err("envelope expires_at_unix_ms must be after created_at_unix_ms".to_string());
+    }
+    Ok(())
+}
+
+fn parse_recipient_key_wraps(recipients: Value) -> Result<Vec<RecipientKeyWrapV2>, String> {
+    let Value::Array(entries) = recipients else {
+        return Err("envelope recipients must be an array".to_string());
+    };
+    if entries.is_empty() || entries.len() > MAX_ENVELOPE_RECIPIENTS {
+        return Err(format!(
+            "envelope recipients must list between 1 and {MAX_ENVELOPE_RECIPIENTS} entries"
+        ));
+    }
+
+    let mut key_wraps = Vec::<RecipientKeyWrapV2>::with_capacity(entries.len());
+    for entry in entries {
+        let Value::Map(entry_fields) = entry else {
+            return Err("envelope recipient entry must be a map".to_string());
+        };
+        let mut field_map = BTreeMap::<String, Value>::new();
+        for (key, value) in entry_fields {
+            let Value::Text(key_text) = key else {
+                return Err("envelope recipient keys must be UTF-8 strings".to_string());
+            };
+            if field_map.insert(key_text, value).is_some() {
+                return Err("envelope recipient entry contains duplicate keys".to_string());
+            }
+        }
+        let key_wrap = RecipientKeyWrapV2 {
+            to_wayfarer_id: take_envelope_bytes(&mut field_map, "to_wayfarer_id")?
+                .try_into()
+                .map_err(|_| "invalid recipient to_wayfarer_id length in envelope".to_string())?,
+            wrapped_key: take_envelope_bytes(&mut field_map, "wrapped_key")?,
+        };
+        if !field_map.is_empty() {
+            return Err("envelope recipient entry must contain exactly required keys".to_string());
+        }
+        if key_wrap.wrapped_key.len() != WRAPPED_CONTENT_KEY_BYTES {
+            return Err("invalid recipient wrapped_key length in envelope".to_string());
+        }
+        if key_wraps
+            .iter()
+            .any(|existing| existing.to_wayfarer_id == key_wrap.to_wayfarer_id)
+        {
+            return Err("envelope recipients list a recipient twice".to_string());
+        }
+        key_wraps.push(key_wrap);
+    }
+    Ok(key_wraps)
+}
+
+fn take_envelope_bytes(
+    field_map: &mut BTreeMap<String, Value>,
+    key: &str,
+) -> Result<Vec<u8>, String> {
+    match field_map.remove(key) {
+        Some(Value::Bytes(bytes)) => Ok(bytes),
+        Some(_) => Err(format!("envelope field {key} must be a byte string")),
+        None => Err(format!("missing envelope field {key}")),
+    }
+}
+
+fn take_envelope_u64(field_map: &mut BTreeMap<String, Value>, key: &str) -> Result<u64, String> {
+    match field_map.remove(key) {
+        Some(Value::Integer(integer)) => u64::try_from(integer)
+            .map_err(|_| format!("envelope field {key} must be an unsigned integer")),
+        Some(_) => Err(format!("envelope field {key} must be an unsigned integer")),
+        None => Err(format!("missing envelope field {key}")),
+    }
+}
+
 fn build_signing_payload_v1(
     to_wayfarer_id: &[u8; 32],
     manifest_id: &[u8],
@@ -277,6 +1037,73 @@ fn envelope_signing_digest_v1(signing_payload: &[u8]) -> [u8; 32] {
     hasher.finalize().into()
 }
 
+fn recipient_tag_v2(static_secret: &[u8; 32], epoch: u64) -> Result<[u8; 32], String> {
+    if static_secret.iter().all(|byte| *byte == 0) {
+        return Err("recipient tag key agreement produced a low-order shared secret".to_string());
+    }
+    let mut hasher = Sha256::new();
+    hasher.update(ENVELOPE_V2_RECIPIENT_TAG_DOMAIN);
+    hasher.update(static_secret);
+    hasher.update(epoch.to_be_bytes());
+    Ok(hasher.finalize().into())
+}
+
+fn envelope_v2_body_key(
+    shared_secret: &[u8; 32],
+    ephemeral_pubkey: &[u8; 32],
+    recipient_pubkey: &[u8; 32],
+) -> Result<[u8; 32], String> {
+    if shared_secret.iter().all(|byte| *byte == 0) {
+        return Err("envelope key agreement produced a low-order shared secret".to_string());
+    }
+    let mut hasher = Sha256::new();
+    hasher.update(ENVELOPE_V2_BODY_KEY_DOMAIN);
+    hasher.update(shared_secret);
+    hasher.update(ephemeral_pubkey);
+    hasher.update(recipient_pubkey);
+    Ok(hasher.finalize().into())
+}
+
+/// Cleartext `manifest_id` for a sealed body. Keying it by the body key keeps carriers
+/// from confirming a guessed plaintext against the signed envelope.
+fn envelope_v2_manifest_id(body_key: &[u8; 32], body: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(ENVELOPE_V2_MANIFEST_ID_DOMAIN);
+    hasher.update(body_key);
+    hasher.update(body);
+    hasher.finalize().to_vec()
+}
+
+/// Message identifier that reactions, edits and receipts refer to: the SHA-256 of the
+/// opened app body. For v1 envelopes this equals the cleartext `manifest_id`.
+pub fn body_manifest_id_hex(body: &[u8]) -> String {
+    bytes_to_hex_lower(&Sha256::digest(body))
+}
+
+fn envelope_v2_key_wrap_cipher(
+    shared_secret: &[u8; 32],
+    ephemeral_pubkey: &[u8; 32],
+    recipient_pubkey: &[u8; 32],
+) -> Result<ChaCha20Poly1305, String> {
+    if shared_secret.iter().all(|byte| *byte == 0) {
+        return Err("envelope key agreement produced a low-order shared secret".to_string());
+    }
+    let mut hasher = Sha256::new();
+    hasher.update(ENVELOPE_V2_KEY_WRAP_DOMAIN);
+    hasher.update(shared_secret);
+    hasher.update(ephemeral_pubkey);
+    hasher.update(recipient_pubkey);
+    let key = hasher.finalize();
+    Ok(ChaCha20Poly1305::new(Key::from_slice(&key)))
+}
+
+fn envelope_v2_body_aad(author_pubkey: &[u8; 32], manifest_id: &[u8]) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(author_pubkey.len() + manifest_id.len());
+    aad.extend_from_slice(author_pubkey);
+    aad.extend_from_slice(manifest_id);
+    aad
+}
+
 pub fn to_cbor_value<T: Serialize>(value: &T) -> Result<Value, String> {
     let mut raw = Vec::new();
     into_writer(value, &mut raw).map_err(|err| format!("CBOR encode failed: {err}"))?;
@@ -296,48 +1123,7 @@ pub fn decode_cbor_value_exact(raw: &[u8], context: &str) -> Result<Value, Strin
 }
 
 pub fn encode_cbor_value_deterministic(value: &Value) -> Result<Vec<u8>, String> {
-    let canonical = canonicalize_cbor_value(value.clone())?;
-    let mut out = Vec::new();
-    into_writer(&canonical, &mut out)
-        .map_err(|err| format!("deterministic CBOR encode failed: {err}"))?;
-    Ok(out)
-}
-
-fn canonicalize_cbor_value(mut value: Value) -> Result<Value, String> {
-    match &mut value {
-        Value::Array(items) => {
-            for item in items.iter_mut() {
-                *item = canonicalize_cbor_value(item.clone())?;
-            }
-        }
-        Value::Map(entries) => {
-            for (key, entry_value) in entries.iter_mut() {
-                *key = canonicalize_cbor_value(key.clone())?;
-                *entry_value = canonicalize_cbor_value(entry_value.clone())?;
-            }
-
-            let mut encoded_entries = Vec::with_capacity(entries.len());
-            for (key, entry_value) in entries.drain(..) {
-                let encoded_key = encode_cbor_value_raw(&key)?;
-                encoded_entries.push((encoded_key, key, entry_value));
-            }
-            encoded_entries.sort_by(|(left_encoded, _, _), (right_encoded, _, _)| {
-                left_encoded.cmp(right_encoded)
-            });
-            *entries = encoded_entries
-                .into_iter()
-                .map(|(_, key, entry_value)| (key, entry_value))
-                .collect();
-        }
-        _ => {}
-    }
-    Ok(value)
-}
-
-fn encode_cbor_value_raw(value: &Value) -> Result<Vec<u8>, String> {
-    let mut out = Vec::new();
-    into_writer(value, &mut out).map_err(|err| format!("CBOR key encode failed: {err}"))?;
-    Ok(out)
+    encode_canonical(value).map_err(|err| format!("deterministic CBOR encode failed: {err}"))
 }
 
 #[allow(dead_code)]
@@ -348,37 +1134,14 @@ pub fn decode_envelope_payload_utf8_preview(payload_b64: &str) -> Result<String,
 
 pub fn decode_envelope_payload_text_preview(payload_b64: &str) -> Result<String, String> {
     let decoded = decode_envelope_payload_b64(payload_b64)?;
-    extract_wayfarer_chat_text_from_cbor(&decoded.body)
-        .ok_or_else(|| "payload is not a valid wayfarer.chat.v1 message".to_string())
+    decode_body_text_preview(&decoded.body)
 }
 
-fn extract_wayfarer_chat_text_from_cbor(body: &[u8]) -> Option<String> {
-    let value = decode_cbor_value_exact(body, "wayfarer payload").ok()?;
-    let Value::Map(entries) = value else {
-        return None;
-    };
-
-    let mut type_string: Option<String> = None;
-    let mut text: Option<String> = None;
-    let mut created_at_present = false;
-
-    for (key, value) in entries {
-        let Value::Text(key_text) = key else {
-            return None;
-        };
-        match (key_text.as_str(), value) {
-            ("type", Value::Text(value)) => type_string = Some(value),
-            ("text", Value::Text(value)) if !value.is_empty() => text = Some(value),
-            ("created_at_unix_ms", Value::Integer(_)) => created_at_present = true,
-            _ => {}
-        }
-    }
-
-    if type_string.as_deref() != Some("wayfarer.chat.v1") || !created_at_present {
-        return None;
+pub fn decode_body_text_preview(body: &[u8]) -> Result<String, String> {
+    match decode_app_body(body) {
+        Ok(AppBody::Chat(chat)) => Ok(chat.text),
+        _ => Err("payload is not a valid wayfarer.chat.v1 message".to_string()),
     }
-
-    text
 }
 
 fn parse_wayfarer_id_hex(hex_lower: &str) -> Result<[u8; 32], String> {
@@ -409,12 +1172,18 @@ pub fn bytes_to_hex_lower(input: &[u8]) -> String {
 
 #[cfg(test)]
 mod tests {
-    use crate::aethos_core::vectors::load_envelope_vectors;
+    use crate::aethos_core::vectors::{load_envelope_v2_vectors, load_envelope_vectors};
 
     use super::{
-        build_envelope_payload_b64, build_envelope_payload_b64_from_utf8, bytes_to_hex_lower,
+        body_manifest_id_hex, build_blinded_envelope_v2_payload_b64,
+        build_device_signed_envelope_v2_payload_b64, build_envelope_payload_b64,
+        build_envelope_payload_b64_from_utf8, build_envelope_v2_payload_b64,
+        build_envelope_v2_payload_b64_with_ephemeral_seed,
+        build_multi_recipient_envelope_v2_payload_b64,
+        build_multi_recipient_envelope_v2_payload_b64_with_ephemeral_seed, bytes_to_hex_lower,
         decode_envelope_payload_b64, decode_envelope_payload_text_preview,
-        encode_cbor_value_deterministic, parse_envelope_cbor,
+        encode_cbor_value_deterministic, envelope_matches_recipient, open_envelope_body,
+        parse_envelope_cbor, EnvelopeLifetime, RECIPIENT_TAG_EPOCH_MS,
     };
     use base64::Engine;
     use ciborium::value::Value;
@@ -623,4 +1392,363 @@ mod tests {
             );
         }
     }
+
+    fn hex_to_bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|index| u8::from_str_radix(&hex[index..index + 2], 16).expect("hex byte"))
+            .collect()
+    }
+
+    fn seed_from_hex(hex: &str) -> [u8; 32] {
+        hex_to_bytes(hex).try_into().expect("32-byte seed")
+    }
+
+    #[test]
+    fn envelope_v2_seals_body_and_opens_for_recipient() {
+        let recipient_seed = [0x21u8; 32];
+        let recipient_pubkey = SigningKey::from_bytes(&recipient_seed)
+            .verifying_key()
+            .to_bytes();
+        let envelope_b64 = build_envelope_v2_payload_b64(
+            &recipient_pubkey,
+            VECTOR_BODY.as_bytes(),
+            &TEST_SIGNING_KEY_SEED,
+            EnvelopeLifetime::from_ttl(1_735_689_600_000, 60_000),
+        )
+        .expect("build sealed envelope");
+
+        let decoded = decode_envelope_payload_b64(&envelope_b64).expect("carrier decode");
+        assert!(decoded.is_sealed());
+        assert_ne!(decoded.body, VECTOR_BODY.as_bytes());
+        assert_eq!(
+            decoded.author_wayfarer_id_hex,
+            expected_author_wayfarer_id()
+        );
+        assert_eq!(
+            decoded.to_wayfarer_id_hex,
+            bytes_to_hex_lower(&Sha256::digest(recipient_pubkey))
+        );
+
+        assert_ne!(
+            decoded.manifest_id_hex,
+            body_manifest_id_hex(VECTOR_BODY.as_bytes()),
+            "carriers must not see the plaintext digest"
+        );
+
+        let body = open_envelope_body(&decoded, &recipient_seed).expect("recipient opens body");
+        assert_eq!(body, VECTOR_BODY.as_bytes());
+        assert!(open_envelope_body(&decoded, &[0x22u8; 32])
+            .expect_err("other identity cannot open body")
+            .contains("not addressed to this identity"));
+    }
+
+    #[test]
+    fn blinded_envelope_v2_hides_recipient_and_rotates_tag_per_epoch() {
+        let recipient_seed = [0x41u8; 32];
+        let recipient_pubkey = SigningKey::from_bytes(&recipient_seed)
+            .verifying_key()
+            .to_bytes();
+        let now_ms = 1_735_689_600_000u64;
+        let build = |now_ms| {
+            let envelope_b64 = build_blinded_envelope_v2_payload_b64(
+                &recipient_pubkey,
+                VECTOR_BODY.as_bytes(),
+                &TEST_SIGNING_KEY_SEED,
+                EnvelopeLifetime::from_ttl(now_ms, 60_000),
+            )
+            .expect("build blinded envelope");
+            decode_envelope_payload_b64(&envelope_b64).expect("carrier decode")
+        };
+        let tag_of = |decoded: &super::DecodedEnvelopeV1| {
+            decoded
+                .sealed_body
+                .as_ref()
+                .and_then(|sealed| sealed.recipient_tag)
+                .expect("recipient tag")
+        };
+
+        let first = build(now_ms);
+        let same_epoch = build(now_ms + 1);
+        let next_epoch = build(now_ms + RECIPIENT_TAG_EPOCH_MS);
+
+        assert!(first.is_blinded());
+        assert!(first.to_wayfarer_id_hex.is_empty());
+        assert_eq!(tag_of(&first).tag, tag_of(&same_epoch).tag);
+        assert_ne!(tag_of(&first).tag, tag_of(&next_epoch).tag);
+        assert!(envelope_matches_recipient(&first, &recipient_seed));
+        assert!(envelope_matches_recipient(&next_epoch, &recipient_seed));
+        assert!(!envelope_matches_recipient(&first, &[0x42u8; 32]));
+        assert_eq!(
+            open_envelope_body(&first, &recipient_seed).expect("recipient opens body"),
+            VECTOR_BODY.as_bytes()
+        );
+    }
+
+    #[test]
+    fn multi_recipient_envelope_v2_opens_for_each_listed_recipient() {
+        let recipient_seeds = [[0x61u8; 32], [0x62u8; 32], [0x63u8; 32]];
+        let recipient_pubkeys = recipient_seeds
+            .iter()
+            .map(|seed| SigningKey::from_bytes(seed).verifying_key().to_bytes())
+            .collect::<Vec<_>>();
+        let recipient_ids = recipient_pubkeys
+            .iter()
+            .map(|pubkey| bytes_to_hex_lower(&Sha256::digest(pubkey)))
+            .collect::<Vec<_>>();
+        let envelope_b64 = build_multi_recipient_envelope_v2_payload_b64(
+            &recipient_pubkeys,
+            VECTOR_BODY.as_bytes(),
+            &TEST_SIGNING_KEY_SEED,
+            EnvelopeLifetime::from_ttl(1_735_689_600_000, 60_000),
+        )
+        .expect("build multi-recipient envelope");
+
+        let decoded = decode_envelope_payload_b64(&envelope_b64).expect("carrier decode");
+        assert!(decoded.to_wayfarer_id_hex.is_empty());
+        assert_eq!(decoded.recipient_wayfarer_ids_hex, recipient_ids);
+        assert_eq!(
+            decoded.matched_recipient(&["ff".repeat(32).as_str(), recipient_ids[1].as_str()]),
+            Some(recipient_ids[1].as_str())
+        );
+        for seed in &recipient_seeds {
+            assert!(envelope_matches_recipient(&decoded, seed));
+            assert_eq!(
+                open_envelope_body(&decoded, seed).expect("recipient opens body"),
+                VECTOR_BODY.as_bytes()
+            );
+        }
+        assert!(open_envelope_body(&decoded, &[0x64u8; 32])
+            .expect_err("unlisted identity cannot open body")
+            .contains("not addressed to this identity"));
+    }
+
+    #[test]
+    fn device_signed_envelope_v2_is_attributed_to_the_root_identity() {
+        use crate::aethos_core::device_link::issue_device_certificate;
+
+        let device_seed = [0x71u8; 32];
+        let device_pubkey = SigningKey::from_bytes(&device_seed)
+            .verifying_key()
+            .to_bytes();
+        let certificate =
+            issue_device_certificate(&TEST_SIGNING_KEY_SEED, &device_pubkey, "laptop", 1)
+                .expect("issue certificate");
+        let recipient_seed = [0x61u8; 32];
+        let recipient_pubkey = SigningKey::from_bytes(&recipient_seed)
+            .verifying_key()
+            .to_bytes();
+        let lifetime = EnvelopeLifetime::from_ttl(1_735_689_600_000, 60_000);
+
+        let envelope_b64 = build_device_signed_envelope_v2_payload_b64(
+            &[recipient_pubkey],
+            VECTOR_BODY.as_bytes(),
+            &device_seed,
+            &certificate,
+            lifetime,
+        )
+        .expect("build device-signed envelope");
+        let decoded = decode_envelope_payload_b64(&envelope_b64).expect("decode envelope");
+        assert_eq!(
+            decoded.author_wayfarer_id_hex,
+            expected_author_wayfarer_id()
+        );
+        assert_eq!(decoded.author_device_pubkey, Some(device_pubkey));
+        assert_eq!(
+            open_envelope_body(&decoded, &recipient_seed).expect("open body"),
+            VECTOR_BODY.as_bytes()
+        );
+
+        let other_certificate =
+            issue_device_certificate(&TEST_SIGNING_KEY_SEED, &recipient_pubkey, "phone", 1)
+                .expect("issue other certificate");
+        assert!(build_device_signed_envelope_v2_payload_b64(
+            &[recipient_pubkey],
+            VECTOR_BODY.as_bytes(),
+            &device_seed,
+            &other_certificate,
+            lifetime,
+        )
+        .is_err());
+        let forged = build_multi_recipient_envelope_v2_payload_b64_with_ephemeral_seed(
+            &[recipient_pubkey],
+            VECTOR_BODY.as_bytes(),
+            &device_seed,
+            &[0x55u8; 32],
+            lifetime,
+            Some(&other_certificate),
+        )
+        .expect("build envelope with mismatched certificate");
+        assert!(decode_envelope_payload_b64(&forged)
+            .expect_err("mismatched certificate must fail")
+            .contains("does not match author_pubkey"));
+    }
+
+    #[test]
+    fn multi_recipient_envelope_v2_rejects_duplicate_recipients() {
+        let recipient_pubkey = SigningKey::from_bytes(&[0x61u8; 32])
+            .verifying_key()
+            .to_bytes();
+        assert!(build_multi_recipient_envelope_v2_payload_b64(
+            &[recipient_pubkey, recipient_pubkey],
+            VECTOR_BODY.as_bytes(),
+            &TEST_SIGNING_KEY_SEED,
+            EnvelopeLifetime::from_ttl(1_735_689_600_000, 60_000),
+        )
+        .expect_err("duplicate recipients must fail")
+        .contains("recipient twice"));
+    }
+
+    #[test]
+    fn envelope_v2_decode_rejects_tampered_ciphertext() {
+        let recipient_pubkey = SigningKey::from_bytes(&[0x21u8; 32])
+            .verifying_key()
+            .to_bytes();
+        let envelope_b64 = build_envelope_v2_payload_b64(
+            &recipient_pubkey,
+            VECTOR_BODY.as_bytes(),
+            &TEST_SIGNING_KEY_SEED,
+            EnvelopeLifetime::from_ttl(1_735_689_600_000, 60_000),
+        )
+        .expect("build sealed envelope");
+        let raw = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(&envelope_b64)
+            .expect("decode envelope bytes");
+        let Value::Map(mut entries) =
+            ciborium::de::from_reader::<Value, _>(raw.as_slice()).expect("decode cbor")
+        else {
+            panic!("expected envelope map")
+        };
+        for (key, value) in entries.iter_mut() {
+            if let (Value::Text(key_text), Value::Bytes(bytes)) = (key, value) {
+                if key_text == "ciphertext" {
+                    bytes[0] ^= 0x01;
+                }
+            }
+        }
+        let tampered = encode_cbor_value_deterministic(&Value::Map(entries))
+            .expect("encode tampered envelope");
+
+        assert!(parse_envelope_cbor(&tampered)
+            .expect_err("tampered ciphertext must fail signature check")
+            .contains("invalid envelope signature"));
+    }
+
+    #[test]
+    fn envelope_v2_decode_requires_lifetime() {
+        let recipient_pubkey = SigningKey::from_bytes(&[0x21u8; 32])
+            .verifying_key()
+            .to_bytes();
+        let envelope_b64 = build_envelope_v2_payload_b64(
+            &recipient_pubkey,
+            VECTOR_BODY.as_bytes(),
+            &TEST_SIGNING_KEY_SEED,
+            EnvelopeLifetime::from_ttl(1_735_689_600_000, 60_000),
+        )
+        .expect("build sealed envelope");
+        let raw = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(&envelope_b64)
+            .expect("decode envelope bytes");
+        let Value::Map(mut entries) =
+            ciborium::de::from_reader::<Value, _>(raw.as_slice()).expect("decode cbor")
+        else {
+            panic!("expected envelope map")
+        };
+        entries.retain(|(key, _)| {
+            !matches!(key, Value::Text(key_text)
+                if key_text == "created_at_unix_ms" || key_text == "expires_at_unix_ms")
+        });
+        let stripped = encode_cbor_value_deterministic(&Value::Map(entries))
+            .expect("encode stripped envelope");
+
+        assert!(parse_envelope_cbor(&stripped)
+            .expect_err("v2 envelope without a lifetime must be rejected")
+            .contains("missing envelope field created_at_unix_ms"));
+    }
+
+    #[test]
+    fn cross_client_v2_vectors_decode_verify_and_open() {
+        let vector_set = load_envelope_v2_vectors();
+
+        for vector in vector_set.vectors {
+            let recipient_seed = seed_from_hex(&vector.recipient_signing_key_seed_hex);
+            let author_seed = seed_from_hex(&vector.author_signing_key_seed_hex);
+            let ephemeral_seed = seed_from_hex(&vector.ephemeral_seed_hex);
+            let recipient_pubkey = SigningKey::from_bytes(&recipient_seed)
+                .verifying_key()
+                .to_bytes();
+            let body = hex_to_bytes(&vector.expected_decoded.body_hex);
+            let lifetime = EnvelopeLifetime {
+                created_at_unix_ms: vector.lifetime.created_at_unix_ms,
+                expires_at_unix_ms: vector.lifetime.expires_at_unix_ms,
+            };
+            let additional_recipient_seeds = vector
+                .additional_recipient_signing_key_seeds_hex
+                .iter()
+                .map(|seed_hex| seed_from_hex(seed_hex))
+                .collect::<Vec<_>>();
+
+            let rebuilt = if additional_recipient_seeds.is_empty() {
+                build_envelope_v2_payload_b64_with_ephemeral_seed(
+                    &recipient_pubkey,
+                    &body,
+                    &author_seed,
+                    &ephemeral_seed,
+                    vector.recipient_epoch,
+                    lifetime,
+                )
+            } else {
+                let recipient_pubkeys = std::iter::once(recipient_seed)
+                    .chain(additional_recipient_seeds.iter().copied())
+                    .map(|seed| SigningKey::from_bytes(&seed).verifying_key().to_bytes())
+                    .collect::<Vec<_>>();
+                build_multi_recipient_envelope_v2_payload_b64_with_ephemeral_seed(
+                    &recipient_pubkeys,
+                    &body,
+                    &author_seed,
+                    &ephemeral_seed,
+                    lifetime,
+                    None,
+                )
+            }
+            .expect("rebuild vector envelope");
+            assert_eq!(rebuilt, vector.payload_b64);
+
+            let envelope_raw = base64::engine::general_purpose::URL_SAFE_NO_PAD
+                .decode(&vector.payload_b64)
+                .expect("decode payload_b64");
+            assert_eq!(
+                bytes_to_hex_lower(&envelope_raw),
+                vector.canonical_envelope_cbor_hex
+            );
+            assert_eq!(
+                bytes_to_hex_lower(&Sha256::digest(&envelope_raw)),
+                vector.item_id_hex
+            );
+
+            let decoded =
+                decode_envelope_payload_b64(&vector.payload_b64).expect("decode envelope payload");
+            assert_eq!(
+                decoded.to_wayfarer_id_hex,
+                vector.expected_decoded.to_wayfarer_id
+            );
+            assert_eq!(decoded.manifest_id_hex, vector.expected_decoded.manifest_id);
+            assert_eq!(
+                decoded.author_wayfarer_id_hex,
+                vector.expected_decoded.author_wayfarer_id
+            );
+            assert_eq!(decoded.is_blinded(), vector.recipient_epoch.is_some());
+            assert_eq!(decoded.lifetime, Some(lifetime));
+            if let Some(recipient_ids) = vector.expected_decoded.recipient_wayfarer_ids.as_ref() {
+                assert_eq!(&decoded.recipient_wayfarer_ids_hex, recipient_ids);
+            }
+            for seed in std::iter::once(recipient_seed).chain(additional_recipient_seeds) {
+                assert!(envelope_matches_recipient(&decoded, &seed));
+                assert_eq!(
+                    open_envelope_body(&decoded, &seed).expect("open vector body"),
+                    body
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,119 @@
+use std::collections::BTreeMap;
+
+use ciborium::value::Value;
+use ed25519_dalek::SigningKey;
+
+use crate::aethos_core::app_body::{decode_app_body, required_u64_integer, AppBody};
+use crate::aethos_core::identity_store::wayfarer_id_from_pubkey;
+use crate::aethos_core::protocol::DecodedEnvelopeV1;
+use crate::aethos_core::signed_record::{
+    decode_prefixed_code, issue_signed_record, prefixed_code, required_bytes, verify_signed_record,
+    SignedRecord,
+};
+
+pub const WAYFARER_REVOCATION_CERT_V1: &str = "wayfarer.revocation_cert.v1";
+/// Text form handed to the user for safekeeping, e.g. printed or stored off-device.
+pub const REVOCATION_CODE_PREFIX: &str = "aethos-revoke:";
+/// How long carriers keep a revocation item; far beyond any message TTL so late peers still
+/// learn of it.
+pub const REVOCATION_RETENTION_MS: u64 = 365 * 24 * 60 * 60 * 1000;
+
+/// Statement by the key holder that `pubkey` must no longer be trusted. It carries no
+/// revocation time of its own: it is made in advance, before anyone knows when it is needed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevocationCertificate {
+    pub pubkey: [u8; 32],
+    pub issued_at_unix_ms: u64,
+}
+
+impl RevocationCertificate {
+    pub fn wayfarer_id(&self) -> String {
+        wayfarer_id_from_pubkey(&self.pubkey)
+    }
+}
+
+impl SignedRecord for RevocationCertificate {
+    const PAYLOAD_TYPE: &'static str = WAYFARER_REVOCATION_CERT_V1;
+    const SIGNING_DOMAIN: &'static [u8] = b"AETHOS_REVOCATION_V1";
+    const LABEL: &'static str = "revocation certificate";
+    const SIGNATURE_FIELDS: &'static [&'static str] = &["sig"];
+
+    fn fields(&self) -> BTreeMap<String, Value> {
+        let mut fields = BTreeMap::new();
+        fields.insert("pubkey".to_string(), Value::Bytes(self.pubkey.to_vec()));
+        fields.insert(
+            "issued_at_unix_ms".to_string(),
+            Value::Integer(self.issued_at_unix_ms.into()),
+        );
+        fields
+    }
+
+    fn from_fields(fields: &BTreeMap<String, Value>) -> Result<Self, String> {
+        Ok(Self {
+            pubkey: required_bytes(fields, "pubkey")?,
+            issued_at_unix_ms: required_u64_integer(fields, "issued_at_unix_ms")?,
+        })
+    }
+
+    fn signer_pubkeys(&self) -> Vec<[u8; 32]> {
+        vec![self.pubkey]
+    }
+}
+
+pub fn issue_revocation_certificate(
+    signing_seed: &[u8; 32],
+    issued_at_unix_ms: u64,
+) -> Result<Vec<u8>, String> {
+    let signing_key = SigningKey::from_bytes(signing_seed);
+    let certificate = RevocationCertificate {
+        pubkey: signing_key.verifying_key().to_bytes(),
+        issued_at_unix_ms,
+    };
+    issue_signed_record(&certificate, &[&signing_key])
+}
+
+/// Decodes a revocation certificate and checks its self-signature.
+pub fn verify_revocation_certificate(raw: &[u8]) -> Result<RevocationCertificate, String> {
+    verify_signed_record(raw)
+}
+
+/// Certificate carried by a cleartext revocation item, if `parsed` is one.
+pub fn revocation_certificate_in(parsed: &DecodedEnvelopeV1) -> Option<Vec<u8>> {
+    if parsed.is_sealed() {
+        return None;
+    }
+    match decode_app_body(&parsed.body) {
+        Ok(AppBody::Revocation(revocation)) => Some(revocation.certificate),
+        _ => None,
+    }
+}
+
+pub fn revocation_certificate_code(certificate: &[u8]) -> String {
+    prefixed_code(REVOCATION_CODE_PREFIX, certificate)
+}
+
+pub fn revocation_certificate_from_code(code: &str) -> Result<Vec<u8>, String> {
+    decode_prefixed_code(code, REVOCATION_CODE_PREFIX, "revocation code")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn revocation_certificate_round_trips_through_code() {
+        let raw = issue_revocation_certificate(&[5u8; 32], 42).expect("issue certificate");
+        let code = revocation_certificate_code(&raw);
+        assert!(code.starts_with(REVOCATION_CODE_PREFIX));
+
+        let certificate =
+            verify_revocation_certificate(&revocation_certificate_from_code(&code).expect("code"))
+                .expect("verify certificate");
+        let pubkey = SigningKey::from_bytes(&[5u8; 32])
+            .verifying_key()
+            .to_bytes();
+        assert_eq!(certificate.pubkey, pubkey);
+        assert_eq!(certificate.wayfarer_id(), wayfarer_id_from_pubkey(&pubkey));
+        assert_eq!(certificate.issued_at_unix_ms, 42);
+    }
+}
@@ -0,0 +1,142 @@
+use sha2::{Digest, Sha256};
+
+const SAFETY_NUMBER_DOMAIN: &[u8] = b"aethos-safety-number-v1";
+/// Hash iterations per party fingerprint, raising the cost of searching for a colliding key.
+const FINGERPRINT_ITERATIONS: usize = 5200;
+const DIGIT_BLOCKS_PER_PARTY: usize = 6;
+const DIGITS_PER_BLOCK: u64 = 100_000;
+pub const SAFETY_NUMBER_WORD_COUNT: usize = 8;
+
+/// Word list for the spoken form of a safety number; one word per fingerprint byte.
+pub const SAFETY_NUMBER_WORDS: [&str; 256] = [
+    "acid", "acorn", "actor", "adobe", "agent", "alarm", "album", "alley", "amber", "angle",
+    "apple", "apron", "arena", "armor", "arrow", "aspen", "atlas", "attic", "autumn", "award",
+    "bacon", "badge", "bagel", "baker", "bamboo", "banjo", "barge", "basil", "basin", "beach",
+    "beacon", "beard", "beetle", "bench", "berry", "bison", "blade", "blaze", "blimp", "bloom",
+    "board", "bonus", "boots", "bottle", "boxer", "brain", "brass", "bread", "brick", "bridge",
+    "broom", "brush", "bucket", "bugle", "cabin", "cable", "cactus", "camel", "canal", "candle",
+    "canoe", "canyon", "carbon", "cargo", "carpet", "castle", "cedar", "cello", "chalk", "charm",
+    "cherry", "cider", "circus", "citrus", "clay", "cliff", "clock", "cloud", "clover", "coast",
+    "cobra", "comet", "copper", "coral", "cotton", "cougar", "crane", "crater", "crayon", "crown",
+    "cube", "cycle", "dagger", "daisy", "delta", "denim", "desert", "diesel", "dingo", "dock",
+    "dolphin", "donkey", "dragon", "drum", "eagle", "easel", "echo", "elbow", "ember", "engine",
+    "falcon", "feather", "fern", "ferry", "fiddle", "flute", "forest", "fossil", "fox", "garden",
+    "garlic", "gecko", "ginger", "globe", "goblet", "gopher", "granite", "grape", "gravel",
+    "guitar", "hammer", "harbor", "harp", "hazel", "helmet", "heron", "honey", "hornet", "igloo",
+    "island", "ivory", "jacket", "jaguar", "jelly", "jigsaw", "jungle", "kayak", "kernel",
+    "kettle", "kiwi", "koala", "ladder", "lagoon", "lantern", "laser", "lava", "lemon", "lily",
+    "lizard", "llama", "locket", "lotus", "magnet", "mango", "maple", "marble", "meadow", "melon",
+    "meteor", "mint", "mirror", "monkey", "moose", "muffin", "nectar", "needle", "nickel",
+    "noodle", "nutmeg", "oasis", "ocean", "olive", "onion", "orbit", "orchid", "otter", "oyster",
+    "paddle", "palace", "panda", "paper", "parrot", "peach", "pebble", "pepper", "piano", "pickle",
+    "pigeon", "pillow", "pine", "pirate", "planet", "plum", "pocket", "polar", "pony", "potato",
+    "prism", "puzzle", "quartz", "quill", "rabbit", "radar", "radish", "raft", "raven", "reef",
+    "ribbon", "river", "robot", "rocket", "rose", "ruby", "saddle", "salmon", "satin", "scarf",
+    "shadow", "shovel", "silver", "skate", "sled", "snail", "socket", "spider", "sponge", "squid",
+    "storm", "sugar", "summit", "sunset", "swan", "tablet", "tango", "teapot", "tiger", "timber",
+    "tomato", "tulip", "turtle", "velvet", "violin", "walnut", "walrus", "wizard", "yacht",
+];
+
+/// Pairwise fingerprint for two identities. Both parties compute the same value, so it can be
+/// compared in person or read aloud.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SafetyNumber {
+    /// Twelve five-digit blocks, six derived from each party's key.
+    pub blocks: Vec<String>,
+    pub words: Vec<&'static str>,
+}
+
+impl SafetyNumber {
+    pub fn digits(&self) -> String {
+        self.blocks.join(" ")
+    }
+
+    pub fn phrase(&self) -> String {
+        self.words.join(" ")
+    }
+}
+
+/// Computes the safety number for the identities with `local_pubkey` and `remote_pubkey`.
+/// The result is independent of which side is local.
+pub fn compute_safety_number(local_pubkey: &[u8; 32], remote_pubkey: &[u8; 32]) -> SafetyNumber {
+    let mut fingerprints = [
+        party_fingerprint(local_pubkey),
+        party_fingerprint(remote_pubkey),
+    ];
+    fingerprints.sort();
+
+    let blocks = fingerprints
+        .iter()
+        .flat_map(|fingerprint| {
+            fingerprint
+                .chunks_exact(5)
+                .take(DIGIT_BLOCKS_PER_PARTY)
+                .map(|chunk| {
+                    let value = chunk
+                        .iter()
+                        .fold(0u64, |value, byte| (value << 8) | *byte as u64);
+                    format!("{:05}", value % DIGITS_PER_BLOCK)
+                })
+        })
+        .collect();
+
+    let mut hasher = Sha256::new();
+    hasher.update(SAFETY_NUMBER_DOMAIN);
+    hasher.update(fingerprints[0]);
+    hasher.update(fingerprints[1]);
+    let words = hasher
+        .finalize()
+        .iter()
+        .take(SAFETY_NUMBER_WORD_COUNT)
+        .map(|byte| SAFETY_NUMBER_WORDS[*byte as usize])
+        .collect();
+
+    SafetyNumber { blocks, words }
+}
+
+fn party_fingerprint(pubkey: &[u8; 32]) -> [u8; 32] {
+    let mut digest: [u8; 32] = Sha256::new()
+        .chain_update(SAFETY_NUMBER_DOMAIN)
+        .chain_update(pubkey)
+        .finalize()
+        .into();
+    for _ in 1..FINGERPRINT_ITERATIONS {
+        digest = Sha256::new()
+            .chain_update(digest)
+            .chain_update(pubkey)
+            .finalize()
+            .into();
+    }
+    digest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn safety_number_is_symmetric_and_key_dependent() {
+        let alice = [1u8; 32];
+        let bob = [2u8; 32];
+        let carol = [3u8; 32];
+
+        let alice_view = compute_safety_number(&alice, &bob);
+        assert_eq!(alice_view, compute_safety_number(&bob, &alice));
+        assert_ne!(alice_view, compute_safety_number(&alice, &carol));
+
+        assert_eq!(alice_view.blocks.len(), 2 * DIGIT_BLOCKS_PER_PARTY);
+        assert!(alice_view
+            .blocks
+            .iter()
+            .all(|block| block.len() == 5 && block.bytes().all(|byte| byte.is_ascii_digit())));
+        assert_eq!(alice_view.words.len(), SAFETY_NUMBER_WORD_COUNT);
+        assert_eq!(alice_view.digits().len(), 12 * 5 + 11);
+    }
+
+    #[test]
+    fn word_list_entries_are_distinct() {
+        let distinct: BTreeSet<_> = SAFETY_NUMBER_WORDS.iter().collect();
+        assert_eq!(distinct.len(), SAFETY_NUMBER_WORDS.len());
+    }
+}
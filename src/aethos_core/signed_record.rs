@@ -0,0 +1,272 @@
+use std::collections::BTreeMap;
+
+use base64::Engine;
+use ciborium::value::Value;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+
+use crate::aethos_core::app_body::{text_keyed_map_value, value_as_text_keyed_map};
+use crate::aethos_core::canonical_cbor::check_canonical;
+use crate::aethos_core::protocol::{decode_cbor_value_exact, encode_cbor_value_deterministic};
+
+/// A signed statement encoded as one canonical CBOR map: a `type` entry, the record's own
+/// fields, and one Ed25519 signature per signer over `SIGNING_DOMAIN` followed by the encoding
+/// of everything except the signatures. Implementors only map their fields; decoding,
+/// canonical-form, type, unexpected-field and signature checks live here.
+pub trait SignedRecord: Sized {
+    const PAYLOAD_TYPE: &'static str;
+    const SIGNING_DOMAIN: &'static [u8];
+    /// Human-readable name used in error messages, e.g. "device certificate".
+    const LABEL: &'static str;
+    /// Map keys holding the signatures, in the order `signer_pubkeys` returns their keys.
+    const SIGNATURE_FIELDS: &'static [&'static str];
+
+    /// The record's fields, without `type` or signatures.
+    fn fields(&self) -> BTreeMap<String, Value>;
+
+    /// Reads the record from its unsigned fields and applies any type-specific validation.
+    fn from_fields(fields: &BTreeMap<String, Value>) -> Result<Self, String>;
+
+    /// The key each entry of `SIGNATURE_FIELDS` must verify under.
+    fn signer_pubkeys(&self) -> Vec<[u8; 32]>;
+}
+
+/// Signs `record` with `signers`, one per `SIGNATURE_FIELDS` entry, and encodes it.
+pub fn issue_signed_record<R: SignedRecord>(
+    record: &R,
+    signers: &[&SigningKey],
+) -> Result<Vec<u8>, String> {
+    let pubkeys = record.signer_pubkeys();
+    if signers.len() != R::SIGNATURE_FIELDS.len() || pubkeys.len() != signers.len() {
+        return Err(format!(
+            "{} needs {} signers",
+            R::LABEL,
+            R::SIGNATURE_FIELDS.len()
+        ));
+    }
+    if signers
+        .iter()
+        .zip(&pubkeys)
+        .any(|(signer, pubkey)| signer.verifying_key().to_bytes() != *pubkey)
+    {
+        return Err(format!("{} signer does not match the record", R::LABEL));
+    }
+
+    let mut fields = unsigned_fields(record);
+    let message = signing_message::<R>(&fields)?;
+    for (field, signer) in R::SIGNATURE_FIELDS.iter().zip(signers) {
+        fields.insert(
+            field.to_string(),
+            Value::Bytes(signer.sign(&message).to_bytes().to_vec()),
+        );
+    }
+    encode_cbor_value_deterministic(&text_keyed_map_value(fields))
+}
+
+/// Decodes `raw` and checks it is canonical, of the expected type, carries nothing beyond the
+/// record's fields, and verifies under every signer.
+pub fn verify_signed_record<R: SignedRecord>(raw: &[u8]) -> Result<R, String> {
+    let decoded = decode_cbor_value_exact(raw, R::LABEL)?;
+    check_canonical(raw).map_err(|err| format!("{} is not canonical CBOR: {err}", R::LABEL))?;
+    let mut fields = value_as_text_keyed_map(decoded, R::LABEL)?;
+
+    match fields.get("type") {
+        Some(Value::Text(payload_type)) if payload_type == R::PAYLOAD_TYPE => {}
+        _ => return Err(format!("{} type is not {}", R::LABEL, R::PAYLOAD_TYPE)),
+    }
+    let signatures = R::SIGNATURE_FIELDS
+        .iter()
+        .map(|field| match fields.remove(*field) {
+            Some(Value::Bytes(bytes)) => <[u8; 64]>::try_from(bytes)
+                .map_err(|_| format!("{} {field} must be 64 bytes", R::LABEL)),
+            _ => Err(format!("{} {field} is missing", R::LABEL)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let record = R::from_fields(&fields)?;
+    if unsigned_fields(&record) != fields {
+        return Err(format!("{} has unexpected fields", R::LABEL));
+    }
+
+    let message = signing_message::<R>(&fields)?;
+    for ((field, signature), pubkey) in R::SIGNATURE_FIELDS
+        .iter()
+        .zip(&signatures)
+        .zip(record.signer_pubkeys())
+    {
+        VerifyingKey::from_bytes(&pubkey)
+            .map_err(|err| format!("invalid {} key: {err}", R::LABEL))?
+            .verify(&message, &Signature::from_bytes(signature))
+            .map_err(|_| format!("{} {field} is invalid", R::LABEL))?;
+    }
+    Ok(record)
+}
+
+pub fn required_bytes<const N: usize>(
+    fields: &BTreeMap<String, Value>,
+    key: &str,
+) -> Result<[u8; N], String> {
+    match fields.get(key) {
+        Some(Value::Bytes(bytes)) => bytes
+            .clone()
+            .try_into()
+            .map_err(|_| format!("{key} must be {N} bytes")),
+        _ => Err(format!("{key} is missing")),
+    }
+}
+
+/// Text form of a record for copy-paste or QR: `prefix` then unpadded base64url.
+pub fn prefixed_code(prefix: &str, raw: &[u8]) -> String {
+    format!(
+        "{prefix}{}",
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(raw)
+    )
+}
+
+pub fn decode_prefixed_code(code: &str, prefix: &str, context: &str) -> Result<Vec<u8>, String> {
+    let encoded = code
+        .trim()
+        .strip_prefix(prefix)
+        .ok_or_else(|| format!("{context} must start with {prefix}"))?;
+    base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(encoded)
+        .map_err(|err| format!("{context} is not base64url: {err}"))
+}
+
+fn unsigned_fields<R: SignedRecord>(record: &R) -> BTreeMap<String, Value> {
+    let mut fields = record.fields();
+    fields.insert("type".to_string(), Value::Text(R::PAYLOAD_TYPE.to_string()));
+    fields
+}
+
+fn signing_message<R: SignedRecord>(
+    unsigned_fields: &BTreeMap<String, Value>,
+) -> Result<Vec<u8>, String> {
+    let mut message = R::SIGNING_DOMAIN.to_vec();
+    message.extend(encode_cbor_value_deterministic(&text_keyed_map_value(
+        unsigned_fields.clone(),
+    ))?);
+    Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two-signer record standing in for every `SignedRecord` type.
+    #[derive(Debug, PartialEq)]
+    struct Handover {
+        from_pubkey: [u8; 32],
+        to_pubkey: [u8; 32],
+    }
+
+    impl SignedRecord for Handover {
+        const PAYLOAD_TYPE: &'static str = "test.handover.v1";
+        const SIGNING_DOMAIN: &'static [u8] = b"TEST_HANDOVER_V1";
+        const LABEL: &'static str = "handover";
+        const SIGNATURE_FIELDS: &'static [&'static str] = &["from_sig", "to_sig"];
+
+        fn fields(&self) -> BTreeMap<String, Value> {
+            let mut fields = BTreeMap::new();
+            fields.insert(
+                "from_pubkey".to_string(),
+                Value::Bytes(self.from_pubkey.to_vec()),
+            );
+            fields.insert(
+                "to_pubkey".to_string(),
+                Value::Bytes(self.to_pubkey.to_vec()),
+            );
+            fields
+        }
+
+        fn from_fields(fields: &BTreeMap<String, Value>) -> Result<Self, String> {
+            Ok(Self {
+                from_pubkey: required_bytes(fields, "from_pubkey")?,
+                to_pubkey: required_bytes(fields, "to_pubkey")?,
+            })
+        }
+
+        fn signer_pubkeys(&self) -> Vec<[u8; 32]> {
+            vec![self.from_pubkey, self.to_pubkey]
+        }
+    }
+
+    fn reencode(raw: &[u8], edit: impl FnOnce(&mut BTreeMap<String, Value>)) -> Vec<u8> {
+        let mut fields = value_as_text_keyed_map(
+            decode_cbor_value_exact(raw, "record").expect("decode"),
+            "record",
+        )
+        .expect("map");
+        edit(&mut fields);
+        encode_cbor_value_deterministic(&text_keyed_map_value(fields)).expect("encode")
+    }
+
+    #[test]
+    fn tampered_signed_records_are_rejected() {
+        let from = SigningKey::from_bytes(&[7u8; 32]);
+        let to = SigningKey::from_bytes(&[8u8; 32]);
+        let other = SigningKey::from_bytes(&[9u8; 32]);
+        let record = Handover {
+            from_pubkey: from.verifying_key().to_bytes(),
+            to_pubkey: to.verifying_key().to_bytes(),
+        };
+        let raw = issue_signed_record(&record, &[&from, &to]).expect("issue record");
+        assert_eq!(verify_signed_record::<Handover>(&raw), Ok(record));
+        assert!(issue_signed_record(
+            &Handover {
+                from_pubkey: from.verifying_key().to_bytes(),
+                to_pubkey: to.verifying_key().to_bytes(),
+            },
+            &[&from, &other],
+        )
+        .is_err());
+
+        let cases: Vec<(Vec<u8>, &str)> = vec![
+            (
+                reencode(&raw, |fields| {
+                    fields.insert(
+                        "to_pubkey".to_string(),
+                        Value::Bytes(other.verifying_key().to_bytes().to_vec()),
+                    );
+                }),
+                "handover from_sig is invalid",
+            ),
+            (
+                reencode(&raw, |fields| {
+                    fields.insert("note".to_string(), Value::Text("added".to_string()));
+                }),
+                "handover has unexpected fields",
+            ),
+            (
+                reencode(&raw, |fields| {
+                    fields.remove("to_sig");
+                }),
+                "handover to_sig is missing",
+            ),
+            (
+                reencode(&raw, |fields| {
+                    fields.insert("type".to_string(), Value::Text("test.other.v1".to_string()));
+                }),
+                "handover type is not test.handover.v1",
+            ),
+        ];
+        for (tampered, expected) in cases {
+            assert_eq!(
+                verify_signed_record::<Handover>(&tampered).unwrap_err(),
+                expected
+            );
+        }
+
+        // A map head with a non-minimal length is not canonical.
+        let mut non_canonical = vec![0xb8, raw[0] & 0x1f];
+        non_canonical.extend_from_slice(&raw[1..]);
+        assert!(verify_signed_record::<Handover>(&non_canonical)
+            .unwrap_err()
+            .starts_with("handover is not canonical CBOR"));
+
+        let code = prefixed_code("test-handover:", &raw);
+        assert_eq!(
+            decode_prefixed_code(&code, "test-handover:", "handover code"),
+            Ok(raw)
+        );
+        assert!(decode_prefixed_code(&code, "other:", "handover code").is_err());
+    }
+}
@@ -0,0 +1,302 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use ciborium::value::Value;
+use ed25519_dalek::SigningKey;
+
+use crate::aethos_core::app_body::required_u64_integer;
+use crate::aethos_core::identity_store::wayfarer_id_from_pubkey;
+use crate::aethos_core::signed_record::{
+    issue_signed_record, required_bytes, verify_signed_record, SignedRecord,
+};
+
+pub const WAYFARER_RECOVERY_SHARE_V1: &str = "wayfarer.recovery_share.v1";
+pub const MIN_RECOVERY_THRESHOLD: u8 = 2;
+pub const MAX_RECOVERY_SHARES: u8 = 16;
+
+/// One Shamir share of an identity signing seed over GF(256), signed by the identity key so a
+/// holder cannot alter it unnoticed. Shares only combine with others from the same `split_id`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecoveryShare {
+    pub owner_pubkey: [u8; 32],
+    pub split_id: [u8; 16],
+    pub threshold: u8,
+    /// Evaluation point of the share polynomial; never zero.
+    pub index: u8,
+    pub share: [u8; 32],
+}
+
+impl RecoveryShare {
+    pub fn owner_wayfarer_id(&self) -> String {
+        wayfarer_id_from_pubkey(&self.owner_pubkey)
+    }
+}
+
+impl SignedRecord for RecoveryShare {
+    const PAYLOAD_TYPE: &'static str = WAYFARER_RECOVERY_SHARE_V1;
+    const SIGNING_DOMAIN: &'static [u8] = b"AETHOS_RECOVERY_SHARE_V1";
+    const LABEL: &'static str = "recovery share";
+    const SIGNATURE_FIELDS: &'static [&'static str] = &["sig"];
+
+    fn fields(&self) -> BTreeMap<String, Value> {
+        let mut fields = BTreeMap::new();
+        fields.insert(
+            "owner_pubkey".to_string(),
+            Value::Bytes(self.owner_pubkey.to_vec()),
+        );
+        fields.insert("split_id".to_string(), Value::Bytes(self.split_id.to_vec()));
+        fields.insert(
+            "threshold".to_string(),
+            Value::Integer(self.threshold.into()),
+        );
+        fields.insert("index".to_string(), Value::Integer(self.index.into()));
+        fields.insert("share".to_string(), Value::Bytes(self.share.to_vec()));
+        fields
+    }
+
+    fn from_fields(fields: &BTreeMap<String, Value>) -> Result<Self, String> {
+        let share = Self {
+            owner_pubkey: required_bytes(fields, "owner_pubkey")?,
+            split_id: required_bytes(fields, "split_id")?,
+            threshold: required_u8(fields, "threshold")?,
+            index: required_u8(fields, "index")?,
+            share: required_bytes(fields, "share")?,
+        };
+        if share.index == 0 || share.threshold < MIN_RECOVERY_THRESHOLD {
+            return Err("recovery share index or threshold is out of range".to_string());
+        }
+        Ok(share)
+    }
+
+    fn signer_pubkeys(&self) -> Vec<[u8; 32]> {
+        vec![self.owner_pubkey]
+    }
+}
+
+/// Splits `seed` into `count` signed shares, any `threshold` of which rebuild it. `coefficients`
+/// supplies the random polynomial terms, `threshold - 1` bytes per seed byte.
+pub fn split_signing_seed(
+    seed: &[u8; 32],
+    threshold: u8,
+    count: u8,
+    split_id: [u8; 16],
+    mut coefficients: impl FnMut() -> u8,
+) -> Result<Vec<Vec<u8>>, String> {
+    if threshold < MIN_RECOVERY_THRESHOLD {
+        return Err(format!(
+            "recovery threshold must be at least {MIN_RECOVERY_THRESHOLD}"
+        ));
+    }
+    if count < threshold || count > MAX_RECOVERY_SHARES {
+        return Err(format!(
+            "recovery share count must be between the threshold and {MAX_RECOVERY_SHARES}"
+        ));
+    }
+
+    let signing_key = SigningKey::from_bytes(seed);
+    let polynomials: Vec<Vec<u8>> = seed
+        .iter()
+        .map(|secret_byte| {
+            std::iter::once(*secret_byte)
+                .chain((1..threshold).map(|_| coefficients()))
+                .collect()
+        })
+        .collect();
+
+    (1..=count)
+        .map(|index| {
+            let mut share = [0u8; 32];
+            for (byte, polynomial) in share.iter_mut().zip(&polynomials) {
+                *byte = gf256_evaluate(polynomial, index);
+            }
+            let share = RecoveryShare {
+                owner_pubkey: signing_key.verifying_key().to_bytes(),
+                split_id,
+                threshold,
+                index,
+                share,
+            };
+            issue_signed_record(&share, &[&signing_key])
+        })
+        .collect()
+}
+
+/// Decodes a recovery share and checks the owner's signature over it.
+pub fn verify_recovery_share(raw: &[u8]) -> Result<RecoveryShare, String> {
+    verify_signed_record(raw)
+}
+
+/// Rebuilds the signing seed from verified shares of one split and checks it against the
+/// owner key the shares name.
+pub fn combine_recovery_shares(shares: &[RecoveryShare]) -> Result<[u8; 32], String> {
+    let first = shares
+        .first()
+        .ok_or_else(|| "no recovery shares collected".to_string())?;
+    if shares.iter().any(|share| {
+        share.owner_pubkey != first.owner_pubkey
+            || share.split_id != first.split_id
+            || share.threshold != first.threshold
+    }) {
+        return Err("recovery shares come from different splits".to_string());
+    }
+    let by_index: BTreeMap<u8, &RecoveryShare> =
+        shares.iter().map(|share| (share.index, share)).collect();
+    if by_index.len() < usize::from(first.threshold) {
+        return Err(format!(
+            "need {} distinct recovery shares, have {}",
+            first.threshold,
+            by_index.len()
+        ));
+    }
+
+    let points: Vec<&RecoveryShare> = by_index
+        .values()
+        .take(usize::from(first.threshold))
+        .copied()
+        .collect();
+    let mut seed = [0u8; 32];
+    for (position, byte) in seed.iter_mut().enumerate() {
+        *byte = points.iter().fold(0u8, |acc, point| {
+            acc ^ gf256_mul(
+                point.share[position],
+                lagrange_basis_at_zero(point.index, &points),
+            )
+        });
+    }
+
+    if SigningKey::from_bytes(&seed).verifying_key().to_bytes() != first.owner_pubkey {
+        return Err("recovered key does not match the shares' owner".to_string());
+    }
+    Ok(seed)
+}
+
+/// Groups verified shares by split and returns the seed from the first split that has reached
+/// its threshold.
+pub fn recover_from_shares(shares: &[RecoveryShare]) -> Option<Result<[u8; 32], String>> {
+    let mut splits = BTreeMap::<[u8; 16], Vec<RecoveryShare>>::new();
+    for share in shares {
+        splits
+            .entry(share.split_id)
+            .or_default()
+            .push(share.clone());
+    }
+    splits
+        .into_values()
+        .find(|split| {
+            let distinct: BTreeSet<u8> = split.iter().map(|share| share.index).collect();
+            distinct.len() >= usize::from(split[0].threshold)
+        })
+        .map(|split| combine_recovery_shares(&split))
+}
+
+fn lagrange_basis_at_zero(index: u8, points: &[&RecoveryShare]) -> u8 {
+    points
+        .iter()
+        .filter(|other| other.index != index)
+        .fold(1u8, |acc, other| {
+            // In GF(256) subtraction is XOR, so (0 - x_j) / (x_i - x_j) = x_j / (x_i ^ x_j).
+            gf256_mul(acc, gf256_div(other.index, index ^ other.index))
+        })
+}
+
+fn gf256_evaluate(polynomial: &[u8], x: u8) -> u8 {
+    polynomial
+        .iter()
+        .rev()
+        .fold(0u8, |acc, coefficient| gf256_mul(acc, x) ^ coefficient)
+}
+
+fn gf256_mul(mut left: u8, mut right: u8) -> u8 {
+    let mut product = 0u8;
+    while right != 0 {
+        if right & 1 != 0 {
+            product ^= left;
+        }
+        let carry = left & 0x80 != 0;
+        left <<= 1;
+        if carry {
+            left ^= 0x1b;
+        }
+        right >>= 1;
+    }
+    product
+}
+
+fn gf256_div(numerator: u8, denominator: u8) -> u8 {
+    // a^254 is the multiplicative inverse of a in GF(256).
+    let mut inverse = 1u8;
+    for _ in 0..254 {
+        inverse = gf256_mul(inverse, denominator);
+    }
+    gf256_mul(numerator, inverse)
+}
+
+fn required_u8(fields: &BTreeMap<String, Value>, key: &str) -> Result<u8, String> {
+    u8::try_from(required_u64_integer(fields, key)?).map_err(|_| format!("{key} is out of range"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_shares(seed: &[u8; 32], threshold: u8, count: u8) -> Vec<RecoveryShare> {
+        let mut counter = 0u8;
+        split_signing_seed(seed, threshold, count, [3u8; 16], || {
+            counter = counter.wrapping_mul(31).wrapping_add(7);
+            counter
+        })
+        .expect("split seed")
+        .iter()
+        .map(|raw| verify_recovery_share(raw).expect("verify share"))
+        .collect()
+    }
+
+    #[test]
+    fn any_threshold_subset_recovers_the_seed() {
+        let seed = [42u8; 32];
+        let shares = test_shares(&seed, 3, 5);
+        assert_eq!(shares.len(), 5);
+
+        for subset in [[0, 1, 2], [0, 2, 4], [1, 3, 4], [4, 3, 2]] {
+            let picked: Vec<_> = subset.iter().map(|&i| shares[i].clone()).collect();
+            assert_eq!(combine_recovery_shares(&picked).expect("combine"), seed);
+        }
+        assert_eq!(
+            combine_recovery_shares(&shares[..2]).unwrap_err(),
+            "need 3 distinct recovery shares, have 2"
+        );
+        assert_eq!(
+            combine_recovery_shares(&[shares[0].clone(), shares[0].clone(), shares[1].clone()])
+                .unwrap_err(),
+            "need 3 distinct recovery shares, have 2"
+        );
+        assert!(recover_from_shares(&shares[..2]).is_none());
+        assert_eq!(
+            recover_from_shares(&shares[2..]).expect("threshold reached"),
+            Ok(seed)
+        );
+        assert!(split_signing_seed(&seed, 1, 3, [0u8; 16], || 0).is_err());
+        assert!(split_signing_seed(&seed, 4, 3, [0u8; 16], || 0).is_err());
+    }
+
+    #[test]
+    fn forged_or_mixed_shares_do_not_combine() {
+        let seed = [42u8; 32];
+        let raw = split_signing_seed(&seed, 2, 3, [3u8; 16], || 9).expect("split seed");
+
+        // A share that slipped past signature checks still cannot yield a wrong key.
+        let mut forged = verify_recovery_share(&raw[0]).expect("verify share");
+        forged.share[0] ^= 1;
+        let genuine = verify_recovery_share(&raw[1]).expect("verify share");
+        assert_eq!(
+            combine_recovery_shares(&[forged, genuine.clone()]).unwrap_err(),
+            "recovered key does not match the shares' owner"
+        );
+
+        let other_split = split_signing_seed(&seed, 2, 3, [4u8; 16], || 9).expect("split seed");
+        let other = verify_recovery_share(&other_split[0]).expect("verify share");
+        assert_eq!(
+            combine_recovery_shares(&[genuine, other]).unwrap_err(),
+            "recovery shares come from different splits"
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+
+use ciborium::value::Value;
+use ed25519_dalek::SigningKey;
+
+use crate::aethos_core::app_body::required_u64_integer;
+use crate::aethos_core::identity_store::wayfarer_id_from_pubkey;
+use crate::aethos_core::signed_record::{
+    issue_signed_record, required_bytes, verify_signed_record, SignedRecord,
+};
+
+pub const WAYFARER_SUCCESSION_RECORD_V1: &str = "wayfarer.succession_record.v1";
+/// How long a rotated-away identity keeps decrypting and displaying messages sent to it.
+pub const SUCCESSION_GRACE_WINDOW_MS: u64 = 30 * 24 * 60 * 60 * 1000;
+
+/// Statement that the identity behind `old_pubkey` has moved to `new_pubkey`. Both keys sign
+/// it, so a record can neither be forged by a stranger nor point at a key nobody holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuccessionRecord {
+    pub old_pubkey: [u8; 32],
+    pub new_pubkey: [u8; 32],
+    pub issued_at_unix_ms: u64,
+}
+
+impl SuccessionRecord {
+    pub fn old_wayfarer_id(&self) -> String {
+        wayfarer_id_from_pubkey(&self.old_pubkey)
+    }
+
+    pub fn new_wayfarer_id(&self) -> String {
+        wayfarer_id_from_pubkey(&self.new_pubkey)
+    }
+}
+
+impl SignedRecord for SuccessionRecord {
+    const PAYLOAD_TYPE: &'static str = WAYFARER_SUCCESSION_RECORD_V1;
+    const SIGNING_DOMAIN: &'static [u8] = b"AETHOS_SUCCESSION_V1";
+    const LABEL: &'static str = "succession record";
+    const SIGNATURE_FIELDS: &'static [&'static str] = &["old_sig", "new_sig"];
+
+    fn fields(&self) -> BTreeMap<String, Value> {
+        let mut fields = BTreeMap::new();
+        fields.insert(
+            "old_pubkey".to_string(),
+            Value::Bytes(self.old_pubkey.to_vec()),
+        );
+        fields.insert(
+            "new_pubkey".to_string(),
+            Value::Bytes(self.new_pubkey.to_vec()),
+        );
+        fields.insert(
+            "issued_at_unix_ms".to_string(),
+            Value::Integer(self.issued_at_unix_ms.into()),
+        );
+        fields
+    }
+
+    fn from_fields(fields: &BTreeMap<String, Value>) -> Result<Self, String> {
+        let record = Self {
+            old_pubkey: required_bytes(fields, "old_pubkey")?,
+            new_pubkey: required_bytes(fields, "new_pubkey")?,
+            issued_at_unix_ms: required_u64_integer(fields, "issued_at_unix_ms")?,
+        };
+        if record.old_pubkey == record.new_pubkey {
+            return Err("succession record must point at a different key".to_string());
+        }
+        Ok(record)
+    }
+
+    fn signer_pubkeys(&self) -> Vec<[u8; 32]> {
+        vec![self.old_pubkey, self.new_pubkey]
+    }
+}
+
+pub fn issue_succession_record(
+    old_signing_seed: &[u8; 32],
+    new_signing_seed: &[u8; 32],
+    issued_at_unix_ms: u64,
+) -> Result<Vec<u8>, String> {
+    let old_key = SigningKey::from_bytes(old_signing_seed);
+    let new_key = SigningKey::from_bytes(new_signing_seed);
+    let record = SuccessionRecord {
+        old_pubkey: old_key.verifying_key().to_bytes(),
+        new_pubkey: new_key.verifying_key().to_bytes(),
+        issued_at_unix_ms,
+    };
+    if record.old_pubkey == record.new_pubkey {
+        return Err("succession record must point at a different key".to_string());
+    }
+    issue_signed_record(&record, &[&old_key, &new_key])
+}
+
+/// Decodes a succession record and checks both signatures.
+pub fn verify_succession_record(raw: &[u8]) -> Result<SuccessionRecord, String> {
+    verify_signed_record(raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn succession_record_links_old_and_new_identity() {
+        let raw = issue_succession_record(&[7u8; 32], &[8u8; 32], 42).expect("issue record");
+        let record = verify_succession_record(&raw).expect("verify record");
+
+        let old_pubkey = SigningKey::from_bytes(&[7u8; 32])
+            .verifying_key()
+            .to_bytes();
+        let new_pubkey = SigningKey::from_bytes(&[8u8; 32])
+            .verifying_key()
+            .to_bytes();
+        assert_eq!(
+            record.old_wayfarer_id(),
+            wayfarer_id_from_pubkey(&old_pubkey)
+        );
+        assert_eq!(
+            record.new_wayfarer_id(),
+            wayfarer_id_from_pubkey(&new_pubkey)
+        );
+        assert_eq!(record.issued_at_unix_ms, 42);
+        assert!(issue_succession_record(&[7u8; 32], &[7u8; 32], 42).is_err());
+    }
+}
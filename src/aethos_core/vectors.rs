@@ -45,3 +45,64 @@ pub(crate) fn load_envelope_vectors() -> EnvelopeVectorSet {
     );
     vector_set
 }
+
+#[cfg(test)]
+const ENVELOPE_V2_VECTORS_JSON: &str =
+    include_str!("../../test-data/gossip-v1/envelope_v2_vectors.json");
+
+#[cfg(test)]
+#[derive(Debug, Deserialize)]
+pub(crate) struct EnvelopeV2VectorSet {
+    pub(crate) version: u32,
+    pub(crate) vectors: Vec<EnvelopeV2Vector>,
+}
+
+#[cfg(test)]
+#[derive(Debug, Deserialize)]
+pub(crate) struct EnvelopeV2Vector {
+    pub(crate) recipient_signing_key_seed_hex: String,
+    #[serde(default)]
+    pub(crate) additional_recipient_signing_key_seeds_hex: Vec<String>,
+    pub(crate) author_signing_key_seed_hex: String,
+    pub(crate) ephemeral_seed_hex: String,
+    #[serde(default)]
+    pub(crate) recipient_epoch: Option<u64>,
+    pub(crate) lifetime: EnvelopeV2VectorLifetime,
+    pub(crate) canonical_envelope_cbor_hex: String,
+    pub(crate) payload_b64: String,
+    pub(crate) item_id_hex: String,
+    pub(crate) expected_decoded: ExpectedDecodedV2,
+}
+
+#[cfg(test)]
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub(crate) struct EnvelopeV2VectorLifetime {
+    pub(crate) created_at_unix_ms: u64,
+    pub(crate) expires_at_unix_ms: u64,
+}
+
+#[cfg(test)]
+#[derive(Debug, Deserialize)]
+pub(crate) struct ExpectedDecodedV2 {
+    pub(crate) to_wayfarer_id: String,
+    #[serde(default)]
+    pub(crate) recipient_wayfarer_ids: Option<Vec<String>>,
+    pub(crate) manifest_id: String,
+    pub(crate) author_wayfarer_id: String,
+    pub(crate) body_hex: String,
+}
+
+#[cfg(test)]
+pub(crate) fn load_envelope_v2_vectors() -> EnvelopeV2VectorSet {
+    let vector_set: EnvelopeV2VectorSet =
+        serde_json::from_str(ENVELOPE_V2_VECTORS_JSON).expect("parse v2 vector json");
+    assert_eq!(
+        vector_set.version, ENVELOPE_VECTORS_SCHEMA_VERSION,
+        "envelope v2 vector schema version must match"
+    );
+    assert!(
+        !vector_set.vectors.is_empty(),
+        "envelope v2 vectors must not be empty"
+    );
+    vector_set
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::io::Read;
 use std::net::TcpStream;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering as AtomicOrdering};
@@ -15,13 +15,15 @@ use crate::aethos_core::encounter_orchestration::{
     BearerAdapter, EncounterManager, TransitionReason,
 };
 use crate::aethos_core::gossip_sync::{
-    build_hello_frame, build_relay_ingest_frame, build_request_frame, build_summary_frame,
-    import_transfer_items, missing_item_ids, parse_frame,
-    select_request_item_ids_from_summary_with_candidates, serialize_frame,
-    transfer_items_for_request_with_shadow_context_and_diagnostics, GossipSyncFrame, HelloFrame,
-    RelayIngestFrame,
+    build_hello_frame, build_relay_ingest_frame, build_request_frame, build_summary_frame_for_peer,
+    build_summary_page_frame, build_summary_page_request_frame, complete_hello_auth,
+    import_transfer_items, missing_item_ids, negotiate_session_profile, parse_frame_for_peer,
+    select_request_item_ids_from_summary_page,
+    select_request_item_ids_from_summary_with_candidates, serialize_frame_for_peer,
+    summary_has_unpreviewed_items, transfer_items_for_request_with_shadow_context_and_diagnostics,
+    GossipCapability, GossipSessionProfile, GossipSyncFrame, HelloFrame, RelayIngestFrame,
 };
-use crate::aethos_core::identity_store::LocalIdentitySummary;
+use crate::aethos_core::identity_store::{load_local_signing_key_seed, LocalIdentitySummary};
 use crate::aethos_core::logging::log_verbose;
 
 type RelaySocket = tungstenite::WebSocket<MaybeTlsStream<TcpStream>>;
@@ -268,7 +270,7 @@ pub fn connect_to_relay_gossipv1_with_auth(
 
     lease.transition("hello_sent", "hello_dispatch");
     match complete_hello_handshake(&mut socket, identity) {
-        Ok(peer_hello) => {
+        Ok((peer_hello, profile, _)) => {
             lease.transition("active", "hello_validated");
             lease.transition("closing", "connect_probe_complete");
             graceful_close_socket(
@@ -280,7 +282,7 @@ pub fn connect_to_relay_gossipv1_with_auth(
             );
             format!(
                 "connected + HELLO(version={}, peer={})",
-                peer_hello.version,
+                profile.version,
                 &peer_hello.node_id[..12]
             )
         }
@@ -311,6 +313,9 @@ pub struct EncounterReport {
 #[derive(Debug, Clone)]
 pub struct EncounterMessagePreview {
     pub author_wayfarer_id: Option<String>,
+    #[allow(dead_code)]
+    pub author_pubkey: Option<[u8; 32]>,
+    pub author_device_pubkey: Option<[u8; 32]>,
     pub session_peer: Option<String>,
     pub transport_peer: Option<String>,
     pub item_id: String,
@@ -327,6 +332,8 @@ pub struct RelayPersistentSession {
     lease: RelaySessionLease,
     socket: RelaySocket,
     peer_hello: HelloFrame,
+    profile: GossipSessionProfile,
+    peer_authenticated: bool,
     relay_ingest_allowed: bool,
     last_heartbeat_at: Instant,
 }
@@ -350,23 +357,24 @@ pub fn open_relay_persistent_session(
         }
     };
     lease.transition("hello_sent", "hello_dispatch");
-    let peer_hello = match complete_hello_handshake(&mut socket, identity) {
-        Ok(peer_hello) => {
-            lease.transition("active", "hello_validated");
-            peer_hello
-        }
-        Err(err) => {
-            lease.transition("closing", "hello_failed");
-            graceful_close_socket(
-                &mut socket,
-                relay_ws,
-                lease.attempt_id,
-                "hello_failed",
-                "open_relay_persistent_session",
-            );
-            return Err(err);
-        }
-    };
+    let (peer_hello, profile, peer_authenticated) =
+        match complete_hello_handshake(&mut socket, identity) {
+            Ok(peer) => {
+                lease.transition("active", "hello_validated");
+                peer
+            }
+            Err(err) => {
+                lease.transition("closing", "hello_failed");
+                graceful_close_socket(
+                    &mut socket,
+                    relay_ws,
+                    lease.attempt_id,
+                    "hello_failed",
+                    "open_relay_persistent_session",
+                );
+                return Err(err);
+            }
+        };
 
     log_verbose(&format!(
         "relay_session_opened: relay_ws={} attempt_id={} peer_node={} mode=persistent",
@@ -378,6 +386,8 @@ pub fn open_relay_persistent_session(
         lease,
         socket,
         peer_hello,
+        profile,
+        peer_authenticated,
         relay_ingest_allowed: is_relay_ingest_allowed(relay_ws),
         last_heartbeat_at: Instant::now(),
     })
@@ -395,6 +405,8 @@ pub fn run_relay_round_on_persistent_session(
         &session.relay_ws,
         identity,
         &session.peer_hello,
+        &session.profile,
+        session.peer_authenticated,
         session.relay_ingest_allowed,
         trace_item_id,
         encounter_window,
@@ -414,6 +426,8 @@ pub fn poll_relay_inbound_on_persistent_session(
         &session.relay_ws,
         identity,
         &session.peer_hello,
+        &session.profile,
+        session.peer_authenticated,
         session.relay_ingest_allowed,
         trace_item_id,
         encounter_window,
@@ -500,26 +514,27 @@ pub fn run_relay_encounter_gossipv1_for_duration(
         }
     };
     lease.transition("hello_sent", "hello_dispatch");
-    let peer_hello = match complete_hello_handshake(&mut socket, identity) {
-        Ok(peer_hello) => {
-            lease.transition("active", "hello_validated");
-            peer_hello
-        }
-        Err(err) => {
-            lease.transition("closing", "hello_failed");
-            graceful_close_socket(
-                &mut socket,
-                relay_ws,
-                lease.attempt_id,
-                "hello_failed",
-                "run_relay_encounter_gossipv1",
-            );
-            return Err(err);
-        }
-    };
+    let (peer_hello, profile, peer_authenticated) =
+        match complete_hello_handshake(&mut socket, identity) {
+            Ok(peer) => {
+                lease.transition("active", "hello_validated");
+                peer
+            }
+            Err(err) => {
+                lease.transition("closing", "hello_failed");
+                graceful_close_socket(
+                    &mut socket,
+                    relay_ws,
+                    lease.attempt_id,
+                    "hello_failed",
+                    "run_relay_encounter_gossipv1",
+                );
+                return Err(err);
+            }
+        };
     log_verbose(&format!(
-        "relay_encounter_hello_ok: relay_ws={} peer_node={} peer_max_want={} peer_max_transfer={}",
-        relay_ws, peer_hello.node_id, peer_hello.max_want, peer_hello.max_transfer
+        "relay_encounter_hello_ok: relay_ws={} peer_node={} version={} max_want={} max_transfer={}",
+        relay_ws, peer_hello.node_id, profile.version, profile.max_want, profile.max_transfer
     ));
 
     let report = match run_relay_round_on_socket(
@@ -527,6 +542,8 @@ pub fn run_relay_encounter_gossipv1_for_duration(
         relay_ws,
         identity,
         &peer_hello,
+        &profile,
+        peer_authenticated,
         relay_ingest_allowed,
         trace_item_id,
         encounter_window,
@@ -563,6 +580,8 @@ fn run_relay_round_on_socket(
     relay_ws: &str,
     identity: &LocalIdentitySummary,
     peer_hello: &HelloFrame,
+    profile: &GossipSessionProfile,
+    peer_authenticated: bool,
     relay_ingest_allowed: bool,
     trace_item_id: Option<&str>,
     encounter_window: Duration,
@@ -591,12 +610,16 @@ fn run_relay_round_on_socket(
             "relay_encounter_post_hello_send_summary: relay_ws={}",
             relay_ws
         ));
-        send_binary_frame(socket, &build_summary_frame(now_unix_ms())?)?;
+        send_binary_frame(
+            socket,
+            &build_summary_frame_for_peer(now_unix_ms(), profile)?,
+            profile,
+        )?;
         log_verbose(&format!(
             "relay_encounter_post_hello_send_relay_ingest: relay_ws={}",
             relay_ws
         ));
-        send_binary_frame(socket, &build_relay_ingest_frame(now_unix_ms())?)?;
+        send_binary_frame(socket, &build_relay_ingest_frame(now_unix_ms())?, profile)?;
     }
 
     let mut transferred_items = 0usize;
@@ -615,9 +638,11 @@ fn run_relay_round_on_socket(
     let mut trace_receipted_by_peer = false;
     let mut no_progress_streak = 0usize;
     let mut remote_closed = false;
+    let mut summary_paging_started = false;
+    let mut page_requested_item_ids = BTreeSet::new();
 
     while Instant::now() <= deadline {
-        let frame = match read_binary_frame(socket) {
+        let frame = match read_binary_frame(socket, profile) {
             Ok(frame) => frame,
             Err(err) if is_nonfatal_read_timeout(&err) => {
                 log_verbose(&format!(
@@ -672,14 +697,65 @@ fn run_relay_round_on_socket(
                 latest_summary = Some(summary.clone());
                 let want = select_request_item_ids_from_summary_with_candidates(
                     &summary,
-                    peer_hello.max_want as usize,
+                    profile.max_want,
                     &relay_ingest_candidates,
                 )?;
                 made_progress = !want.is_empty();
                 send_binary_frame(
                     socket,
-                    &build_request_frame(want, peer_hello.max_want as usize)?,
+                    &build_request_frame(want, profile.max_want)?,
+                    profile,
                 )?;
+                if !summary_paging_started
+                    && profile.supports(GossipCapability::SummaryPaging)
+                    && summary_has_unpreviewed_items(&summary)
+                {
+                    summary_paging_started = true;
+                    made_progress = true;
+                    send_binary_frame(
+                        socket,
+                        &build_summary_page_request_frame(None, profile)?,
+                        profile,
+                    )?;
+                }
+            }
+            GossipSyncFrame::SummaryPage(page) => {
+                log_verbose(&format!(
+                    "relay_encounter_recv_summary_page: relay_ws={} item_ids={} has_more={}",
+                    relay_ws,
+                    page.item_ids.len(),
+                    page.next_cursor.is_some()
+                ));
+                let want = select_request_item_ids_from_summary_page(
+                    &page,
+                    profile.max_want,
+                    &page_requested_item_ids,
+                )?;
+                if !want.is_empty() {
+                    page_requested_item_ids.extend(want.iter().cloned());
+                    made_progress = true;
+                    send_binary_frame(
+                        socket,
+                        &build_request_frame(want, profile.max_want)?,
+                        profile,
+                    )?;
+                }
+                if let Some(next_cursor) = page.next_cursor.as_deref() {
+                    made_progress = true;
+                    send_binary_frame(
+                        socket,
+                        &build_summary_page_request_frame(Some(next_cursor), profile)?,
+                        profile,
+                    )?;
+                }
+            }
+            GossipSyncFrame::SummaryPageRequest(request) => {
+                let page =
+                    build_summary_page_frame(now_unix_ms(), request.after.as_deref(), profile)?;
+                if let GossipSyncFrame::SummaryPage(page_frame) = &page {
+                    made_progress = !page_frame.item_ids.is_empty();
+                }
+                send_binary_frame(socket, &page, profile)?;
             }
             GossipSyncFrame::RelayIngest(RelayIngestFrame { item_ids }) => {
                 saw_relay_ingest = true;
@@ -744,13 +820,14 @@ fn run_relay_round_on_socket(
                 if let Some(summary) = latest_summary.as_ref() {
                     let want = select_request_item_ids_from_summary_with_candidates(
                         summary,
-                        peer_hello.max_want as usize,
+                        profile.max_want,
                         &relay_ingest_candidates,
                     )?;
                     made_progress = !want.is_empty();
                     send_binary_frame(
                         socket,
-                        &build_request_frame(want, peer_hello.max_want as usize)?,
+                        &build_request_frame(want, profile.max_want)?,
+                        profile,
                     )?;
                 }
             }
@@ -772,10 +849,11 @@ fn run_relay_round_on_socket(
                 ));
                 let selection = transfer_items_for_request_with_shadow_context_and_diagnostics(
                     &request.want,
-                    peer_hello.max_transfer as u32,
-                    crate::aethos_core::gossip_sync::MAX_TRANSFER_BYTES,
+                    profile.max_transfer as u32,
+                    profile.max_transfer_bytes,
                     now_unix_ms(),
                     Some(&peer_hello.node_id),
+                    peer_authenticated,
                 )?;
                 log_verbose(&format!(
                     "encounter_scheduler_plan_detail relay_ws={} planner={} consumed_bytes={} ranking_top={} selected_top={}",
@@ -805,6 +883,7 @@ fn run_relay_round_on_socket(
                     &GossipSyncFrame::Transfer(crate::aethos_core::gossip_sync::TransferFrame {
                         objects,
                     }),
+                    profile,
                 )?;
                 encounter_manager.record_scheduler_execution(
                     &format!("relay-{}-{}", relay_ws, recv_frame_count),
@@ -842,6 +921,7 @@ fn run_relay_round_on_socket(
                     &GossipSyncFrame::Receipt(crate::aethos_core::gossip_sync::ReceiptFrame {
                         received,
                     }),
+                    profile,
                 )?;
                 log_verbose(&format!(
                     "relay_encounter_send_receipt: relay_ws={} received_items={} new_messages={}",
@@ -858,6 +938,8 @@ fn run_relay_round_on_socket(
                     };
                     pulled_messages.push(EncounterMessagePreview {
                         author_wayfarer_id: message.author_wayfarer_id,
+                        author_pubkey: message.author_pubkey,
+                        author_device_pubkey: message.author_device_pubkey,
                         session_peer: message.session_peer,
                         transport_peer: message.transport_peer,
                         item_id: message.item_id,
@@ -889,6 +971,11 @@ fn run_relay_round_on_socket(
                     relay_ws, peer.node_id
                 ));
             }
+            GossipSyncFrame::HelloChallenge(_) | GossipSyncFrame::HelloProof(_) => {
+                log_verbose(&format!(
+                    "relay_encounter_recv_hello_auth_midstream: relay_ws={relay_ws}"
+                ));
+            }
         }
 
         if made_progress {
@@ -1202,10 +1289,13 @@ fn ensure_rustls_provider_installed() {
     });
 }
 
+/// Exchanges HELLOs, negotiates the session profile and, when the peer supports it, proves key
+/// possession both ways. Returns the peer HELLO, the profile and whether its `node_id` was
+/// authenticated.
 fn complete_hello_handshake(
     socket: &mut RelaySocket,
     identity: &LocalIdentitySummary,
-) -> Result<HelloFrame, String> {
+) -> Result<(HelloFrame, GossipSessionProfile, bool), String> {
     let node_pubkey_raw = base64::engine::general_purpose::STANDARD
         .decode(&identity.verifying_key_b64)
         .map_err(|err| format!("failed decoding local pubkey: {err}"))?;
@@ -1216,19 +1306,62 @@ fn complete_hello_handshake(
         identity.wayfarer_id,
         &identity.device_id.chars().take(8).collect::<String>()
     ));
-    send_binary_frame(socket, &hello)?;
+    let baseline = GossipSessionProfile::baseline();
+    send_binary_frame(socket, &hello, &baseline)?;
 
-    let frame = read_binary_frame(socket)?;
-    match frame {
-        GossipSyncFrame::Hello(peer) => Ok(peer),
-        other => Err(format!(
-            "connected; unexpected first frame after HELLO: {other:?}"
-        )),
+    let frame = read_binary_frame(socket, &baseline)?;
+    let peer = match frame {
+        GossipSyncFrame::Hello(peer) => peer,
+        other => {
+            return Err(format!(
+                "connected; unexpected first frame after HELLO: {other:?}"
+            ))
+        }
+    };
+
+    let GossipSyncFrame::Hello(local_hello) = &hello else {
+        return Err("local HELLO frame has unexpected type".to_string());
+    };
+    let profile = negotiate_session_profile(local_hello, &peer)?;
+    log_verbose(&format!(
+        "relay_handshake_profile: peer_node={} version={} features={:?} max_want={} max_transfer={}",
+        peer.node_id, profile.version, profile.features, profile.max_want, profile.max_transfer
+    ));
+
+    let signing_seed = load_local_signing_key_seed()?;
+    let authenticated = complete_hello_auth(
+        socket,
+        local_hello,
+        &peer,
+        &signing_seed,
+        |socket, frame| send_binary_frame(socket, frame, &profile),
+        |socket| read_hello_auth_frame(socket, &profile),
+    )?;
+    log_verbose(&format!(
+        "relay_handshake_peer_auth: peer_node={} authenticated={authenticated}",
+        peer.node_id
+    ));
+    Ok((peer, profile, authenticated))
+}
+
+fn read_hello_auth_frame(
+    socket: &mut RelaySocket,
+    profile: &GossipSessionProfile,
+) -> Result<GossipSyncFrame, String> {
+    loop {
+        match read_binary_frame(socket, profile) {
+            Err(err) if err == "WouldBlock" => continue,
+            other => return other,
+        }
     }
 }
 
-fn send_binary_frame(socket: &mut RelaySocket, frame: &GossipSyncFrame) -> Result<(), String> {
-    let raw = serialize_frame(frame)?;
+fn send_binary_frame(
+    socket: &mut RelaySocket,
+    frame: &GossipSyncFrame,
+    profile: &GossipSessionProfile,
+) -> Result<(), String> {
+    let raw = serialize_frame_for_peer(frame, profile)?;
     let framed = encode_stream_frame(&raw)?;
     log_verbose(&format!(
         "relay_frame_send: type={} payload_bytes={} framed_bytes={}",
@@ -1241,9 +1374,12 @@ fn send_binary_frame(socket: &mut RelaySocket, frame: &GossipSyncFrame) -> Resul
         .map_err(|err| format!("websocket send failed: {err}"))
 }
 
-fn read_binary_frame(socket: &mut RelaySocket) -> Result<GossipSyncFrame, String> {
+fn read_binary_frame(
+    socket: &mut RelaySocket,
+    profile: &GossipSessionProfile,
+) -> Result<GossipSyncFrame, String> {
     match socket.read() {
-        Ok(Message::Binary(raw)) => parse_relay_binary_message(&raw),
+        Ok(Message::Binary(raw)) => parse_relay_binary_message(&raw, profile),
         Ok(Message::Ping(payload)) => {
             let _ = socket.send(Message::Pong(payload));
             Err("WouldBlock".to_string())
@@ -1262,29 +1398,32 @@ fn read_binary_frame(socket: &mut RelaySocket) -> Result<GossipSyncFrame, String
             cursor
                 .read_to_end(&mut raw)
                 .map_err(|err| format!("text frame read failed: {err}"))?;
-            parse_frame(&raw)
+            parse_frame_for_peer(&raw, profile)
         }
         Ok(other) => Err(format!("unexpected relay frame: {other:?}")),
         Err(err) => Err(format!("websocket read failed: {err}")),
     }
 }
 
-fn parse_relay_binary_message(raw: &[u8]) -> Result<GossipSyncFrame, String> {
+fn parse_relay_binary_message(
+    raw: &[u8],
+    profile: &GossipSessionProfile,
+) -> Result<GossipSyncFrame, String> {
     log_verbose(&format!(
         "relay_frame_recv_binary_raw: ws_bytes={} max_frame_bytes={} prefix_hex={}",
         raw.len(),
-        crate::aethos_core::gossip_sync::MAX_FRAME_BYTES,
+        profile.max_frame_bytes,
         hex_prefix(raw, 16)
     ));
 
-    match decode_stream_frame(raw) {
+    match decode_stream_frame(raw, profile.max_frame_bytes) {
         Ok(payload) => {
             log_verbose(&format!(
                 "relay_frame_recv_binary: framing=length-prefixed framed_bytes={} payload_bytes={}",
                 raw.len(),
                 payload.len()
             ));
-            match parse_frame(payload) {
+            match parse_frame_for_peer(payload, profile) {
                 Ok(frame) => Ok(frame),
                 Err(payload_err) => {
                     log_verbose(&format!(
@@ -1293,7 +1432,7 @@ fn parse_relay_binary_message(raw: &[u8]) -> Result<GossipSyncFrame, String> {
                         payload.len(),
                         payload_err
                     ));
-                    match parse_frame(raw) {
+                    match parse_frame_for_peer(raw, profile) {
                         Ok(frame) => {
                             log_verbose(&format!(
                                 "relay_frame_recv_binary: framing=raw-cbor-after-prefixed-failure bytes={}",
@@ -1314,7 +1453,7 @@ fn parse_relay_binary_message(raw: &[u8]) -> Result<GossipSyncFrame, String> {
                 raw.len(),
                 prefix_err
             ));
-            match parse_frame(raw) {
+            match parse_frame_for_peer(raw, profile) {
                 Ok(frame) => {
                     log_verbose(&format!(
                         "relay_frame_recv_binary: framing=raw-cbor bytes={}",
@@ -1369,14 +1508,14 @@ fn encode_stream_frame(payload: &[u8]) -> Result<Vec<u8>, String> {
     Ok(out)
 }
 
-fn decode_stream_frame(raw: &[u8]) -> Result<&[u8], String> {
+fn decode_stream_frame(raw: &[u8], max_frame_bytes: usize) -> Result<&[u8], String> {
     if raw.len() < 4 {
         return Err("gossip stream frame too short".to_string());
     }
     let mut len_bytes = [0u8; 4];
     len_bytes.copy_from_slice(&raw[..4]);
     let frame_len = u32::from_be_bytes(len_bytes) as usize;
-    if frame_len > crate::aethos_core::gossip_sync::MAX_FRAME_BYTES {
+    if frame_len > max_frame_bytes {
         return Err(format!("gossip frame length exceeds max: {frame_len}"));
     }
     if raw.len() != frame_len + 4 {
@@ -1397,6 +1536,10 @@ fn relay_frame_type(frame: &GossipSyncFrame) -> &'static str {
         GossipSyncFrame::Transfer(_) => "TRANSFER",
         GossipSyncFrame::Receipt(_) => "RECEIPT",
         GossipSyncFrame::RelayIngest(_) => "RELAY_INGEST",
+        GossipSyncFrame::HelloChallenge(_) => "HELLO_CHALLENGE",
+        GossipSyncFrame::HelloProof(_) => "HELLO_PROOF",
+        GossipSyncFrame::SummaryPageRequest(_) => "SUMMARY_PAGE_REQUEST",
+        GossipSyncFrame::SummaryPage(_) => "SUMMARY_PAGE",
     }
 }
 